@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type AccountResponse = {
+  id: bigint;
+  name: string;
+  player_entity_id: bigint | null;
+  player_linked_at: string | null;
+  created_at: string;
+};
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ChangeCause = "Unknown" | "Craft" | "Trade" | "PickUp" | "Drop" | "Deposit" | "Withdraw" | "MarketFill" | "MarketOrder" | "Deconstruct" | "Extract" | "Move" | "Equipment" | "Consume" | "Quest" | "Loot" | "Build" | "Admin";
\ No newline at end of file
@@ -0,0 +1,26 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ClaimMember } from "./ClaimMember";
+import type { ResolvedInventory } from "./ResolvedInventory";
+
+/**
+ * Something that happened to a claim, published on the `claim` topic with the claim id.
+ */
+export type ClaimEvent =
+  | { t: "TreasuryChanged"; c: { treasury: number; previous: number } }
+  | { t: "SuppliesChanged"; c: { supplies: number; previous: number } }
+  | { t: "MemberJoined"; c: ClaimMember }
+  | { t: "MemberUpdated"; c: ClaimMember }
+  | { t: "MemberLeft"; c: ClaimMember }
+  | { t: "ResearchStarted"; c: { tech_id: number } }
+  | { t: "ResearchFinished"; c: { tech_id: number } }
+  | { t: "ResearchCancelled"; c: { tech_id: number } }
+  | {
+      t: "BuildingConstructed";
+      c: {
+        entity_id: bigint;
+        building_description_id: number;
+        constructed_by_player_entity_id: bigint;
+      };
+    }
+  | { t: "BuildingDestroyed"; c: { entity_id: bigint; building_description_id: number } }
+  | { t: "StorageChanged"; c: { resolved_inventory: ResolvedInventory } };
@@ -0,0 +1,11 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type ClaimMember = {
+  entity_id: bigint;
+  player_entity_id: bigint;
+  user_name: string;
+  inventory_permission: boolean;
+  build_permission: boolean;
+  officer_permission: boolean;
+  co_owner_permission: boolean;
+};
@@ -0,0 +1,8 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccountResponse } from "./AccountResponse";
+import type { CreatedApiKeyResponse } from "./CreatedApiKeyResponse";
+
+export type CreatedAccountResponse = {
+  account: AccountResponse;
+  api_key: CreatedApiKeyResponse;
+};
@@ -0,0 +1,7 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ApiKeyResponse } from "./ApiKeyResponse";
+
+/**
+ * A freshly created key, `key` is only ever shown in this response.
+ */
+export type CreatedApiKeyResponse = { key: string; api_key: ApiKeyResponse };
@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { ChangeCause } from "./ChangeCause";
 import type { ItemType } from "./ItemType";
 import type { TypeOfChange } from "./TypeOfChange";
 
@@ -14,5 +15,6 @@ export type InventoryChangelog = {
   new_item_id: number | null;
   new_item_quantity: number | null;
   type_of_change: TypeOfChange;
+  cause: ChangeCause;
   timestamp: string;
 };
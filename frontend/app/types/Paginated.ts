@@ -0,0 +1,9 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type Paginated<T> = {
+  items: Array<T>;
+  page: bigint;
+  per_page: bigint;
+  total: bigint;
+  total_pages: bigint;
+};
@@ -0,0 +1,10 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export type PlayerLinkChallengeResponse = {
+  player_entity_id: bigint;
+  /**
+   * Has to be set as the nickname of a building the player constructed.
+   */
+  code: string;
+  expires_at: string;
+};
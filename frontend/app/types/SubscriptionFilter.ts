@@ -0,0 +1,23 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+/**
+ * Subscribes to every message of `topic` that matches all the given predicates.
+ */
+export type SubscriptionFilter = {
+  topic: string;
+  /**
+   * The id the topic is keyed by, the same as in `topic.id`.
+   */
+  entity_id?: bigint;
+  /**
+   * Matches the claim itself or players that are a member of it.
+   */
+  claim_id?: bigint;
+  region?: number;
+  item_id?: number;
+  /**
+   * `0` for items and `1` for cargo, only used together with `item_id`.
+   */
+  item_type?: number;
+  item_tag?: string;
+};
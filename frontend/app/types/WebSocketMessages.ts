@@ -1,18 +1,34 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { ActionState } from "./ActionState";
 import type { AuctionListingState } from "./AuctionListingState";
+import type { ClaimEvent } from "./ClaimEvent";
 import type { ClaimLocalState } from "./ClaimLocalState";
 import type { MobileEntityState } from "./MobileEntityState";
 import type { PlayerActionState } from "./PlayerActionState";
 import type { PlayerState } from "./PlayerState";
 import type { ResolvedInventory } from "./ResolvedInventory";
+import type { SubscriptionFilter } from "./SubscriptionFilter";
 import type { TravelerTaskState } from "./TravelerTaskState";
 
 export type WebSocketMessages =
-  | { t: "Subscribe"; c: { topics: Array<string> } }
+  | {
+      t: "Subscribe";
+      c: {
+        topics: Array<string>;
+        /**
+         * Send a `Snapshot` of the current state for every topic before its deltas and wrap
+         * every following delta in `Sequenced`.
+         */
+        snapshot?: boolean;
+      };
+    }
   | { t: "ListSubscribedTopics" }
   | { t: "SubscribedTopics"; c: Array<string> }
   | { t: "Unsubscribe"; c: { topic: string } }
+  | { t: "SubscribeFilter"; c: { id: string; filter: SubscriptionFilter } }
+  | { t: "SubscribedFilter"; c: { id: string } }
+  | { t: "UnsubscribeFilter"; c: { id: string } }
+  | { t: "SubscriptionError"; c: { topic: string; error: string } }
   | { t: "MobileEntityState"; c: MobileEntityState }
   | {
       t: "Experience";
@@ -32,9 +48,11 @@ export type WebSocketMessages =
   | { t: "PlayerActionStateChangeName"; c: [string, bigint] }
   | { t: "Level"; c: { level: bigint; user_id: bigint; skill_name: string } }
   | { t: "PlayerState"; c: PlayerState }
+  | { t: "PlayerStateDelete"; c: PlayerState }
   | { t: "TravelerTaskState"; c: TravelerTaskState }
   | { t: "TravelerTaskStateDelete"; c: TravelerTaskState }
   | { t: "ClaimLocalState"; c: ClaimLocalState }
+  | { t: "ClaimEvent"; c: { claim_id: bigint; event: ClaimEvent } }
   | { t: "Message"; c: string }
   | { t: "ActionState"; c: ActionState }
   | { t: "InsertSellOrder"; c: AuctionListingState }
@@ -45,4 +63,6 @@ export type WebSocketMessages =
   | { t: "RemoveBuyOrder"; c: AuctionListingState }
   | { t: "InventoryUpdate"; c: { resolved_inventory: ResolvedInventory } }
   | { t: "InventoryRemove"; c: { resolved_inventory: ResolvedInventory } }
-  | { t: "InventoryInsert"; c: { resolved_inventory: ResolvedInventory; player_owner_id: bigint } };
+  | { t: "InventoryInsert"; c: { resolved_inventory: ResolvedInventory; player_owner_id: bigint } }
+  | { t: "Snapshot"; c: { topic: string; sequence: bigint; messages: Array<WebSocketMessages> } }
+  | { t: "Sequenced"; c: { sequence: bigint; message: WebSocketMessages } };
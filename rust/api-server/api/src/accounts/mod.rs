@@ -0,0 +1,827 @@
+use crate::error::ApiError;
+use crate::rate_limit::ClientIp;
+use crate::{AppRouter, AppState};
+use axum::extract::{FromRequestParts, OptionalFromRequestParts, Path, State};
+use axum::http::request::Parts;
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::{Json, Router};
+use chrono::{DateTime, Duration, Utc};
+use log::error;
+use sea_orm::sea_query::Expr;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set, SqlErr};
+use serde::{Deserialize, Serialize};
+use service::Query as QueryCore;
+use sha2::{Digest, Sha256};
+use spacetimedb_sdk::Identity;
+use tower_cookies::{Cookie, Cookies};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+pub(crate) const SESSION_COOKIE: &str = "bitcraft_hub_session";
+const SESSION_LIFETIME_DAYS: i64 = 30;
+const API_KEY_PREFIX: &str = "bh_";
+const MAX_ACTIVE_API_KEYS: usize = 20;
+const PLAYER_LINK_LIFETIME_MINUTES: i64 = 30;
+const PLAYER_LINK_CODE_PREFIX: &str = "HUB-";
+const PLAYER_LINK_ALPHABET: [char; 32] = [
+    'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'J', 'K', 'L', 'M', 'N', 'P', 'Q', 'R', 'S', 'T', 'U',
+    'V', 'W', 'X', 'Y', 'Z', '2', '3', '4', '5', '6', '7', '8', '9',
+];
+
+pub(crate) fn get_routes() -> AppRouter {
+    Router::new()
+        .route(
+            "/api/bitcraft/accounts",
+            axum_codec::routing::post(create_account).into(),
+        )
+        .route(
+            "/api/bitcraft/accounts/me",
+            axum_codec::routing::get(get_me).into(),
+        )
+        .route(
+            "/api/bitcraft/accounts/sessions",
+            axum::routing::MethodRouter::from(axum_codec::routing::post(create_session))
+                .delete(delete_session),
+        )
+        .route(
+            "/api/bitcraft/accounts/api-keys",
+            axum_codec::routing::get(list_api_keys)
+                .post(create_api_key)
+                .into(),
+        )
+        .route(
+            "/api/bitcraft/accounts/api-keys/{id}",
+            axum::routing::delete(revoke_api_key),
+        )
+        .route(
+            "/api/bitcraft/accounts/player-link",
+            axum::routing::MethodRouter::from(axum_codec::routing::post(start_player_link))
+                .delete(unlink_player),
+        )
+        .route(
+            "/api/bitcraft/accounts/player-link/verify",
+            axum_codec::routing::post(verify_player_link).into(),
+        )
+}
+
+/// Hex encoded SHA-256, only hashes of keys and session tokens end up in the database.
+pub(crate) fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+fn internal_error(error: sea_orm::DbErr) -> ApiError {
+    error!("Error: {error}");
+
+    ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+}
+
+/// The account a request was made for, either through an API key or the session cookie.
+#[derive(Clone, Debug)]
+pub(crate) struct AuthenticatedAccount {
+    pub(crate) account: entity::account::Model,
+    /// `None` when the request was authenticated with the session cookie
+    pub(crate) api_key_id: Option<i64>,
+}
+
+fn api_key_from_headers(headers: &HeaderMap) -> Option<&str> {
+    if let Some(value) = headers.get(header::AUTHORIZATION)
+        && let Ok(value) = value.to_str()
+        && let Some(key) = value.strip_prefix("Bearer ")
+    {
+        return Some(key.trim());
+    }
+
+    headers
+        .get("x-api-key")
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+}
+
+impl OptionalFromRequestParts<AppState> for AuthenticatedAccount {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Option<Self>, Self::Rejection> {
+        // Already resolved by the rate limiter
+        if let Some(authenticated) = parts.extensions.get::<Self>() {
+            return Ok(Some(authenticated.clone()));
+        }
+
+        // A key that was sent but is wrong is an error, a stale session cookie is not.
+        if let Some(key) = api_key_from_headers(&parts.headers) {
+            let found = QueryCore::find_account_by_api_key_hash(&state.conn, &hash_token(key))
+                .await
+                .map_err(internal_error)?;
+            let Some((api_key, account)) = found else {
+                return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Invalid API key"));
+            };
+
+            touch_api_key(state, &api_key);
+
+            return Ok(Some(Self {
+                account,
+                api_key_id: Some(api_key.id),
+            }));
+        }
+
+        let cookies = Cookies::from_request_parts(parts, state).await?;
+        let Some(token) = cookies.get(SESSION_COOKIE) else {
+            return Ok(None);
+        };
+
+        let account = QueryCore::find_account_by_session_hash(
+            &state.conn,
+            &hash_token(token.value()),
+            Utc::now(),
+        )
+        .await
+        .map_err(internal_error)?;
+
+        Ok(account.map(|account| Self {
+            account,
+            api_key_id: None,
+        }))
+    }
+}
+
+impl FromRequestParts<AppState> for AuthenticatedAccount {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        <Self as OptionalFromRequestParts<AppState>>::from_request_parts(parts, state)
+            .await?
+            .ok_or(ApiError::new(
+                StatusCode::UNAUTHORIZED,
+                "Authentication required",
+            ))
+    }
+}
+
+/// `last_used_at` only needs to be roughly right, so busy keys do not write on every request.
+fn touch_api_key(state: &AppState, api_key: &entity::api_key::Model) {
+    let now = Utc::now();
+    if api_key
+        .last_used_at
+        .is_some_and(|last_used_at| now - last_used_at < Duration::minutes(1))
+    {
+        return;
+    }
+
+    let conn = state.conn.clone();
+    let id = api_key.id;
+    tokio::spawn(async move {
+        if let Err(error) = entity::api_key::Entity::update_many()
+            .col_expr(entity::api_key::Column::LastUsedAt, Expr::value(now))
+            .filter(entity::api_key::Column::Id.eq(id))
+            .exec(&conn)
+            .await
+        {
+            error!("Could not update last_used_at of api key {id}: {error}");
+        }
+    });
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct AccountResponse {
+    id: i64,
+    name: String,
+    player_entity_id: Option<i64>,
+    player_linked_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<entity::account::Model> for AccountResponse {
+    fn from(value: entity::account::Model) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            player_entity_id: value.player_entity_id,
+            player_linked_at: value.player_linked_at,
+            created_at: value.created_at,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct ApiKeyResponse {
+    id: i64,
+    name: String,
+    prefix: String,
+    created_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<entity::api_key::Model> for ApiKeyResponse {
+    fn from(value: entity::api_key::Model) -> Self {
+        Self {
+            id: value.id,
+            name: value.name,
+            prefix: value.prefix,
+            created_at: value.created_at,
+            last_used_at: value.last_used_at,
+            revoked_at: value.revoked_at,
+        }
+    }
+}
+
+/// A freshly created key, `key` is only ever shown in this response.
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct CreatedApiKeyResponse {
+    key: String,
+    api_key: ApiKeyResponse,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct CreatedAccountResponse {
+    account: AccountResponse,
+    api_key: CreatedApiKeyResponse,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(crate) struct CreateAccountRequest {
+    name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(crate) struct CreateApiKeyRequest {
+    name: String,
+}
+
+fn validate_name(name: &str) -> Result<(), ApiError> {
+    let valid = (3..=32).contains(&name.chars().count())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "Name must be 3 to 32 characters of letters, digits, _ or -",
+        ))
+    }
+}
+
+async fn insert_api_key(
+    state: &AppState,
+    account_id: i64,
+    name: String,
+) -> Result<CreatedApiKeyResponse, ApiError> {
+    let key = format!("{API_KEY_PREFIX}{}", nanoid::nanoid!(40));
+
+    let api_key = entity::api_key::ActiveModel {
+        account_id: Set(account_id),
+        name: Set(name),
+        prefix: Set(key[..API_KEY_PREFIX.len() + 6].to_string()),
+        key_hash: Set(hash_token(&key)),
+        created_at: Set(Utc::now()),
+        last_used_at: Set(None),
+        revoked_at: Set(None),
+        ..Default::default()
+    }
+    .insert(&state.conn)
+    .await
+    .map_err(internal_error)?;
+
+    Ok(CreatedApiKeyResponse {
+        key,
+        api_key: api_key.into(),
+    })
+}
+
+async fn start_session(
+    state: &AppState,
+    cookies: &Cookies,
+    account_id: i64,
+) -> Result<(), ApiError> {
+    let token = nanoid::nanoid!(48);
+    let now = Utc::now();
+
+    entity::account_session::ActiveModel {
+        token_hash: Set(hash_token(&token)),
+        account_id: Set(account_id),
+        created_at: Set(now),
+        expires_at: Set(now + Duration::days(SESSION_LIFETIME_DAYS)),
+    }
+    .insert(&state.conn)
+    .await
+    .map_err(internal_error)?;
+
+    cookies.add(
+        Cookie::build((SESSION_COOKIE, token))
+            .path("/")
+            .http_only(true)
+            .secure(true)
+            .same_site(tower_cookies::cookie::SameSite::Lax)
+            .max_age(tower_cookies::cookie::time::Duration::days(
+                SESSION_LIFETIME_DAYS,
+            ))
+            .build(),
+    );
+
+    Ok(())
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bitcraft/accounts",
+    tag = "accounts",
+    request_body = CreateAccountRequest,
+    responses(
+        (status = OK, body = CreatedAccountResponse),
+        (status = BAD_REQUEST, description = "Invalid account name"),
+        (status = CONFLICT, description = "Account name is already taken"),
+        (status = TOO_MANY_REQUESTS, description = "Too many accounts created from this IP"),
+    )
+)]
+pub(crate) async fn create_account(
+    state: State<AppState>,
+    cookies: Cookies,
+    ClientIp(ip): ClientIp,
+    Json(request): Json<CreateAccountRequest>,
+) -> Result<axum_codec::Codec<CreatedAccountResponse>, ApiError> {
+    let name = request.name.trim().to_string();
+    validate_name(&name)?;
+
+    if state.rate_limiter.check_account_creation(&ip).is_err() {
+        return Err(ApiError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many accounts created, try again later",
+        ));
+    }
+
+    let account = entity::account::ActiveModel {
+        name: Set(name),
+        player_entity_id: Set(None),
+        player_linked_at: Set(None),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(&state.conn)
+    .await
+    .map_err(|error| match error.sql_err() {
+        Some(SqlErr::UniqueConstraintViolation(_)) => {
+            ApiError::new(StatusCode::CONFLICT, "Account name is already taken")
+        }
+        _ => internal_error(error),
+    })?;
+
+    let api_key = insert_api_key(&state, account.id, "default".to_string()).await?;
+    start_session(&state, &cookies, account.id).await?;
+
+    Ok(axum_codec::Codec(CreatedAccountResponse {
+        account: account.into(),
+        api_key,
+    }))
+}
+
+/// Exchanges an API key for a session cookie, so the browser does not have to keep the key.
+#[utoipa::path(
+    post,
+    path = "/api/bitcraft/accounts/sessions",
+    tag = "accounts",
+    security(("api_key" = []), ("bearer" = []), ("session" = [])),
+    responses(
+        (status = OK, body = AccountResponse),
+        (status = BAD_REQUEST, description = "Not authenticated with an API key"),
+        (status = UNAUTHORIZED, description = "Authentication required"),
+    )
+)]
+pub(crate) async fn create_session(
+    state: State<AppState>,
+    cookies: Cookies,
+    authenticated: AuthenticatedAccount,
+) -> Result<axum_codec::Codec<AccountResponse>, ApiError> {
+    if authenticated.api_key_id.is_none() {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "An API key is required to log in",
+        ));
+    }
+
+    start_session(&state, &cookies, authenticated.account.id).await?;
+
+    Ok(axum_codec::Codec(authenticated.account.into()))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/bitcraft/accounts/sessions",
+    tag = "accounts",
+    responses(
+        (status = NO_CONTENT, description = "Logged out"),
+    )
+)]
+pub(crate) async fn delete_session(
+    state: State<AppState>,
+    cookies: Cookies,
+) -> Result<StatusCode, ApiError> {
+    if let Some(token) = cookies.get(SESSION_COOKIE) {
+        entity::account_session::Entity::delete_by_id(hash_token(token.value()))
+            .exec(&state.conn)
+            .await
+            .map_err(internal_error)?;
+    }
+
+    cookies.remove(Cookie::build(SESSION_COOKIE).path("/").build());
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/accounts/me",
+    tag = "accounts",
+    security(("api_key" = []), ("bearer" = []), ("session" = [])),
+    responses(
+        (status = OK, body = AccountResponse),
+        (status = UNAUTHORIZED, description = "Authentication required"),
+    )
+)]
+pub(crate) async fn get_me(
+    authenticated: AuthenticatedAccount,
+) -> Result<axum_codec::Codec<AccountResponse>, ApiError> {
+    Ok(axum_codec::Codec(authenticated.account.into()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/accounts/api-keys",
+    tag = "accounts",
+    security(("api_key" = []), ("bearer" = []), ("session" = [])),
+    responses(
+        (status = OK, body = Vec<ApiKeyResponse>),
+        (status = UNAUTHORIZED, description = "Authentication required"),
+    )
+)]
+pub(crate) async fn list_api_keys(
+    state: State<AppState>,
+    authenticated: AuthenticatedAccount,
+) -> Result<axum_codec::Codec<Vec<ApiKeyResponse>>, ApiError> {
+    let api_keys = QueryCore::get_api_keys_for_account(&state.conn, authenticated.account.id)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(axum_codec::Codec(
+        api_keys.into_iter().map(ApiKeyResponse::from).collect(),
+    ))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bitcraft/accounts/api-keys",
+    tag = "accounts",
+    request_body = CreateApiKeyRequest,
+    security(("api_key" = []), ("bearer" = []), ("session" = [])),
+    responses(
+        (status = OK, body = CreatedApiKeyResponse),
+        (status = BAD_REQUEST, description = "Invalid key name"),
+        (status = UNAUTHORIZED, description = "Authentication required"),
+        (status = CONFLICT, description = "Too many active API keys"),
+    )
+)]
+pub(crate) async fn create_api_key(
+    state: State<AppState>,
+    authenticated: AuthenticatedAccount,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Result<axum_codec::Codec<CreatedApiKeyResponse>, ApiError> {
+    let name = request.name.trim().to_string();
+    validate_name(&name)?;
+
+    let active_keys = QueryCore::get_api_keys_for_account(&state.conn, authenticated.account.id)
+        .await
+        .map_err(internal_error)?
+        .into_iter()
+        .filter(|api_key| api_key.revoked_at.is_none())
+        .count();
+    if active_keys >= MAX_ACTIVE_API_KEYS {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "Too many active API keys",
+        ));
+    }
+
+    let api_key = insert_api_key(&state, authenticated.account.id, name).await?;
+
+    Ok(axum_codec::Codec(api_key))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/bitcraft/accounts/api-keys/{id}",
+    tag = "accounts",
+    params(("id" = i64, Path, description = "API key id")),
+    security(("api_key" = []), ("bearer" = []), ("session" = [])),
+    responses(
+        (status = NO_CONTENT, description = "API key revoked"),
+        (status = UNAUTHORIZED, description = "Authentication required"),
+        (status = NOT_FOUND, description = "API key not found"),
+    )
+)]
+pub(crate) async fn revoke_api_key(
+    state: State<AppState>,
+    authenticated: AuthenticatedAccount,
+    Path(id): Path<i64>,
+) -> Result<StatusCode, ApiError> {
+    let result = entity::api_key::Entity::update_many()
+        .col_expr(entity::api_key::Column::RevokedAt, Expr::value(Utc::now()))
+        .filter(entity::api_key::Column::Id.eq(id))
+        .filter(entity::api_key::Column::AccountId.eq(authenticated.account.id))
+        .filter(entity::api_key::Column::RevokedAt.is_null())
+        .exec(&state.conn)
+        .await
+        .map_err(internal_error)?;
+
+    if result.rows_affected == 0 {
+        return Err(ApiError::new(StatusCode::NOT_FOUND, "API key not found"));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// A pending proof that an account owns a player, kept in memory until it is verified or expires.
+#[derive(Clone, Debug)]
+pub(crate) struct PlayerLinkChallenge {
+    player_entity_id: i64,
+    code: String,
+    expires_at: DateTime<Utc>,
+    /// Set once the player renamed a building to `code` themselves.
+    proven: bool,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub(crate) struct StartPlayerLinkRequest {
+    player_entity_id: i64,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct PlayerLinkChallengeResponse {
+    player_entity_id: i64,
+    /// The player has to name a building with it, the rename has to come from their own client.
+    code: String,
+    expires_at: DateTime<Utc>,
+}
+
+fn player_link_code() -> String {
+    format!(
+        "{PLAYER_LINK_CODE_PREFIX}{}",
+        nanoid::nanoid!(8, &PLAYER_LINK_ALPHABET)
+    )
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bitcraft/accounts/player-link",
+    tag = "accounts",
+    request_body = StartPlayerLinkRequest,
+    security(("api_key" = []), ("bearer" = []), ("session" = [])),
+    responses(
+        (status = OK, body = PlayerLinkChallengeResponse),
+        (status = UNAUTHORIZED, description = "Authentication required"),
+        (status = NOT_FOUND, description = "Player not found"),
+        (status = CONFLICT, description = "Player is already linked to an account"),
+    )
+)]
+pub(crate) async fn start_player_link(
+    state: State<AppState>,
+    authenticated: AuthenticatedAccount,
+    Json(request): Json<StartPlayerLinkRequest>,
+) -> Result<axum_codec::Codec<PlayerLinkChallengeResponse>, ApiError> {
+    let player_exists = state.player_state.contains_key(&request.player_entity_id)
+        || QueryCore::find_player_by_id(&state.conn, request.player_entity_id)
+            .await
+            .map_err(internal_error)?
+            .is_some();
+    if !player_exists {
+        return Err(ApiError::new(StatusCode::NOT_FOUND, "Player not found"));
+    }
+
+    let linked_account =
+        QueryCore::find_account_by_player_entity_id(&state.conn, request.player_entity_id)
+            .await
+            .map_err(internal_error)?;
+    if linked_account.is_some() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "Player is already linked to an account",
+        ));
+    }
+
+    let challenge = PlayerLinkChallenge {
+        player_entity_id: request.player_entity_id,
+        code: player_link_code(),
+        expires_at: Utc::now() + Duration::minutes(PLAYER_LINK_LIFETIME_MINUTES),
+        proven: false,
+    };
+    state
+        .player_link_challenges
+        .insert(authenticated.account.id, challenge.clone());
+
+    Ok(axum_codec::Codec(PlayerLinkChallengeResponse {
+        player_entity_id: challenge.player_entity_id,
+        code: challenge.code,
+        expires_at: challenge.expires_at,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/bitcraft/accounts/player-link/verify",
+    tag = "accounts",
+    security(("api_key" = []), ("bearer" = []), ("session" = [])),
+    responses(
+        (status = OK, body = AccountResponse),
+        (status = UNAUTHORIZED, description = "Authentication required"),
+        (status = NOT_FOUND, description = "No player link in progress"),
+        (status = CONFLICT, description = "The player did not name a building with the code yet"),
+        (status = GONE, description = "Player link code expired"),
+    )
+)]
+pub(crate) async fn verify_player_link(
+    state: State<AppState>,
+    authenticated: AuthenticatedAccount,
+) -> Result<axum_codec::Codec<AccountResponse>, ApiError> {
+    let Some(challenge) = state
+        .player_link_challenges
+        .get(&authenticated.account.id)
+        .map(|challenge| challenge.clone())
+    else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "No player link in progress",
+        ));
+    };
+
+    if challenge.expires_at < Utc::now() {
+        state
+            .player_link_challenges
+            .remove(&authenticated.account.id);
+        return Err(ApiError::new(StatusCode::GONE, "Player link code expired"));
+    }
+
+    if !challenge.proven {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "The player did not name a building with the code yet",
+        ));
+    }
+
+    let mut account: entity::account::ActiveModel = authenticated.account.into();
+    account.player_entity_id = Set(Some(challenge.player_entity_id));
+    account.player_linked_at = Set(Some(Utc::now()));
+    let account = account
+        .update(&state.conn)
+        .await
+        .map_err(|error| match error.sql_err() {
+            Some(SqlErr::UniqueConstraintViolation(_)) => ApiError::new(
+                StatusCode::CONFLICT,
+                "Player is already linked to an account",
+            ),
+            _ => internal_error(error),
+        })?;
+
+    state.player_link_challenges.remove(&account.id);
+
+    Ok(axum_codec::Codec(account.into()))
+}
+
+/// Called for every building rename. Anyone allowed to rename a building can set the code, so
+/// only a rename made by the challenged player's own identity proves the link.
+pub(crate) fn record_building_rename(state: &AppState, nickname: &str, caller: Identity) {
+    let nickname = nickname.trim();
+    if !nickname.starts_with(PLAYER_LINK_CODE_PREFIX) {
+        return;
+    }
+
+    let Some(player_entity_id) = state
+        .user_state
+        .get(&caller)
+        .map(|entity_id| *entity_id as i64)
+    else {
+        return;
+    };
+
+    for mut challenge in state.player_link_challenges.iter_mut() {
+        if challenge.code == nickname && challenge.player_entity_id == player_entity_id {
+            challenge.proven = true;
+        }
+    }
+}
+
+/// Expired sessions are never used again, they only have to be deleted at some point.
+pub(crate) fn start_session_cleanup(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60 * 60));
+
+        loop {
+            interval.tick().await;
+
+            let now = Utc::now();
+            state
+                .player_link_challenges
+                .retain(|_, challenge| challenge.expires_at >= now);
+
+            if let Err(error) = entity::account_session::Entity::delete_many()
+                .filter(entity::account_session::Column::ExpiresAt.lt(now))
+                .exec(&state.conn)
+                .await
+            {
+                error!("Could not delete expired account sessions: {error}");
+            }
+        }
+    });
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/bitcraft/accounts/player-link",
+    tag = "accounts",
+    security(("api_key" = []), ("bearer" = []), ("session" = [])),
+    responses(
+        (status = NO_CONTENT, description = "Player unlinked"),
+        (status = UNAUTHORIZED, description = "Authentication required"),
+    )
+)]
+pub(crate) async fn unlink_player(
+    state: State<AppState>,
+    authenticated: AuthenticatedAccount,
+) -> Result<StatusCode, ApiError> {
+    state
+        .player_link_challenges
+        .remove(&authenticated.account.id);
+
+    let mut account: entity::account::ActiveModel = authenticated.account.into();
+    account.player_entity_id = Set(None);
+    account.player_linked_at = Set(None);
+    account.update(&state.conn).await.map_err(internal_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_key_is_read_from_either_header() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", "bh_one".parse().unwrap());
+        assert_eq!(api_key_from_headers(&headers), Some("bh_one"));
+
+        headers.insert(header::AUTHORIZATION, "Bearer bh_two".parse().unwrap());
+        assert_eq!(api_key_from_headers(&headers), Some("bh_two"));
+    }
+
+    #[test]
+    fn only_renames_by_the_player_prove_a_link() {
+        let state = AppState::new(
+            sea_orm::DatabaseConnection::Disconnected,
+            tokio::sync::mpsc::unbounded_channel().0,
+            std::collections::HashMap::new(),
+            crate::config::RateLimitConfig::default(),
+            &crate::config::GraphqlConfig::default(),
+        );
+        let player = Identity::from_byte_array([1; 32]);
+        let claim_member = Identity::from_byte_array([2; 32]);
+        state.user_state.insert(player, 10);
+        state.user_state.insert(claim_member, 11);
+        let code = player_link_code();
+        state.player_link_challenges.insert(
+            1,
+            PlayerLinkChallenge {
+                player_entity_id: 10,
+                code: code.clone(),
+                expires_at: Utc::now() + Duration::minutes(PLAYER_LINK_LIFETIME_MINUTES),
+                proven: false,
+            },
+        );
+
+        record_building_rename(&state, &code, claim_member);
+        assert!(!state.player_link_challenges.get(&1).unwrap().proven);
+
+        record_building_rename(&state, "HUB-OTHER123", player);
+        assert!(!state.player_link_challenges.get(&1).unwrap().proven);
+
+        record_building_rename(&state, &format!(" {code} "), player);
+        assert!(state.player_link_challenges.get(&1).unwrap().proven);
+    }
+
+    #[test]
+    fn player_link_codes_fit_a_nickname() {
+        let code = player_link_code();
+        assert_eq!(code.len(), 12);
+        assert!(code.chars().all(|c| c == '-' || c.is_ascii_alphanumeric()));
+    }
+}
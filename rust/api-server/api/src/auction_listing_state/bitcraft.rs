@@ -1,17 +1,20 @@
 use crate::AppState;
+use crate::websocket::ingest_queue::IngestReceiver;
+use crate::websocket::reconcile::reconcile_map;
 use crate::websocket::{SpacetimeUpdateMessages, WebSocketMessages, record_worker_received};
+use entity::inventory_changelog::TypeOfChange;
+use entity::market_order_changelog::MarketOrderType;
 use game_module::module_bindings::AuctionListingState;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use sea_orm::{EntityTrait, sea_query};
 use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::sleep;
 
 pub(crate) fn start_worker_sell_order_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<AuctionListingState>>,
-    _batch_size: usize,
+    mut rx: IngestReceiver<AuctionListingState>,
+    batch_size: usize,
     time_limit: Duration,
 ) {
     tokio::spawn(async move {
@@ -27,9 +30,12 @@ pub(crate) fn start_worker_sell_order_state(
         //     ])
         //     .to_owned();
 
+        // Kept across batches, so changes that could not be inserted are retried
+        let mut messages = Vec::new();
+
         loop {
-            // let mut messages = Vec::new();
             // let mut ids = vec![];
+            let mut written = true;
             let timer = sleep(time_limit);
             tokio::pin!(timer);
 
@@ -39,11 +45,28 @@ pub(crate) fn start_worker_sell_order_state(
                         record_worker_received("sell_order_state", 1);
                         match msg {
                             SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
-                                data.into_par_iter().for_each(|value| {
-                                    let model: ::entity::auction_listing_state::AuctionListingState = ::entity::auction_listing_state::AuctionListingStateBuilder::new(value).with_region(database_name.to_string().replace("bitcraft-live-", "").parse().unwrap()).build();
-                                    let _ = global_app_state.tx.send(WebSocketMessages::InsertSellOrder(model.clone()));
-                                    global_app_state.sell_order_state.insert(model.entity_id as i64, model);
-                                });
+                                let snapshot = data
+                                    .into_par_iter()
+                                    .map(|value| ::entity::auction_listing_state::AuctionListingStateBuilder::new(value).with_region(database_name).build())
+                                    .collect::<Vec<_>>();
+                                let reconciliation = reconcile_map(
+                                    "sell_order_state",
+                                    &global_app_state.sell_order_state,
+                                    database_name,
+                                    snapshot,
+                                    |model| model.entity_id as i64,
+                                    |model| model.region,
+                                );
+
+                                for model in reconciliation.inserted {
+                                    let _ = global_app_state.tx.send(WebSocketMessages::InsertSellOrder(model));
+                                }
+                                for model in reconciliation.updated {
+                                    let _ = global_app_state.tx.send(WebSocketMessages::UpdateSellOrder(model));
+                                }
+                                for model in reconciliation.deleted {
+                                    let _ = global_app_state.tx.send(WebSocketMessages::RemoveSellOrder(model));
+                                }
 
                                 // let mut local_messages = vec![];
                                 // let mut trade_order = ::entity::trade_order::Entity::find()
@@ -93,6 +116,10 @@ pub(crate) fn start_worker_sell_order_state(
                                 let model: ::entity::auction_listing_state::AuctionListingState = ::entity::auction_listing_state::AuctionListingStateBuilder::new(new).with_region(database_name.to_string().replace("bitcraft-live-", "").parse().unwrap()).build();
                                 global_app_state.sell_order_state.insert(model.entity_id as i64, model.clone());
                                 let _ = global_app_state.tx.send(WebSocketMessages::InsertSellOrder(model.clone()));
+                                messages.push(::entity::market_order_changelog::ModelBuilder::new(model, MarketOrderType::Sell, TypeOfChange::Add).with_reducer_name(reducer_name).build());
+                                if messages.len() >= batch_size {
+                                    break;
+                                }
 
                                 // tracing::warn!("Insert sell reducer {:?}", reducer_name);
 
@@ -126,6 +153,10 @@ pub(crate) fn start_worker_sell_order_state(
                                 // }
 
                                 let _ = global_app_state.tx.send(WebSocketMessages::UpdateSellOrder(model.clone()));
+                                messages.push(::entity::market_order_changelog::ModelBuilder::new(model, MarketOrderType::Sell, TypeOfChange::Update).with_old_quantity(old.quantity).with_reducer_name(reducer_name).build());
+                                if messages.len() >= batch_size {
+                                    break;
+                                }
 
                                 // ids.push(model.entity_id);
                                 //
@@ -138,6 +169,10 @@ pub(crate) fn start_worker_sell_order_state(
                                 let model: ::entity::auction_listing_state::AuctionListingState = ::entity::auction_listing_state::AuctionListingStateBuilder::new(delete).with_region(database_name.to_string().replace("bitcraft-live-", "").parse().unwrap()).build();
                                 global_app_state.sell_order_state.remove(&(model.entity_id as i64));
                                 let _ = global_app_state.tx.send(WebSocketMessages::RemoveSellOrder(model.clone()));
+                                messages.push(::entity::market_order_changelog::ModelBuilder::new(model, MarketOrderType::Sell, TypeOfChange::Remove).with_reducer_name(reducer_name).build());
+                                if messages.len() >= batch_size {
+                                    break;
+                                }
                                 // tracing::warn!("Remove sell reducer {:?}", reducer_name);
 
 
@@ -168,12 +203,19 @@ pub(crate) fn start_worker_sell_order_state(
                 }
             }
 
-            // if !messages.is_empty() {
-            //tracing::info!("Processing {} messages in batch", messages.len());
+            if let Err(err) =
+                insert_multiple_market_order_changelog(&global_app_state, &mut messages).await
+            {
+                tracing::error!(
+                    changes = messages.len(),
+                    "Error inserting MarketOrderChangelog, retrying: {}",
+                    err
+                );
+                written = false;
+                sleep(time_limit).await;
+            }
 
-            // trade_order(&global_app_state, &on_conflict, &mut messages).await;
-            // Your batch processing logic here
-            // }
+            rx.commit_if(written);
 
             // If the channel is closed and we processed the last batch, exit the outer loop
             // if messages.is_empty() && rx.is_closed() {
@@ -184,6 +226,24 @@ pub(crate) fn start_worker_sell_order_state(
     });
 }
 
+/// Only clears `messages` once they are inserted, so a failed batch can be retried.
+async fn insert_multiple_market_order_changelog(
+    global_app_state: &AppState,
+    messages: &mut Vec<::entity::market_order_changelog::ActiveModel>,
+) -> Result<(), sea_orm::DbErr> {
+    if messages.is_empty() {
+        return Ok(());
+    }
+
+    ::entity::market_order_changelog::Entity::insert_many(messages.clone())
+        .exec(&global_app_state.conn)
+        .await?;
+
+    messages.clear();
+
+    Ok(())
+}
+
 #[allow(dead_code)]
 async fn insert_multiple_trade_order(
     global_app_state: &AppState,
@@ -204,8 +264,8 @@ async fn insert_multiple_trade_order(
 
 pub(crate) fn start_worker_buy_order_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<AuctionListingState>>,
-    _batch_size: usize,
+    mut rx: IngestReceiver<AuctionListingState>,
+    batch_size: usize,
     time_limit: Duration,
 ) {
     tokio::spawn(async move {
@@ -221,9 +281,12 @@ pub(crate) fn start_worker_buy_order_state(
         //     ])
         //     .to_owned();
 
+        // Kept across batches, so changes that could not be inserted are retried
+        let mut messages = Vec::new();
+
         loop {
-            // let mut messages = Vec::new();
             // let mut ids = vec![];
+            let mut written = true;
             let timer = sleep(time_limit);
             tokio::pin!(timer);
 
@@ -233,11 +296,28 @@ pub(crate) fn start_worker_buy_order_state(
                         record_worker_received("buy_order_state", 1);
                         match msg {
                             SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
-                                data.into_par_iter().for_each(|value| {
-                                    let model: ::entity::auction_listing_state::AuctionListingState = ::entity::auction_listing_state::AuctionListingStateBuilder::new(value).with_region(database_name.to_string().replace("bitcraft-live-", "").parse().unwrap()).build();
-                                    let _ = global_app_state.tx.send(WebSocketMessages::InsertBuyOrder(model.clone()));
-                                    global_app_state.buy_order_state.insert(model.entity_id as i64, model);
-                                });
+                                let snapshot = data
+                                    .into_par_iter()
+                                    .map(|value| ::entity::auction_listing_state::AuctionListingStateBuilder::new(value).with_region(database_name).build())
+                                    .collect::<Vec<_>>();
+                                let reconciliation = reconcile_map(
+                                    "buy_order_state",
+                                    &global_app_state.buy_order_state,
+                                    database_name,
+                                    snapshot,
+                                    |model| model.entity_id as i64,
+                                    |model| model.region,
+                                );
+
+                                for model in reconciliation.inserted {
+                                    let _ = global_app_state.tx.send(WebSocketMessages::InsertBuyOrder(model));
+                                }
+                                for model in reconciliation.updated {
+                                    let _ = global_app_state.tx.send(WebSocketMessages::UpdateBuyOrder(model));
+                                }
+                                for model in reconciliation.deleted {
+                                    let _ = global_app_state.tx.send(WebSocketMessages::RemoveBuyOrder(model));
+                                }
 
                                 // let mut local_messages = vec![];
                                 // let mut trade_order = ::entity::trade_order::Entity::find()
@@ -285,8 +365,12 @@ pub(crate) fn start_worker_buy_order_state(
                             }
                             SpacetimeUpdateMessages::Insert { new, database_name, reducer_name, .. } => {
                                 let model: ::entity::auction_listing_state::AuctionListingState = ::entity::auction_listing_state::AuctionListingStateBuilder::new(new).with_region(database_name.to_string().replace("bitcraft-live-", "").parse().unwrap()).build();
-                                    let _ = global_app_state.tx.send(WebSocketMessages::InsertBuyOrder(model.clone()));
-                                global_app_state.buy_order_state.insert(model.entity_id as i64, model);
+                                let _ = global_app_state.tx.send(WebSocketMessages::InsertBuyOrder(model.clone()));
+                                global_app_state.buy_order_state.insert(model.entity_id as i64, model.clone());
+                                messages.push(::entity::market_order_changelog::ModelBuilder::new(model, MarketOrderType::Buy, TypeOfChange::Add).with_reducer_name(reducer_name).build());
+                                if messages.len() >= batch_size {
+                                    break;
+                                }
                                 // tracing::warn!("Insert buy reducer {:?}", reducer_name);
                                 // if ids.contains(&model.entity_id) {
                                 //     if let Some(index) = messages.iter().position(|value: &::entity::trade_order::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
@@ -304,7 +388,11 @@ pub(crate) fn start_worker_buy_order_state(
                                 let model: ::entity::auction_listing_state::AuctionListingState = ::entity::auction_listing_state::AuctionListingStateBuilder::new(new).with_region(database_name.to_string().replace("bitcraft-live-", "").parse().unwrap()).build();
                                 let _ = global_app_state.tx.send(WebSocketMessages::UpdateBuyOrder(model.clone()));
                                 // tracing::warn!("Update buy reducer {:?} old: {} new: {}", reducer_name, old.quantity, model.quantity);
-                                global_app_state.buy_order_state.insert(model.entity_id as i64, model);
+                                global_app_state.buy_order_state.insert(model.entity_id as i64, model.clone());
+                                messages.push(::entity::market_order_changelog::ModelBuilder::new(model, MarketOrderType::Buy, TypeOfChange::Update).with_old_quantity(old.quantity).with_reducer_name(reducer_name).build());
+                                if messages.len() >= batch_size {
+                                    break;
+                                }
 
                                 // match event {
                                 //     spacetimedb_sdk::Event::Reducer(reducer) => {
@@ -333,6 +421,10 @@ pub(crate) fn start_worker_buy_order_state(
                                 let model: ::entity::auction_listing_state::AuctionListingState = ::entity::auction_listing_state::AuctionListingStateBuilder::new(delete).with_region(database_name.to_string().replace("bitcraft-live-", "").parse().unwrap()).build();
                                 let _ = global_app_state.tx.send(WebSocketMessages::RemoveBuyOrder(model.clone()));
                                 global_app_state.buy_order_state.remove(&(model.entity_id as i64));
+                                messages.push(::entity::market_order_changelog::ModelBuilder::new(model, MarketOrderType::Buy, TypeOfChange::Remove).with_reducer_name(reducer_name).build());
+                                if messages.len() >= batch_size {
+                                    break;
+                                }
 
                                 // tracing::warn!("Remove buy reducer {:?}", reducer_name);
                                 // let id = model.entity_id;
@@ -362,12 +454,19 @@ pub(crate) fn start_worker_buy_order_state(
                 }
             }
 
-            // if !messages.is_empty() {
-            //tracing::info!("Processing {} messages in batch", messages.len());
+            if let Err(err) =
+                insert_multiple_market_order_changelog(&global_app_state, &mut messages).await
+            {
+                tracing::error!(
+                    changes = messages.len(),
+                    "Error inserting MarketOrderChangelog, retrying: {}",
+                    err
+                );
+                written = false;
+                sleep(time_limit).await;
+            }
 
-            // trade_order(&global_app_state, &on_conflict, &mut messages).await;
-            // Your batch processing logic here
-            // }
+            rx.commit_if(written);
 
             // If the channel is closed and we processed the last batch, exit the outer loop
             // if messages.is_empty() && rx.is_closed() {
@@ -1,13 +1,19 @@
 pub(crate) mod bitcraft;
 
+use crate::error::ApiError;
 use crate::{AppRouter, AppState};
 use axum::Router;
 use axum::extract::{Query, State};
 use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
 use entity::inventory::ItemType;
+use entity::market_order_changelog::MarketPriceCandle;
+use log::error;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use service::Query as QueryCore;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new()
@@ -23,15 +29,24 @@ pub(crate) fn get_routes() -> AppRouter {
             "/market/item_cargo_desc",
             axum_codec::routing::get(market_item_cargo_desc).into(),
         )
+        .route(
+            "/market/history",
+            axum_codec::routing::get(get_market_price_history).into(),
+        )
+        .route(
+            "/market/depth",
+            axum_codec::routing::get(get_market_depth).into(),
+        )
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct MarketOrdersParams {
     items: Option<String>,
     return_all: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema, Default)]
 #[ts(export)]
 pub(crate) struct MarketOrderStats {
     buy: u64,
@@ -47,13 +62,13 @@ pub(crate) struct MarketOrderStats {
     sell_amount_highest: Option<i32>,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct MarketOrderStatsResponse {
     order_counts: HashMap<String, MarketOrderStats>,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct MarketOrdersResponse {
     buy_orders: HashMap<String, Vec<entity::auction_listing_state::AuctionListingState>>,
@@ -70,9 +85,224 @@ fn parse_selected_item_keys(items: Option<&str>) -> HashSet<String> {
         .collect()
 }
 
+fn parse_item_key(item: &str) -> Option<(i32, i32)> {
+    let (item_type, item_id) = item.trim().split_once(':')?;
+
+    Some((item_type.parse().ok()?, item_id.parse().ok()?))
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, TS, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub(crate) enum MarketHistoryBucket {
+    #[default]
+    Hour,
+    Day,
+    Week,
+}
+
+impl MarketHistoryBucket {
+    fn width(&self) -> chrono::Duration {
+        match self {
+            MarketHistoryBucket::Hour => chrono::Duration::hours(1),
+            MarketHistoryBucket::Day => chrono::Duration::days(1),
+            MarketHistoryBucket::Week => chrono::Duration::weeks(1),
+        }
+    }
+
+    /// Time range used when the request does not specify a `start`.
+    fn default_range(&self) -> chrono::Duration {
+        match self {
+            MarketHistoryBucket::Hour => chrono::Duration::days(7),
+            MarketHistoryBucket::Day => chrono::Duration::days(90),
+            MarketHistoryBucket::Week => chrono::Duration::weeks(52),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct MarketHistoryParams {
+    item: String,
+    region: Option<entity::shared::Region>,
+    bucket: Option<MarketHistoryBucket>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct MarketPriceHistoryResponse {
+    item: String,
+    region: Option<entity::shared::Region>,
+    bucket: MarketHistoryBucket,
+    candles: Vec<MarketPriceCandle>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/market/history",
+    tag = "market",
+    params(MarketHistoryParams),
+    responses(
+        (status = OK, body = MarketPriceHistoryResponse),
+        (status = BAD_REQUEST, description = "Invalid item key"),
+    )
+)]
+pub(crate) async fn get_market_price_history(
+    state: State<AppState>,
+    Query(params): Query<MarketHistoryParams>,
+) -> Result<axum_codec::Codec<MarketPriceHistoryResponse>, ApiError> {
+    let Some((item_type, item_id)) = parse_item_key(&params.item) else {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "Invalid item key"));
+    };
+
+    let bucket = params.bucket.unwrap_or_default();
+    let end = params.end.unwrap_or_else(Utc::now);
+    let start = params.start.unwrap_or(end - bucket.default_range());
+
+    if start > end {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "start must be before end",
+        ));
+    }
+
+    let candles = QueryCore::get_market_price_candles(
+        &state.conn,
+        item_type,
+        item_id,
+        params.region,
+        bucket.width(),
+        start,
+        end,
+    )
+    .await
+    .map_err(|e| {
+        error!("Error: {e:?}");
+
+        (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+    })?;
+
+    Ok(axum_codec::Codec(MarketPriceHistoryResponse {
+        item: format!("{item_type}:{item_id}"),
+        region: params.region,
+        bucket,
+        candles,
+    }))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct MarketDepthParams {
+    item: String,
+    region: Option<entity::shared::Region>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct MarketDepthLevel {
+    price: i32,
+    quantity: i64,
+    orders: u64,
+    cumulative_quantity: i64,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct MarketDepthResponse {
+    item: String,
+    region: Option<entity::shared::Region>,
+    buy: Vec<MarketDepthLevel>,
+    sell: Vec<MarketDepthLevel>,
+}
+
+fn collect_depth_levels<'a>(
+    orders: impl Iterator<Item = &'a entity::auction_listing_state::AuctionListingState>,
+    descending: bool,
+) -> Vec<MarketDepthLevel> {
+    let mut levels = BTreeMap::<i32, (i64, u64)>::new();
+
+    for order in orders {
+        let level = levels.entry(order.price_threshold).or_default();
+        level.0 += order.quantity as i64;
+        level.1 += 1;
+    }
+
+    let mut cumulative_quantity = 0;
+    let mut build_level = |(price, (quantity, orders)): (i32, (i64, u64))| {
+        cumulative_quantity += quantity;
+        MarketDepthLevel {
+            price,
+            quantity,
+            orders,
+            cumulative_quantity,
+        }
+    };
+
+    if descending {
+        levels.into_iter().rev().map(&mut build_level).collect()
+    } else {
+        levels.into_iter().map(&mut build_level).collect()
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/market/depth",
+    tag = "market",
+    params(MarketDepthParams),
+    responses(
+        (status = OK, body = MarketDepthResponse),
+        (status = BAD_REQUEST, description = "Invalid item key"),
+    )
+)]
+pub(crate) async fn get_market_depth(
+    state: State<AppState>,
+    Query(params): Query<MarketDepthParams>,
+) -> Result<axum_codec::Codec<MarketDepthResponse>, ApiError> {
+    let Some((item_type, item_id)) = parse_item_key(&params.item) else {
+        return Err(ApiError::new(StatusCode::BAD_REQUEST, "Invalid item key"));
+    };
+
+    let matches = |order: &entity::auction_listing_state::AuctionListingState| {
+        order.item_type == item_type
+            && order.item_id == item_id
+            && params.region.is_none_or(|region| order.region == region)
+    };
+
+    let buy_orders = state
+        .buy_order_state
+        .iter()
+        .filter(|order| matches(order.value()))
+        .map(|order| order.value().clone())
+        .collect::<Vec<_>>();
+    let sell_orders = state
+        .sell_order_state
+        .iter()
+        .filter(|order| matches(order.value()))
+        .map(|order| order.value().clone())
+        .collect::<Vec<_>>();
+
+    Ok(axum_codec::Codec(MarketDepthResponse {
+        item: format!("{item_type}:{item_id}"),
+        region: params.region,
+        buy: collect_depth_levels(buy_orders.iter(), true),
+        sell: collect_depth_levels(sell_orders.iter(), false),
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/market",
+    tag = "market",
+    responses(
+        (status = OK, body = MarketOrderStatsResponse),
+    )
+)]
 pub(crate) async fn get_market_order_stats(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<MarketOrderStatsResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<MarketOrderStatsResponse>, ApiError> {
     let mut order_counts = HashMap::<String, MarketOrderStats>::new();
 
     for order in state.buy_order_state.iter() {
@@ -132,10 +362,19 @@ pub(crate) async fn get_market_order_stats(
     Ok(axum_codec::Codec(MarketOrderStatsResponse { order_counts }))
 }
 
+#[utoipa::path(
+    get,
+    path = "/market/orders",
+    tag = "market",
+    params(MarketOrdersParams),
+    responses(
+        (status = OK, body = MarketOrdersResponse),
+    )
+)]
 pub(crate) async fn find_market_place_order(
     state: State<AppState>,
     Query(params): Query<MarketOrdersParams>,
-) -> Result<axum_codec::Codec<MarketOrdersResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<MarketOrdersResponse>, ApiError> {
     let selected_item_keys = parse_selected_item_keys(params.items.as_deref());
 
     if let Some(item) = params.return_all {
@@ -192,7 +431,7 @@ pub(crate) async fn find_market_place_order(
     }))
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, TS)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, TS, ToSchema)]
 pub struct ItemOption {
     pub label: String,
     pub name: String,
@@ -205,16 +444,24 @@ pub struct ItemOption {
     pub rarity: String,
 }
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, TS)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct MarketItemCargoDescResponse {
     pub items_grouped: HashMap<String, Vec<ItemOption>>,
     pub item_name_by_key: HashMap<String, String>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/market/item_cargo_desc",
+    tag = "market",
+    responses(
+        (status = OK, body = MarketItemCargoDescResponse),
+    )
+)]
 pub(crate) async fn market_item_cargo_desc(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<MarketItemCargoDescResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<MarketItemCargoDescResponse>, ApiError> {
     let mut items_grouped = HashMap::new();
     let mut item_name_by_key = HashMap::new();
 
@@ -1,4 +1,8 @@
 use crate::AppState;
+use crate::accounts::record_building_rename;
+use crate::claims::events::{ClaimEvent, publish_claim_event};
+use crate::desc_cache::DescTable;
+use crate::websocket::ingest_queue::IngestReceiver;
 use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
 use entity::building_state::ActiveModel;
 use game_module::module_bindings::{BuildingDesc, BuildingNicknameState, BuildingState};
@@ -6,12 +10,11 @@ use migration::{OnConflict, sea_query};
 use sea_orm::{ColumnTrait, DbErr, EntityTrait, InsertResult, IntoActiveModel, QueryFilter};
 use std::collections::HashMap;
 use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::sleep;
 
 pub(crate) fn start_worker_building_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<BuildingState>>,
+    mut rx: IngestReceiver<BuildingState>,
     batch_size: usize,
     time_limit: Duration,
 ) {
@@ -30,6 +33,9 @@ pub(crate) fn start_worker_building_state(
         loop {
             let mut messages = Vec::with_capacity(batch_size + 10);
             let mut messages_delete = Vec::with_capacity(batch_size + 10);
+            // Only constructed and destroyed buildings change the counts
+            let mut counts_changed = false;
+            let mut written = true;
             let timer = sleep(time_limit);
             tokio::pin!(timer);
 
@@ -62,6 +68,8 @@ pub(crate) fn start_worker_building_state(
                                 }) {
                                     use std::collections::hash_map::Entry;
 
+                                    track_building_claim(&global_app_state, &model);
+
                                     match currently_known_building_state.entry(model.entity_id) {
                                         Entry::Occupied(entry) => {
                                             let existing_model = entry.get();
@@ -79,7 +87,8 @@ pub(crate) fn start_worker_building_state(
                                        let insert = insert_multiple_building_state(&global_app_state, &on_conflict, &mut local_messages).await;
 
                                         if let Err(err) = insert {
-                                            tracing::error!("Error inserting ItemListDesc: {}", err)
+                                            tracing::error!("Error inserting ItemListDesc: {}", err);
+                                            written = false;
                                         }
                                     }
                                 };
@@ -87,7 +96,8 @@ pub(crate) fn start_worker_building_state(
                                     let insert = insert_multiple_building_state(&global_app_state, &on_conflict, &mut local_messages).await;
 
                                     if let Err(err) = insert {
-                                        tracing::error!("Error inserting ItemListDesc: {}", err)
+                                        tracing::error!("Error inserting ItemListDesc: {}", err);
+                                        written = false;
                                     }
                                 }
 
@@ -100,12 +110,26 @@ pub(crate) fn start_worker_building_state(
                                     {
                                         let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
                                         tracing::error!(BuildingState = chunk_ids_str.join(","), error = error.to_string(), "Could not delete BuildingState");
+                                        written = false;
                                     }
                                 }
+                                global_app_state.desc_cache.bump(DescTable::BuildingCount);
                             }
                             SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
+                                counts_changed = true;
                                 let model: ::entity::building_state::Model = ::entity::building_state::ModelBuilder::new(new).with_region(database_name).build();
 
+                                track_building_claim(&global_app_state, &model);
+                                publish_claim_event(
+                                    &global_app_state,
+                                    model.claim_entity_id,
+                                    ClaimEvent::BuildingConstructed {
+                                        entity_id: model.entity_id,
+                                        building_description_id: model.building_description_id,
+                                        constructed_by_player_entity_id: model.constructed_by_player_entity_id,
+                                    },
+                                );
+
                                 if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
                                     messages_delete.remove(index);
                                 }
@@ -122,6 +146,8 @@ pub(crate) fn start_worker_building_state(
                             SpacetimeUpdateMessages::Update { new, database_name, .. } => {
                                 let model: ::entity::building_state::Model = ::entity::building_state::ModelBuilder::new(new).with_region(database_name).build();
 
+                                track_building_claim(&global_app_state, &model);
+
                                 if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
                                     messages_delete.remove(index);
                                 }
@@ -136,9 +162,20 @@ pub(crate) fn start_worker_building_state(
                                 }
                             }
                             SpacetimeUpdateMessages::Remove { delete, database_name, .. } => {
+                                counts_changed = true;
                                 let model: ::entity::building_state::Model = ::entity::building_state::ModelBuilder::new(delete).with_region(database_name).build();
                                 let id = model.entity_id;
 
+                                global_app_state.building_claim_id.remove(&id);
+                                publish_claim_event(
+                                    &global_app_state,
+                                    model.claim_entity_id,
+                                    ClaimEvent::BuildingDestroyed {
+                                        entity_id: model.entity_id,
+                                        building_description_id: model.building_description_id,
+                                    },
+                                );
+
                                 if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
                                     messages.remove(index);
                                 }
@@ -170,7 +207,8 @@ pub(crate) fn start_worker_building_state(
                         .await;
 
                 if let Err(err) = insert {
-                    tracing::error!("Error inserting ItemListDesc: {}", err)
+                    tracing::error!("Error inserting ItemListDesc: {}", err);
+                    written = false;
                 }
 
                 // Your batch processing logic here
@@ -192,11 +230,18 @@ pub(crate) fn start_worker_building_state(
                             error = error.to_string(),
                             "Could not delete BuildingState"
                         );
+                        written = false;
                     }
                 }
                 messages_delete.clear();
             }
 
+            if counts_changed {
+                global_app_state.desc_cache.bump(DescTable::BuildingCount);
+            }
+
+            rx.commit_if(written);
+
             // If the channel is closed and we processed the last batch, exit the outer loop
             if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
                 break;
@@ -205,6 +250,16 @@ pub(crate) fn start_worker_building_state(
     });
 }
 
+fn track_building_claim(global_app_state: &AppState, model: &::entity::building_state::Model) {
+    if model.claim_entity_id == 0 {
+        global_app_state.building_claim_id.remove(&model.entity_id);
+    } else {
+        global_app_state
+            .building_claim_id
+            .insert(model.entity_id, model.claim_entity_id);
+    }
+}
+
 async fn insert_multiple_building_state(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
@@ -222,7 +277,7 @@ async fn insert_multiple_building_state(
 
 pub(crate) fn start_worker_building_desc(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<BuildingDesc>>,
+    mut rx: IngestReceiver<BuildingDesc>,
     batch_size: usize,
     time_limit: Duration,
 ) {
@@ -256,6 +311,7 @@ pub(crate) fn start_worker_building_desc(
         loop {
             let mut messages = Vec::with_capacity(batch_size + 10);
             let mut messages_delete = Vec::with_capacity(batch_size + 10);
+            let mut written = true;
             let timer = sleep(time_limit);
             tokio::pin!(timer);
 
@@ -300,11 +356,11 @@ pub(crate) fn start_worker_building_desc(
                                         }
                                     }
                                     if local_messages.len() >= batch_size {
-                                       insert_multiple_build_desc(&global_app_state, &on_conflict, &mut local_messages).await;
+                                       written &= insert_multiple_build_desc(&global_app_state, &on_conflict, &mut local_messages).await;
                                     }
                                 };
                                 if !local_messages.is_empty() {
-                                    insert_multiple_build_desc(&global_app_state, &on_conflict, &mut local_messages).await;
+                                    written &= insert_multiple_build_desc(&global_app_state, &on_conflict, &mut local_messages).await;
                                 }
 
                                 for chunk_ids in currently_known_building_desc.into_keys().collect::<Vec<_>>().chunks(1000) {
@@ -312,8 +368,10 @@ pub(crate) fn start_worker_building_desc(
                                     if let Err(error) = ::entity::building_desc::Entity::delete_many().filter(::entity::building_desc::Column::Id.is_in(chunk_ids.clone())).exec(&global_app_state.conn).await {
                                         let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
                                         tracing::error!(BuildingDesc = chunk_ids_str.join(","), error = error.to_string(), "Could not delete BuildingDesc");
+                                        written = false;
                                     }
                                 }
+                                global_app_state.desc_cache.bump(DescTable::BuildingDesc);
                             }
                             SpacetimeUpdateMessages::Insert { new, .. } => {
                                 let model: ::entity::building_desc::Model = new.into();
@@ -366,13 +424,17 @@ pub(crate) fn start_worker_building_desc(
                 }
             }
 
+            let changed = !messages.is_empty() || !messages_delete.is_empty();
+
             if !messages.is_empty() {
                 tracing::debug!(
                     "BuildingDesc ->>>> Processing {} messages in batch",
                     messages.len()
                 );
 
-                insert_multiple_build_desc(&global_app_state, &on_conflict, &mut messages).await;
+                written &=
+                    insert_multiple_build_desc(&global_app_state, &on_conflict, &mut messages)
+                        .await;
                 // Your batch processing logic here
             }
 
@@ -392,11 +454,18 @@ pub(crate) fn start_worker_building_desc(
                             error = error.to_string(),
                             "Could not delete BuildingDesc"
                         );
+                        written = false;
                     }
                 }
                 messages_delete.clear();
             }
 
+            if changed {
+                global_app_state.desc_cache.bump(DescTable::BuildingDesc);
+            }
+
+            rx.commit_if(written);
+
             // If the channel is closed and we processed the last batch, exit the outer loop
             if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
                 break;
@@ -405,26 +474,28 @@ pub(crate) fn start_worker_building_desc(
     });
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_build_desc(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::building_desc::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::building_desc::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting BuildingDesc: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
 }
 
 pub(crate) fn start_worker_building_nickname_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<BuildingNicknameState>>,
+    mut rx: IngestReceiver<BuildingNicknameState>,
     batch_size: usize,
     time_limit: Duration,
 ) {
@@ -440,6 +511,7 @@ pub(crate) fn start_worker_building_nickname_state(
         loop {
             let mut messages = Vec::with_capacity(batch_size + 10);
             let mut messages_delete = Vec::with_capacity(batch_size + 10);
+            let mut written = true;
             let timer = sleep(time_limit);
             tokio::pin!(timer);
 
@@ -485,11 +557,11 @@ pub(crate) fn start_worker_building_nickname_state(
                                         }
                                     }
                                     if local_messages.len() >= batch_size {
-                                       insert_multiple_building_nickname_state(&global_app_state, &on_conflict, &mut local_messages).await;
+                                       written &= insert_multiple_building_nickname_state(&global_app_state, &on_conflict, &mut local_messages).await;
                                     }
                                 };
                                 if !local_messages.is_empty() {
-                                    insert_multiple_building_nickname_state(&global_app_state, &on_conflict, &mut local_messages).await;
+                                    written &= insert_multiple_building_nickname_state(&global_app_state, &on_conflict, &mut local_messages).await;
                                 }
 
                                 for chunk_ids in currently_known_building_nickname_state.into_keys().collect::<Vec<_>>().chunks(1000) {
@@ -501,12 +573,16 @@ pub(crate) fn start_worker_building_nickname_state(
                                     {
                                         let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
                                         tracing::error!(BuildingNicknameState = chunk_ids_str.join(","), error = error.to_string(), "Could not delete BuildingNicknameState");
+                                        written = false;
                                     }
                                 }
                             }
-                            SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
+                            SpacetimeUpdateMessages::Insert { new, database_name, event, .. } => {
                                 let model: ::entity::building_nickname_state::Model = ::entity::building_nickname_state::ModelBuilder::new(new).with_region(database_name).build();
 
+                                if let Some(event) = event {
+                                    record_building_rename(&global_app_state, &model.nickname, event.caller_identity);
+                                }
                                 global_app_state.building_nickname_state.insert(model.entity_id, model.clone());
                                 if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
                                     messages_delete.remove(index);
@@ -516,8 +592,11 @@ pub(crate) fn start_worker_building_nickname_state(
                                     break;
                                 }
                             }
-                            SpacetimeUpdateMessages::Update { new, database_name, .. } => {
+                            SpacetimeUpdateMessages::Update { new, database_name, event, .. } => {
                                 let model: ::entity::building_nickname_state::Model = ::entity::building_nickname_state::ModelBuilder::new(new).with_region(database_name).build();
+                                if let Some(event) = event {
+                                    record_building_rename(&global_app_state, &model.nickname, event.caller_identity);
+                                }
                                 global_app_state.building_nickname_state.insert(model.entity_id, model.clone());
                                 if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
                                     messages_delete.remove(index);
@@ -560,7 +639,7 @@ pub(crate) fn start_worker_building_nickname_state(
                     "BuildingNicknameState ->>>> Processing {} messages in batch",
                     messages.len()
                 );
-                insert_multiple_building_nickname_state(
+                written &= insert_multiple_building_nickname_state(
                     &global_app_state,
                     &on_conflict,
                     &mut messages,
@@ -588,11 +667,14 @@ pub(crate) fn start_worker_building_nickname_state(
                             error = error.to_string(),
                             "Could not delete BuildingNicknameState"
                         );
+                        written = false;
                     }
                 }
                 messages_delete.clear();
             }
 
+            rx.commit_if(written);
+
             // If the channel is closed and we processed the last batch, exit the outer loop
             if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
                 break;
@@ -601,19 +683,21 @@ pub(crate) fn start_worker_building_nickname_state(
     });
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_building_nickname_state(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::building_nickname_state::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::building_nickname_state::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting BuildingNicknameState: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
 }
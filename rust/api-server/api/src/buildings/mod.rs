@@ -1,13 +1,21 @@
 pub(crate) mod bitcraft;
 
+use crate::desc_cache::{CachedRequest, DescTable};
+use crate::error::{ApiError, ErrorResponse};
+use crate::pagination::{ListParams, ListQuery, Paginated};
 use crate::{AppRouter, AppState, Params};
 use axum::Router;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use axum::response::Response;
 use entity::{building_desc, building_state};
+use log::error;
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::{IntoSimpleExpr, Order};
 use serde::{Deserialize, Serialize};
 use service::Query as QueryCore;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new()
@@ -29,11 +37,11 @@ pub(crate) fn get_routes() -> AppRouter {
         )
         .route(
             "/api/bitcraft/desc/buildings",
-            axum_codec::routing::get(find_building_descriptions).into(),
+            axum::routing::get(find_building_descriptions),
         )
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, ToSchema)]
 pub(crate) struct BuildingDescriptionsResponse {
     buildings: Vec<building_desc::ApiResponse>,
     per_page: u64,
@@ -41,10 +49,36 @@ pub(crate) struct BuildingDescriptionsResponse {
     page: u64,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/desc/buildings",
+    tag = "buildings",
+    params(Params),
+    responses(
+        (status = OK, body = BuildingDescriptionsResponse),
+        (status = NOT_MODIFIED, description = "The `If-None-Match` ETag is still current"),
+    )
+)]
 pub(crate) async fn find_building_descriptions(
     state: State<AppState>,
     Query(params): Query<Params>,
-) -> Result<axum_codec::Codec<BuildingDescriptionsResponse>, (StatusCode, &'static str)> {
+    request: CachedRequest,
+) -> Result<Response, ApiError> {
+    state
+        .desc_cache
+        .respond(
+            request,
+            &[DescTable::BuildingDesc, DescTable::BuildingCount],
+            || building_descriptions_page(&state, params),
+        )
+        .await
+}
+
+/// A page of building descriptions, each with how many of it are built.
+async fn building_descriptions_page(
+    state: &AppState,
+    params: Params,
+) -> Result<BuildingDescriptionsResponse, ApiError> {
     let page = params.page.unwrap_or(1);
     let per_page = params.per_page.unwrap_or(30);
     let search = params.search;
@@ -86,28 +120,75 @@ pub(crate) async fn find_building_descriptions(
         })
         .collect::<Vec<building_desc::ApiResponse>>();
 
-    Ok(axum_codec::Codec(BuildingDescriptionsResponse {
+    Ok(BuildingDescriptionsResponse {
         buildings,
         per_page,
         total: posts.1.number_of_items,
         page,
-    }))
+    })
 }
 
+/// Building descriptions by id, with how many of each are built.
+#[utoipa::path(
+    get,
+    path = "/api/v1/building-descriptions",
+    tag = "buildings",
+    params(ListParams),
+    responses(
+        (status = OK, body = Paginated<building_desc::ApiResponse>),
+        (status = BAD_REQUEST, body = ErrorResponse),
+    )
+)]
+pub(crate) async fn list_building_descriptions(
+    state: State<AppState>,
+    list: ListQuery,
+) -> Result<axum_codec::Codec<Paginated<building_desc::ApiResponse>>, ApiError> {
+    list.no_sort()?;
+
+    let response = building_descriptions_page(
+        &state,
+        Params {
+            page: Some(list.page),
+            per_page: Some(list.per_page),
+            search: list.search.clone(),
+        },
+    )
+    .await?;
+
+    Ok(axum_codec::Codec(Paginated::new(
+        response.buildings,
+        &list,
+        response.total,
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/desc/buildings/{id}",
+    tag = "buildings",
+    params(("id" = u64, Path, description = "Building description id")),
+    responses(
+        (status = OK, body = building_desc::Model),
+        (status = NOT_FOUND, description = "Building description not found"),
+    )
+)]
 pub(crate) async fn find_claim_description(
     state: State<AppState>,
     Path(id): Path<u64>,
-) -> Result<axum_codec::Codec<building_desc::Model>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<building_desc::Model>, ApiError> {
     let building_desc = state.building_desc.get(&(id as i64));
 
     if building_desc.is_none() {
-        return Err((StatusCode::NOT_FOUND, "BuildingDesc not found"));
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "BuildingDesc not found",
+        ));
     }
 
     Ok(axum_codec::Codec(building_desc.unwrap().to_owned()))
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct BuildingStateWithName {
     pub entity_id: i64,
@@ -119,7 +200,7 @@ pub(crate) struct BuildingStateWithName {
     pub location: Option<entity::location::Model>,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct BuildingStatesResponse {
     buildings: Vec<BuildingStateWithName>,
@@ -128,7 +209,8 @@ pub(crate) struct BuildingStatesResponse {
     page: u64,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct BuildingStatesParams {
     page: Option<u64>,
     per_page: Option<u64>,
@@ -137,145 +219,224 @@ pub(crate) struct BuildingStatesParams {
     skip_static_buildings: Option<bool>,
 }
 
-pub(crate) async fn find_building_states(
-    state: State<AppState>,
-    Query(params): Query<BuildingStatesParams>,
-) -> Result<axum_codec::Codec<BuildingStatesResponse>, (StatusCode, &'static str)> {
-    let page = params.page.unwrap_or(1);
-    let per_page = params.per_page.unwrap_or(30);
-    let search = params.claim_entity_id;
-    let with_inventory = params.with_inventory.unwrap_or(false);
-    let skip_static_buildings = params.skip_static_buildings.unwrap_or(false);
-    let mut buildings_with_inventory_ids = None;
-    let mut buildings_without_static_buildings_ids: Option<Vec<_>> = None;
-
-    if skip_static_buildings {
-        buildings_without_static_buildings_ids = Some(
-            state
-                .building_desc
-                .iter()
-                .filter(|building_desc| {
-                    if building_desc.name.contains("Wall") {
-                        return false;
-                    }
-
-                    if building_desc.name.contains("Fence") {
-                        return false;
-                    }
-
-                    if building_desc.name.contains("Farming Field") {
-                        return false;
-                    }
-
-                    if building_desc.name.contains("Outdoor Garden Plot") {
-                        return false;
-                    }
-
-                    if building_desc.name.contains("Outdoor Planterbox") {
-                        return false;
-                    }
-
-                    if building_desc.name.contains("Outdoor Planter Boxes") {
-                        return false;
-                    }
-
-                    if building_desc.name.contains(" Gate") {
-                        return false;
-                    }
-
-                    true
-                })
-                .map(|building_desc| building_desc.clone().id)
-                .collect::<Vec<_>>(),
-        );
+/// Walls, fences, gates and fields, there are a lot of them and they rarely matter.
+fn is_static_building(name: &str) -> bool {
+    [
+        "Wall",
+        "Fence",
+        "Farming Field",
+        "Outdoor Garden Plot",
+        "Outdoor Planterbox",
+        "Outdoor Planter Boxes",
+        " Gate",
+    ]
+    .iter()
+    .any(|static_name| name.contains(static_name))
+}
+
+/// The building descriptions matching all given filters, `None` when nothing is filtered.
+fn filter_building_descriptions(
+    state: &AppState,
+    with_inventory: bool,
+    skip_static_buildings: bool,
+    search: Option<&str>,
+) -> Option<Vec<i64>> {
+    if !with_inventory && !skip_static_buildings && search.is_none() {
+        return None;
     }
 
-    if with_inventory {
-        let buildings_with_inventory = state
+    let search = search.map(str::to_lowercase);
+
+    Some(
+        state
             .building_desc
             .iter()
             .filter(|building_desc| {
-                building_desc
-                    .functions
-                    .iter()
-                    .any(|function| function.cargo_slots > 0 || function.storage_slots > 0)
+                (!with_inventory
+                    || building_desc
+                        .functions
+                        .iter()
+                        .any(|function| function.cargo_slots > 0 || function.storage_slots > 0))
+                    && (!skip_static_buildings || !is_static_building(&building_desc.name))
+                    && search
+                        .as_ref()
+                        .is_none_or(|search| building_desc.name.to_lowercase().contains(search))
             })
-            .map(|building_desc| building_desc.clone().id)
-            .collect::<Vec<_>>();
-
-        buildings_with_inventory_ids = Some(
-            if let Some(local_buildings_without_static_buildings_ids) =
-                &buildings_without_static_buildings_ids
-            {
-                buildings_with_inventory
-                    .into_iter()
-                    .filter(|id| local_buildings_without_static_buildings_ids.contains(id))
-                    .collect()
-            } else {
-                buildings_with_inventory
-            },
-        );
-    }
+            .map(|building_desc| building_desc.id)
+            .collect(),
+    )
+}
 
-    let posts = QueryCore::find_building_states(
+async fn find_buildings_with_name(
+    state: &AppState,
+    page: u64,
+    per_page: u64,
+    claim_entity_id: Option<i64>,
+    building_description_ids: Option<Vec<i64>>,
+    sort: Option<(SimpleExpr, Order)>,
+) -> Result<(Vec<BuildingStateWithName>, u64), ApiError> {
+    let (buildings, num_pages) = QueryCore::find_building_states(
         &state.conn,
         page,
         per_page,
-        search,
-        match (with_inventory, skip_static_buildings) {
-            (true, true) => buildings_with_inventory_ids,
-            (true, false) => buildings_with_inventory_ids,
-            (false, true) => buildings_without_static_buildings_ids,
-            (false, false) => None,
-        },
+        claim_entity_id,
+        building_description_ids,
+        sort,
     )
     .await
-    .expect("Cannot find posts in page");
+    .map_err(|error| {
+        error!("Error: {error}");
+
+        (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+    })?;
+
+    let buildings = buildings
+        .iter()
+        .map(|building_state| BuildingStateWithName {
+            entity_id: building_state.entity_id,
+            claim_entity_id: building_state.claim_entity_id,
+            direction_index: building_state.direction_index,
+            building_description_id: building_state.building_description_id,
+            constructed_by_player_entity_id: building_state.constructed_by_player_entity_id,
+            building_name: state
+                .building_nickname_state
+                .get(&(building_state.entity_id))
+                .map_or_else(
+                    || {
+                        state
+                            .building_desc
+                            .get(&(building_state.building_description_id as i64))
+                            .map_or("".into(), |building_desc| building_desc.name.clone())
+                    },
+                    |building_nickname_state| building_nickname_state.nickname.clone(),
+                ),
+            location: state
+                .location_state
+                .get(&(building_state.entity_id))
+                .map(|location_state| location_state.to_owned()),
+        })
+        .collect();
+
+    Ok((buildings, num_pages.number_of_items))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/buildings",
+    tag = "buildings",
+    params(BuildingStatesParams),
+    responses(
+        (status = OK, body = BuildingStatesResponse),
+    )
+)]
+pub(crate) async fn find_building_states(
+    state: State<AppState>,
+    Query(params): Query<BuildingStatesParams>,
+) -> Result<axum_codec::Codec<BuildingStatesResponse>, ApiError> {
+    let page = params.page.unwrap_or(1);
+    let per_page = params.per_page.unwrap_or(30);
+    let building_description_ids = filter_building_descriptions(
+        &state,
+        params.with_inventory.unwrap_or(false),
+        params.skip_static_buildings.unwrap_or(false),
+        None,
+    );
+
+    let (buildings, total) = find_buildings_with_name(
+        &state,
+        page,
+        per_page,
+        params.claim_entity_id,
+        building_description_ids,
+        None,
+    )
+    .await?;
 
     Ok(axum_codec::Codec(BuildingStatesResponse {
-        buildings: posts
-            .0
-            .iter()
-            .map(|building_state| BuildingStateWithName {
-                entity_id: building_state.entity_id,
-                claim_entity_id: building_state.claim_entity_id,
-                direction_index: building_state.direction_index,
-                building_description_id: building_state.building_description_id,
-                constructed_by_player_entity_id: building_state.constructed_by_player_entity_id,
-                building_name: state
-                    .building_nickname_state
-                    .get(&(building_state.entity_id))
-                    .map_or_else(
-                        || {
-                            state
-                                .building_desc
-                                .get(&(building_state.building_description_id as i64))
-                                .map_or("".into(), |building_desc| building_desc.name.clone())
-                        },
-                        |building_nickname_state| building_nickname_state.nickname.clone(),
-                    ),
-                location: state
-                    .location_state
-                    .get(&(building_state.entity_id))
-                    .map(|location_state| location_state.to_owned()),
-            })
-            .collect(),
+        buildings,
         per_page,
-        total: posts.1.number_of_items,
+        total,
         page,
     }))
 }
 
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct BuildingFilters {
+    claim_entity_id: Option<i64>,
+    with_inventory: Option<bool>,
+    skip_static_buildings: Option<bool>,
+}
+
+/// Buildings sorted by `entity_id`, `search` matches the name of their description.
+#[utoipa::path(
+    get,
+    path = "/api/v1/buildings",
+    tag = "buildings",
+    params(ListParams, BuildingFilters),
+    responses(
+        (status = OK, body = Paginated<BuildingStateWithName>),
+        (status = BAD_REQUEST, body = ErrorResponse),
+    )
+)]
+pub(crate) async fn list_buildings(
+    state: State<AppState>,
+    list: ListQuery,
+    Query(filters): Query<BuildingFilters>,
+) -> Result<axum_codec::Codec<Paginated<BuildingStateWithName>>, ApiError> {
+    let sort = list.sort_by(&[
+        (
+            "entity_id",
+            building_state::Column::EntityId.into_simple_expr(),
+        ),
+        (
+            "building_description_id",
+            building_state::Column::BuildingDescriptionId.into_simple_expr(),
+        ),
+    ])?;
+    let building_description_ids = filter_building_descriptions(
+        &state,
+        filters.with_inventory.unwrap_or(false),
+        filters.skip_static_buildings.unwrap_or(false),
+        list.search.as_deref(),
+    );
+
+    let (buildings, total) = find_buildings_with_name(
+        &state,
+        list.page,
+        list.per_page,
+        filters.claim_entity_id,
+        building_description_ids,
+        sort,
+    )
+    .await?;
+
+    Ok(axum_codec::Codec(Paginated::new(buildings, &list, total)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/buildings/{id}",
+    tag = "buildings",
+    params(("id" = u64, Path, description = "Building entity id")),
+    responses(
+        (status = OK, body = building_state::Model),
+        (status = NOT_FOUND, description = "Building not found"),
+    )
+)]
 pub(crate) async fn find_building_state(
     state: State<AppState>,
     Path(id): Path<u64>,
-) -> Result<axum_codec::Codec<building_state::Model>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<building_state::Model>, ApiError> {
     let posts = QueryCore::find_building_state_by_id(&state.conn, id as i64)
         .await
         .expect("Cannot find posts in page");
 
     if posts.is_none() {
-        return Err((StatusCode::NOT_FOUND, "BuildingState not found"));
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            "BuildingState not found",
+        ));
     }
 
     Ok(axum_codec::Codec(posts.unwrap()))
@@ -1,205 +1,53 @@
 use crate::AppState;
-use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
+use crate::desc_cache::DescTable;
+use crate::websocket::reconcile::reconciled_table;
+use crate::websocket::table_sync::SyncedTable;
+use dashmap::DashMap;
 use entity::cargo_desc;
+use entity::shared::Region;
 use game_module::module_bindings::CargoDesc;
-use migration::{OnConflict, sea_query};
-use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter};
-use std::collections::HashMap;
-use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
-use tokio::time::sleep;
 
-pub(crate) fn start_worker_cargo_desc(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<CargoDesc>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict = sea_query::OnConflict::column(cargo_desc::Column::Id)
-            .update_columns([
-                cargo_desc::Column::Name,
-                cargo_desc::Column::Description,
-                cargo_desc::Column::Volume,
-                cargo_desc::Column::SecondaryKnowledgeId,
-                cargo_desc::Column::ModelAssetName,
-                cargo_desc::Column::IconAssetName,
-                cargo_desc::Column::CarriedModelAssetName,
-                cargo_desc::Column::PickUpAnimationStart,
-                cargo_desc::Column::PickUpAnimationEnd,
-                cargo_desc::Column::DropAnimationStart,
-                cargo_desc::Column::DropAnimationEnd,
-                cargo_desc::Column::PickUpTime,
-                cargo_desc::Column::PlaceTime,
-                cargo_desc::Column::AnimatorState,
-                cargo_desc::Column::MovementModifier,
-                cargo_desc::Column::BlocksPath,
-                cargo_desc::Column::OnDestroyYieldCargos,
-                cargo_desc::Column::DespawnTime,
-                cargo_desc::Column::Tier,
-                cargo_desc::Column::Tag,
-                cargo_desc::Column::Rarity,
-                cargo_desc::Column::NotPickupable,
-            ])
-            .to_owned();
+reconciled_table!(cargo_desc, id: i32, Id, shared);
 
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
+impl SyncedTable for cargo_desc::Entity {
+    type Row = CargoDesc;
 
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("cargo_desc", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                let mut currently_known_cargo_desc = ::entity::cargo_desc::Entity::find()
-                                    .all(&global_app_state.conn)
-                                    .await
-                                    .unwrap_or_else(|error| {
-                                        tracing::error!(
-                                            error = error.to_string(),
-                                            "Error while query whole cargo_desc state"
-                                        );
-                                        vec![]
-                                    })
-                                    .into_iter()
-                                    .map(|value| (value.id, value))
-                                    .collect::<HashMap<_, _>>();
-
-                                for model in data.into_iter().map(|value| {
-                                    let model: ::entity::cargo_desc::Model = value.into();
-
-                                    model
-                                }) {
-                                    global_app_state.cargo_desc.insert(model.id, model.clone());
-                                    use std::collections::hash_map::Entry;
-                                    match currently_known_cargo_desc.entry(model.id) {
-                                        Entry::Occupied(entry) => {
-                                            let existing_model = entry.get();
-                                            if &model != existing_model {
-                                                local_messages.push(model.into_active_model());
-                                            }
-                                            entry.remove();
-                                        }
-                                        Entry::Vacant(_entry) => {
-                                            local_messages.push(model.into_active_model());
-                                        }
-                                    }
-                                    if local_messages.len() >= batch_size {
-                                       insert_multiple_cargo_desc(&global_app_state, &on_conflict, &mut local_messages).await;
-                                    }
-                                };
-                                if !local_messages.is_empty() {
-                                    insert_multiple_cargo_desc(&global_app_state, &on_conflict, &mut local_messages).await;
-                                }
-
-                                for chunk_ids in currently_known_cargo_desc.into_keys().collect::<Vec<_>>().chunks(1000) {
-                                    let chunk_ids = chunk_ids.to_vec();
-                                    if let Err(error) = ::entity::cargo_desc::Entity::delete_many().filter(::entity::cargo_desc::Column::Id.is_in(chunk_ids.clone())).exec(&global_app_state.conn).await {
-                                        let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-                                        tracing::error!(CargoDesc = chunk_ids_str.join(","), error = error.to_string(), "Could not delete CargoDesc");
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, .. } => {
-                                let model: ::entity::cargo_desc::Model = new.into();
-                                global_app_state.cargo_desc.insert(model.id, model.clone());
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.id) {
-                                    messages_delete.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, .. } => {
-                                let model: ::entity::cargo_desc::Model = new.into();
-                                global_app_state.cargo_desc.insert(model.id, model.clone());
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.id) {
-                                    messages_delete.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete,.. } => {
-                                let model: ::entity::cargo_desc::Model = delete.into();
-                                let id = model.id;
-                                global_app_state.cargo_desc.remove(&id);
-                                if let Some(index) = messages.iter().position(|value| value.id.as_ref() == &model.id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => {
-                        // Time limit reached
-                        break;
-                    }
-                    else => {
-                        // Channel closed and no more messages
-                        break;
-                    }
-                }
-            }
-
-            if !messages.is_empty() {
-                //tracing::info!("Processing {} messages in batch", messages.len());
-
-                insert_multiple_cargo_desc(&global_app_state, &on_conflict, &mut messages).await;
-            }
-
-            if !messages_delete.is_empty() {
-                tracing::debug!("CargoDesc::Remove");
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::cargo_desc::Entity::delete_many()
-                        .filter(::entity::cargo_desc::Column::Id.is_in(chunk_ids.clone()))
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            CargoDesc = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete CargoDesc"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
-
-            // If the channel is closed and we processed the last batch, exit the outer loop
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
+    fn model(row: Self::Row, _region: Region) -> Self::Model {
+        row.into()
+    }
 
-async fn insert_multiple_cargo_desc(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::cargo_desc::ActiveModel>,
-) {
-    let result = ::entity::cargo_desc::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await;
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            cargo_desc::Column::Name,
+            cargo_desc::Column::Description,
+            cargo_desc::Column::Volume,
+            cargo_desc::Column::SecondaryKnowledgeId,
+            cargo_desc::Column::ModelAssetName,
+            cargo_desc::Column::IconAssetName,
+            cargo_desc::Column::CarriedModelAssetName,
+            cargo_desc::Column::PickUpAnimationStart,
+            cargo_desc::Column::PickUpAnimationEnd,
+            cargo_desc::Column::DropAnimationStart,
+            cargo_desc::Column::DropAnimationEnd,
+            cargo_desc::Column::PickUpTime,
+            cargo_desc::Column::PlaceTime,
+            cargo_desc::Column::AnimatorState,
+            cargo_desc::Column::MovementModifier,
+            cargo_desc::Column::BlocksPath,
+            cargo_desc::Column::OnDestroyYieldCargos,
+            cargo_desc::Column::DespawnTime,
+            cargo_desc::Column::Tier,
+            cargo_desc::Column::Tag,
+            cargo_desc::Column::Rarity,
+            cargo_desc::Column::NotPickupable,
+        ]
+    }
 
-    if let Err(error) = result {
-        tracing::error!(error = error.to_string(), "Error while saving cargo_desc");
+    fn cache(state: &AppState) -> Option<&DashMap<Self::Key, Self::Model>> {
+        Some(state.cargo_desc.as_ref())
     }
 
-    messages.clear();
+    fn desc_table() -> Option<DescTable> {
+        Some(DescTable::CargoDesc)
+    }
 }
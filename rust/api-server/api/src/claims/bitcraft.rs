@@ -1,6 +1,10 @@
 use crate::AppState;
+use crate::claims::events::{ClaimEvent, publish_claim_event, research_events};
 use crate::websocket::batched_worker::BatchedWorker;
-use crate::websocket::{SpacetimeUpdateMessages, WebSocketMessages};
+use crate::websocket::ingest_queue::{self, Checkpoint, IngestReceiver, IngestSender};
+use crate::websocket::reconcile::{reconcile_table, reconciled_table};
+use crate::websocket::{SpacetimeUpdateMessages, WebSocketMessages, record_worker_received};
+use dashmap::DashMap;
 use entity::{claim_local_state, claim_member_state, claim_state, claim_tech_state};
 use game_module::module_bindings::{
     ClaimLocalState, ClaimMemberState, ClaimState, ClaimTechDesc, ClaimTechState,
@@ -8,22 +12,23 @@ use game_module::module_bindings::{
 use migration::{OnConflict, sea_query};
 use sea_orm::QueryFilter;
 use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, TryIntoModel};
-use std::collections::HashMap;
+use spacetimedb_sdk::__codegen::__lib;
+use std::collections::{HashMap, HashSet};
 use std::time::Duration;
-use tokio::sync::mpsc::{Sender, UnboundedReceiver, UnboundedSender, channel, unbounded_channel};
+use tokio::sync::mpsc::{Sender, UnboundedSender, channel, unbounded_channel};
+use tokio::sync::oneshot;
 
 enum ClaimStateDbOperation {
     Upsert(Vec<::entity::claim_state::ActiveModel>),
     Delete(Vec<i64>),
-    DeleteForRegion {
-        ids: Vec<i64>,
-        region: entity::shared::Region,
-    },
+    Commit(Checkpoint),
+    /// Answered once every operation queued before it ran.
+    Flushed(oneshot::Sender<()>),
 }
 
 pub(crate) struct ClaimStateWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<ClaimState>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<ClaimState>>,
+    rx: IngestReceiver<ClaimState>,
+    tx: IngestSender<ClaimState>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -35,7 +40,7 @@ pub(crate) struct ClaimStateWorker {
 impl ClaimStateWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_claim_state_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("claim_state");
 
         Self {
             rx,
@@ -79,23 +84,6 @@ impl ClaimStateWorker {
         }
     }
 
-    async fn queue_region_deletes(&self, ids: Vec<i64>, region: entity::shared::Region) {
-        if ids.is_empty() {
-            return;
-        }
-
-        if let Err(error) = self
-            .db_tx
-            .send(ClaimStateDbOperation::DeleteForRegion { ids, region })
-            .await
-        {
-            tracing::error!(
-                error = error.to_string(),
-                "Could not queue ClaimState region deletes"
-            );
-        }
-    }
-
     async fn process_message(&mut self, msg: SpacetimeUpdateMessages<ClaimState>) {
         match msg {
             SpacetimeUpdateMessages::Initial {
@@ -139,65 +127,49 @@ impl ClaimStateWorker {
                 claim_state::Column::Region,
             ])
             .to_owned();
-        let mut local_messages = Vec::with_capacity(self.batch_size + 10);
-        let mut currently_known_claim_state = ::entity::claim_state::Entity::find()
-            .filter(::entity::claim_state::Column::Region.eq(database_name))
-            .all(&self.global_app_state.conn)
-            .await
-            .unwrap_or_else(|error| {
-                tracing::error!(
-                    error = error.to_string(),
-                    "Error while query whole claim_state state"
-                );
-                vec![]
-            })
+        let snapshot = data
             .into_iter()
-            .map(|value| (value.entity_id, value))
-            .collect::<HashMap<_, _>>();
-
-        for model in data.into_iter().map(|value| {
-            let model: ::entity::claim_state::Model =
+            .map(|value| {
                 ::entity::claim_state::ModelBuilder::new(value)
                     .with_region(database_name)
-                    .build();
-
-            model
-        }) {
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        let ids = snapshot
+            .iter()
+            .map(|model| model.entity_id)
+            .collect::<HashSet<_>>();
+        self.global_app_state
+            .claim_state
+            .retain(|id, claim_state| claim_state.region != database_name || ids.contains(id));
+        for model in &snapshot {
             self.global_app_state
                 .claim_state
                 .insert(model.entity_id, model.clone());
-            use std::collections::hash_map::Entry;
-            match currently_known_claim_state.entry(model.entity_id) {
-                Entry::Occupied(entry) => {
-                    let existing_model = entry.get();
-                    if &model != existing_model {
-                        local_messages.push(model.into_active_model());
-                    }
-                    entry.remove();
-                }
-                Entry::Vacant(_entry) => {
-                    local_messages.push(model.into_active_model());
-                }
-            }
-            if local_messages.len() >= self.batch_size {
-                insert_multiple_claim_state(
-                    &self.global_app_state,
-                    &on_conflict,
-                    &mut local_messages,
-                )
-                .await;
-            }
         }
-        if !local_messages.is_empty() {
-            insert_multiple_claim_state(&self.global_app_state, &on_conflict, &mut local_messages)
-                .await;
+
+        // Batched writes have to land first, or they would bring back rows the snapshot removed
+        self.flush().await;
+        let (flushed, wait) = oneshot::channel();
+        if self
+            .db_tx
+            .send(ClaimStateDbOperation::Flushed(flushed))
+            .await
+            .is_ok()
+        {
+            let _ = wait.await;
         }
 
-        self.queue_region_deletes(
-            currently_known_claim_state.into_keys().collect(),
+        if let Err(error) = reconcile_table::<claim_state::Entity>(
+            &self.global_app_state.conn,
             database_name,
+            snapshot,
+            &on_conflict,
         )
-        .await;
+        .await
+        {
+            tracing::error!(error = error.to_string(), "Could not reconcile claim_state");
+        }
     }
 
     async fn handle_insert(&mut self, new: ClaimState, database_name: entity::shared::Region) {
@@ -290,11 +262,11 @@ impl ClaimStateWorker {
 impl BatchedWorker for ClaimStateWorker {
     type Entity = ClaimState;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -324,6 +296,14 @@ impl BatchedWorker for ClaimStateWorker {
         self.flush_messages().await;
         self.flush_deletes().await;
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(ClaimStateDbOperation::Commit(checkpoint))
+            .await;
+    }
 }
 
 fn start_claim_state_db_worker(global_app_state: AppState) -> Sender<ClaimStateDbOperation> {
@@ -339,18 +319,23 @@ fn start_claim_state_db_worker(global_app_state: AppState) -> Sender<ClaimStateD
         .to_owned();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                ClaimStateDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 ClaimStateDbOperation::Upsert(messages) => {
                     let mut messages = messages;
-                    insert_multiple_claim_state(&global_app_state, &on_conflict, &mut messages)
-                        .await;
+                    written &=
+                        insert_multiple_claim_state(&global_app_state, &on_conflict, &mut messages)
+                            .await;
                 }
                 ClaimStateDbOperation::Delete(ids) => {
-                    delete_multiple_claim_state(&global_app_state, ids).await;
+                    written &= delete_multiple_claim_state(&global_app_state, ids).await;
                 }
-                ClaimStateDbOperation::DeleteForRegion { ids, region } => {
-                    delete_multiple_claim_state_for_region(&global_app_state, ids, region).await;
+                ClaimStateDbOperation::Flushed(flushed) => {
+                    let _ = flushed.send(());
                 }
             }
         }
@@ -359,7 +344,9 @@ fn start_claim_state_db_worker(global_app_state: AppState) -> Sender<ClaimStateD
     tx
 }
 
-async fn delete_multiple_claim_state(global_app_state: &AppState, ids: Vec<i64>) {
+/// Returns whether every row was deleted.
+async fn delete_multiple_claim_state(global_app_state: &AppState, ids: Vec<i64>) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::claim_state::Entity::delete_many()
@@ -373,62 +360,41 @@ async fn delete_multiple_claim_state(global_app_state: &AppState, ids: Vec<i64>)
                 error = error.to_string(),
                 "Could not delete ClaimState"
             );
+            deleted = false;
         }
     }
-}
 
-async fn delete_multiple_claim_state_for_region(
-    global_app_state: &AppState,
-    ids: Vec<i64>,
-    region: entity::shared::Region,
-) {
-    for chunk_ids in ids.chunks(1000) {
-        let chunk_ids = chunk_ids.to_vec();
-        if let Err(error) = ::entity::claim_state::Entity::delete_many()
-            .filter(::entity::claim_state::Column::EntityId.is_in(chunk_ids.clone()))
-            .filter(::entity::claim_state::Column::Region.eq(region.clone()))
-            .exec(&global_app_state.conn)
-            .await
-        {
-            let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-            tracing::error!(
-                ClaimState = chunk_ids_str.join(","),
-                error = error.to_string(),
-                "Could not delete ClaimState"
-            );
-        }
-    }
+    deleted
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_claim_state(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::claim_state::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::claim_state::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting ClaimState: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
 }
 
 enum ClaimLocalStateDbOperation {
     Upsert(Vec<::entity::claim_local_state::ActiveModel>),
     Delete(Vec<i64>),
-    DeleteForRegion {
-        ids: Vec<i64>,
-        region: entity::shared::Region,
-    },
+    Commit(Checkpoint),
 }
 
 pub(crate) struct ClaimLocalStateWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<ClaimLocalState>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<ClaimLocalState>>,
+    rx: IngestReceiver<ClaimLocalState>,
+    tx: IngestSender<ClaimLocalState>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -440,7 +406,7 @@ pub(crate) struct ClaimLocalStateWorker {
 impl ClaimLocalStateWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_claim_local_state_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("claim_local_state");
 
         Self {
             rx,
@@ -483,22 +449,6 @@ impl ClaimLocalStateWorker {
         }
     }
 
-    fn queue_region_deletes(&self, ids: Vec<i64>, region: entity::shared::Region) {
-        if ids.is_empty() {
-            return;
-        }
-
-        if let Err(error) = self
-            .db_tx
-            .send(ClaimLocalStateDbOperation::DeleteForRegion { ids, region })
-        {
-            tracing::error!(
-                error = error.to_string(),
-                "Could not queue ClaimLocalState region deletes"
-            );
-        }
-    }
-
     async fn process_message(&mut self, msg: SpacetimeUpdateMessages<ClaimLocalState>) {
         match msg {
             SpacetimeUpdateMessages::Initial {
@@ -551,74 +501,48 @@ impl ClaimLocalStateWorker {
                 claim_local_state::Column::Region,
             ])
             .to_owned();
-        let mut local_messages = Vec::with_capacity(self.batch_size + 10);
-        let mut currently_known_claim_local_state = ::entity::claim_local_state::Entity::find()
-            .filter(::entity::claim_local_state::Column::Region.eq(database_name))
-            .all(&self.global_app_state.conn)
-            .await
-            .unwrap_or_else(|error| {
-                tracing::error!(
-                    error = error.to_string(),
-                    "Error while query whole claim_local_state state"
-                );
-                vec![]
-            })
+        let snapshot = data
             .into_iter()
-            .map(|value| (value.entity_id, value))
-            .collect::<HashMap<_, _>>();
-
-        for model in data.into_iter().map(|value| {
-            let model: ::entity::claim_local_state::Model =
+            .map(|value| {
                 ::entity::claim_local_state::ModelBuilder::new(value)
                     .with_region(database_name)
-                    .build();
-
-            model
-        }) {
-            let org_id = model.entity_id;
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        for model in &snapshot {
             self.global_app_state
                 .claim_local_state
-                .insert(org_id as u64, model.clone());
-            let _ = self
-                .global_app_state
-                .tx
-                .send(WebSocketMessages::ClaimLocalState(model.clone()));
+                .insert(model.entity_id as u64, model.clone());
+        }
 
-            use std::collections::hash_map::Entry;
-            match currently_known_claim_local_state.entry(model.entity_id) {
-                Entry::Occupied(entry) => {
-                    let existing_model = entry.get();
-                    if &model != existing_model {
-                        local_messages.push(model.into_active_model());
-                    }
-                    entry.remove();
+        match reconcile_table::<claim_local_state::Entity>(
+            &self.global_app_state.conn,
+            database_name,
+            snapshot,
+            &on_conflict,
+        )
+        .await
+        {
+            Ok(reconciliation) => {
+                for model in reconciliation.upserted() {
+                    let _ = self
+                        .global_app_state
+                        .tx
+                        .send(WebSocketMessages::ClaimLocalState(model.clone()));
                 }
-                Entry::Vacant(_entry) => {
-                    local_messages.push(model.into_active_model());
+                for model in reconciliation.deleted {
+                    self.global_app_state
+                        .claim_local_state
+                        .remove(&(model.entity_id as u64));
                 }
             }
-            if local_messages.len() >= self.batch_size {
-                insert_multiple_claim_local_state(
-                    &self.global_app_state,
-                    &on_conflict,
-                    &mut local_messages,
-                )
-                .await;
+            Err(error) => {
+                tracing::error!(
+                    error = error.to_string(),
+                    "Could not reconcile claim_local_state"
+                );
             }
         }
-        if !local_messages.is_empty() {
-            insert_multiple_claim_local_state(
-                &self.global_app_state,
-                &on_conflict,
-                &mut local_messages,
-            )
-            .await;
-        }
-
-        self.queue_region_deletes(
-            currently_known_claim_local_state.into_keys().collect(),
-            database_name,
-        );
     }
 
     async fn handle_insert(&mut self, new: ClaimLocalState, database_name: entity::shared::Region) {
@@ -680,6 +604,27 @@ impl ClaimLocalStateWorker {
                 .with_region(database_name)
                 .build();
 
+        if old.treasury as i32 != model.treasury {
+            publish_claim_event(
+                &self.global_app_state,
+                model.entity_id,
+                ClaimEvent::TreasuryChanged {
+                    treasury: model.treasury,
+                    previous: old.treasury as i32,
+                },
+            );
+        }
+        if old.supplies != model.supplies {
+            publish_claim_event(
+                &self.global_app_state,
+                model.entity_id,
+                ClaimEvent::SuppliesChanged {
+                    supplies: model.supplies,
+                    previous: old.supplies,
+                },
+            );
+        }
+
         self.global_app_state
             .claim_local_state
             .insert(org_id, model.clone());
@@ -757,11 +702,11 @@ impl ClaimLocalStateWorker {
 impl BatchedWorker for ClaimLocalStateWorker {
     type Entity = ClaimLocalState;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -791,6 +736,13 @@ impl BatchedWorker for ClaimLocalStateWorker {
         self.flush_messages();
         self.flush_deletes();
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(ClaimLocalStateDbOperation::Commit(checkpoint));
+    }
 }
 
 fn start_claim_local_state_db_worker(
@@ -814,11 +766,15 @@ fn start_claim_local_state_db_worker(
         .to_owned();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                ClaimLocalStateDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 ClaimLocalStateDbOperation::Upsert(messages) => {
                     let mut messages = messages;
-                    insert_multiple_claim_local_state(
+                    written &= insert_multiple_claim_local_state(
                         &global_app_state,
                         &on_conflict,
                         &mut messages,
@@ -826,11 +782,7 @@ fn start_claim_local_state_db_worker(
                     .await;
                 }
                 ClaimLocalStateDbOperation::Delete(ids) => {
-                    delete_multiple_claim_local_state(&global_app_state, ids).await;
-                }
-                ClaimLocalStateDbOperation::DeleteForRegion { ids, region } => {
-                    delete_multiple_claim_local_state_for_region(&global_app_state, ids, region)
-                        .await;
+                    written &= delete_multiple_claim_local_state(&global_app_state, ids).await;
                 }
             }
         }
@@ -839,7 +791,9 @@ fn start_claim_local_state_db_worker(
     tx
 }
 
-async fn delete_multiple_claim_local_state(global_app_state: &AppState, ids: Vec<i64>) {
+/// Returns whether every row was deleted.
+async fn delete_multiple_claim_local_state(global_app_state: &AppState, ids: Vec<i64>) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::claim_local_state::Entity::delete_many()
@@ -853,62 +807,41 @@ async fn delete_multiple_claim_local_state(global_app_state: &AppState, ids: Vec
                 error = error.to_string(),
                 "Could not delete ClaimLocalState"
             );
+            deleted = false;
         }
     }
-}
 
-async fn delete_multiple_claim_local_state_for_region(
-    global_app_state: &AppState,
-    ids: Vec<i64>,
-    region: entity::shared::Region,
-) {
-    for chunk_ids in ids.chunks(1000) {
-        let chunk_ids = chunk_ids.to_vec();
-        if let Err(error) = ::entity::claim_local_state::Entity::delete_many()
-            .filter(::entity::claim_local_state::Column::EntityId.is_in(chunk_ids.clone()))
-            .filter(::entity::claim_local_state::Column::Region.eq(region.clone()))
-            .exec(&global_app_state.conn)
-            .await
-        {
-            let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-            tracing::error!(
-                ClaimLocalState = chunk_ids_str.join(","),
-                error = error.to_string(),
-                "Could not delete ClaimLocalState"
-            );
-        }
-    }
+    deleted
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_claim_local_state(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::claim_local_state::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::claim_local_state::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting ClaimLocalState: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
 }
 
 enum ClaimMemberStateDbOperation {
     Upsert(Vec<::entity::claim_member_state::ActiveModel>),
     Delete(Vec<i64>),
-    DeleteForRegion {
-        ids: Vec<i64>,
-        region: entity::shared::Region,
-    },
+    Commit(Checkpoint),
 }
 
 pub(crate) struct ClaimMemberStateWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<ClaimMemberState>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<ClaimMemberState>>,
+    rx: IngestReceiver<ClaimMemberState>,
+    tx: IngestSender<ClaimMemberState>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -920,7 +853,7 @@ pub(crate) struct ClaimMemberStateWorker {
 impl ClaimMemberStateWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_claim_member_state_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("claim_member_state");
 
         Self {
             rx,
@@ -963,22 +896,6 @@ impl ClaimMemberStateWorker {
         }
     }
 
-    fn queue_region_deletes(&self, ids: Vec<i64>, region: entity::shared::Region) {
-        if ids.is_empty() {
-            return;
-        }
-
-        if let Err(error) = self
-            .db_tx
-            .send(ClaimMemberStateDbOperation::DeleteForRegion { ids, region })
-        {
-            tracing::error!(
-                error = error.to_string(),
-                "Could not queue ClaimMemberState region deletes"
-            );
-        }
-    }
-
     async fn process_message(&mut self, msg: SpacetimeUpdateMessages<ClaimMemberState>) {
         match msg {
             SpacetimeUpdateMessages::Initial {
@@ -1025,71 +942,57 @@ impl ClaimMemberStateWorker {
                 claim_member_state::Column::Region,
             ])
             .to_owned();
-        let mut local_messages = Vec::with_capacity(self.batch_size + 10);
-        let mut currently_known_claim_member_state = ::entity::claim_member_state::Entity::find()
-            .filter(::entity::claim_member_state::Column::Region.eq(database_name))
-            .all(&self.global_app_state.conn)
-            .await
-            .unwrap_or_else(|error| {
-                tracing::error!(
-                    error = error.to_string(),
-                    "Error while query whole claim_member_state state"
-                );
-                vec![]
-            })
+        let snapshot = data
             .into_iter()
-            .map(|value| (value.entity_id, value))
-            .collect::<HashMap<_, _>>();
-
-        for model in data.into_iter().map(|value| {
-            let model: ::entity::claim_member_state::Model =
+            .map(|value| {
                 ::entity::claim_member_state::ModelBuilder::new(value)
                     .with_region(database_name)
-                    .build();
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        for model in &snapshot {
+            self.global_app_state.add_claim_member(model.clone());
+        }
 
-            model
-        }) {
-            use std::collections::hash_map::Entry;
-            match currently_known_claim_member_state.entry(model.entity_id) {
-                Entry::Occupied(entry) => {
-                    let existing_model = entry.get();
-                    if &model != existing_model {
-                        local_messages.push(model.into_active_model());
-                    }
-                    self.global_app_state
-                        .add_claim_member(existing_model.clone());
-                    entry.remove();
+        match reconcile_table::<claim_member_state::Entity>(
+            &self.global_app_state.conn,
+            database_name,
+            snapshot,
+            &on_conflict,
+        )
+        .await
+        {
+            Ok(reconciliation) => {
+                for model in &reconciliation.inserted {
+                    publish_claim_event(
+                        &self.global_app_state,
+                        model.claim_entity_id,
+                        ClaimEvent::MemberJoined(model.into()),
+                    );
                 }
-                Entry::Vacant(_entry) => {
-                    local_messages.push(model.into_active_model());
+                for model in &reconciliation.updated {
+                    publish_claim_event(
+                        &self.global_app_state,
+                        model.claim_entity_id,
+                        ClaimEvent::MemberUpdated(model.into()),
+                    );
+                }
+                for model in reconciliation.deleted {
+                    publish_claim_event(
+                        &self.global_app_state,
+                        model.claim_entity_id,
+                        ClaimEvent::MemberLeft((&model).into()),
+                    );
+                    self.global_app_state.remove_claim_member(model);
                 }
             }
-            if local_messages.len() >= self.batch_size {
-                insert_multiple_claim_member_state(
-                    &self.global_app_state,
-                    &on_conflict,
-                    &mut local_messages,
-                )
-                .await;
+            Err(error) => {
+                tracing::error!(
+                    error = error.to_string(),
+                    "Could not reconcile claim_member_state"
+                );
             }
         }
-        if !local_messages.is_empty() {
-            insert_multiple_claim_member_state(
-                &self.global_app_state,
-                &on_conflict,
-                &mut local_messages,
-            )
-            .await;
-        }
-
-        let ids = currently_known_claim_member_state
-            .into_iter()
-            .map(|(id, value)| {
-                self.global_app_state.remove_claim_member(value.clone());
-                id
-            })
-            .collect();
-        self.queue_region_deletes(ids, database_name);
     }
 
     async fn handle_insert(
@@ -1102,6 +1005,12 @@ impl ClaimMemberStateWorker {
                 .with_region(database_name)
                 .build();
 
+        publish_claim_event(
+            &self.global_app_state,
+            model.claim_entity_id,
+            ClaimEvent::MemberJoined((&model).into()),
+        );
+
         if let Some(index) = self
             .messages_delete
             .iter()
@@ -1122,6 +1031,12 @@ impl ClaimMemberStateWorker {
                 .with_region(database_name)
                 .build();
 
+        publish_claim_event(
+            &self.global_app_state,
+            model.claim_entity_id,
+            ClaimEvent::MemberUpdated((&model).into()),
+        );
+
         if let Some(index) = self
             .messages
             .iter()
@@ -1160,6 +1075,11 @@ impl ClaimMemberStateWorker {
             self.messages.remove(index);
         }
 
+        publish_claim_event(
+            &self.global_app_state,
+            model.claim_entity_id,
+            ClaimEvent::MemberLeft((&model).into()),
+        );
         self.global_app_state.remove_claim_member(model.clone());
         self.messages_delete.push(id);
     }
@@ -1191,11 +1111,11 @@ impl ClaimMemberStateWorker {
 impl BatchedWorker for ClaimMemberStateWorker {
     type Entity = ClaimMemberState;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -1225,6 +1145,13 @@ impl BatchedWorker for ClaimMemberStateWorker {
         self.flush_messages();
         self.flush_deletes();
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(ClaimMemberStateDbOperation::Commit(checkpoint));
+    }
 }
 
 fn start_claim_member_state_db_worker(
@@ -1245,11 +1172,15 @@ fn start_claim_member_state_db_worker(
         .to_owned();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                ClaimMemberStateDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 ClaimMemberStateDbOperation::Upsert(messages) => {
                     let mut messages = messages;
-                    insert_multiple_claim_member_state(
+                    written &= insert_multiple_claim_member_state(
                         &global_app_state,
                         &on_conflict,
                         &mut messages,
@@ -1257,11 +1188,7 @@ fn start_claim_member_state_db_worker(
                     .await;
                 }
                 ClaimMemberStateDbOperation::Delete(ids) => {
-                    delete_multiple_claim_member_state(&global_app_state, ids).await;
-                }
-                ClaimMemberStateDbOperation::DeleteForRegion { ids, region } => {
-                    delete_multiple_claim_member_state_for_region(&global_app_state, ids, region)
-                        .await;
+                    written &= delete_multiple_claim_member_state(&global_app_state, ids).await;
                 }
             }
         }
@@ -1270,7 +1197,9 @@ fn start_claim_member_state_db_worker(
     tx
 }
 
-async fn delete_multiple_claim_member_state(global_app_state: &AppState, ids: Vec<i64>) {
+/// Returns whether every row was deleted.
+async fn delete_multiple_claim_member_state(global_app_state: &AppState, ids: Vec<i64>) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::claim_member_state::Entity::delete_many()
@@ -1284,38 +1213,19 @@ async fn delete_multiple_claim_member_state(global_app_state: &AppState, ids: Ve
                 error = error.to_string(),
                 "Could not delete ClaimMemberState"
             );
+            deleted = false;
         }
     }
-}
 
-async fn delete_multiple_claim_member_state_for_region(
-    global_app_state: &AppState,
-    ids: Vec<i64>,
-    region: entity::shared::Region,
-) {
-    for chunk_ids in ids.chunks(1000) {
-        let chunk_ids = chunk_ids.to_vec();
-        if let Err(error) = ::entity::claim_member_state::Entity::delete_many()
-            .filter(::entity::claim_member_state::Column::EntityId.is_in(chunk_ids.clone()))
-            .filter(::entity::claim_member_state::Column::Region.eq(region.clone()))
-            .exec(&global_app_state.conn)
-            .await
-        {
-            let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-            tracing::error!(
-                ClaimMemberState = chunk_ids_str.join(","),
-                error = error.to_string(),
-                "Could not delete ClaimMemberState"
-            );
-        }
-    }
+    deleted
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_claim_member_state(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::claim_member_state::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::claim_member_state::Entity::insert_many(
         messages
             .iter()
@@ -1329,11 +1239,12 @@ async fn insert_multiple_claim_member_state(
     .exec(&global_app_state.conn)
     .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting ClaimMemberState: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
 }
 
 enum ClaimTechStateDbOperation {
@@ -1343,11 +1254,12 @@ enum ClaimTechStateDbOperation {
         ids: Vec<i64>,
         region: entity::shared::Region,
     },
+    Commit(Checkpoint),
 }
 
 pub(crate) struct ClaimTechStateWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<ClaimTechState>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<ClaimTechState>>,
+    rx: IngestReceiver<ClaimTechState>,
+    tx: IngestSender<ClaimTechState>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -1359,7 +1271,7 @@ pub(crate) struct ClaimTechStateWorker {
 impl ClaimTechStateWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_claim_tech_state_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("claim_tech_state");
 
         Self {
             rx,
@@ -1430,9 +1342,12 @@ impl ClaimTechStateWorker {
                 self.handle_insert(new, database_name).await;
             }
             SpacetimeUpdateMessages::Update {
-                new, database_name, ..
+                new,
+                old,
+                database_name,
+                ..
             } => {
-                self.handle_update(new, database_name).await;
+                self.handle_update(new, old, database_name).await;
             }
             SpacetimeUpdateMessages::Remove {
                 delete,
@@ -1459,6 +1374,7 @@ impl ClaimTechStateWorker {
             ])
             .to_owned();
         let mut local_messages = Vec::with_capacity(self.batch_size + 10);
+        let mut written = true;
         let mut currently_known_claim_tech_state = ::entity::claim_tech_state::Entity::find()
             .filter(::entity::claim_tech_state::Column::Region.eq(database_name))
             .all(&self.global_app_state.conn)
@@ -1496,7 +1412,7 @@ impl ClaimTechStateWorker {
                 }
             }
             if local_messages.len() >= self.batch_size {
-                insert_multiple_claim_tech_state(
+                written &= insert_multiple_claim_tech_state(
                     &self.global_app_state,
                     &on_conflict,
                     &mut local_messages,
@@ -1505,7 +1421,7 @@ impl ClaimTechStateWorker {
             }
         }
         if !local_messages.is_empty() {
-            insert_multiple_claim_tech_state(
+            written &= insert_multiple_claim_tech_state(
                 &self.global_app_state,
                 &on_conflict,
                 &mut local_messages,
@@ -1513,6 +1429,10 @@ impl ClaimTechStateWorker {
             .await;
         }
 
+        if !written {
+            self.rx.checkpoint().hold();
+        }
+
         self.queue_region_deletes(
             currently_known_claim_tech_state.into_keys().collect(),
             database_name,
@@ -1545,11 +1465,24 @@ impl ClaimTechStateWorker {
         self.messages.push(model.into_active_model());
     }
 
-    async fn handle_update(&mut self, new: ClaimTechState, database_name: entity::shared::Region) {
+    async fn handle_update(
+        &mut self,
+        new: ClaimTechState,
+        old: ClaimTechState,
+        database_name: entity::shared::Region,
+    ) {
         let model: ::entity::claim_tech_state::Model =
             ::entity::claim_tech_state::ModelBuilder::new(new)
                 .with_region(database_name)
                 .build();
+        let old_model: ::entity::claim_tech_state::Model =
+            ::entity::claim_tech_state::ModelBuilder::new(old)
+                .with_region(database_name)
+                .build();
+
+        for event in research_events(&old_model, &model) {
+            publish_claim_event(&self.global_app_state, model.entity_id, event);
+        }
 
         if let Some(index) = self
             .messages_delete
@@ -1619,11 +1552,11 @@ impl ClaimTechStateWorker {
 impl BatchedWorker for ClaimTechStateWorker {
     type Entity = ClaimTechState;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -1653,6 +1586,13 @@ impl BatchedWorker for ClaimTechStateWorker {
         self.flush_messages();
         self.flush_deletes();
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(ClaimTechStateDbOperation::Commit(checkpoint));
+    }
 }
 
 fn start_claim_tech_state_db_worker(
@@ -1670,11 +1610,15 @@ fn start_claim_tech_state_db_worker(
         .to_owned();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                ClaimTechStateDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 ClaimTechStateDbOperation::Upsert(messages) => {
                     let mut messages = messages;
-                    insert_multiple_claim_tech_state(
+                    written &= insert_multiple_claim_tech_state(
                         &global_app_state,
                         &on_conflict,
                         &mut messages,
@@ -1682,11 +1626,12 @@ fn start_claim_tech_state_db_worker(
                     .await;
                 }
                 ClaimTechStateDbOperation::Delete(ids) => {
-                    delete_multiple_claim_tech_state(&global_app_state, ids).await;
+                    written &= delete_multiple_claim_tech_state(&global_app_state, ids).await;
                 }
                 ClaimTechStateDbOperation::DeleteForRegion { ids, region } => {
-                    delete_multiple_claim_tech_state_for_region(&global_app_state, ids, region)
-                        .await;
+                    written &=
+                        delete_multiple_claim_tech_state_for_region(&global_app_state, ids, region)
+                            .await;
                 }
             }
         }
@@ -1695,7 +1640,9 @@ fn start_claim_tech_state_db_worker(
     tx
 }
 
-async fn delete_multiple_claim_tech_state(global_app_state: &AppState, ids: Vec<i64>) {
+/// Returns whether every row was deleted.
+async fn delete_multiple_claim_tech_state(global_app_state: &AppState, ids: Vec<i64>) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::claim_tech_state::Entity::delete_many()
@@ -1709,15 +1656,20 @@ async fn delete_multiple_claim_tech_state(global_app_state: &AppState, ids: Vec<
                 error = error.to_string(),
                 "Could not delete ClaimTechState"
             );
+            deleted = false;
         }
     }
+
+    deleted
 }
 
+/// Returns whether every row was deleted.
 async fn delete_multiple_claim_tech_state_for_region(
     global_app_state: &AppState,
     ids: Vec<i64>,
     region: entity::shared::Region,
-) {
+) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::claim_tech_state::Entity::delete_many()
@@ -1732,35 +1684,41 @@ async fn delete_multiple_claim_tech_state_for_region(
                 error = error.to_string(),
                 "Could not delete ClaimTechState"
             );
+            deleted = false;
         }
     }
+
+    deleted
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_claim_tech_state(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::claim_tech_state::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::claim_tech_state::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting ClaimTechState: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
 }
 
 enum ClaimTechDescDbOperation {
     Upsert(Vec<::entity::claim_tech_desc::ActiveModel>),
     Delete(Vec<i32>),
+    Commit(Checkpoint),
 }
 
 pub(crate) struct ClaimTechDescWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<ClaimTechDesc>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<ClaimTechDesc>>,
+    rx: IngestReceiver<ClaimTechDesc>,
+    tx: IngestSender<ClaimTechDesc>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -1772,7 +1730,7 @@ pub(crate) struct ClaimTechDescWorker {
 impl ClaimTechDescWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_claim_tech_desc_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("claim_tech_desc");
 
         Self {
             rx,
@@ -1845,6 +1803,7 @@ impl ClaimTechDescWorker {
             ])
             .to_owned();
         let mut local_messages = Vec::with_capacity(self.batch_size + 10);
+        let mut written = true;
         let mut currently_known_claim_tech_desc = ::entity::claim_tech_desc::Entity::find()
             .all(&self.global_app_state.conn)
             .await
@@ -1878,7 +1837,7 @@ impl ClaimTechDescWorker {
                 }
             }
             if local_messages.len() >= self.batch_size {
-                insert_multiple_claim_tech_desc(
+                written &= insert_multiple_claim_tech_desc(
                     &self.global_app_state,
                     &on_conflict,
                     &mut local_messages,
@@ -1887,7 +1846,7 @@ impl ClaimTechDescWorker {
             }
         }
         if !local_messages.is_empty() {
-            insert_multiple_claim_tech_desc(
+            written &= insert_multiple_claim_tech_desc(
                 &self.global_app_state,
                 &on_conflict,
                 &mut local_messages,
@@ -1895,6 +1854,10 @@ impl ClaimTechDescWorker {
             .await;
         }
 
+        if !written {
+            self.rx.checkpoint().hold();
+        }
+
         self.queue_deletes(currently_known_claim_tech_desc.into_keys().collect());
     }
 
@@ -1964,11 +1927,11 @@ impl ClaimTechDescWorker {
 impl BatchedWorker for ClaimTechDescWorker {
     type Entity = ClaimTechDesc;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -1998,6 +1961,13 @@ impl BatchedWorker for ClaimTechDescWorker {
         self.flush_messages();
         self.flush_deletes();
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(ClaimTechDescDbOperation::Commit(checkpoint));
+    }
 }
 
 fn start_claim_tech_desc_db_worker(
@@ -2020,15 +1990,23 @@ fn start_claim_tech_desc_db_worker(
         .to_owned();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                ClaimTechDescDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 ClaimTechDescDbOperation::Upsert(messages) => {
                     let mut messages = messages;
-                    insert_multiple_claim_tech_desc(&global_app_state, &on_conflict, &mut messages)
-                        .await;
+                    written &= insert_multiple_claim_tech_desc(
+                        &global_app_state,
+                        &on_conflict,
+                        &mut messages,
+                    )
+                    .await;
                 }
                 ClaimTechDescDbOperation::Delete(ids) => {
-                    delete_multiple_claim_tech_desc(&global_app_state, ids).await;
+                    written &= delete_multiple_claim_tech_desc(&global_app_state, ids).await;
                 }
             }
         }
@@ -2037,7 +2015,9 @@ fn start_claim_tech_desc_db_worker(
     tx
 }
 
-async fn delete_multiple_claim_tech_desc(global_app_state: &AppState, ids: Vec<i32>) {
+/// Returns whether every row was deleted.
+async fn delete_multiple_claim_tech_desc(global_app_state: &AppState, ids: Vec<i32>) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::claim_tech_desc::Entity::delete_many()
@@ -2051,23 +2031,309 @@ async fn delete_multiple_claim_tech_desc(global_app_state: &AppState, ids: Vec<i
                 error = error.to_string(),
                 "Could not delete ClaimTechDesc"
             );
+            deleted = false;
         }
     }
+
+    deleted
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_claim_tech_desc(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::claim_tech_desc::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::claim_tech_desc::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting ClaimTechDesc: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
+}
+
+/// A `claim_tile_state` row together with the chunk of its `location_state`, which is resolved
+/// from the client cache when the row arrives.
+#[derive(Clone, Debug, __lib::ser::Serialize, __lib::de::Deserialize)]
+#[sats(crate = __lib)]
+pub(crate) struct ClaimTile {
+    pub(crate) entity_id: u64,
+    pub(crate) claim_id: u64,
+    pub(crate) chunk_index: Option<u64>,
+}
+
+/// Keeps `AppState::claim_tile_state` as a chunk -> claim index. Tiles of several claims can
+/// share a chunk, so the tiles per chunk and claim are kept and a chunk is only dropped from
+/// the index once its last tile is gone.
+#[derive(Default)]
+struct ClaimTileIndex {
+    // Tile entity id -> (chunk index, claim id, region)
+    tiles: HashMap<u64, (u64, u64, entity::shared::Region)>,
+    // Chunk index -> claim id -> tile entity ids
+    chunk_claims: HashMap<u64, HashMap<u64, HashSet<u64>>>,
+}
+
+impl ClaimTileIndex {
+    fn insert(
+        &mut self,
+        claim_tiles: &DashMap<u64, entity::claim_tile_state::Model>,
+        tile: ClaimTile,
+        region: entity::shared::Region,
+    ) {
+        let Some(chunk_index) = tile.chunk_index else {
+            tracing::debug!(
+                entity_id = tile.entity_id,
+                "Claim tile without location_state"
+            );
+            return;
+        };
+
+        if let Some(existing) = self.tiles.get(&tile.entity_id) {
+            if *existing == (chunk_index, tile.claim_id, region) {
+                return;
+            }
+
+            self.remove(claim_tiles, tile.entity_id);
+        }
+
+        self.tiles
+            .insert(tile.entity_id, (chunk_index, tile.claim_id, region));
+        self.chunk_claims
+            .entry(chunk_index)
+            .or_default()
+            .entry(tile.claim_id)
+            .or_default()
+            .insert(tile.entity_id);
+
+        if !claim_tiles.contains_key(&chunk_index) {
+            claim_tiles.insert(
+                chunk_index,
+                entity::claim_tile_state::Model {
+                    entity_id: tile.entity_id,
+                    claim_id: tile.claim_id,
+                    region,
+                },
+            );
+        }
+    }
+
+    fn remove(
+        &mut self,
+        claim_tiles: &DashMap<u64, entity::claim_tile_state::Model>,
+        entity_id: u64,
+    ) {
+        let Some((chunk_index, claim_id, _)) = self.tiles.remove(&entity_id) else {
+            return;
+        };
+
+        let Some(claims) = self.chunk_claims.get_mut(&chunk_index) else {
+            return;
+        };
+
+        if let Some(tiles) = claims.get_mut(&claim_id) {
+            tiles.remove(&entity_id);
+            if tiles.is_empty() {
+                claims.remove(&claim_id);
+            }
+        }
+
+        if claims.is_empty() {
+            self.chunk_claims.remove(&chunk_index);
+            claim_tiles.remove(&chunk_index);
+            return;
+        }
+
+        let indexed_claim_id = claim_tiles.get(&chunk_index).map(|model| model.claim_id);
+        if indexed_claim_id != Some(claim_id) || claims.contains_key(&claim_id) {
+            return;
+        }
+
+        // The indexed claim has no tiles left in the chunk, one of the others takes it over
+        let other = claims.iter().find_map(|(other_claim_id, tiles)| {
+            let other_entity_id = *tiles.iter().next()?;
+            let (_, _, region) = self.tiles.get(&other_entity_id)?;
+
+            Some(entity::claim_tile_state::Model {
+                entity_id: other_entity_id,
+                claim_id: *other_claim_id,
+                region: *region,
+            })
+        });
+        if let Some(other) = other {
+            claim_tiles.insert(chunk_index, other);
+        }
+    }
+}
+
+pub(crate) fn start_worker_claim_tile_state(
+    global_app_state: AppState,
+    mut rx: IngestReceiver<ClaimTile>,
+) {
+    tokio::spawn(async move {
+        let mut index = ClaimTileIndex::default();
+        let mut buffer = Vec::with_capacity(500);
+
+        loop {
+            buffer.shrink_to(500);
+            let count = rx.recv_many(&mut buffer, 500).await;
+            if count == 0 {
+                break;
+            }
+            record_worker_received("claim_tile_state", count);
+
+            for msg in buffer.drain(..) {
+                match msg {
+                    SpacetimeUpdateMessages::Initial {
+                        data,
+                        database_name,
+                    } => {
+                        let known_ids = data
+                            .iter()
+                            .map(|tile| tile.entity_id)
+                            .collect::<HashSet<_>>();
+                        let removed_ids = index
+                            .tiles
+                            .iter()
+                            .filter(|(entity_id, (_, _, region))| {
+                                *region == database_name && !known_ids.contains(entity_id)
+                            })
+                            .map(|(entity_id, _)| *entity_id)
+                            .collect::<Vec<_>>();
+
+                        for entity_id in removed_ids {
+                            index.remove(&global_app_state.claim_tile_state, entity_id);
+                        }
+
+                        for tile in data {
+                            index.insert(&global_app_state.claim_tile_state, tile, database_name);
+                        }
+
+                        tracing::debug!(
+                            region = database_name,
+                            tiles = index.tiles.len(),
+                            chunks = index.chunk_claims.len(),
+                            "Processed Initial ClaimTileState"
+                        );
+                    }
+                    SpacetimeUpdateMessages::Insert {
+                        new, database_name, ..
+                    } => {
+                        index.insert(&global_app_state.claim_tile_state, new, database_name);
+                    }
+                    SpacetimeUpdateMessages::Update {
+                        new, database_name, ..
+                    } => {
+                        index.insert(&global_app_state.claim_tile_state, new, database_name);
+                    }
+                    SpacetimeUpdateMessages::Remove { delete, .. } => {
+                        index.remove(&global_app_state.claim_tile_state, delete.entity_id);
+                    }
+                }
+            }
+        }
+    });
+}
+
+reconciled_table!(claim_state, entity_id: i64, EntityId);
+reconciled_table!(claim_local_state, entity_id: i64, EntityId);
+reconciled_table!(claim_member_state, entity_id: i64, EntityId);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile(entity_id: u64, claim_id: u64, chunk_index: u64) -> ClaimTile {
+        ClaimTile {
+            entity_id,
+            claim_id,
+            chunk_index: Some(chunk_index),
+        }
+    }
+
+    fn claim(entity_id: u64) -> ClaimState {
+        ClaimState {
+            entity_id,
+            owner_player_entity_id: 0,
+            owner_building_entity_id: 0,
+            name: format!("Claim {entity_id}"),
+            neutral: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn claim_state_follows_the_snapshot_when_postgres_fails() {
+        use crate::config::{GraphqlConfig, RateLimitConfig};
+
+        // Nothing listens there, every query fails
+        let conn = sea_orm::Database::connect(
+            sea_orm::ConnectOptions::new("postgres://postgres@127.0.0.1:1/bitcraft")
+                .connect_lazy(true)
+                .acquire_timeout(Duration::from_millis(100))
+                .to_owned(),
+        )
+        .await
+        .unwrap();
+        let state = AppState::new(
+            conn,
+            unbounded_channel().0,
+            HashMap::new(),
+            RateLimitConfig::default(),
+            &GraphqlConfig::default(),
+        );
+        let mut worker = ClaimStateWorker::new(state.clone(), 100, Duration::from_secs(1));
+        worker.handle_initial(vec![claim(1), claim(2)], 1).await;
+        worker.handle_initial(vec![claim(3)], 2).await;
+
+        worker.handle_initial(vec![claim(2)], 1).await;
+
+        assert!(!state.claim_state.contains_key(&1));
+        assert!(state.claim_state.contains_key(&2));
+        assert!(state.claim_state.contains_key(&3));
+    }
+
+    #[test]
+    fn claim_tile_index_keeps_a_chunk_until_its_last_tile_is_gone() {
+        let claim_tiles = DashMap::new();
+        let mut index = ClaimTileIndex::default();
+
+        index.insert(&claim_tiles, tile(1, 10, 100), 2);
+        index.insert(&claim_tiles, tile(2, 10, 100), 2);
+        index.insert(&claim_tiles, tile(3, 20, 100), 2);
+        assert_eq!(claim_tiles.get(&100).unwrap().claim_id, 10);
+
+        index.remove(&claim_tiles, 1);
+        assert_eq!(claim_tiles.get(&100).unwrap().claim_id, 10);
+
+        // The other claim in the chunk takes over once the indexed one has no tiles left
+        index.remove(&claim_tiles, 2);
+        let indexed = claim_tiles.get(&100).unwrap().clone();
+        assert_eq!((indexed.entity_id, indexed.claim_id), (3, 20));
+
+        index.remove(&claim_tiles, 3);
+        assert!(claim_tiles.is_empty());
+        assert!(index.chunk_claims.is_empty());
+        assert!(index.tiles.is_empty());
+    }
+
+    #[test]
+    fn claim_tile_index_moves_a_tile_between_chunks() {
+        let claim_tiles = DashMap::new();
+        let mut index = ClaimTileIndex::default();
+
+        index.insert(&claim_tiles, tile(1, 10, 100), 2);
+        index.insert(&claim_tiles, tile(1, 10, 101), 2);
+
+        assert!(!claim_tiles.contains_key(&100));
+        assert_eq!(claim_tiles.get(&101).unwrap().claim_id, 10);
+        assert_eq!(index.tiles.len(), 1);
+
+        // Removing an unknown tile is a no-op
+        index.remove(&claim_tiles, 42);
+        assert_eq!(claim_tiles.len(), 1);
+    }
 }
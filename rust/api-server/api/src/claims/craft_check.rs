@@ -0,0 +1,410 @@
+use crate::AppState;
+use crate::claims::{InventoryLocationEntry, get_inventory_locations};
+use crate::error::ApiError;
+use crate::recipes::planner::{
+    CraftingPlan, CraftingPlanNode, CraftingPlanSource, MAX_PLAN_QUANTITY,
+};
+use crate::recipes::{CraftingPlanParams, crafting_planner, parse_preferred_recipes};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use entity::crafting_recipe::BuildingRequirement;
+use entity::inventory::ItemType;
+use log::error;
+use serde::{Deserialize, Serialize};
+use service::Query as QueryCore;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct ClaimCraftRequirement {
+    pub item_id: i32,
+    pub item_type: ItemType,
+    pub source: CraftingPlanSource,
+    pub needed: i64,
+    pub held: i64,
+    pub missing: i64,
+    pub locations: Vec<InventoryLocationEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct ClaimRecipeGate {
+    pub recipe_id: i32,
+    pub crafts: i64,
+    pub building_requirement: Option<BuildingRequirement>,
+    pub has_building: bool,
+    pub required_claim_tech_id: i32,
+    pub has_claim_tech: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct ClaimCraftCheckResponse {
+    pub claim_id: i64,
+    pub plan: CraftingPlan,
+    /// Everything the plan touches once the claim's stock has been used up top-down.
+    pub requirements: Vec<ClaimCraftRequirement>,
+    /// The leaves of the tree that still have to be gathered or bought.
+    pub shortfall: Vec<ClaimCraftRequirement>,
+    pub gates: Vec<ClaimRecipeGate>,
+    pub can_craft: bool,
+}
+
+#[derive(Default)]
+struct Allocation {
+    stock: HashMap<(ItemType, i32), i64>,
+    requirements: BTreeMap<(bool, i32), (ItemType, CraftingPlanSource, i64, i64)>,
+    shortfall: BTreeMap<(bool, i32), (ItemType, CraftingPlanSource, i64)>,
+    crafts: BTreeMap<i32, i64>,
+}
+
+impl Allocation {
+    /// Takes what the claim already holds for `node` and only descends into the crafts that are
+    /// still missing, scaling the inputs down accordingly.
+    fn allocate(&mut self, node: &CraftingPlanNode, needed: i64) {
+        if needed <= 0 {
+            return;
+        }
+
+        let available = self
+            .stock
+            .entry((node.item_type.clone(), node.item_id))
+            .or_default();
+        let held = needed.min(*available);
+        *available -= held;
+        let missing = needed - held;
+
+        let requirement = self
+            .requirements
+            .entry((node.item_type == ItemType::Cargo, node.item_id))
+            .or_insert((node.item_type.clone(), node.source, 0, 0));
+        requirement.2 = requirement.2.saturating_add(needed);
+        requirement.3 += held;
+
+        if missing == 0 {
+            return;
+        }
+
+        match (node.source, node.recipe_id) {
+            (CraftingPlanSource::Recipe, Some(recipe_id)) if node.crafts > 0 => {
+                let per_craft = (node.quantity.saturating_add(node.surplus) / node.crafts).max(1);
+                let crafts = missing / per_craft + i64::from(missing % per_craft != 0);
+                let recipe_crafts = self.crafts.entry(recipe_id).or_default();
+                *recipe_crafts = recipe_crafts.saturating_add(crafts);
+
+                for child in &node.children {
+                    // The child's share of the missing crafts, rounded up, in i128 so it cannot
+                    // overflow
+                    let child_needed =
+                        ((child.quantity as i128 * crafts as i128 + node.crafts as i128 - 1)
+                            / node.crafts as i128)
+                            .min(i64::MAX as i128) as i64;
+                    self.allocate(child, child_needed);
+                }
+            }
+            _ => {
+                let shortfall = self
+                    .shortfall
+                    .entry((node.item_type == ItemType::Cargo, node.item_id))
+                    .or_insert((node.item_type.clone(), node.source, 0));
+                shortfall.2 = shortfall.2.saturating_add(missing);
+            }
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/claims/{id}/craft_check",
+    tag = "claims",
+    params(("id" = i64, Path, description = "Claim entity id"), CraftingPlanParams),
+    responses(
+        (status = OK, body = ClaimCraftCheckResponse),
+        (status = BAD_REQUEST, description = "Invalid quantity or recipe id"),
+        (status = NOT_FOUND, description = "Claim or item not found"),
+    )
+)]
+pub(crate) async fn get_claim_craft_check(
+    state: State<AppState>,
+    Path(id): Path<i64>,
+    Query(params): Query<CraftingPlanParams>,
+) -> Result<axum_codec::Codec<ClaimCraftCheckResponse>, ApiError> {
+    let quantity = params.quantity.unwrap_or(1);
+    if !(1..=MAX_PLAN_QUANTITY).contains(&quantity) {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "Quantity must be between 1 and 1000000",
+        ));
+    }
+
+    let preferred_recipes = parse_preferred_recipes(params.prefer.as_deref())
+        .ok_or((StatusCode::BAD_REQUEST, "Invalid recipe id"))?;
+
+    if !state.claim_state.contains_key(&id) {
+        let claim = QueryCore::find_claim_state(&state.conn, id)
+            .await
+            .map_err(|error| {
+                error!("Error loading claim state: {error}");
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+            })?;
+        if claim.is_none() {
+            return Err(ApiError::new(StatusCode::NOT_FOUND, "Claim not found"));
+        }
+    }
+
+    let item_type = params.item_type.unwrap_or(ItemType::Item);
+    let known_item = match item_type {
+        ItemType::Item => state.item_desc.contains_key(&params.item_id),
+        ItemType::Cargo => state.cargo_desc.contains_key(&params.item_id),
+    };
+    if !known_item {
+        return Err(ApiError::new(StatusCode::NOT_FOUND, "Item not found"));
+    }
+
+    let learned_techs: HashSet<i32> =
+        QueryCore::find_claim_tech_state_by_ids(&state.conn, vec![id])
+            .await
+            .map_err(|error| {
+                error!("Error loading claim tech states: {error}");
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+            })?
+            .into_iter()
+            .find(|claim_tech_state| claim_tech_state.entity_id == id)
+            .map(|claim_tech_state| claim_tech_state.learned.into_iter().collect())
+            .unwrap_or_default();
+
+    let building_states = QueryCore::find_building_state_by_claim_id(&state.conn, id)
+        .await
+        .map_err(|error| {
+            error!("Error loading building states: {error}");
+
+            (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+        })?;
+
+    let building_desc_map: HashMap<i64, i32> = building_states
+        .iter()
+        .map(|building| (building.entity_id, building.building_description_id))
+        .collect();
+    let building_names: HashMap<i64, String> = building_states
+        .iter()
+        .filter_map(|building| {
+            if let Some(nickname) = state.building_nickname_state.get(&building.entity_id) {
+                return Some((building.entity_id, nickname.nickname.clone()));
+            }
+            let desc = state
+                .building_desc
+                .get(&(building.building_description_id as i64))?;
+            Some((building.entity_id, desc.name.clone()))
+        })
+        .collect();
+    let building_functions: Vec<(i32, i32)> = building_states
+        .iter()
+        .filter_map(|building| {
+            state
+                .building_desc
+                .get(&(building.building_description_id as i64))
+        })
+        .flat_map(|desc| {
+            desc.functions
+                .iter()
+                .map(|function| (function.function_type, function.level))
+                .collect::<Vec<_>>()
+        })
+        .collect();
+
+    let claim_inventories = QueryCore::get_inventorys_by_owner_entity_ids(
+        &state.conn,
+        building_desc_map.keys().copied().collect(),
+    )
+    .await
+    .map_err(|error| {
+        error!("Error loading claim inventories: {error}");
+
+        (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+    })?;
+    let inventory_locations = get_inventory_locations(
+        claim_inventories,
+        &state.item_desc,
+        &state.cargo_desc,
+        &HashMap::new(),
+        &building_desc_map,
+        &building_names,
+    );
+
+    let planner = crafting_planner(&state).await?;
+    let plan = planner.plan(item_type, params.item_id, quantity, &preferred_recipes);
+
+    let mut allocation = Allocation::default();
+    let mut locations: HashMap<(ItemType, i32), Vec<InventoryLocationEntry>> = HashMap::new();
+    for location in inventory_locations {
+        let key = (location.item_type.clone(), location.item_id);
+        *allocation.stock.entry(key.clone()).or_default() += location
+            .locations
+            .iter()
+            .map(|entry| entry.quantity as i64)
+            .sum::<i64>();
+        locations.entry(key).or_default().extend(location.locations);
+    }
+    allocation.allocate(&plan.tree, plan.tree.quantity);
+
+    let gates: Vec<ClaimRecipeGate> = allocation
+        .crafts
+        .iter()
+        .filter_map(|(recipe_id, crafts)| {
+            let recipe = state.crafting_recipe_desc.get(recipe_id)?;
+            let has_building = recipe
+                .building_requirement
+                .as_ref()
+                .is_none_or(|requirement| {
+                    building_functions.iter().any(|(function_type, level)| {
+                        *function_type == requirement.building_type && *level >= requirement.tier
+                    })
+                });
+            let has_claim_tech = recipe.required_claim_tech_id == 0
+                || learned_techs.contains(&recipe.required_claim_tech_id);
+
+            Some(ClaimRecipeGate {
+                recipe_id: *recipe_id,
+                crafts: *crafts,
+                building_requirement: recipe.building_requirement.clone(),
+                has_building,
+                required_claim_tech_id: recipe.required_claim_tech_id,
+                has_claim_tech,
+            })
+        })
+        .collect();
+
+    let requirements: Vec<ClaimCraftRequirement> = allocation
+        .requirements
+        .into_iter()
+        .map(
+            |((_, item_id), (item_type, source, needed, held))| ClaimCraftRequirement {
+                locations: locations
+                    .get(&(item_type.clone(), item_id))
+                    .cloned()
+                    .unwrap_or_default(),
+                item_id,
+                item_type,
+                source,
+                needed,
+                held,
+                missing: needed - held,
+            },
+        )
+        .collect();
+
+    let shortfall: Vec<ClaimCraftRequirement> = allocation
+        .shortfall
+        .into_iter()
+        .map(
+            |((_, item_id), (item_type, source, missing))| ClaimCraftRequirement {
+                locations: vec![],
+                item_id,
+                item_type,
+                source,
+                needed: missing,
+                held: 0,
+                missing,
+            },
+        )
+        .collect();
+
+    let can_craft = shortfall.is_empty()
+        && gates
+            .iter()
+            .all(|gate| gate.has_building && gate.has_claim_tech);
+
+    Ok(axum_codec::Codec(ClaimCraftCheckResponse {
+        claim_id: id,
+        plan,
+        requirements,
+        shortfall,
+        gates,
+        can_craft,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(
+        item_id: i32,
+        quantity: i64,
+        source: CraftingPlanSource,
+        crafts: i64,
+        surplus: i64,
+        children: Vec<CraftingPlanNode>,
+    ) -> CraftingPlanNode {
+        CraftingPlanNode {
+            item_id,
+            item_type: ItemType::Item,
+            quantity,
+            source,
+            recipe_id: (source == CraftingPlanSource::Recipe).then_some(1),
+            alternative_recipe_ids: vec![],
+            extraction_recipe_ids: vec![],
+            crafts,
+            surplus,
+            children,
+        }
+    }
+
+    /// 7 planks take 3 crafts of 3 planks from 2 logs each.
+    fn planks() -> CraftingPlanNode {
+        node(
+            20,
+            7,
+            CraftingPlanSource::Recipe,
+            3,
+            2,
+            vec![node(10, 6, CraftingPlanSource::Raw, 0, 0, vec![])],
+        )
+    }
+
+    #[test]
+    fn allocation_only_crafts_what_the_claim_is_missing() {
+        let mut allocation = Allocation::default();
+        allocation.stock.insert((ItemType::Item, 20), 4);
+        allocation.stock.insert((ItemType::Item, 10), 1);
+
+        let tree = planks();
+        allocation.allocate(&tree, tree.quantity);
+
+        // 3 planks are missing, a single craft needs 2 logs and one of them is held
+        assert_eq!(allocation.crafts.get(&1), Some(&1));
+        assert_eq!(
+            allocation
+                .requirements
+                .get(&(false, 20))
+                .map(|r| (r.2, r.3)),
+            Some((7, 4))
+        );
+        assert_eq!(
+            allocation
+                .requirements
+                .get(&(false, 10))
+                .map(|r| (r.2, r.3)),
+            Some((2, 1))
+        );
+        assert_eq!(allocation.shortfall.get(&(false, 10)).map(|s| s.2), Some(1));
+    }
+
+    #[test]
+    fn allocation_stops_at_items_the_claim_holds() {
+        let mut allocation = Allocation::default();
+        allocation.stock.insert((ItemType::Item, 20), 10);
+
+        let tree = planks();
+        allocation.allocate(&tree, tree.quantity);
+
+        assert!(allocation.crafts.is_empty());
+        assert!(allocation.shortfall.is_empty());
+        assert!(!allocation.requirements.contains_key(&(false, 10)));
+        assert_eq!(allocation.stock.get(&(ItemType::Item, 20)), Some(&3));
+    }
+}
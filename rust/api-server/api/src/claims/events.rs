@@ -0,0 +1,150 @@
+use crate::AppState;
+use crate::websocket::WebSocketMessages;
+use entity::inventory::ResolvedInventory;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// Something that happened to a claim, published on the `claim` topic with the claim id.
+#[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[ts(export)]
+#[serde(tag = "t", content = "c")]
+pub(crate) enum ClaimEvent {
+    TreasuryChanged {
+        treasury: i32,
+        previous: i32,
+    },
+    SuppliesChanged {
+        supplies: i32,
+        previous: i32,
+    },
+    MemberJoined(ClaimMember),
+    MemberUpdated(ClaimMember),
+    MemberLeft(ClaimMember),
+    ResearchStarted {
+        tech_id: i32,
+    },
+    ResearchFinished {
+        tech_id: i32,
+    },
+    ResearchCancelled {
+        tech_id: i32,
+    },
+    BuildingConstructed {
+        entity_id: i64,
+        building_description_id: i32,
+        constructed_by_player_entity_id: i64,
+    },
+    BuildingDestroyed {
+        entity_id: i64,
+        building_description_id: i32,
+    },
+    StorageChanged {
+        resolved_inventory: ResolvedInventory,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, TS)]
+#[ts(export)]
+pub(crate) struct ClaimMember {
+    pub entity_id: i64,
+    pub player_entity_id: i64,
+    pub user_name: String,
+    pub inventory_permission: bool,
+    pub build_permission: bool,
+    pub officer_permission: bool,
+    pub co_owner_permission: bool,
+}
+
+impl From<&entity::claim_member_state::Model> for ClaimMember {
+    fn from(value: &entity::claim_member_state::Model) -> Self {
+        Self {
+            entity_id: value.entity_id,
+            player_entity_id: value.player_entity_id,
+            user_name: value.user_name.clone(),
+            inventory_permission: value.inventory_permission,
+            build_permission: value.build_permission,
+            officer_permission: value.officer_permission,
+            co_owner_permission: value.co_owner_permission,
+        }
+    }
+}
+
+pub(crate) fn publish_claim_event(state: &AppState, claim_id: i64, event: ClaimEvent) {
+    if claim_id == 0 {
+        return;
+    }
+
+    let _ = state
+        .tx
+        .send(WebSocketMessages::ClaimEvent { claim_id, event });
+}
+
+/// The research events between two versions of a claim's tech state.
+pub(crate) fn research_events(
+    old: &entity::claim_tech_state::Model,
+    new: &entity::claim_tech_state::Model,
+) -> Vec<ClaimEvent> {
+    let mut events = new
+        .learned
+        .iter()
+        .filter(|tech_id| !old.learned.contains(tech_id))
+        .map(|tech_id| ClaimEvent::ResearchFinished { tech_id: *tech_id })
+        .collect::<Vec<_>>();
+
+    if old.researching != new.researching {
+        if old.researching != 0 && !new.learned.contains(&old.researching) {
+            events.push(ClaimEvent::ResearchCancelled {
+                tech_id: old.researching,
+            });
+        }
+
+        if new.researching != 0 {
+            events.push(ClaimEvent::ResearchStarted {
+                tech_id: new.researching,
+            });
+        }
+    }
+
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tech_state(learned: Vec<i32>, researching: i32) -> entity::claim_tech_state::Model {
+        entity::claim_tech_state::Model {
+            entity_id: 1,
+            learned,
+            researching,
+            start_timestamp: entity::shared::timestamp::Timestamp {
+                __timestamp_micros_since_unix_epoch__: chrono::DateTime::UNIX_EPOCH,
+            },
+            scheduled_id: None,
+            region: 1,
+        }
+    }
+
+    #[test]
+    fn finishing_research_is_not_a_cancel() {
+        let events = research_events(&tech_state(vec![1], 2), &tech_state(vec![1, 2], 3));
+
+        assert!(matches!(
+            events.as_slice(),
+            [
+                ClaimEvent::ResearchFinished { tech_id: 2 },
+                ClaimEvent::ResearchStarted { tech_id: 3 }
+            ]
+        ));
+    }
+
+    #[test]
+    fn dropped_research_is_a_cancel() {
+        let events = research_events(&tech_state(vec![1], 2), &tech_state(vec![1], 0));
+
+        assert!(matches!(
+            events.as_slice(),
+            [ClaimEvent::ResearchCancelled { tech_id: 2 }]
+        ));
+    }
+}
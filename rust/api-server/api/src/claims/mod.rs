@@ -2,9 +2,13 @@ pub(crate) mod bitcraft;
 pub(crate) mod claim_local_state;
 pub(crate) mod claim_member_state;
 pub(crate) mod claim_state;
+pub(crate) mod craft_check;
+pub(crate) mod events;
 
+use crate::error::{ApiError, ErrorResponse};
 use crate::inventory::{InventoryChangesParams, resolve_contents, resolve_pocket};
 use crate::leaderboard::experience_to_level;
+use crate::pagination::{ListParams, ListQuery, Paginated};
 use crate::{AppRouter, AppState};
 use axum::Router;
 use axum::extract::{Path, Query, State};
@@ -16,7 +20,8 @@ use entity::{
     player_state,
 };
 use log::error;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::{ColumnTrait, EntityTrait, IntoSimpleExpr, Order, QueryFilter};
 use serde::{Deserialize, Serialize};
 use service::Query as QueryCore;
 use std::cmp::Ordering;
@@ -24,6 +29,7 @@ use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 use tokio::task::JoinHandle;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new()
@@ -60,9 +66,13 @@ pub(crate) fn get_routes() -> AppRouter {
             "/claims/inventory_changelog/{id}",
             axum_codec::routing::get(get_claim_inventory_change_log).into(),
         )
+        .route(
+            "/claims/{id}/craft_check",
+            axum_codec::routing::get(craft_check::get_claim_craft_check).into(),
+        )
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 pub struct ClaimDescriptionState {
     pub entity_id: i64,
     pub owner_player_entity_id: i64,
@@ -84,7 +94,7 @@ pub struct ClaimDescriptionState {
     pub region: entity::shared::Region,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ClaimSummaryResponse {
     pub name: String,
@@ -92,7 +102,7 @@ pub struct ClaimSummaryResponse {
     pub location: Option<Location>,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct ClaimDescriptionStateWithInventoryAndPlayTime {
     pub entity_id: i64,
@@ -125,7 +135,7 @@ pub struct ClaimDescriptionStateWithInventoryAndPlayTime {
     pub building_states: Vec<building_state::Model>,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) enum InventoryOwnerType {
     Player,
@@ -133,7 +143,7 @@ pub(crate) enum InventoryOwnerType {
     Unknown,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct InventoryLocationEntry {
     pub inventory_entity_id: i64,
@@ -148,7 +158,7 @@ pub(crate) struct InventoryLocationEntry {
     pub quantity: i32,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct InventoryItemLocation {
     pub item_id: i32,
@@ -158,7 +168,7 @@ pub(crate) struct InventoryItemLocation {
     pub locations: Vec<InventoryLocationEntry>,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct ClaimResponse {
     pub claims: Vec<ClaimDescriptionState>,
@@ -168,14 +178,14 @@ pub(crate) struct ClaimResponse {
     pub page: u64,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct ClaimAuctionListingsResponse {
     pub buy_orders: Vec<entity::auction_listing_state::AuctionListingState>,
     pub sell_orders: Vec<entity::auction_listing_state::AuctionListingState>,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct ClaimDescriptionStateMember {
     pub entity_id: i64,
@@ -189,17 +199,26 @@ pub(crate) struct ClaimDescriptionStateMember {
     pub inventory: Option<ResolvedInventory>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) enum OnlineState {
     Online,
     Offline,
 }
 
+#[utoipa::path(
+    get,
+    path = "/claims/tiles/{id}",
+    tag = "claims",
+    params(("id" = u64, Path, description = "Claim entity id")),
+    responses(
+        (status = OK, body = Vec<entity::claim_tile_state::Model>),
+    )
+)]
 pub(crate) async fn get_claim_tiles(
     state: State<AppState>,
     Path(id): Path<u64>,
-) -> Result<axum_codec::Codec<Vec<entity::claim_tile_state::Model>>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<Vec<entity::claim_tile_state::Model>>, ApiError> {
     let claim_tiles = state
         .claim_tile_state
         .iter()
@@ -211,10 +230,19 @@ pub(crate) async fn get_claim_tiles(
     ))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/claims/{id}/auction_listings",
+    tag = "claims",
+    params(("id" = u64, Path, description = "Claim entity id")),
+    responses(
+        (status = OK, body = ClaimAuctionListingsResponse),
+    )
+)]
 pub(crate) async fn get_claim_auction_listings(
     state: State<AppState>,
     Path(id): Path<u64>,
-) -> Result<axum_codec::Codec<ClaimAuctionListingsResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<ClaimAuctionListingsResponse>, ApiError> {
     let buy_orders = state
         .buy_order_state
         .iter()
@@ -246,11 +274,20 @@ struct InventoryJobResult {
 type FlatInventoryTasks = Vec<JoinHandle<anyhow::Result<InventoryJobResult>>>;
 type FlatTravelerTasks = Vec<JoinHandle<anyhow::Result<(String, HashMap<i32, Vec<i64>>)>>>;
 
+#[utoipa::path(
+    get,
+    path = "/claims/inventory_changelog/{id}",
+    tag = "claims",
+    params(("id" = i64, Path, description = "Claim entity id"), InventoryChangesParams),
+    responses(
+        (status = OK, body = Vec<inventory_changelog::Model>),
+    )
+)]
 pub(crate) async fn get_claim_inventory_change_log(
     state: State<AppState>,
     Path(id): Path<i64>,
     Query(params): Query<InventoryChangesParams>,
-) -> Result<axum_codec::Codec<Vec<inventory_changelog::Model>>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<Vec<inventory_changelog::Model>>, ApiError> {
     let building_states = QueryCore::find_building_state_by_claim_id(&state.conn, id)
         .await
         .unwrap_or_else(|err| {
@@ -279,6 +316,7 @@ pub(crate) async fn get_claim_inventory_change_log(
         params.item_id,
         params.item_type,
         params.user_id,
+        params.cause,
     )
     .await
     .map_err(|e| {
@@ -289,9 +327,17 @@ pub(crate) async fn get_claim_inventory_change_log(
     Ok(axum_codec::Codec(inventory_changes))
 }
 
+#[utoipa::path(
+    get,
+    path = "/claims/names",
+    tag = "claims",
+    responses(
+        (status = OK, body = HashMap<i64, ClaimSummaryResponse>),
+    )
+)]
 pub(crate) async fn get_claim_names(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<HashMap<i64, ClaimSummaryResponse>>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<HashMap<i64, ClaimSummaryResponse>>, ApiError> {
     let mut claims = HashMap::new();
 
     for claim_state in state.claim_state.iter() {
@@ -313,13 +359,20 @@ pub(crate) async fn get_claim_names(
     Ok(axum_codec::Codec(claims))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/claims/{id}",
+    tag = "claims",
+    params(("id" = u64, Path, description = "Claim entity id")),
+    responses(
+        (status = OK, body = ClaimDescriptionStateWithInventoryAndPlayTime),
+        (status = NOT_FOUND, description = "Claim not found"),
+    )
+)]
 pub(crate) async fn get_claim(
     state: State<AppState>,
     Path(id): Path<u64>,
-) -> Result<
-    axum_codec::Codec<ClaimDescriptionStateWithInventoryAndPlayTime>,
-    (StatusCode, &'static str),
-> {
+) -> Result<axum_codec::Codec<ClaimDescriptionStateWithInventoryAndPlayTime>, ApiError> {
     let claim = match state.claim_state.get(&(id as i64)) {
         Some(claim) => claim.clone(),
         None => {
@@ -327,7 +380,7 @@ pub(crate) async fn get_claim(
                 .await
                 .expect("Cannot find posts in page");
             if claim.is_none() {
-                return Err((StatusCode::NOT_FOUND, "Claim not found"));
+                return Err(ApiError::new(StatusCode::NOT_FOUND, "Claim not found"));
             }
 
             claim.unwrap()
@@ -909,7 +962,8 @@ fn split_tool_inventories(
     (standard_inventories, tool_inventories)
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct ListClaimsParams {
     page: Option<u64>,
     per_page: Option<u64>,
@@ -918,21 +972,24 @@ pub(crate) struct ListClaimsParams {
     running_upgrade: Option<bool>,
 }
 
-pub(crate) async fn list_claims(
-    state: State<AppState>,
-    Query(params): Query<ListClaimsParams>,
-) -> Result<axum_codec::Codec<ClaimResponse>, (StatusCode, &'static str)> {
-    let page = params.page.unwrap_or(1);
-    let posts_per_page = params.per_page.unwrap_or(25);
-    let search = params.search;
-
+/// A page of claims with their members and upgrades, and the number of matching claims.
+async fn find_claims_with_state(
+    state: &AppState,
+    page: u64,
+    per_page: u64,
+    search: Option<String>,
+    research: Option<i32>,
+    running_upgrade: Option<bool>,
+    sort: Option<(SimpleExpr, Order)>,
+) -> Result<(Vec<ClaimDescriptionState>, u64), ApiError> {
     let (claims, num_pages) = QueryCore::find_claim_descriptions(
         &state.conn,
         page,
-        posts_per_page,
+        per_page,
         search,
-        params.research,
-        params.running_upgrade,
+        research,
+        running_upgrade,
+        sort,
     )
     .await
     .expect("Cannot find posts in page");
@@ -1060,14 +1117,89 @@ pub(crate) async fn list_claims(
         })
         .collect::<Vec<ClaimDescriptionState>>();
 
+    Ok((claims, num_pages.number_of_items))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/claims",
+    tag = "claims",
+    params(ListClaimsParams),
+    responses(
+        (status = OK, body = ClaimResponse),
+    )
+)]
+pub(crate) async fn list_claims(
+    state: State<AppState>,
+    Query(params): Query<ListClaimsParams>,
+) -> Result<axum_codec::Codec<ClaimResponse>, ApiError> {
+    let page = params.page.unwrap_or(1);
+    let posts_per_page = params.per_page.unwrap_or(25);
+
+    let (claims, total) = find_claims_with_state(
+        &state,
+        page,
+        posts_per_page,
+        params.search,
+        params.research,
+        params.running_upgrade,
+        None,
+    )
+    .await?;
+
     Ok(axum_codec::Codec(ClaimResponse {
         claims,
         per_page: posts_per_page,
-        total: num_pages.number_of_items,
+        total,
         page,
     }))
 }
 
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ClaimFilters {
+    research: Option<i32>,
+    running_upgrade: Option<bool>,
+}
+
+/// Claims sorted by their tier unless `sort` is `name` or `entity_id`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/claims",
+    tag = "claims",
+    params(ListParams, ClaimFilters),
+    responses(
+        (status = OK, body = Paginated<ClaimDescriptionState>),
+        (status = BAD_REQUEST, body = ErrorResponse),
+    )
+)]
+pub(crate) async fn list_claims_v1(
+    state: State<AppState>,
+    list: ListQuery,
+    Query(filters): Query<ClaimFilters>,
+) -> Result<axum_codec::Codec<Paginated<ClaimDescriptionState>>, ApiError> {
+    let sort = list.sort_by(&[
+        ("name", entity::claim_state::Column::Name.into_simple_expr()),
+        (
+            "entity_id",
+            entity::claim_state::Column::EntityId.into_simple_expr(),
+        ),
+    ])?;
+
+    let (claims, total) = find_claims_with_state(
+        &state,
+        list.page,
+        list.per_page,
+        list.search.clone(),
+        filters.research,
+        filters.running_upgrade,
+        sort,
+    )
+    .await?;
+
+    Ok(axum_codec::Codec(Paginated::new(claims, &list, total)))
+}
+
 pub(crate) fn get_merged_inventories(
     inventorys: Vec<inventory::Model>,
     items: &Arc<dashmap::DashMap<i32, item_desc::Model>>,
@@ -1,207 +1,40 @@
-use crate::AppState;
-use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
+use crate::websocket::reconcile::reconciled_table;
+use crate::websocket::table_sync::SyncedTable;
 use entity::collectible_desc;
+use entity::shared::Region;
 use game_module::module_bindings::CollectibleDesc;
-use migration::{OnConflict, sea_query};
-use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter};
-use std::collections::HashMap;
-use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
-use tokio::time::sleep;
 
-pub(crate) fn start_worker_collectible_desc(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<CollectibleDesc>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict = sea_query::OnConflict::column(collectible_desc::Column::Id)
-            .update_columns([
-                collectible_desc::Column::Name,
-                collectible_desc::Column::Description,
-                collectible_desc::Column::CollectibleType,
-                collectible_desc::Column::InvalidatesType,
-                collectible_desc::Column::AutoCollect,
-                collectible_desc::Column::CollectibleRarity,
-                collectible_desc::Column::StartingLoadout,
-                collectible_desc::Column::Locked,
-                collectible_desc::Column::Variant,
-                collectible_desc::Column::Color,
-                collectible_desc::Column::Emission,
-                collectible_desc::Column::MaxEquipCount,
-                collectible_desc::Column::ModelAssetName,
-                collectible_desc::Column::VariantMaterial,
-                collectible_desc::Column::IconAssetName,
-                collectible_desc::Column::Tag,
-                collectible_desc::Column::DisplayString,
-                collectible_desc::Column::ItemDeedId,
-                collectible_desc::Column::RequiredKnowledgesToUse,
-                collectible_desc::Column::RequiredKnowledgesToConvert,
-            ])
-            .to_owned();
+reconciled_table!(collectible_desc, id: i32, Id, shared);
 
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
+impl SyncedTable for collectible_desc::Entity {
+    type Row = CollectibleDesc;
 
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("collectible_desc", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                let mut currently_known_collectible_desc = ::entity::collectible_desc::Entity::find()
-                                    .all(&global_app_state.conn)
-                                    .await
-                                    .unwrap_or_else(|error| {
-                                        tracing::error!(
-                                            error = error.to_string(),
-                                            "Error while query whole collectible_desc state"
-                                        );
-                                        vec![]
-                                    })
-                                    .into_iter()
-                                    .map(|value| (value.id, value))
-                                    .collect::<HashMap<_, _>>();
-
-                                for model in data.into_iter().map(|value| {
-                                    let model: ::entity::collectible_desc::Model = value.into();
-
-                                    model
-                                }) {
-                                    // global_app_state.collectible_desc.insert(model.id, model.clone());
-                                    use std::collections::hash_map::Entry;
-                                    match currently_known_collectible_desc.entry(model.id) {
-                                        Entry::Occupied(entry) => {
-                                            let existing_model = entry.get();
-                                            if &model != existing_model {
-                                                local_messages.push(model.into_active_model());
-                                            }
-                                            entry.remove();
-                                        }
-                                        Entry::Vacant(_entry) => {
-                                            local_messages.push(model.into_active_model());
-                                        }
-                                    }
-                                    if local_messages.len() >= batch_size {
-                                       insert_multiple_collectible_desc(&global_app_state, &on_conflict, &mut local_messages).await;
-                                    }
-                                };
-                                if !local_messages.is_empty() {
-                                    insert_multiple_collectible_desc(&global_app_state, &on_conflict, &mut local_messages).await;
-                                }
-
-                                for chunk_ids in currently_known_collectible_desc.into_keys().collect::<Vec<_>>().chunks(1000) {
-                                    let chunk_ids = chunk_ids.to_vec();
-                                    if let Err(error) = ::entity::collectible_desc::Entity::delete_many().filter(::entity::collectible_desc::Column::Id.is_in(chunk_ids.clone())).exec(&global_app_state.conn).await {
-                                        let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-                                        tracing::error!(CollectibleDesc = chunk_ids_str.join(","), error = error.to_string(), "Could not delete CollectibleDesc");
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, .. } => {
-                                let model: ::entity::collectible_desc::Model = new.into();
-                                // global_app_state.collectible_desc.insert(model.id, model.clone());
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.id) {
-                                    messages_delete.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, .. } => {
-                                let model: ::entity::collectible_desc::Model = new.into();
-                                // global_app_state.collectible_desc.insert(model.id, model.clone());
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.id) {
-                                    messages_delete.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete,.. } => {
-                                let model: ::entity::collectible_desc::Model = delete.into();
-                                let id = model.id;
-                                // global_app_state.collectible_desc.remove(&id);
-                                if let Some(index) = messages.iter().position(|value| value.id.as_ref() == &model.id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => {
-                        // Time limit reached
-                        break;
-                    }
-                    else => {
-                        // Channel closed and no more messages
-                        break;
-                    }
-                }
-            }
-
-            if !messages.is_empty() {
-                //tracing::info!("Processing {} messages in batch", messages.len());
-
-                insert_multiple_collectible_desc(&global_app_state, &on_conflict, &mut messages)
-                    .await;
-            }
-
-            if !messages_delete.is_empty() {
-                tracing::debug!("CollectibleDesc::Remove");
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::collectible_desc::Entity::delete_many()
-                        .filter(::entity::collectible_desc::Column::Id.is_in(chunk_ids.clone()))
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            CollectibleDesc = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete CollectibleDesc"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
-
-            // If the channel is closed and we processed the last batch, exit the outer loop
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
-
-async fn insert_multiple_collectible_desc(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::collectible_desc::ActiveModel>,
-) {
-    let result = ::entity::collectible_desc::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await;
-
-    if let Err(error) = result {
-        tracing::error!(
-            error = error.to_string(),
-            "Error while saving collectible_desc"
-        );
+    fn model(row: Self::Row, _region: Region) -> Self::Model {
+        row.into()
     }
 
-    messages.clear();
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            collectible_desc::Column::Name,
+            collectible_desc::Column::Description,
+            collectible_desc::Column::CollectibleType,
+            collectible_desc::Column::InvalidatesType,
+            collectible_desc::Column::AutoCollect,
+            collectible_desc::Column::CollectibleRarity,
+            collectible_desc::Column::StartingLoadout,
+            collectible_desc::Column::Locked,
+            collectible_desc::Column::Variant,
+            collectible_desc::Column::Color,
+            collectible_desc::Column::Emission,
+            collectible_desc::Column::MaxEquipCount,
+            collectible_desc::Column::ModelAssetName,
+            collectible_desc::Column::VariantMaterial,
+            collectible_desc::Column::IconAssetName,
+            collectible_desc::Column::Tag,
+            collectible_desc::Column::DisplayString,
+            collectible_desc::Column::ItemDeedId,
+            collectible_desc::Column::RequiredKnowledgesToUse,
+            collectible_desc::Column::RequiredKnowledgesToConvert,
+        ]
+    }
 }
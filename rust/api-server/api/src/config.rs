@@ -19,6 +19,9 @@ pub(crate) struct Config {
     #[serde(alias = "liveupdatesws")]
     pub(crate) live_updates_ws: bool,
     pub(crate) tech_tier_research_map: TechTierResearchMap,
+    pub(crate) leaderboard_snapshot: LeaderboardSnapshotConfig,
+    pub(crate) rate_limit: RateLimitConfig,
+    pub(crate) graphql: GraphqlConfig,
 }
 
 impl Default for Config {
@@ -44,6 +47,9 @@ impl Default for Config {
             origins: AllowedOriginConfig::default(),
             live_updates_ws: false,
             tech_tier_research_map,
+            leaderboard_snapshot: LeaderboardSnapshotConfig::default(),
+            rate_limit: RateLimitConfig::default(),
+            graphql: GraphqlConfig::default(),
         }
     }
 }
@@ -107,9 +113,14 @@ pub(crate) struct SpacetimeDbConfig {
     pub(crate) protocol: String,
     pub(crate) database: String,
     pub(crate) databases: Vec<String>,
+    /// The `databases` `/readyz` waits for, all of them when unset.
+    pub(crate) required_databases: Option<Vec<String>>,
     pub(crate) password: String,
     pub(crate) username: String,
     pub(crate) websocket_protocol: String,
+    pub(crate) tables: TableSubscriptionConfig,
+    pub(crate) recording: RecordingConfig,
+    pub(crate) ingest: IngestConfig,
 }
 
 impl Default for SpacetimeDbConfig {
@@ -119,9 +130,184 @@ impl Default for SpacetimeDbConfig {
             protocol: "https://".to_string(),
             database: "".to_string(),
             databases: vec![],
+            required_databases: None,
             password: "".to_string(),
             username: "token".to_string(),
             websocket_protocol: "wss://".to_string(),
+            tables: TableSubscriptionConfig::default(),
+            recording: RecordingConfig::default(),
+            ingest: IngestConfig::default(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub(crate) struct TableSubscriptionConfig {
+    /// Whether tables without an entry in `overrides` use their built-in default.
+    /// When `false` only tables explicitly enabled in `overrides` get ingested.
+    pub(crate) default_enabled: bool,
+    pub(crate) overrides: std::collections::HashMap<String, TableConfig>,
+}
+
+impl Default for TableSubscriptionConfig {
+    fn default() -> Self {
+        Self {
+            default_enabled: true,
+            overrides: std::collections::HashMap::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+#[serde(default)]
+pub(crate) struct TableConfig {
+    pub(crate) enabled: Option<bool>,
+    /// Only rows whose columns equal these values get subscribed to, e.g. `{ claim_id = 10 }`.
+    pub(crate) filter: std::collections::BTreeMap<String, FilterValue>,
+}
+
+/// The value a column of [`TableConfig::filter`] is compared with.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub(crate) enum FilterValue {
+    Bool(bool),
+    Integer(i64),
+    Text(String),
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub(crate) struct RecordingConfig {
+    /// Every message the listeners hand to the workers gets appended to this file.
+    pub(crate) record_path: Option<String>,
+    /// Feeds a recorded file into the workers instead of connecting to SpacetimeDB.
+    pub(crate) replay_path: Option<String>,
+    /// Playback speed relative to the recording, `0` replays as fast as the workers accept it.
+    pub(crate) replay_speed: f64,
+}
+
+impl Default for RecordingConfig {
+    fn default() -> Self {
+        Self {
+            record_path: None,
+            replay_path: None,
+            replay_speed: 1.0,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub(crate) struct IngestConfig {
+    /// Messages each worker queue holds in memory before it spills to its log.
+    pub(crate) capacity: usize,
+    /// Directory of the per worker logs, `None` keeps the queues in memory only and drops
+    /// messages once a queue is full.
+    pub(crate) log_path: Option<String>,
+    /// A log segment is rotated after this many bytes, acknowledged segments get deleted.
+    pub(crate) segment_bytes: u64,
+}
+
+impl Default for IngestConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 10_000,
+            log_path: Some("ingest".to_string()),
+            segment_bytes: 64 * 1024 * 1024,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub(crate) struct LeaderboardSnapshotConfig {
+    pub(crate) enabled: bool,
+    /// Seconds between two snapshots.
+    pub(crate) interval: u64,
+    /// How many entries of each global board get stored, `None` stores every ranked player.
+    pub(crate) global_top_n: Option<usize>,
+    /// How many entries of each region board get stored.
+    pub(crate) region_top_n: usize,
+}
+
+impl Default for LeaderboardSnapshotConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: 60 * 60,
+            global_top_n: Some(10_000),
+            region_top_n: 1000,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub(crate) struct RateLimitConfig {
+    pub(crate) enabled: bool,
+    /// Tokens an anonymous client can spend at once, keyed by its IP.
+    pub(crate) burst: u32,
+    /// Tokens an anonymous client gets back per second.
+    pub(crate) per_second: f64,
+    /// Used instead of `burst` for requests with a valid API key or session.
+    pub(crate) authenticated_burst: u32,
+    pub(crate) authenticated_per_second: f64,
+    /// Take the client IP from `X-Forwarded-For`, only enable this behind a proxy that sets it.
+    pub(crate) trust_forwarded_for: bool,
+    /// Tokens a request costs by route pattern, e.g. `/api/bitcraft/claims/{id}`. Other routes
+    /// cost 1, a cost of 0 exempts the route.
+    pub(crate) route_costs: std::collections::HashMap<String, u32>,
+    /// Accounts one IP can create per hour, creating them needs no authentication.
+    pub(crate) account_creations_per_hour: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        let route_costs = [
+            ("/metrics", 0),
+            ("/healthz", 0),
+            ("/readyz", 0),
+            ("/api/bitcraft/claims/{id}", 5),
+            ("/api/v1/claims/{id}", 5),
+            ("/inventory/all_inventory_stats", 20),
+            ("/api/v1/inventories/stats", 20),
+            ("/api/bitcraft/itemsAndCargo/all", 20),
+            ("/api/v1/items-and-cargo/all", 20),
+            ("/api/bitcraft/graphql", 5),
+        ]
+        .into_iter()
+        .map(|(route, cost)| (route.to_string(), cost))
+        .collect();
+
+        Self {
+            enabled: true,
+            burst: 120,
+            per_second: 10.0,
+            authenticated_burst: 600,
+            authenticated_per_second: 50.0,
+            trust_forwarded_for: false,
+            route_costs,
+            account_creations_per_hour: 5,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(default)]
+pub(crate) struct GraphqlConfig {
+    /// How deeply fields of a query can be nested.
+    pub(crate) max_depth: usize,
+    /// Upper bound of a query's complexity. Fields cost 1, fields hitting the database 10 and
+    /// lists multiply the cost of their fields by how many items they can return.
+    pub(crate) max_complexity: usize,
+}
+
+impl Default for GraphqlConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 10,
+            max_complexity: 5000,
         }
     }
 }
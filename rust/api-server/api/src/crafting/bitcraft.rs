@@ -1,16 +1,16 @@
 use crate::AppState;
+use crate::websocket::ingest_queue::IngestReceiver;
 use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
 use game_module::module_bindings::ProgressiveActionState;
 use sea_orm::QueryFilter;
 use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, sea_query};
 use std::collections::HashMap;
 use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::sleep;
 
 pub(crate) fn start_worker_progressive_action_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<ProgressiveActionState>>,
+    mut rx: IngestReceiver<ProgressiveActionState>,
     batch_size: usize,
     time_limit: Duration,
 ) {
@@ -35,6 +35,7 @@ pub(crate) fn start_worker_progressive_action_state(
             let mut messages = Vec::with_capacity(batch_size + 10);
             let mut ids = vec![];
             let mut messages_delete = Vec::with_capacity(batch_size + 10);
+            let mut written = true;
             let timer = sleep(time_limit);
             tokio::pin!(timer);
 
@@ -78,11 +79,11 @@ pub(crate) fn start_worker_progressive_action_state(
                                         }
                                     }
                                     if local_messages.len() >= batch_size {
-                                       insert_multiple_progressive_action_state(&global_app_state, &on_conflict, &mut local_messages).await;
+                                       written &= insert_multiple_progressive_action_state(&global_app_state, &on_conflict, &mut local_messages).await;
                                     }
                                 };
                                 if !local_messages.is_empty() {
-                                    insert_multiple_progressive_action_state(&global_app_state, &on_conflict, &mut local_messages).await;
+                                    written &= insert_multiple_progressive_action_state(&global_app_state, &on_conflict, &mut local_messages).await;
                                 }
 
                                 for chunk_ids in currently_known_progressive_action_state.into_keys().collect::<Vec<_>>().chunks(1000) {
@@ -95,6 +96,7 @@ pub(crate) fn start_worker_progressive_action_state(
                                     {
                                         let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
                                         tracing::error!(ProgressiveActionState = chunk_ids_str.join(","), error = error.to_string(), "Could not delete ProgressiveActionState");
+                                        written = false;
                                     }
                                 }
                             }
@@ -176,7 +178,7 @@ pub(crate) fn start_worker_progressive_action_state(
             if !messages.is_empty() {
                 //tracing::info!("Processing {} messages in batch", messages.len());
 
-                insert_multiple_progressive_action_state(
+                written &= insert_multiple_progressive_action_state(
                     &global_app_state,
                     &on_conflict,
                     &mut messages,
@@ -204,11 +206,14 @@ pub(crate) fn start_worker_progressive_action_state(
                             error = error.to_string(),
                             "Could not delete ProgressiveActionState"
                         );
+                        written = false;
                     }
                 }
                 messages_delete.clear();
             }
 
+            rx.commit_if(written);
+
             // If the channel is closed and we processed the last batch, exit the outer loop
             if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
                 break;
@@ -217,19 +222,21 @@ pub(crate) fn start_worker_progressive_action_state(
     });
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_progressive_action_state(
     global_app_state: &AppState,
     on_conflict: &sea_query::OnConflict,
     messages: &mut Vec<::entity::progressive_action_state::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::progressive_action_state::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting ProgressiveActionState: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
 }
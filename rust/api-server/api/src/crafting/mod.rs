@@ -1,5 +1,6 @@
 pub(crate) mod bitcraft;
 
+use crate::error::ApiError;
 use crate::{AppRouter, AppState};
 use axum::Router;
 use axum::extract::{Path, Query, State};
@@ -51,7 +52,7 @@ pub(crate) struct PlayerUsernameStateResponse {
 }
 pub(crate) async fn get_all(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<PlayerUsernameStateResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<PlayerUsernameStateResponse>, ApiError> {
     let currently_known_player_username_state = ::entity::player_username_state::Entity::find()
         .all(&state.conn)
         .await
@@ -67,14 +68,14 @@ pub(crate) async fn get_all(
 pub async fn list_players(
     state: State<AppState>,
     Query(params): Query<ListPlayersParams>,
-) -> Result<axum_codec::Codec<PlayersResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<PlayersResponse>, ApiError> {
     let page = params.page.unwrap_or(1);
     let posts_per_page = params.per_page.unwrap_or(5);
     let search = params.search;
     let online = params.online;
 
     let (player, player_usernames, num_pages) =
-        QueryCore::find_players(&state.conn, page, posts_per_page, search, online)
+        QueryCore::find_players(&state.conn, page, posts_per_page, search, online, None)
             .await
             .expect("Cannot find player_state in page");
 
@@ -144,14 +145,14 @@ pub(crate) struct FindPlayerByIdResponse {
 pub async fn find_player_by_id(
     State(state): State<AppState>,
     Path(id): Path<i64>,
-) -> Result<axum_codec::Codec<FindPlayerByIdResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<FindPlayerByIdResponse>, ApiError> {
     let player = player_state::Entity::find_by_id(id)
         .one(&state.conn)
         .await
         .expect("Cannot find player");
 
     if player.is_none() {
-        return Err((StatusCode::NOT_FOUND, "Player not found"));
+        return Err(ApiError::new(StatusCode::NOT_FOUND, "Player not found"));
     }
 
     let player = player.unwrap();
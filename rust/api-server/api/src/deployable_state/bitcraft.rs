@@ -1,206 +1,35 @@
 use crate::AppState;
-use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
+use crate::websocket::reconcile::reconciled_table;
+use crate::websocket::table_sync::SyncedTable;
+use dashmap::DashMap;
 use entity::deployable_state;
+use entity::shared::Region;
 use game_module::module_bindings::DeployableState;
-use migration::{OnConflict, sea_query};
-use sea_orm::QueryFilter;
-use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel};
-use std::collections::HashMap;
-use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
-use tokio::time::sleep;
 
-pub(crate) fn start_worker_deployable_state(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<DeployableState>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict = sea_query::OnConflict::column(deployable_state::Column::EntityId)
-            .update_columns([
-                deployable_state::Column::OwnerId,
-                deployable_state::Column::ClaimEntityId,
-                deployable_state::Column::Direction,
-                deployable_state::Column::DeployableDescriptionId,
-                deployable_state::Column::Nickname,
-                deployable_state::Column::Hidden,
-                deployable_state::Column::Region,
-            ])
-            .to_owned();
+reconciled_table!(deployable_state, entity_id: i64, EntityId);
 
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
+impl SyncedTable for deployable_state::Entity {
+    type Row = DeployableState;
 
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("deployable_state", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                let mut currently_known_deployable_state = ::entity::deployable_state::Entity::find()
-                                    .filter(::entity::deployable_state::Column::Region.eq(database_name))
-                                    .all(&global_app_state.conn)
-                                    .await
-                                    .unwrap_or_else(|error| {
-                                        tracing::error!(
-                                            error = error.to_string(),
-                                            "Error while query whole deployable_state state"
-                                        );
-                                        vec![]
-                                    })
-                                    .into_iter()
-                                    .map(|value| (value.entity_id, value))
-                                    .collect::<HashMap<_, _>>();
-
-                                for model in data.into_iter().map(|value| {
-                                    let model: ::entity::deployable_state::Model = ::entity::deployable_state::ModelBuilder::new(value).with_region(database_name).build();
-
-                                    global_app_state.deployable_state.insert(model.entity_id, model.clone());
-
-                                    model
-                                }) {
-                                    use std::collections::hash_map::Entry;
-                                    match currently_known_deployable_state.entry(model.entity_id) {
-                                        Entry::Occupied(entry) => {
-                                            let existing_model = entry.get();
-                                            if &model != existing_model {
-                                                local_messages.push(model.into_active_model());
-                                            }
-                                            entry.remove();
-                                        }
-                                        Entry::Vacant(_entry) => {
-                                            local_messages.push(model.into_active_model());
-                                        }
-                                    }
-                                    if local_messages.len() >= batch_size {
-                                       insert_multiple_deployable_state(&global_app_state, &on_conflict, &mut local_messages).await;
-                                    }
-                                };
-                                if !local_messages.is_empty() {
-                                    insert_multiple_deployable_state(&global_app_state, &on_conflict, &mut local_messages).await;
-                                }
-
-                                for chunk_ids in currently_known_deployable_state.into_keys().collect::<Vec<_>>().chunks(1000) {
-                                    let chunk_ids = chunk_ids.to_vec();
-                                    if let Err(error) = ::entity::deployable_state::Entity::delete_many()
-                                    .filter(::entity::deployable_state::Column::EntityId.is_in(chunk_ids.clone()))
-                                    .filter(::entity::deployable_state::Column::Region.eq(database_name))
-                                    .exec(&global_app_state.conn).await {
-                                        let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-                                        tracing::error!(DeployableState = chunk_ids_str.join(","), error = error.to_string(), "Could not delete DeployableState");
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
-                                let model: ::entity::deployable_state::Model = ::entity::deployable_state::ModelBuilder::new(new).with_region(database_name).build();
-                                global_app_state.deployable_state.insert(model.entity_id, model.clone());
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value: &::entity::deployable_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, database_name, .. } => {
-                                let model: ::entity::deployable_state::Model = ::entity::deployable_state::ModelBuilder::new(new).with_region(database_name).build();
-                                global_app_state.deployable_state.insert(model.entity_id, model.clone());
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value: &::entity::deployable_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete, database_name, .. } => {
-                                let model: ::entity::deployable_state::Model = ::entity::deployable_state::ModelBuilder::new(delete).with_region(database_name).build();
-                                global_app_state.deployable_state.remove(&model.entity_id);
-                                let id = model.entity_id;
-
-                                if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => {
-                        // Time limit reached
-                        break;
-                    }
-                    else => {
-                        // Channel closed and no more messages
-                        break;
-                    }
-                }
-            }
-
-            if !messages.is_empty() {
-                //tracing::info!("Processing {} messages in batch", messages.len());
-                insert_multiple_deployable_state(&global_app_state, &on_conflict, &mut messages)
-                    .await;
-                // Your batch processing logic here
-            }
-
-            if !messages_delete.is_empty() {
-                tracing::debug!("DeployableState::Remove");
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::deployable_state::Entity::delete_many()
-                        .filter(
-                            ::entity::deployable_state::Column::EntityId.is_in(chunk_ids.clone()),
-                        )
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            DeployableState = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete DeployableState"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
-
-            // If the channel is closed and we processed the last batch, exit the outer loop
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
-
-async fn insert_multiple_deployable_state(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::deployable_state::ActiveModel>,
-) {
-    let insert = ::entity::deployable_state::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await;
+    fn model(row: Self::Row, region: Region) -> Self::Model {
+        deployable_state::ModelBuilder::new(row)
+            .with_region(region)
+            .build()
+    }
 
-    if let Err(err) = insert {
-        tracing::error!("Error inserting deployable_state: {}", err)
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            deployable_state::Column::OwnerId,
+            deployable_state::Column::ClaimEntityId,
+            deployable_state::Column::Direction,
+            deployable_state::Column::DeployableDescriptionId,
+            deployable_state::Column::Nickname,
+            deployable_state::Column::Hidden,
+            deployable_state::Column::Region,
+        ]
     }
 
-    messages.clear();
+    fn cache(state: &AppState) -> Option<&DashMap<Self::Key, Self::Model>> {
+        Some(state.deployable_state.as_ref())
+    }
 }
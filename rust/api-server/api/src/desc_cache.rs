@@ -0,0 +1,235 @@
+use crate::error::ApiError;
+use axum::body::Bytes;
+use axum::extract::FromRequestParts;
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH, VARY};
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum_codec::{Accept, CodecEncode, ContentType};
+use dashmap::DashMap;
+use sha2::{Digest, Sha256};
+use std::convert::Infallible;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Descriptions only change with game updates, clients still revalidate every minute.
+const CACHE_CONTROL_VALUE: &str = "public, max-age=60, must-revalidate";
+/// Every search of `/desc/buildings` is its own body, so the cache is bounded.
+const MAX_BODIES: usize = 1024;
+
+/// The tables cached endpoints are built from, the workers bump them whenever rows change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DescTable {
+    BuildingDesc,
+    /// Buildings being constructed or destroyed, `/desc/buildings` counts them.
+    BuildingCount,
+    CargoDesc,
+    CraftingRecipeDesc,
+    ExtractionRecipeDesc,
+    ItemDesc,
+    ItemListDesc,
+    NpcDesc,
+    TravelerTaskDesc,
+}
+
+const DESC_TABLES: usize = DescTable::TravelerTaskDesc as usize + 1;
+
+#[derive(Clone, PartialEq, Eq, Hash)]
+struct BodyKey {
+    path: String,
+    query: String,
+    content_type: &'static str,
+}
+
+struct CachedBody {
+    tables: &'static [DescTable],
+    versions: Vec<u64>,
+    etag: HeaderValue,
+    body: Bytes,
+}
+
+/// Serialized bodies of the description endpoints, per query and encoding.
+#[derive(Default)]
+pub(crate) struct DescCache {
+    versions: [AtomicU64; DESC_TABLES],
+    bodies: DashMap<BodyKey, CachedBody>,
+}
+
+/// What a cached response depends on besides the tables, extracted from the request.
+pub(crate) struct CachedRequest {
+    key: BodyKey,
+    content_type: ContentType,
+    if_none_match: Option<HeaderValue>,
+}
+
+impl<S: Send + Sync + 'static> FromRequestParts<S> for CachedRequest {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = Accept::from_request_parts(parts, state)
+            .await?
+            .content_type();
+
+        Ok(Self {
+            key: BodyKey {
+                path: parts.uri.path().to_string(),
+                query: parts.uri.query().unwrap_or_default().to_string(),
+                content_type: content_type.as_str(),
+            },
+            content_type,
+            if_none_match: parts.headers.get(IF_NONE_MATCH).cloned(),
+        })
+    }
+}
+
+impl DescCache {
+    pub(crate) fn bump(&self, table: DescTable) {
+        self.versions[table as usize].fetch_add(1, Ordering::Release);
+    }
+
+    /// The current version of each of `tables`, it changes whenever one of them does.
+    pub(crate) fn versions(&self, tables: &[DescTable]) -> Vec<u64> {
+        tables
+            .iter()
+            .map(|table| self.versions[*table as usize].load(Ordering::Acquire))
+            .collect()
+    }
+
+    /// Answers with the cached body while none of `tables` changed, otherwise with a fresh
+    /// one from `build`, or with 304 if the client already has it.
+    pub(crate) async fn respond<T, F, Fut>(
+        &self,
+        request: CachedRequest,
+        tables: &'static [DescTable],
+        build: F,
+    ) -> Result<Response, ApiError>
+    where
+        T: CodecEncode,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, ApiError>>,
+    {
+        // Read before building, a change while building then only costs a rebuild
+        let versions = self.versions(tables);
+
+        let cached = self
+            .bodies
+            .get(&request.key)
+            .filter(|cached| cached.versions == versions)
+            .map(|cached| (cached.etag.clone(), cached.body.clone()));
+
+        let (etag, body) = match cached {
+            Some(cached) => {
+                metrics::counter!("desc_cache_requests_total", &[("result", "hit")]).increment(1);
+                cached
+            }
+            None => {
+                metrics::counter!("desc_cache_requests_total", &[("result", "miss")]).increment(1);
+                let body = axum_codec::Codec(build().await?)
+                    .to_bytes(request.content_type)
+                    .map_err(|_| {
+                        ApiError::new(
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            "Could not encode the response",
+                        )
+                    })?;
+                let body = Bytes::from(body);
+                let etag = etag_of(&body);
+
+                self.insert(
+                    request.key.clone(),
+                    CachedBody {
+                        tables,
+                        versions,
+                        etag: etag.clone(),
+                        body: body.clone(),
+                    },
+                );
+
+                (etag, body)
+            }
+        };
+
+        let headers = [
+            (ETAG, etag.clone()),
+            (CACHE_CONTROL, HeaderValue::from_static(CACHE_CONTROL_VALUE)),
+            (VARY, HeaderValue::from_static("accept")),
+        ];
+
+        if request
+            .if_none_match
+            .is_some_and(|if_none_match| matches_etag(&if_none_match, &etag))
+        {
+            metrics::counter!("desc_cache_requests_total", &[("result", "not_modified")])
+                .increment(1);
+            return Ok((StatusCode::NOT_MODIFIED, headers).into_response());
+        }
+
+        Ok((
+            headers,
+            [(CONTENT_TYPE, request.content_type.into_header())],
+            body,
+        )
+            .into_response())
+    }
+
+    fn insert(&self, key: BodyKey, body: CachedBody) {
+        if self.bodies.len() >= MAX_BODIES {
+            self.bodies
+                .retain(|_, cached| cached.versions == self.versions(cached.tables));
+        }
+        if self.bodies.len() < MAX_BODIES {
+            self.bodies.insert(key, body);
+        }
+    }
+}
+
+/// A strong ETag, the same body always gets the same one, even across restarts.
+fn etag_of(body: &[u8]) -> HeaderValue {
+    let digest = Sha256::digest(body);
+
+    HeaderValue::from_str(&format!("\"{}\"", hex::encode(&digest[..16])))
+        .expect("hex is a valid header value")
+}
+
+/// `If-None-Match` is a list of ETags or `*`, compared weakly.
+fn matches_etag(if_none_match: &HeaderValue, etag: &HeaderValue) -> bool {
+    let (Ok(if_none_match), Ok(etag)) = (if_none_match.to_str(), etag.to_str()) else {
+        return false;
+    };
+
+    if_none_match
+        .split(',')
+        .map(str::trim)
+        .any(|tag| tag == "*" || tag.trim_start_matches("W/") == etag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn if_none_match_accepts_lists_and_weak_tags() {
+        let etag = etag_of(b"{}");
+        let tag = etag.to_str().unwrap().to_string();
+
+        for if_none_match in [tag.clone(), format!("\"other\", W/{tag}"), "*".to_string()] {
+            assert!(matches_etag(
+                &HeaderValue::from_str(&if_none_match).unwrap(),
+                &etag
+            ));
+        }
+        assert!(!matches_etag(&HeaderValue::from_static("\"other\""), &etag));
+    }
+
+    #[test]
+    fn bumping_a_table_invalidates_bodies_built_from_it() {
+        let cache = DescCache::default();
+        let tables = [DescTable::ItemDesc, DescTable::CargoDesc];
+        let versions = cache.versions(&tables);
+
+        cache.bump(DescTable::NpcDesc);
+        assert_eq!(cache.versions(&tables), versions);
+
+        cache.bump(DescTable::CargoDesc);
+        assert_ne!(cache.versions(&tables), versions);
+    }
+}
@@ -0,0 +1,96 @@
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde::Serialize;
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// The error every handler returns, it is sent as an [`ErrorResponse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ApiError {
+    pub(crate) status: StatusCode,
+    pub(crate) message: &'static str,
+}
+
+impl ApiError {
+    pub(crate) const fn new(status: StatusCode, message: &'static str) -> Self {
+        Self { status, message }
+    }
+
+    /// `TOO_MANY_REQUESTS` becomes `too_many_requests`, so clients can match on it.
+    fn code(&self) -> String {
+        self.status
+            .canonical_reason()
+            .unwrap_or("Unknown")
+            .to_lowercase()
+            .replace([' ', '-'], "_")
+            .replace('\'', "")
+    }
+}
+
+/// So `?` keeps working on the `(StatusCode, &'static str)` tuples helpers return.
+impl From<(StatusCode, &'static str)> for ApiError {
+    fn from((status, message): (StatusCode, &'static str)) -> Self {
+        Self::new(status, message)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct ErrorResponse {
+    pub status: u16,
+    pub code: String,
+    pub message: String,
+}
+
+impl From<ApiError> for ErrorResponse {
+    fn from(error: ApiError) -> Self {
+        let code = error.code();
+        let message = if error.message.is_empty() {
+            error.status.canonical_reason().unwrap_or_default()
+        } else {
+            error.message
+        };
+
+        Self {
+            status: error.status.as_u16(),
+            code,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.status, axum::Json(ErrorResponse::from(self))).into_response()
+    }
+}
+
+/// Errors are always JSON, whatever codec the request negotiated.
+impl axum_codec::IntoCodecResponse for ApiError {
+    fn into_codec_response(self, _content_type: axum_codec::ContentType) -> Response {
+        self.into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_body_has_a_snake_case_code() {
+        let body = ErrorResponse::from(ApiError::new(StatusCode::NOT_FOUND, "Claim not found"));
+
+        assert_eq!(
+            body,
+            ErrorResponse {
+                status: 404,
+                code: "not_found".to_string(),
+                message: "Claim not found".to_string(),
+            }
+        );
+        assert_eq!(
+            ErrorResponse::from(ApiError::new(StatusCode::TOO_MANY_REQUESTS, "")).message,
+            "Too Many Requests"
+        );
+    }
+}
@@ -1,20 +1,24 @@
 pub(crate) mod bitcraft;
 
+use crate::desc_cache::{CachedRequest, DescTable};
+use crate::error::ApiError;
 use crate::{AppRouter, AppState};
 use axum::extract::State;
 use axum::http::StatusCode;
+use axum::response::Response;
 use serde::{Deserialize, Serialize};
 use service::Query as QueryCore;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 pub(crate) fn get_routes() -> AppRouter {
     axum::Router::new().route(
         "/api/bitcraft/extractionRecipes/all",
-        axum_codec::routing::get(get_all).into(),
+        axum::routing::get(get_all),
     )
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct ExtractionRecipeResponse {
     pub id: i32,
@@ -27,30 +31,44 @@ pub(crate) struct ExtractionRecipeResponse {
     pub stamina_requirement: f32,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/extractionRecipes/all",
+    tag = "recipes",
+    operation_id = "get_all_extraction_recipes",
+    responses(
+        (status = OK, body = Vec<ExtractionRecipeResponse>),
+        (status = NOT_MODIFIED, description = "The `If-None-Match` ETag is still current"),
+    )
+)]
 pub(crate) async fn get_all(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<Vec<ExtractionRecipeResponse>>, (StatusCode, &'static str)> {
-    let recipes = QueryCore::all_extraction_recipe_desc(&state.conn)
-        .await
-        .map_err(|_| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                "Cannot find extraction recipes",
-            )
-        })?;
+    request: CachedRequest,
+) -> Result<Response, ApiError> {
+    state
+        .desc_cache
+        .respond(request, &[DescTable::ExtractionRecipeDesc], || async {
+            let recipes = QueryCore::all_extraction_recipe_desc(&state.conn)
+                .await
+                .map_err(|_| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "Cannot find extraction recipes",
+                    )
+                })?;
 
-    let response: Vec<ExtractionRecipeResponse> = recipes
-        .into_iter()
-        .map(|recipe| ExtractionRecipeResponse {
-            id: recipe.id,
-            resource_id: recipe.resource_id,
-            extracted_item_stacks: recipe.extracted_item_stacks,
-            tool_requirements: recipe.tool_requirements,
-            allow_use_hands: recipe.allow_use_hands,
-            time_requirement: recipe.time_requirement,
-            stamina_requirement: recipe.stamina_requirement,
+            Ok(recipes
+                .into_iter()
+                .map(|recipe| ExtractionRecipeResponse {
+                    id: recipe.id,
+                    resource_id: recipe.resource_id,
+                    extracted_item_stacks: recipe.extracted_item_stacks,
+                    tool_requirements: recipe.tool_requirements,
+                    allow_use_hands: recipe.allow_use_hands,
+                    time_requirement: recipe.time_requirement,
+                    stamina_requirement: recipe.stamina_requirement,
+                })
+                .collect::<Vec<ExtractionRecipeResponse>>())
         })
-        .collect();
-
-    Ok(axum_codec::Codec(response))
+        .await
 }
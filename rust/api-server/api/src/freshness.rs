@@ -0,0 +1,329 @@
+use crate::v1::LEGACY_ROUTES;
+use crate::{AppRouter, AppState};
+use axum::Router;
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::HeaderValue;
+use axum::middleware::Next;
+use axum::response::Response;
+use dashmap::DashMap;
+use entity::shared::Region;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+const DATA_FRESHNESS: &str = "x-data-freshness";
+
+/// The ingested tables the handler of an `/api/v1` route reads, legacy routes inherit them from
+/// their successor. Descriptions are left out, they only change with game updates.
+const ROUTE_TABLES: &[(&str, &[&str])] = &[
+    (
+        "/api/v1/players",
+        &["player_state", "player_username_state"],
+    ),
+    ("/api/v1/players/usernames", &["player_username_state"]),
+    (
+        "/api/v1/players/{id}",
+        &["player_state", "player_username_state"],
+    ),
+    ("/api/v1/players/{id}/experience", &["experience_state"]),
+    ("/api/v1/players/{id}/rank-history", &["experience_state"]),
+    (
+        "/api/v1/claims",
+        &["claim_state", "claim_local_state", "claim_member_state"],
+    ),
+    ("/api/v1/claims/names", &["claim_state"]),
+    (
+        "/api/v1/claims/{id}",
+        &[
+            "claim_state",
+            "claim_local_state",
+            "claim_member_state",
+            "claim_tech_state",
+        ],
+    ),
+    (
+        "/api/v1/claims/{id}/auction-listings",
+        &["buy_order_state", "sell_order_state"],
+    ),
+    ("/api/v1/claims/{id}/tiles", &["claim_tile_state"]),
+    (
+        "/api/v1/claims/{id}/inventory-changes",
+        &["inventory_state"],
+    ),
+    (
+        "/api/v1/claims/{id}/craft-check",
+        &["inventory_state", "building_state"],
+    ),
+    ("/api/v1/claims/{id}/leaderboard", &["experience_state"]),
+    (
+        "/api/v1/buildings",
+        &["building_state", "building_nickname_state"],
+    ),
+    (
+        "/api/v1/buildings/{id}",
+        &["building_state", "building_nickname_state"],
+    ),
+    ("/api/v1/inventories/{id}", &["inventory_state"]),
+    ("/api/v1/inventories/by-owner/{id}", &["inventory_state"]),
+    ("/api/v1/inventories/{id}/changes", &["inventory_state"]),
+    ("/api/v1/inventories/stats", &["inventory_state"]),
+    ("/api/v1/trade-orders", &["trade_order_state"]),
+    ("/api/v1/traveler-tasks", &["traveler_task_state"]),
+    ("/api/v1/market", &["buy_order_state", "sell_order_state"]),
+    (
+        "/api/v1/market/orders",
+        &["buy_order_state", "sell_order_state"],
+    ),
+    (
+        "/api/v1/market/depth",
+        &["buy_order_state", "sell_order_state"],
+    ),
+    (
+        "/api/v1/market/history",
+        &["buy_order_state", "sell_order_state"],
+    ),
+    ("/api/v1/leaderboards", &["experience_state"]),
+    ("/api/v1/leaderboards/movers", &["experience_state"]),
+    ("/api/v1/houses", &["player_housing_state"]),
+    ("/api/v1/houses/by-owner/{id}", &["player_housing_state"]),
+    ("/api/v1/houses/{id}", &["player_housing_state"]),
+    (
+        "/api/v1/houses/{id}/inventories",
+        &["player_housing_state", "inventory_state"],
+    ),
+];
+
+static TABLES_BY_ROUTE: LazyLock<HashMap<&str, &[&str]>> = LazyLock::new(|| {
+    let mut tables = ROUTE_TABLES.iter().copied().collect::<HashMap<_, _>>();
+    for (legacy, successor) in LEGACY_ROUTES {
+        if let Some(read) = tables.get(successor).copied() {
+            tables.insert(legacy, read);
+        }
+    }
+
+    tables
+});
+
+pub(crate) fn get_routes() -> AppRouter {
+    Router::new().route(
+        "/status/freshness",
+        axum_codec::routing::get(get_freshness).into(),
+    )
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn labels(table: &str, region: Region) -> [(&'static str, String); 2] {
+    [("table", table.to_string()), ("region", region.to_string())]
+}
+
+/// Timestamps are unix milliseconds, 0 while nothing happened yet.
+#[derive(Default, Clone, Copy)]
+struct TableFreshness {
+    /// Sequence numbers of the table's ingest queue, the table has nothing pending when the
+    /// applied one caught up with the received one.
+    received_seq: u64,
+    applied_seq: u64,
+    /// When SpacetimeDB ran the reducer behind the last applied update.
+    event_at: u64,
+    applied_at: u64,
+    committed_at: u64,
+}
+
+/// How old the ingested data of every table and region is, fed by the ingest queues.
+#[derive(Default)]
+pub(crate) struct Freshness {
+    tables: DashMap<(&'static str, Region), TableFreshness>,
+    /// The newest event of a region over all tables, how far its subscription got.
+    regions: DashMap<Region, u64>,
+}
+
+impl Freshness {
+    /// An update was queued for `table`.
+    pub(crate) fn received(
+        &self,
+        table: &'static str,
+        region: Region,
+        seq: u64,
+        event_at: Option<u64>,
+    ) {
+        {
+            let mut freshness = self.tables.entry((table, region)).or_default();
+            freshness.received_seq = freshness.received_seq.max(seq);
+        }
+
+        if let Some(event_at) = event_at {
+            let mut newest = self.regions.entry(region).or_default();
+            *newest = (*newest).max(event_at);
+        }
+    }
+
+    /// The worker of `table` applied an update to the in-memory state.
+    pub(crate) fn applied(&self, table: &'static str, region: Region, seq: u64, event_at: u64) {
+        let applied_at = unix_millis();
+        {
+            let mut freshness = self.tables.entry((table, region)).or_default();
+            freshness.applied_seq = freshness.applied_seq.max(seq);
+            freshness.event_at = freshness.event_at.max(event_at);
+            freshness.applied_at = applied_at;
+        }
+
+        let labels = labels(table, region);
+        metrics::gauge!("table_last_event_timestamp_seconds", &labels)
+            .set(event_at as f64 / 1000.0);
+        metrics::gauge!("table_last_applied_timestamp_seconds", &labels)
+            .set(applied_at as f64 / 1000.0);
+    }
+
+    /// Everything the worker of `table` applied for `region` is in Postgres.
+    pub(crate) fn committed(&self, table: &'static str, region: Region) {
+        let committed_at = unix_millis();
+        self.tables.entry((table, region)).or_default().committed_at = committed_at;
+
+        metrics::gauge!(
+            "table_last_committed_timestamp_seconds",
+            &labels(table, region)
+        )
+        .set(committed_at as f64 / 1000.0);
+    }
+
+    /// Up to when the table reflects the game. With nothing pending that is as far as the
+    /// subscription of its region got, otherwise the last applied update.
+    fn current_as_of(&self, region: Region, freshness: &TableFreshness) -> u64 {
+        if freshness.applied_seq < freshness.received_seq {
+            return freshness.event_at;
+        }
+
+        self.regions
+            .get(&region)
+            .map_or(freshness.event_at, |newest| {
+                (*newest).max(freshness.event_at)
+            })
+    }
+
+    /// Milliseconds since the stalest of `tables` over all regions was current.
+    fn age(&self, tables: &[&str], now: u64) -> Option<u64> {
+        self.tables
+            .iter()
+            .filter(|entry| tables.contains(&entry.key().0))
+            .map(|entry| self.current_as_of(entry.key().1, entry.value()))
+            .filter(|as_of| *as_of > 0)
+            .min()
+            .map(|as_of| now.saturating_sub(as_of))
+    }
+}
+
+/// Adds `X-Data-Freshness` with the age in seconds of the stalest table a route reads.
+pub(crate) async fn data_freshness(
+    state: State<AppState>,
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let tables =
+        matched_path.and_then(|matched_path| TABLES_BY_ROUTE.get(matched_path.as_str()).copied());
+
+    let mut response = next.run(request).await;
+
+    if let Some(age) = tables.and_then(|tables| state.freshness.age(tables, unix_millis())) {
+        response
+            .headers_mut()
+            .insert(DATA_FRESHNESS, HeaderValue::from(age / 1000));
+    }
+
+    response
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct TableFreshnessResponse {
+    table: String,
+    region: Region,
+    /// Unix milliseconds SpacetimeDB ran the reducer behind the last applied update at.
+    last_event_at: Option<u64>,
+    /// Unix milliseconds the last update was applied to the in-memory state.
+    applied_at: Option<u64>,
+    /// Unix milliseconds the last update was committed to Postgres, `None` for tables that are
+    /// only kept in memory.
+    committed_at: Option<u64>,
+    /// Seconds since the table was current, what `X-Data-Freshness` is computed from.
+    age_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct FreshnessResponse {
+    tables: Vec<TableFreshnessResponse>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/status/freshness",
+    tag = "meta",
+    responses(
+        (status = OK, body = FreshnessResponse),
+    )
+)]
+pub(crate) async fn get_freshness(state: State<AppState>) -> axum_codec::Codec<FreshnessResponse> {
+    let now = unix_millis();
+    let known = |timestamp: u64| (timestamp > 0).then_some(timestamp);
+
+    let mut tables = state
+        .freshness
+        .tables
+        .iter()
+        .map(|entry| {
+            let (table, region) = *entry.key();
+            let freshness = entry.value();
+            let as_of = state.freshness.current_as_of(region, freshness);
+
+            TableFreshnessResponse {
+                table: table.to_string(),
+                region,
+                last_event_at: known(freshness.event_at),
+                applied_at: known(freshness.applied_at),
+                committed_at: known(freshness.committed_at),
+                age_seconds: known(as_of).map(|as_of| now.saturating_sub(as_of) as f64 / 1000.0),
+            }
+        })
+        .collect::<Vec<_>>();
+    tables.sort_by(|a, b| (&a.table, a.region).cmp(&(&b.table, b.region)));
+
+    axum_codec::Codec(FreshnessResponse { tables })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drained_tables_are_as_current_as_their_region() {
+        let freshness = Freshness::default();
+
+        freshness.received("player_state", 2, 1, Some(1_000));
+        freshness.applied("player_state", 2, 1, 1_000);
+        freshness.received("buy_order_state", 2, 1, Some(2_000));
+        freshness.applied("buy_order_state", 2, 1, 2_000);
+        freshness.received("buy_order_state", 2, 2, Some(5_000));
+        assert_eq!(freshness.age(&["player_state"], 10_000), Some(5_000));
+        assert_eq!(
+            freshness.age(&["player_state", "buy_order_state"], 10_000),
+            Some(8_000)
+        );
+
+        freshness.applied("buy_order_state", 2, 2, 5_000);
+        assert_eq!(
+            freshness.age(&["player_state", "buy_order_state"], 10_000),
+            Some(5_000)
+        );
+        assert_eq!(freshness.age(&["inventory_state"], 10_000), None);
+    }
+}
@@ -0,0 +1,198 @@
+mod query;
+mod subscription;
+mod types;
+
+use crate::config::GraphqlConfig;
+use crate::graphql::query::QueryRoot;
+use crate::graphql::subscription::SubscriptionRoot;
+use crate::{AppRouter, AppState};
+use async_graphql::dataloader::{DataLoader, HashMapCache, Loader};
+use async_graphql::http::{ALL_WEBSOCKET_PROTOCOLS, GraphiQLSource};
+use async_graphql::{Data, EmptyMutation, Schema};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLResponse, GraphQLWebSocket};
+use axum::Router;
+use axum::extract::State;
+use axum::extract::ws::WebSocketUpgrade;
+use axum::response::{Html, IntoResponse};
+use sea_orm::{DatabaseConnection, DbErr};
+use service::Query as QueryCore;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+
+/// Messages a subscriber can fall behind by before it skips the oldest ones.
+pub(crate) const EVENTS_CAPACITY: usize = 4096;
+
+pub(crate) type GraphqlSchema = Schema<QueryRoot, EmptyMutation, SubscriptionRoot>;
+
+pub(crate) fn get_routes() -> AppRouter {
+    Router::new()
+        .route(
+            "/api/bitcraft/graphql",
+            axum::routing::get(graphiql).post(graphql),
+        )
+        .route("/api/bitcraft/graphql/ws", axum::routing::get(graphql_ws))
+}
+
+pub(crate) fn build_schema(config: &GraphqlConfig) -> GraphqlSchema {
+    Schema::build(QueryRoot, EmptyMutation, SubscriptionRoot)
+        .limit_depth(config.max_depth)
+        .limit_complexity(config.max_complexity)
+        .finish()
+}
+
+/// Logs the actual error, clients only get a generic message like from the REST routes.
+pub(crate) fn database_error(error: impl Display) -> async_graphql::Error {
+    error!("GraphQL resolver error: {error}");
+
+    async_graphql::Error::new("Unexpected error")
+}
+
+/// Batches the username lookups of all players in a response into one query.
+pub(crate) struct UsernameLoader {
+    conn: DatabaseConnection,
+}
+
+impl Loader<i64> for UsernameLoader {
+    type Value = String;
+    type Error = Arc<DbErr>;
+
+    async fn load(&self, keys: &[i64]) -> Result<HashMap<i64, Self::Value>, Self::Error> {
+        Ok(
+            QueryCore::find_player_username_by_ids(&self.conn, keys.to_vec())
+                .await
+                .map_err(Arc::new)?
+                .into_iter()
+                .map(|player_username| (player_username.entity_id, player_username.username))
+                .collect(),
+        )
+    }
+}
+
+/// A GraphQL request, documented here as `async_graphql::Request` has no schema.
+#[allow(dead_code)]
+#[derive(ToSchema)]
+#[schema(rename_all = "camelCase")]
+struct GraphqlRequest {
+    query: String,
+    operation_name: Option<String>,
+    variables: Option<serde_json::Value>,
+}
+
+/// GraphiQL to explore the schema, it also runs subscriptions against the websocket route.
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/graphql",
+    tag = "graphql",
+    responses(
+        (status = OK, description = "GraphiQL", content_type = "text/html"),
+    )
+)]
+pub(crate) async fn graphiql() -> impl IntoResponse {
+    Html(
+        GraphiQLSource::build()
+            .endpoint("/api/bitcraft/graphql")
+            .subscription_endpoint("/api/bitcraft/graphql/ws")
+            .finish(),
+    )
+}
+
+/// Runs a GraphQL query, limited in depth and complexity by the `graphql` config.
+#[utoipa::path(
+    post,
+    path = "/api/bitcraft/graphql",
+    tag = "graphql",
+    request_body = GraphqlRequest,
+    responses(
+        (status = OK, description = "The `data` and `errors` of the query", content_type = "application/json"),
+    )
+)]
+pub(crate) async fn graphql(
+    State(state): State<AppState>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    let request = request
+        .into_inner()
+        .data(DataLoader::with_cache(
+            UsernameLoader {
+                conn: state.conn.clone(),
+            },
+            tokio::spawn,
+            HashMapCache::default(),
+        ))
+        .data(state.clone());
+
+    let response = state.graphql_schema.execute(request).await;
+
+    let outcome = if response.is_ok() { "ok" } else { "error" };
+    metrics::counter!("graphql_requests_total", "outcome" => outcome).increment(1);
+
+    response.into()
+}
+
+/// Upgrades to a `graphql-transport-ws` or `graphql-ws` connection for subscriptions.
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/graphql/ws",
+    tag = "graphql",
+    responses(
+        (status = SWITCHING_PROTOCOLS, description = "Upgraded to a websocket connection"),
+    )
+)]
+pub(crate) async fn graphql_ws(
+    State(state): State<AppState>,
+    protocol: GraphQLProtocol,
+    websocket: WebSocketUpgrade,
+) -> impl IntoResponse {
+    websocket
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |stream| {
+            let mut data = Data::default();
+            // Not cached, the connection can live a lot longer than a username stays the same
+            data.insert(DataLoader::new(
+                UsernameLoader {
+                    conn: state.conn.clone(),
+                },
+                tokio::spawn,
+            ));
+            data.insert(state.clone());
+
+            GraphQLWebSocket::new(stream, state.graphql_schema.clone(), protocol)
+                .with_data(data)
+                .serve()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schema(max_depth: usize, max_complexity: usize) -> GraphqlSchema {
+        build_schema(&GraphqlConfig {
+            max_depth,
+            max_complexity,
+        })
+    }
+
+    #[tokio::test]
+    async fn rejects_queries_nested_too_deep() {
+        let response = schema(3, 10_000)
+            .execute("{ claim(id: 1) { members { player { claims { name } } } } }")
+            .await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("nested too deep"));
+    }
+
+    #[tokio::test]
+    async fn rejects_queries_that_are_too_complex() {
+        let response = schema(10, 100)
+            .execute("{ claims(limit: 100) { name members { userName } } }")
+            .await;
+
+        assert_eq!(response.errors.len(), 1);
+        assert!(response.errors[0].message.contains("too complex"));
+    }
+}
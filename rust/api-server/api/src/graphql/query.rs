@@ -0,0 +1,320 @@
+use crate::AppState;
+use crate::graphql::types::{
+    Building, Cargo, Claim, Inventory, Item, ItemType, LeaderboardEntry, MarketOrder, OrderSide,
+    Player, Recipe, cargo_by_id, claim_by_id, inventories_by_owner, item_by_id, player_by_id,
+};
+use crate::graphql::{UsernameLoader, database_error};
+use crate::leaderboard::Leaderboard;
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, Enum, Object, Result};
+use service::Query as QueryCore;
+
+#[derive(Enum, Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum LeaderboardBoard {
+    Experience,
+    Level,
+    ExperiencePerHour,
+    TimePlayed,
+    TimeSignedIn,
+    /// Needs `skillId`.
+    Skill,
+}
+
+pub(crate) struct QueryRoot;
+
+fn matches_search(name: &str, search: &Option<String>) -> bool {
+    search
+        .as_ref()
+        .is_none_or(|search| name.to_lowercase().contains(&search.to_lowercase()))
+}
+
+#[Object]
+impl QueryRoot {
+    async fn player(&self, ctx: &Context<'_>, id: i64) -> Result<Option<Player>> {
+        Ok(player_by_id(ctx.data::<AppState>()?, id))
+    }
+
+    #[graphql(complexity = "10 + per_page as usize * child_complexity")]
+    async fn players(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        online: Option<bool>,
+        #[graphql(default = 1)] page: u64,
+        #[graphql(default = 24, validator(maximum = 100))] per_page: u64,
+    ) -> Result<Vec<Player>> {
+        let state = ctx.data::<AppState>()?;
+
+        let (players, usernames, _) =
+            QueryCore::find_players(&state.conn, page, per_page, search, online, None)
+                .await
+                .map_err(database_error)?;
+
+        // Saves a lookup per player when `username` is requested
+        ctx.data::<DataLoader<UsernameLoader>>()?
+            .feed_many(
+                usernames
+                    .into_iter()
+                    .map(|username| (username.entity_id, username.username)),
+            )
+            .await;
+
+        Ok(players.into_iter().map(Player).collect())
+    }
+
+    async fn claim(&self, ctx: &Context<'_>, id: i64) -> Result<Option<Claim>> {
+        Ok(claim_by_id(ctx.data::<AppState>()?, id))
+    }
+
+    #[graphql(complexity = "limit * child_complexity")]
+    async fn claims(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        region: Option<i16>,
+        #[graphql(default = 24, validator(maximum = 100))] limit: usize,
+    ) -> Result<Vec<Claim>> {
+        let mut claims = ctx
+            .data::<AppState>()?
+            .claim_state
+            .iter()
+            .filter(|claim| {
+                matches_search(&claim.name, &search)
+                    && region.is_none_or(|region| claim.region == region)
+            })
+            .map(|claim| claim.clone())
+            .collect::<Vec<_>>();
+        claims.sort_by(|a, b| a.name.cmp(&b.name).then(a.entity_id.cmp(&b.entity_id)));
+        claims.truncate(limit);
+
+        Ok(claims.into_iter().map(Claim).collect())
+    }
+
+    #[graphql(complexity = 10)]
+    async fn building(&self, ctx: &Context<'_>, id: i64) -> Result<Option<Building>> {
+        let state = ctx.data::<AppState>()?;
+
+        Ok(QueryCore::find_building_state_by_id(&state.conn, id)
+            .await
+            .map_err(database_error)?
+            .map(Building))
+    }
+
+    #[graphql(complexity = 10)]
+    async fn inventory(&self, ctx: &Context<'_>, id: i64) -> Result<Option<Inventory>> {
+        let state = ctx.data::<AppState>()?;
+
+        Ok(QueryCore::find_inventory_by_id(&state.conn, id)
+            .await
+            .map_err(database_error)?
+            .map(Inventory))
+    }
+
+    /// Inventories owned by a player, building or deployable.
+    #[graphql(complexity = "10 + child_complexity")]
+    async fn inventories(&self, ctx: &Context<'_>, owner_entity_id: i64) -> Result<Vec<Inventory>> {
+        inventories_by_owner(ctx, owner_entity_id).await
+    }
+
+    async fn item(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Item>> {
+        Ok(item_by_id(ctx.data::<AppState>()?, id))
+    }
+
+    #[graphql(complexity = "limit * child_complexity")]
+    async fn items(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        tier: Option<i32>,
+        #[graphql(default = 24, validator(maximum = 100))] limit: usize,
+    ) -> Result<Vec<Item>> {
+        let mut items = ctx
+            .data::<AppState>()?
+            .item_desc
+            .iter()
+            .filter(|item| {
+                matches_search(&item.name, &search) && tier.is_none_or(|tier| item.tier == tier)
+            })
+            .map(|item| item.clone())
+            .collect::<Vec<_>>();
+        items.sort_by_key(|item| item.id);
+        items.truncate(limit);
+
+        Ok(items.into_iter().map(Item).collect())
+    }
+
+    async fn cargo(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Cargo>> {
+        Ok(cargo_by_id(ctx.data::<AppState>()?, id))
+    }
+
+    #[graphql(complexity = "limit * child_complexity")]
+    async fn cargos(
+        &self,
+        ctx: &Context<'_>,
+        search: Option<String>,
+        tier: Option<i32>,
+        #[graphql(default = 24, validator(maximum = 100))] limit: usize,
+    ) -> Result<Vec<Cargo>> {
+        let mut cargos = ctx
+            .data::<AppState>()?
+            .cargo_desc
+            .iter()
+            .filter(|cargo| {
+                matches_search(&cargo.name, &search) && tier.is_none_or(|tier| cargo.tier == tier)
+            })
+            .map(|cargo| cargo.clone())
+            .collect::<Vec<_>>();
+        cargos.sort_by_key(|cargo| cargo.id);
+        cargos.truncate(limit);
+
+        Ok(cargos.into_iter().map(Cargo).collect())
+    }
+
+    async fn recipe(&self, ctx: &Context<'_>, id: i32) -> Result<Option<Recipe>> {
+        Ok(ctx
+            .data::<AppState>()?
+            .crafting_recipe_desc
+            .get(&id)
+            .map(|recipe| Recipe(recipe.clone())))
+    }
+
+    /// Recipes crafting and/or consuming the given item or cargo.
+    #[graphql(complexity = "limit * child_complexity")]
+    async fn recipes(
+        &self,
+        ctx: &Context<'_>,
+        crafted_item_id: Option<i32>,
+        consumed_item_id: Option<i32>,
+        #[graphql(default_with = "ItemType::Item")] item_type: ItemType,
+        #[graphql(default = 24, validator(maximum = 100))] limit: usize,
+    ) -> Result<Vec<Recipe>> {
+        let mut recipes = ctx
+            .data::<AppState>()?
+            .crafting_recipe_desc
+            .iter()
+            .filter(|recipe| {
+                crafted_item_id.is_none_or(|item_id| {
+                    recipe.crafted_item_stacks.iter().any(|stack| {
+                        stack.item_id == item_id
+                            && ItemType::from(stack.item_type.clone()) == item_type
+                    })
+                }) && consumed_item_id.is_none_or(|item_id| {
+                    recipe.consumed_item_stacks.iter().any(|stack| {
+                        stack.item_id == item_id
+                            && ItemType::from(stack.item_type.clone()) == item_type
+                    })
+                })
+            })
+            .map(|recipe| recipe.clone())
+            .collect::<Vec<_>>();
+        recipes.sort_by_key(|recipe| recipe.id);
+        recipes.truncate(limit);
+
+        Ok(recipes.into_iter().map(Recipe).collect())
+    }
+
+    /// Open market orders for an item or cargo, cheapest sell and highest buy orders first.
+    #[graphql(complexity = "limit * child_complexity")]
+    async fn market_orders(
+        &self,
+        ctx: &Context<'_>,
+        item_id: i32,
+        #[graphql(default_with = "ItemType::Item")] item_type: ItemType,
+        side: Option<OrderSide>,
+        #[graphql(default = 50, validator(maximum = 100))] limit: usize,
+    ) -> Result<Vec<MarketOrder>> {
+        let state = ctx.data::<AppState>()?;
+        let item_type = match item_type {
+            ItemType::Item => 0,
+            ItemType::Cargo => 1,
+        };
+
+        let mut orders = vec![];
+        for (order_side, order_state) in [
+            (OrderSide::Sell, &state.sell_order_state),
+            (OrderSide::Buy, &state.buy_order_state),
+        ] {
+            if side.is_some_and(|side| side != order_side) {
+                continue;
+            }
+
+            let mut side_orders = order_state
+                .iter()
+                .filter(|order| order.item_id == item_id && order.item_type == item_type)
+                .map(|order| MarketOrder {
+                    order: order.clone(),
+                    side: order_side,
+                })
+                .collect::<Vec<_>>();
+            match order_side {
+                OrderSide::Sell => side_orders.sort_by_key(|order| order.order.price_threshold),
+                OrderSide::Buy => {
+                    side_orders.sort_by_key(|order| std::cmp::Reverse(order.order.price_threshold))
+                }
+            }
+            side_orders.truncate(limit);
+
+            orders.append(&mut side_orders);
+        }
+
+        Ok(orders)
+    }
+
+    /// A page of a leaderboard, global unless `region` is given. Time played and time signed in
+    /// are only tracked globally.
+    #[graphql(complexity = "limit * child_complexity")]
+    async fn leaderboard(
+        &self,
+        ctx: &Context<'_>,
+        board: LeaderboardBoard,
+        skill_id: Option<i64>,
+        region: Option<i16>,
+        #[graphql(default = 0)] offset: usize,
+        #[graphql(default = 100, validator(maximum = 100))] limit: usize,
+    ) -> Result<Vec<LeaderboardEntry>> {
+        let ranking_system = &ctx.data::<AppState>()?.ranking_system;
+        let region_rankings = match region {
+            Some(region) => Some(ranking_system.region(region).ok_or("Region not found")?),
+            None => None,
+        };
+
+        let entries = {
+            let skill_leaderboard;
+            let leaderboard: &Leaderboard = match (board, &region_rankings) {
+                (LeaderboardBoard::Experience, None) => &ranking_system.global_leaderboard,
+                (LeaderboardBoard::Experience, Some(boards)) => &boards.global_leaderboard,
+                (LeaderboardBoard::Level, None) => &ranking_system.level_leaderboard,
+                (LeaderboardBoard::Level, Some(boards)) => &boards.level_leaderboard,
+                (LeaderboardBoard::ExperiencePerHour, None) => &ranking_system.xp_per_hour,
+                (LeaderboardBoard::ExperiencePerHour, Some(boards)) => &boards.xp_per_hour,
+                (LeaderboardBoard::TimePlayed, None) => &ranking_system.time_played,
+                (LeaderboardBoard::TimeSignedIn, None) => &ranking_system.time_signed_in,
+                (LeaderboardBoard::TimePlayed | LeaderboardBoard::TimeSignedIn, Some(_)) => {
+                    return Err("Time boards are only tracked globally".into());
+                }
+                (LeaderboardBoard::Skill, _) => {
+                    let skill_id = skill_id.ok_or("The skill board needs a skillId")?;
+                    let skill_leaderboards = match &region_rankings {
+                        Some(boards) => &boards.skill_leaderboards,
+                        None => &ranking_system.skill_leaderboards,
+                    };
+
+                    skill_leaderboard =
+                        skill_leaderboards.get(&skill_id).ok_or("Skill not found")?;
+                    skill_leaderboard.value()
+                }
+            };
+
+            leaderboard.get_range(offset, limit)
+        };
+
+        Ok(entries
+            .into_iter()
+            .map(|entry| LeaderboardEntry {
+                rank: entry.rank as u64,
+                player_id: entry.user_id,
+                score: entry.xp,
+            })
+            .collect())
+    }
+}
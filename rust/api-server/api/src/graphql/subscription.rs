@@ -0,0 +1,152 @@
+use crate::AppState;
+use crate::claims::events::ClaimEvent;
+use crate::graphql::types::Player;
+use crate::websocket::WebSocketMessages;
+use crate::websocket::filter::parse_topic;
+use async_graphql::{Context, Json, Result, SimpleObject, Subscription};
+use futures::{Stream, StreamExt};
+use std::collections::HashSet;
+use tokio::sync::broadcast::error::RecvError;
+
+/// How many topics a single `messages` subscription can listen to.
+const MAX_TOPICS_PER_SUBSCRIPTION: usize = 100;
+
+#[derive(SimpleObject)]
+pub(crate) struct LiveMessage {
+    pub(crate) topic: String,
+    pub(crate) topic_id: Option<i64>,
+    /// The message exactly as the websocket would send it.
+    pub(crate) message: Json<WebSocketMessages>,
+}
+
+/// Every message `broadcast_message` hands to websocket clients, skipped ones are counted but
+/// not replayed.
+fn live_messages(state: &AppState) -> impl Stream<Item = WebSocketMessages> + use<> {
+    futures::stream::unfold(
+        state.graphql_events.subscribe(),
+        |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(message) => return Some((message, receiver)),
+                    Err(RecvError::Lagged(skipped)) => {
+                        metrics::counter!("graphql_subscription_lagged_total").increment(skipped);
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        },
+    )
+}
+
+/// The first of the message's topics a subscriber listens to.
+fn matching_topic(
+    message: &WebSocketMessages,
+    topics: &HashSet<(String, Option<i64>)>,
+) -> Option<(String, Option<i64>)> {
+    message
+        .topics()?
+        .into_iter()
+        .find(|topic| topics.contains(topic))
+}
+
+pub(crate) struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    /// Messages for websocket topics such as `player_state.123` or `claim.456`, a topic
+    /// without an id only matches messages published without one.
+    async fn messages(
+        &self,
+        ctx: &Context<'_>,
+        topics: Vec<String>,
+    ) -> Result<impl Stream<Item = LiveMessage>> {
+        if topics.len() > MAX_TOPICS_PER_SUBSCRIPTION {
+            return Err("Too many topics".into());
+        }
+
+        let topics = topics
+            .iter()
+            .map(|topic| parse_topic(topic).map(|(topic, id)| (topic.to_string(), id)))
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        Ok(
+            live_messages(ctx.data::<AppState>()?).filter_map(move |message| {
+                let live_message =
+                    matching_topic(&message, &topics).map(|(topic, topic_id)| LiveMessage {
+                        topic,
+                        topic_id,
+                        message: Json(message),
+                    });
+
+                futures::future::ready(live_message)
+            }),
+        )
+    }
+
+    /// The player every time their state changes, e.g. when they sign in or out.
+    async fn player_state(&self, ctx: &Context<'_>, id: i64) -> Result<impl Stream<Item = Player>> {
+        Ok(
+            live_messages(ctx.data::<AppState>()?).filter_map(move |message| {
+                let player = match message {
+                    WebSocketMessages::PlayerState(player_state)
+                        if player_state.entity_id == id =>
+                    {
+                        Some(Player(player_state))
+                    }
+                    _ => None,
+                };
+
+                futures::future::ready(player)
+            }),
+        )
+    }
+
+    /// The events published on the `claim.<id>` websocket topic.
+    async fn claim_events(
+        &self,
+        ctx: &Context<'_>,
+        claim_id: i64,
+    ) -> Result<impl Stream<Item = Json<ClaimEvent>>> {
+        Ok(
+            live_messages(ctx.data::<AppState>()?).filter_map(move |message| {
+                let event = match message {
+                    WebSocketMessages::ClaimEvent {
+                        claim_id: event_claim_id,
+                        event,
+                    } if event_claim_id == claim_id => Some(Json(event)),
+                    _ => None,
+                };
+
+                futures::future::ready(event)
+            }),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn topics_match_like_websocket_subscriptions() {
+        let message = WebSocketMessages::TotalExperience {
+            user_id: 7,
+            experience: 100,
+            rank: 1,
+            experience_per_hour: 10,
+        };
+        let topics = HashSet::from([("total_experience".to_string(), Some(7))]);
+
+        assert_eq!(
+            matching_topic(&message, &topics),
+            Some(("total_experience".to_string(), Some(7)))
+        );
+        assert_eq!(
+            matching_topic(
+                &message,
+                &HashSet::from([("total_experience".to_string(), None)])
+            ),
+            None
+        );
+    }
+}
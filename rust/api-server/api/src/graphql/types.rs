@@ -0,0 +1,690 @@
+use crate::AppState;
+use crate::graphql::{UsernameLoader, database_error};
+use crate::leaderboard::experience_to_level;
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, Enum, Object, Result, SimpleObject};
+use entity::auction_listing_state::AuctionListingState;
+use service::Query as QueryCore;
+
+#[derive(Enum, Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ItemType {
+    Item,
+    Cargo,
+}
+
+impl From<entity::inventory::ItemType> for ItemType {
+    fn from(value: entity::inventory::ItemType) -> Self {
+        match value {
+            entity::inventory::ItemType::Item => ItemType::Item,
+            entity::inventory::ItemType::Cargo => ItemType::Cargo,
+        }
+    }
+}
+
+#[derive(Enum, Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum OrderSide {
+    Buy,
+    Sell,
+}
+
+pub(crate) struct Player(pub(crate) entity::player_state::Model);
+
+#[Object]
+impl Player {
+    async fn entity_id(&self) -> i64 {
+        self.0.entity_id
+    }
+
+    async fn username(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        ctx.data::<DataLoader<UsernameLoader>>()?
+            .load_one(self.0.entity_id)
+            .await
+            .map_err(database_error)
+    }
+
+    async fn signed_in(&self) -> bool {
+        self.0.signed_in
+    }
+
+    async fn time_played(&self) -> i32 {
+        self.0.time_played
+    }
+
+    async fn time_signed_in(&self) -> i32 {
+        self.0.time_signed_in
+    }
+
+    async fn region(&self) -> i16 {
+        self.0.region
+    }
+
+    async fn claims(&self, ctx: &Context<'_>) -> Result<Vec<Claim>> {
+        let state = ctx.data::<AppState>()?;
+
+        Ok(state
+            .player_to_claim_id_cache
+            .get(&(self.0.entity_id as u64))
+            .map_or(vec![], |claim_ids| {
+                claim_ids
+                    .iter()
+                    .filter_map(|claim_id| claim_by_id(state, *claim_id as i64))
+                    .collect()
+            }))
+    }
+
+    /// Experience, level and rank in every ranked skill.
+    async fn skills(&self, ctx: &Context<'_>) -> Result<Vec<SkillRank>> {
+        let state = ctx.data::<AppState>()?;
+
+        let mut skills = state
+            .skill_desc
+            .iter()
+            .filter(|skill| skill.skill_category != 0)
+            .filter_map(|skill| {
+                let leaderboard = state.ranking_system.skill_leaderboards.get(&skill.id)?;
+                let experience = *leaderboard.scores.get(&self.0.entity_id)?;
+
+                Some(SkillRank {
+                    skill_id: skill.id,
+                    skill_name: skill.name.clone(),
+                    experience,
+                    level: experience_to_level(experience),
+                    rank: leaderboard
+                        .get_rank(self.0.entity_id)
+                        .map(|rank| rank as u64),
+                })
+            })
+            .collect::<Vec<_>>();
+        skills.sort_by_key(|skill| skill.skill_id);
+
+        Ok(skills)
+    }
+
+    #[graphql(complexity = "10 + child_complexity")]
+    async fn inventories(&self, ctx: &Context<'_>) -> Result<Vec<Inventory>> {
+        inventories_by_owner(ctx, self.0.entity_id).await
+    }
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct SkillRank {
+    pub(crate) skill_id: i64,
+    pub(crate) skill_name: String,
+    pub(crate) experience: i64,
+    pub(crate) level: i32,
+    /// `None` for players excluded from the leaderboards.
+    pub(crate) rank: Option<u64>,
+}
+
+pub(crate) struct Claim(pub(crate) entity::claim_state::Model);
+
+#[Object]
+impl Claim {
+    async fn entity_id(&self) -> i64 {
+        self.0.entity_id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn owner_player_entity_id(&self) -> i64 {
+        self.0.owner_player_entity_id
+    }
+
+    async fn owner_building_entity_id(&self) -> i64 {
+        self.0.owner_building_entity_id
+    }
+
+    async fn neutral(&self) -> bool {
+        self.0.neutral
+    }
+
+    async fn region(&self) -> i16 {
+        self.0.region
+    }
+
+    async fn owner(&self, ctx: &Context<'_>) -> Result<Option<Player>> {
+        Ok(player_by_id(
+            ctx.data::<AppState>()?,
+            self.0.owner_player_entity_id,
+        ))
+    }
+
+    async fn supplies(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        Ok(ctx
+            .data::<AppState>()?
+            .claim_local_state
+            .get(&(self.0.entity_id as u64))
+            .map(|claim_local_state| claim_local_state.supplies))
+    }
+
+    async fn treasury(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        Ok(ctx
+            .data::<AppState>()?
+            .claim_local_state
+            .get(&(self.0.entity_id as u64))
+            .map(|claim_local_state| claim_local_state.treasury))
+    }
+
+    async fn num_tiles(&self, ctx: &Context<'_>) -> Result<Option<i32>> {
+        Ok(ctx
+            .data::<AppState>()?
+            .claim_local_state
+            .get(&(self.0.entity_id as u64))
+            .map(|claim_local_state| claim_local_state.num_tiles))
+    }
+
+    #[graphql(complexity = "25 * child_complexity")]
+    async fn members(&self, ctx: &Context<'_>) -> Result<Vec<ClaimMember>> {
+        let mut members = ctx
+            .data::<AppState>()?
+            .claim_member_state
+            .get(&(self.0.entity_id as u64))
+            .map_or(vec![], |members| {
+                members
+                    .iter()
+                    .map(|member| ClaimMember(member.clone()))
+                    .collect::<Vec<_>>()
+            });
+        members.sort_by(|a, b| a.0.user_name.cmp(&b.0.user_name));
+
+        Ok(members)
+    }
+
+    #[graphql(complexity = "10 + 50 * child_complexity")]
+    async fn buildings(&self, ctx: &Context<'_>) -> Result<Vec<Building>> {
+        let state = ctx.data::<AppState>()?;
+
+        Ok(
+            QueryCore::find_building_state_by_claim_id(&state.conn, self.0.entity_id)
+                .await
+                .map_err(database_error)?
+                .into_iter()
+                .map(Building)
+                .collect(),
+        )
+    }
+
+    /// Inventories of every building in the claim.
+    #[graphql(complexity = "20 + child_complexity")]
+    async fn inventories(&self, ctx: &Context<'_>) -> Result<Vec<Inventory>> {
+        let state = ctx.data::<AppState>()?;
+
+        let building_ids =
+            QueryCore::find_building_state_by_claim_id(&state.conn, self.0.entity_id)
+                .await
+                .map_err(database_error)?
+                .into_iter()
+                .map(|building| building.entity_id)
+                .collect::<Vec<_>>();
+
+        Ok(
+            QueryCore::get_inventorys_by_owner_entity_ids(&state.conn, building_ids)
+                .await
+                .map_err(database_error)?
+                .into_iter()
+                .map(Inventory)
+                .collect(),
+        )
+    }
+}
+
+pub(crate) struct ClaimMember(pub(crate) entity::claim_member_state::Model);
+
+#[Object]
+impl ClaimMember {
+    async fn entity_id(&self) -> i64 {
+        self.0.entity_id
+    }
+
+    async fn player_entity_id(&self) -> i64 {
+        self.0.player_entity_id
+    }
+
+    async fn user_name(&self) -> &str {
+        &self.0.user_name
+    }
+
+    async fn inventory_permission(&self) -> bool {
+        self.0.inventory_permission
+    }
+
+    async fn build_permission(&self) -> bool {
+        self.0.build_permission
+    }
+
+    async fn officer_permission(&self) -> bool {
+        self.0.officer_permission
+    }
+
+    async fn co_owner_permission(&self) -> bool {
+        self.0.co_owner_permission
+    }
+
+    async fn player(&self, ctx: &Context<'_>) -> Result<Option<Player>> {
+        Ok(player_by_id(
+            ctx.data::<AppState>()?,
+            self.0.player_entity_id,
+        ))
+    }
+}
+
+pub(crate) struct Building(pub(crate) entity::building_state::Model);
+
+#[Object]
+impl Building {
+    async fn entity_id(&self) -> i64 {
+        self.0.entity_id
+    }
+
+    async fn claim_entity_id(&self) -> i64 {
+        self.0.claim_entity_id
+    }
+
+    async fn building_description_id(&self) -> i32 {
+        self.0.building_description_id
+    }
+
+    async fn constructed_by_player_entity_id(&self) -> i64 {
+        self.0.constructed_by_player_entity_id
+    }
+
+    async fn region(&self) -> i16 {
+        self.0.region
+    }
+
+    /// The name of the building description.
+    async fn name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        Ok(ctx
+            .data::<AppState>()?
+            .building_desc
+            .get(&(self.0.building_description_id as i64))
+            .map(|building_desc| building_desc.name.clone()))
+    }
+
+    async fn nickname(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        Ok(ctx
+            .data::<AppState>()?
+            .building_nickname_state
+            .get(&self.0.entity_id)
+            .map(|building_nickname_state| building_nickname_state.nickname.clone()))
+    }
+
+    async fn claim(&self, ctx: &Context<'_>) -> Result<Option<Claim>> {
+        Ok(claim_by_id(ctx.data::<AppState>()?, self.0.claim_entity_id))
+    }
+
+    #[graphql(complexity = "10 + child_complexity")]
+    async fn inventories(&self, ctx: &Context<'_>) -> Result<Vec<Inventory>> {
+        inventories_by_owner(ctx, self.0.entity_id).await
+    }
+}
+
+pub(crate) struct Inventory(pub(crate) entity::inventory::Model);
+
+#[Object]
+impl Inventory {
+    async fn entity_id(&self) -> i64 {
+        self.0.entity_id
+    }
+
+    async fn owner_entity_id(&self) -> i64 {
+        self.0.owner_entity_id
+    }
+
+    async fn player_owner_entity_id(&self) -> i64 {
+        self.0.player_owner_entity_id
+    }
+
+    async fn inventory_index(&self) -> i32 {
+        self.0.inventory_index
+    }
+
+    async fn cargo_index(&self) -> i32 {
+        self.0.cargo_index
+    }
+
+    async fn region(&self) -> i16 {
+        self.0.region
+    }
+
+    async fn pockets(&self) -> Vec<Pocket> {
+        self.0.pockets.iter().cloned().map(Pocket).collect()
+    }
+}
+
+pub(crate) struct Pocket(entity::inventory::Pocket);
+
+#[Object]
+impl Pocket {
+    async fn volume(&self) -> i32 {
+        self.0.volume
+    }
+
+    async fn locked(&self) -> bool {
+        self.0.locked
+    }
+
+    async fn contents(&self) -> Option<ItemStack> {
+        self.0.contents.as_ref().map(|contents| ItemStack {
+            item_id: contents.item_id,
+            quantity: contents.quantity,
+            item_type: contents.item_type.clone().into(),
+            durability: contents.durability,
+        })
+    }
+}
+
+pub(crate) struct ItemStack {
+    pub(crate) item_id: i32,
+    pub(crate) quantity: i32,
+    pub(crate) item_type: ItemType,
+    pub(crate) durability: Option<i32>,
+}
+
+impl From<&entity::shared::item_stack::ItemStack> for ItemStack {
+    fn from(value: &entity::shared::item_stack::ItemStack) -> Self {
+        Self {
+            item_id: value.item_id,
+            quantity: value.quantity,
+            item_type: value.item_type.clone().into(),
+            durability: value.durability,
+        }
+    }
+}
+
+#[Object]
+impl ItemStack {
+    async fn item_id(&self) -> i32 {
+        self.item_id
+    }
+
+    async fn quantity(&self) -> i32 {
+        self.quantity
+    }
+
+    async fn item_type(&self) -> ItemType {
+        self.item_type
+    }
+
+    async fn durability(&self) -> Option<i32> {
+        self.durability
+    }
+
+    /// The description when `itemType` is `ITEM`.
+    async fn item(&self, ctx: &Context<'_>) -> Result<Option<Item>> {
+        Ok(match self.item_type {
+            ItemType::Item => item_by_id(ctx.data::<AppState>()?, self.item_id),
+            ItemType::Cargo => None,
+        })
+    }
+
+    /// The description when `itemType` is `CARGO`.
+    async fn cargo(&self, ctx: &Context<'_>) -> Result<Option<Cargo>> {
+        Ok(match self.item_type {
+            ItemType::Item => None,
+            ItemType::Cargo => cargo_by_id(ctx.data::<AppState>()?, self.item_id),
+        })
+    }
+}
+
+pub(crate) struct Item(pub(crate) entity::item_desc::Model);
+
+#[Object]
+impl Item {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    async fn tier(&self) -> i32 {
+        self.0.tier
+    }
+
+    async fn tag(&self) -> &str {
+        &self.0.tag
+    }
+
+    async fn volume(&self) -> i32 {
+        self.0.volume
+    }
+
+    async fn icon_asset_name(&self) -> &str {
+        &self.0.icon_asset_name
+    }
+
+    async fn compendium_entry(&self) -> bool {
+        self.0.compendium_entry
+    }
+}
+
+pub(crate) struct Cargo(pub(crate) entity::cargo_desc::Model);
+
+#[Object]
+impl Cargo {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn description(&self) -> &str {
+        &self.0.description
+    }
+
+    async fn tier(&self) -> i32 {
+        self.0.tier
+    }
+
+    async fn tag(&self) -> &str {
+        &self.0.tag
+    }
+
+    async fn volume(&self) -> i32 {
+        self.0.volume
+    }
+
+    async fn icon_asset_name(&self) -> &str {
+        &self.0.icon_asset_name
+    }
+}
+
+pub(crate) struct Recipe(pub(crate) entity::crafting_recipe::Model);
+
+#[Object]
+impl Recipe {
+    async fn id(&self) -> i32 {
+        self.0.id
+    }
+
+    async fn name(&self) -> &str {
+        &self.0.name
+    }
+
+    async fn time_requirement(&self) -> f32 {
+        self.0.time_requirement
+    }
+
+    async fn stamina_requirement(&self) -> f32 {
+        self.0.stamina_requirement
+    }
+
+    async fn actions_required(&self) -> i32 {
+        self.0.actions_required
+    }
+
+    async fn level_requirements(&self) -> Vec<LevelRequirement> {
+        self.0
+            .level_requirements
+            .iter()
+            .map(|level_requirement| LevelRequirement {
+                skill_id: level_requirement.skill_id,
+                level: level_requirement.level,
+            })
+            .collect()
+    }
+
+    async fn consumed_items(&self) -> Vec<ItemStack> {
+        self.0
+            .consumed_item_stacks
+            .iter()
+            .map(|consumed| ItemStack {
+                item_id: consumed.item_id,
+                quantity: consumed.quantity,
+                item_type: consumed.item_type.clone().into(),
+                durability: None,
+            })
+            .collect()
+    }
+
+    async fn crafted_items(&self) -> Vec<ItemStack> {
+        self.0
+            .crafted_item_stacks
+            .iter()
+            .map(ItemStack::from)
+            .collect()
+    }
+}
+
+#[derive(SimpleObject)]
+pub(crate) struct LevelRequirement {
+    pub(crate) skill_id: i32,
+    pub(crate) level: i32,
+}
+
+pub(crate) struct MarketOrder {
+    pub(crate) order: AuctionListingState,
+    pub(crate) side: OrderSide,
+}
+
+#[Object]
+impl MarketOrder {
+    async fn entity_id(&self) -> i64 {
+        self.order.entity_id as i64
+    }
+
+    async fn side(&self) -> OrderSide {
+        self.side
+    }
+
+    async fn owner_entity_id(&self) -> i64 {
+        self.order.owner_entity_id as i64
+    }
+
+    async fn claim_entity_id(&self) -> i64 {
+        self.order.claim_entity_id as i64
+    }
+
+    async fn item_id(&self) -> i32 {
+        self.order.item_id
+    }
+
+    async fn item_type(&self) -> ItemType {
+        if self.order.item_type == 1 {
+            ItemType::Cargo
+        } else {
+            ItemType::Item
+        }
+    }
+
+    async fn price_threshold(&self) -> i32 {
+        self.order.price_threshold
+    }
+
+    async fn quantity(&self) -> i32 {
+        self.order.quantity
+    }
+
+    async fn stored_coins(&self) -> i32 {
+        self.order.stored_coins
+    }
+
+    async fn region(&self) -> i16 {
+        self.order.region
+    }
+
+    async fn claim(&self, ctx: &Context<'_>) -> Result<Option<Claim>> {
+        Ok(claim_by_id(
+            ctx.data::<AppState>()?,
+            self.order.claim_entity_id as i64,
+        ))
+    }
+}
+
+pub(crate) struct LeaderboardEntry {
+    pub(crate) rank: u64,
+    pub(crate) player_id: i64,
+    pub(crate) score: i64,
+}
+
+#[Object]
+impl LeaderboardEntry {
+    async fn rank(&self) -> u64 {
+        self.rank
+    }
+
+    async fn player_id(&self) -> i64 {
+        self.player_id
+    }
+
+    /// Experience, level or seconds, depending on the board.
+    async fn score(&self) -> i64 {
+        self.score
+    }
+
+    async fn username(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        ctx.data::<DataLoader<UsernameLoader>>()?
+            .load_one(self.player_id)
+            .await
+            .map_err(database_error)
+    }
+
+    async fn player(&self, ctx: &Context<'_>) -> Result<Option<Player>> {
+        Ok(player_by_id(ctx.data::<AppState>()?, self.player_id))
+    }
+}
+
+pub(crate) fn player_by_id(state: &AppState, id: i64) -> Option<Player> {
+    state
+        .player_state
+        .get(&id)
+        .map(|player| Player(player.clone()))
+}
+
+pub(crate) fn claim_by_id(state: &AppState, id: i64) -> Option<Claim> {
+    state.claim_state.get(&id).map(|claim| Claim(claim.clone()))
+}
+
+pub(crate) fn item_by_id(state: &AppState, id: i32) -> Option<Item> {
+    state.item_desc.get(&id).map(|item| Item(item.clone()))
+}
+
+pub(crate) fn cargo_by_id(state: &AppState, id: i32) -> Option<Cargo> {
+    state.cargo_desc.get(&id).map(|cargo| Cargo(cargo.clone()))
+}
+
+pub(crate) async fn inventories_by_owner(
+    ctx: &Context<'_>,
+    owner_entity_id: i64,
+) -> Result<Vec<Inventory>> {
+    let state = ctx.data::<AppState>()?;
+
+    Ok(
+        QueryCore::get_inventorys_by_owner_entity_id(&state.conn, owner_entity_id)
+            .await
+            .map_err(database_error)?
+            .into_iter()
+            .map(Inventory)
+            .collect(),
+    )
+}
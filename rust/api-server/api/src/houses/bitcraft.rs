@@ -1,5 +1,10 @@
 use crate::AppState;
+use crate::websocket::ingest_queue::IngestReceiver;
+use crate::websocket::reconcile::reconciled_table;
+use crate::websocket::table_sync::SyncedTable;
 use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
+use entity::shared::Region;
+use entity::{dimension_description_state, interior_network_desc, permission_state, portal_state};
 use game_module::module_bindings::{
     DimensionDescriptionState, InteriorNetworkDesc, PermissionState, PlayerHousingState,
     PortalState,
@@ -7,764 +12,173 @@ use game_module::module_bindings::{
 use migration::{OnConflict, sea_query};
 use sea_orm::{ColumnTrait, DbErr, EntityTrait, IntoActiveModel, QueryFilter};
 use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::sleep;
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Interior Network Desc Worker
-// ─────────────────────────────────────────────────────────────────────────────
-
-pub(crate) fn start_worker_interior_network_desc(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<InteriorNetworkDesc>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict =
-            sea_query::OnConflict::columns([::entity::interior_network_desc::Column::BuildingId])
-                .update_columns([
-                    ::entity::interior_network_desc::Column::DimensionType,
-                    ::entity::interior_network_desc::Column::ChildInteriorInstances,
-                    ::entity::interior_network_desc::Column::Region,
-                ])
-                .to_owned();
-
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
-
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("interior_network_desc", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                for entry in data {
-                                    let model = ::entity::interior_network_desc::ModelBuilder::new(entry)
-                                        .with_region(database_name)
-                                        .build();
-                                    if let Some(index) = local_messages.iter().position(|value: &::entity::interior_network_desc::ActiveModel| value.building_id.as_ref() == &model.building_id) {
-                                        local_messages.remove(index);
-                                    }
-                                    local_messages.push(model.into_active_model());
-                                    if local_messages.len() >= batch_size {
-                                        let insert = insert_many_interior_network_desc(
-                                            &global_app_state,
-                                            &on_conflict,
-                                            &mut local_messages,
-                                        )
-                                        .await;
-                                        if let Err(e) = insert {
-                                            tracing::error!("Error inserting InteriorNetworkDesc: {}", e);
-                                        }
-                                    }
-                                }
-                                if !local_messages.is_empty() {
-                                    let insert = insert_many_interior_network_desc(
-                                        &global_app_state,
-                                        &on_conflict,
-                                        &mut local_messages,
-                                    )
-                                    .await;
-                                    if let Err(e) = insert {
-                                        tracing::error!("Error inserting InteriorNetworkDesc: {}", e);
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
-                                let model = ::entity::interior_network_desc::ModelBuilder::new(new).with_region(database_name).build();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.building_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value: &::entity::interior_network_desc::ActiveModel| value.building_id.as_ref() == &model.building_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, database_name, .. } => {
-                                let model = ::entity::interior_network_desc::ModelBuilder::new(new).with_region(database_name).build();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.building_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value| value.building_id.as_ref() == &model.building_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete, database_name, .. } => {
-                                let model = ::entity::interior_network_desc::ModelBuilder::new(delete).with_region(database_name).build();
-                                if let Some(index) = messages.iter().position(|value| value.building_id.as_ref() == &model.building_id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(model.building_id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => { break; }
-                    else => { break; }
-                }
-            }
-
-            if !messages.is_empty() {
-                tracing::debug!(
-                    "InteriorNetworkDesc -> Processing {} messages in batch",
-                    messages.len()
-                );
-                let insert = insert_many_interior_network_desc(
-                    &global_app_state,
-                    &on_conflict,
-                    &mut messages,
-                )
-                .await;
-                if let Err(e) = insert {
-                    tracing::error!("Error inserting InteriorNetworkDesc: {}", e);
-                }
-            }
-
-            if !messages_delete.is_empty() {
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::interior_network_desc::Entity::delete_many()
-                        .filter(
-                            ::entity::interior_network_desc::Column::BuildingId
-                                .is_in(chunk_ids.clone()),
-                        )
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            InteriorNetworkDesc = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete InteriorNetworkDesc"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
-
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
-
-async fn insert_many_interior_network_desc(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::interior_network_desc::ActiveModel>,
-) -> Result<(), DbErr> {
-    if messages.is_empty() {
-        return Ok(());
-    }
-
-    ::entity::interior_network_desc::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await?;
-
-    messages.clear();
-    Ok(())
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Dimension Description State Worker
-// ─────────────────────────────────────────────────────────────────────────────
-
-pub(crate) fn start_worker_dimension_description_state(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<DimensionDescriptionState>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict = sea_query::OnConflict::columns([
-            ::entity::dimension_description_state::Column::EntityId,
-        ])
-        .update_columns([
-            ::entity::dimension_description_state::Column::DimensionNetworkEntityId,
-            ::entity::dimension_description_state::Column::DimensionId,
-            ::entity::dimension_description_state::Column::DimensionType,
-            ::entity::dimension_description_state::Column::InteriorInstanceId,
-            ::entity::dimension_description_state::Column::Region,
-        ])
-        .to_owned();
-
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
-
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("dimension_description_state", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                for entry in data {
-                                    let model = ::entity::dimension_description_state::ModelBuilder::new(entry)
-                                        .with_region(database_name)
-                                        .build();
-                                    if let Some(index) = local_messages.iter().position(|value: &::entity::dimension_description_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
-                                        local_messages.remove(index);
-                                    }
-                                    local_messages.push(model.into_active_model());
-                                    if local_messages.len() >= batch_size {
-                                        let insert = insert_many_dimension_description_state(
-                                            &global_app_state,
-                                            &on_conflict,
-                                            &mut local_messages,
-                                        )
-                                        .await;
-                                        if let Err(e) = insert {
-                                            tracing::error!("Error inserting DimensionDescriptionState: {}", e);
-                                        }
-                                    }
-                                }
-                                if !local_messages.is_empty() {
-                                    let insert = insert_many_dimension_description_state(
-                                        &global_app_state,
-                                        &on_conflict,
-                                        &mut local_messages,
-                                    )
-                                    .await;
-                                    if let Err(e) = insert {
-                                        tracing::error!("Error inserting DimensionDescriptionState: {}", e);
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
-                                let model = ::entity::dimension_description_state::ModelBuilder::new(new).with_region(database_name).build();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value: &::entity::dimension_description_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, database_name, .. } => {
-                                let model = ::entity::dimension_description_state::ModelBuilder::new(new).with_region(database_name).build();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete, database_name, .. } => {
-                                let model = ::entity::dimension_description_state::ModelBuilder::new(delete).with_region(database_name).build();
-                                if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(model.entity_id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => { break; }
-                    else => { break; }
-                }
-            }
-
-            if !messages.is_empty() {
-                let insert = insert_many_dimension_description_state(
-                    &global_app_state,
-                    &on_conflict,
-                    &mut messages,
-                )
-                .await;
-                if let Err(e) = insert {
-                    tracing::error!("Error inserting DimensionDescriptionState: {}", e);
-                }
-            }
-
-            if !messages_delete.is_empty() {
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::dimension_description_state::Entity::delete_many()
-                        .filter(
-                            ::entity::dimension_description_state::Column::EntityId
-                                .is_in(chunk_ids.clone()),
-                        )
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            DimensionDescriptionState = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete DimensionDescriptionState"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
-
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
-
-async fn insert_many_dimension_description_state(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::dimension_description_state::ActiveModel>,
-) -> Result<(), DbErr> {
-    if messages.is_empty() {
-        return Ok(());
-    }
-
-    ::entity::dimension_description_state::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await?;
-
-    messages.clear();
-    Ok(())
-}
-
-// ─────────────────────────────────────────────────────────────────────────────
-// Player Housing State Worker
-// ─────────────────────────────────────────────────────────────────────────────
-
-pub(crate) fn start_worker_player_housing_state(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<PlayerHousingState>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict =
-            sea_query::OnConflict::columns([::entity::player_housing_state::Column::EntityId])
-                .update_columns([
-                    ::entity::player_housing_state::Column::EntranceBuildingEntityId,
-                    ::entity::player_housing_state::Column::NetworkEntityId,
-                    ::entity::player_housing_state::Column::ExitPortalEntityId,
-                    ::entity::player_housing_state::Column::Rank,
-                    ::entity::player_housing_state::Column::LockedUntil,
-                    ::entity::player_housing_state::Column::IsEmpty,
-                    ::entity::player_housing_state::Column::RegionIndex,
-                ])
-                .to_owned();
-
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
-
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("player_housing_state", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                for entry in data {
-                                    let model = ::entity::player_housing_state::ModelBuilder::new(entry)
-                                        .build();
-                                    if let Some(index) = local_messages.iter().position(|value: &::entity::player_housing_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
-                                        local_messages.remove(index);
-                                    }
-                                    local_messages.push(model.into_active_model());
-                                    if local_messages.len() >= batch_size {
-                                        let insert = insert_many_player_housing_state(
-                                            &global_app_state,
-                                            &on_conflict,
-                                            &mut local_messages,
-                                        )
-                                        .await;
-                                        if let Err(e) = insert {
-                                            tracing::error!("Error inserting PlayerHousingState: {}", e);
-                                        }
-                                    }
-                                }
-                                if !local_messages.is_empty() {
-                                    let insert = insert_many_player_housing_state(
-                                        &global_app_state,
-                                        &on_conflict,
-                                        &mut local_messages,
-                                    )
-                                    .await;
-                                    if let Err(e) = insert {
-                                        tracing::error!("Error inserting PlayerHousingState: {}", e);
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
-                                let model = ::entity::player_housing_state::ModelBuilder::new(new).build();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value: &::entity::player_housing_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, database_name, .. } => {
-                                let model = ::entity::player_housing_state::ModelBuilder::new(new).build();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete, database_name, .. } => {
-                                let model = ::entity::player_housing_state::ModelBuilder::new(delete).build();
-                                if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(model.entity_id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => { break; }
-                    else => { break; }
-                }
-            }
+reconciled_table!(interior_network_desc, building_id: i32, BuildingId);
 
-            if !messages.is_empty() {
-                let insert = insert_many_player_housing_state(
-                    &global_app_state,
-                    &on_conflict,
-                    &mut messages,
-                )
-                .await;
-                if let Err(e) = insert {
-                    tracing::error!("Error inserting PlayerHousingState: {}", e);
-                }
-            }
+impl SyncedTable for interior_network_desc::Entity {
+    type Row = InteriorNetworkDesc;
 
-            if !messages_delete.is_empty() {
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::player_housing_state::Entity::delete_many()
-                        .filter(
-                            ::entity::player_housing_state::Column::EntityId
-                                .is_in(chunk_ids.clone()),
-                        )
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            PlayerHousingState = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete PlayerHousingState"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
+    fn model(row: Self::Row, region: Region) -> Self::Model {
+        interior_network_desc::ModelBuilder::new(row)
+            .with_region(region)
+            .build()
+    }
 
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            interior_network_desc::Column::DimensionType,
+            interior_network_desc::Column::ChildInteriorInstances,
+            interior_network_desc::Column::Region,
+        ]
+    }
 }
 
-async fn insert_many_player_housing_state(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::player_housing_state::ActiveModel>,
-) -> Result<(), DbErr> {
-    if messages.is_empty() {
-        return Ok(());
-    }
+reconciled_table!(dimension_description_state, entity_id: i64, EntityId);
 
-    ::entity::player_housing_state::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await?;
+impl SyncedTable for dimension_description_state::Entity {
+    type Row = DimensionDescriptionState;
 
-    messages.clear();
-    Ok(())
-}
+    fn model(row: Self::Row, region: Region) -> Self::Model {
+        dimension_description_state::ModelBuilder::new(row)
+            .with_region(region)
+            .build()
+    }
 
-// ─────────────────────────────────────────────────────────────────────────────
-// Permission State Worker
-// ─────────────────────────────────────────────────────────────────────────────
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            dimension_description_state::Column::DimensionNetworkEntityId,
+            dimension_description_state::Column::DimensionId,
+            dimension_description_state::Column::DimensionType,
+            dimension_description_state::Column::InteriorInstanceId,
+            dimension_description_state::Column::Region,
+        ]
+    }
+}
 
-pub(crate) fn start_worker_permission_state(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<PermissionState>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict =
-            sea_query::OnConflict::columns([::entity::permission_state::Column::EntityId])
-                .update_columns([
-                    ::entity::permission_state::Column::OrdainedEntityId,
-                    ::entity::permission_state::Column::AllowedEntityId,
-                    ::entity::permission_state::Column::Group,
-                    ::entity::permission_state::Column::Rank,
-                    ::entity::permission_state::Column::Region,
-                ])
-                .to_owned();
+reconciled_table!(permission_state, entity_id: i64, EntityId);
 
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
+impl SyncedTable for permission_state::Entity {
+    type Row = PermissionState;
 
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("permission_state", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                for entry in data {
-                                    let model = ::entity::permission_state::ModelBuilder::new(entry)
-                                        .with_region(database_name)
-                                        .build();
-                                    if let Some(index) = local_messages.iter().position(|value: &::entity::permission_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
-                                        local_messages.remove(index);
-                                    }
-                                    local_messages.push(model.into_active_model());
-                                    if local_messages.len() >= batch_size {
-                                        let insert = insert_many_permission_state(
-                                            &global_app_state,
-                                            &on_conflict,
-                                            &mut local_messages,
-                                        )
-                                        .await;
-                                        if let Err(e) = insert {
-                                            tracing::error!("Error inserting PermissionState: {}", e);
-                                        }
-                                    }
-                                }
-                                if !local_messages.is_empty() {
-                                    let insert = insert_many_permission_state(
-                                        &global_app_state,
-                                        &on_conflict,
-                                        &mut local_messages,
-                                    )
-                                    .await;
-                                    if let Err(e) = insert {
-                                        tracing::error!("Error inserting PermissionState: {}", e);
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
-                                let model = ::entity::permission_state::ModelBuilder::new(new).with_region(database_name).build();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value: &::entity::permission_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, database_name, .. } => {
-                                let model = ::entity::permission_state::ModelBuilder::new(new).with_region(database_name).build();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete, database_name, .. } => {
-                                let model = ::entity::permission_state::ModelBuilder::new(delete).with_region(database_name).build();
-                                if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(model.entity_id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => { break; }
-                    else => { break; }
-                }
-            }
+    fn model(row: Self::Row, region: Region) -> Self::Model {
+        permission_state::ModelBuilder::new(row)
+            .with_region(region)
+            .build()
+    }
 
-            if !messages.is_empty() {
-                let insert =
-                    insert_many_permission_state(&global_app_state, &on_conflict, &mut messages)
-                        .await;
-                if let Err(e) = insert {
-                    tracing::error!("Error inserting PermissionState: {}", e);
-                }
-            }
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            permission_state::Column::OrdainedEntityId,
+            permission_state::Column::AllowedEntityId,
+            permission_state::Column::Group,
+            permission_state::Column::Rank,
+            permission_state::Column::Region,
+        ]
+    }
+}
 
-            if !messages_delete.is_empty() {
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::permission_state::Entity::delete_many()
-                        .filter(
-                            ::entity::permission_state::Column::EntityId.is_in(chunk_ids.clone()),
-                        )
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            PermissionState = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete PermissionState"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
+reconciled_table!(portal_state, entity_id: i64, EntityId);
 
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
+impl SyncedTable for portal_state::Entity {
+    type Row = PortalState;
 
-async fn insert_many_permission_state(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::permission_state::ActiveModel>,
-) -> Result<(), DbErr> {
-    if messages.is_empty() {
-        return Ok(());
+    fn model(row: Self::Row, region: Region) -> Self::Model {
+        portal_state::ModelBuilder::new(row)
+            .with_region(region)
+            .build()
     }
 
-    ::entity::permission_state::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await?;
-
-    messages.clear();
-    Ok(())
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            portal_state::Column::TargetBuildingEntityId,
+            portal_state::Column::DestinationX,
+            portal_state::Column::DestinationZ,
+            portal_state::Column::DestinationDimension,
+            portal_state::Column::Enabled,
+            portal_state::Column::AllowDeployables,
+            portal_state::Column::Region,
+        ]
+    }
 }
 
 // ─────────────────────────────────────────────────────────────────────────────
-// Portal State Worker
+// Player Housing State Worker
 // ─────────────────────────────────────────────────────────────────────────────
 
-pub(crate) fn start_worker_portal_state(
+pub(crate) fn start_worker_player_housing_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<PortalState>>,
+    mut rx: IngestReceiver<PlayerHousingState>,
     batch_size: usize,
     time_limit: Duration,
 ) {
     tokio::spawn(async move {
         let on_conflict =
-            sea_query::OnConflict::columns([::entity::portal_state::Column::EntityId])
+            sea_query::OnConflict::columns([::entity::player_housing_state::Column::EntityId])
                 .update_columns([
-                    ::entity::portal_state::Column::TargetBuildingEntityId,
-                    ::entity::portal_state::Column::DestinationX,
-                    ::entity::portal_state::Column::DestinationZ,
-                    ::entity::portal_state::Column::DestinationDimension,
-                    ::entity::portal_state::Column::Enabled,
-                    ::entity::portal_state::Column::AllowDeployables,
-                    ::entity::portal_state::Column::Region,
+                    ::entity::player_housing_state::Column::EntranceBuildingEntityId,
+                    ::entity::player_housing_state::Column::NetworkEntityId,
+                    ::entity::player_housing_state::Column::ExitPortalEntityId,
+                    ::entity::player_housing_state::Column::Rank,
+                    ::entity::player_housing_state::Column::LockedUntil,
+                    ::entity::player_housing_state::Column::IsEmpty,
+                    ::entity::player_housing_state::Column::RegionIndex,
                 ])
                 .to_owned();
 
         loop {
             let mut messages = Vec::with_capacity(batch_size + 10);
             let mut messages_delete = Vec::with_capacity(batch_size + 10);
+            let mut written = true;
             let timer = sleep(time_limit);
             tokio::pin!(timer);
 
             loop {
                 tokio::select! {
                     Some(msg) = rx.recv() => {
-                        record_worker_received("portal_state", 1);
+                        record_worker_received("player_housing_state", 1);
                         match msg {
                             SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
                                 let mut local_messages = Vec::with_capacity(batch_size + 10);
                                 for entry in data {
-                                    let model = ::entity::portal_state::ModelBuilder::new(entry)
-                                        .with_region(database_name)
+                                    let model = ::entity::player_housing_state::ModelBuilder::new(entry)
                                         .build();
-                                    if let Some(index) = local_messages.iter().position(|value: &::entity::portal_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
+                                    if let Some(index) = local_messages.iter().position(|value: &::entity::player_housing_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
                                         local_messages.remove(index);
                                     }
                                     local_messages.push(model.into_active_model());
                                     if local_messages.len() >= batch_size {
-                                        let insert = insert_many_portal_state(
+                                        let insert = insert_many_player_housing_state(
                                             &global_app_state,
                                             &on_conflict,
                                             &mut local_messages,
                                         )
                                         .await;
                                         if let Err(e) = insert {
-                                            tracing::error!("Error inserting PortalState: {}", e);
+                                            tracing::error!("Error inserting PlayerHousingState: {}", e);
+                                            written = false;
                                         }
                                     }
                                 }
                                 if !local_messages.is_empty() {
-                                    let insert = insert_many_portal_state(
+                                    let insert = insert_many_player_housing_state(
                                         &global_app_state,
                                         &on_conflict,
                                         &mut local_messages,
                                     )
                                     .await;
                                     if let Err(e) = insert {
-                                        tracing::error!("Error inserting PortalState: {}", e);
+                                        tracing::error!("Error inserting PlayerHousingState: {}", e);
+                                        written = false;
                                     }
                                 }
                             }
                             SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
-                                let model = ::entity::portal_state::ModelBuilder::new(new).with_region(database_name).build();
+                                let model = ::entity::player_housing_state::ModelBuilder::new(new).build();
                                 if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
                                     messages_delete.remove(index);
                                 }
-                                if let Some(index) = messages.iter().position(|value: &::entity::portal_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
+                                if let Some(index) = messages.iter().position(|value: &::entity::player_housing_state::ActiveModel| value.entity_id.as_ref() == &model.entity_id) {
                                     messages.remove(index);
                                 }
                                 messages.push(model.into_active_model());
@@ -773,7 +187,7 @@ pub(crate) fn start_worker_portal_state(
                                 }
                             }
                             SpacetimeUpdateMessages::Update { new, database_name, .. } => {
-                                let model = ::entity::portal_state::ModelBuilder::new(new).with_region(database_name).build();
+                                let model = ::entity::player_housing_state::ModelBuilder::new(new).build();
                                 if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
                                     messages_delete.remove(index);
                                 }
@@ -786,7 +200,7 @@ pub(crate) fn start_worker_portal_state(
                                 }
                             }
                             SpacetimeUpdateMessages::Remove { delete, database_name, .. } => {
-                                let model = ::entity::portal_state::ModelBuilder::new(delete).with_region(database_name).build();
+                                let model = ::entity::player_housing_state::ModelBuilder::new(delete).build();
                                 if let Some(index) = messages.iter().position(|value| value.entity_id.as_ref() == &model.entity_id) {
                                     messages.remove(index);
                                 }
@@ -803,37 +217,44 @@ pub(crate) fn start_worker_portal_state(
             }
 
             if !messages.is_empty() {
-                tracing::debug!(
-                    "PortalState -> Processing {} messages in batch",
-                    messages.len()
-                );
-                let insert =
-                    insert_many_portal_state(&global_app_state, &on_conflict, &mut messages).await;
+                let insert = insert_many_player_housing_state(
+                    &global_app_state,
+                    &on_conflict,
+                    &mut messages,
+                )
+                .await;
                 if let Err(e) = insert {
-                    tracing::error!("Error inserting PortalState: {}", e);
+                    tracing::error!("Error inserting PlayerHousingState: {}", e);
+                    written = false;
                 }
             }
 
             if !messages_delete.is_empty() {
                 for chunk_ids in messages_delete.chunks(1000) {
                     let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::portal_state::Entity::delete_many()
-                        .filter(::entity::portal_state::Column::EntityId.is_in(chunk_ids.clone()))
+                    if let Err(error) = ::entity::player_housing_state::Entity::delete_many()
+                        .filter(
+                            ::entity::player_housing_state::Column::EntityId
+                                .is_in(chunk_ids.clone()),
+                        )
                         .exec(&global_app_state.conn)
                         .await
                     {
                         let chunk_ids_str: Vec<String> =
                             chunk_ids.iter().map(|id| id.to_string()).collect();
                         tracing::error!(
-                            PortalState = chunk_ids_str.join(","),
+                            PlayerHousingState = chunk_ids_str.join(","),
                             error = error.to_string(),
-                            "Could not delete PortalState"
+                            "Could not delete PlayerHousingState"
                         );
+                        written = false;
                     }
                 }
                 messages_delete.clear();
             }
 
+            rx.commit_if(written);
+
             if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
                 break;
             }
@@ -841,16 +262,16 @@ pub(crate) fn start_worker_portal_state(
     });
 }
 
-async fn insert_many_portal_state(
+async fn insert_many_player_housing_state(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::portal_state::ActiveModel>,
+    messages: &mut Vec<::entity::player_housing_state::ActiveModel>,
 ) -> Result<(), DbErr> {
     if messages.is_empty() {
         return Ok(());
     }
 
-    ::entity::portal_state::Entity::insert_many(messages.clone())
+    ::entity::player_housing_state::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await?;
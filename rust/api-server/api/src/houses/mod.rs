@@ -1,13 +1,16 @@
 pub(crate) mod bitcraft;
 
+use crate::error::{ApiError, ErrorResponse};
+use crate::pagination::{ListParams, ListQuery, Paginated};
 use crate::{AppRouter, AppState};
 use axum::Router;
 use axum::extract::{Path, State};
 use axum::http::StatusCode;
-use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use sea_orm::{ColumnTrait, EntityTrait, Order, PaginatorTrait, QueryFilter, QueryOrder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new()
@@ -41,7 +44,8 @@ pub(crate) fn get_routes() -> AppRouter {
 
 // ─── Request Types ───────────────────────────────────────────────────────────
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct FindHousesQuery {
     pub page: Option<u64>,
     pub per_page: Option<u64>,
@@ -50,7 +54,7 @@ pub(crate) struct FindHousesQuery {
 
 // ─── Response Types ──────────────────────────────────────────────────────────
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct PermissionEntry {
     pub allowed_entity_id: i64,
@@ -59,7 +63,7 @@ pub(crate) struct PermissionEntry {
     pub rank: i32,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct HouseResponse {
     pub entity_id: i64,
@@ -77,7 +81,7 @@ pub(crate) struct HouseResponse {
     pub permissions: Vec<PermissionEntry>,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct HousesResponse {
     pub houses: Vec<HouseResponse>,
@@ -86,7 +90,7 @@ pub(crate) struct HousesResponse {
     pub total: u64,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct HouseInventoriesResponse {
     pub house_entity_id: i64,
@@ -96,26 +100,22 @@ pub(crate) struct HouseInventoriesResponse {
 
 // ─── Handlers ────────────────────────────────────────────────────────────────
 
-/// GET /houses?owner={owner}&page={page}&per_page={per_page}
-///
-/// Finds houses, optionally filtering by owner. Supports pagination.
-pub(crate) async fn find_houses(
-    state: State<AppState>,
-    query: axum::extract::Query<FindHousesQuery>,
-) -> Result<axum_codec::Codec<HousesResponse>, (StatusCode, &'static str)> {
-    let page = query.page.unwrap_or(1);
-    let per_page = query.per_page.unwrap_or(20);
-    // Limit per_page to avoid massive queries
-    let per_page = per_page.min(100);
-
-    let owner_param = query.owner.as_deref().unwrap_or_default();
+/// A page of houses, optionally only those of `owner`, and the number of matching houses.
+async fn find_houses_page(
+    state: &AppState,
+    page: u64,
+    per_page: u64,
+    owner: Option<&str>,
+    sort: Option<(::entity::player_housing_state::Column, Order)>,
+) -> Result<(Vec<HouseResponse>, u64), ApiError> {
+    let owner_param = owner.unwrap_or_default();
 
     let mut house_query = ::entity::player_housing_state::Entity::find();
 
     // Apply filtering if owner is provided
     if !owner_param.is_empty() {
-        let owner_id = resolve_owner_id_by_param(&state, owner_param).await?;
-        let building_ids = get_owned_building_entity_ids(&state, owner_id).await?;
+        let owner_id = resolve_owner_id_by_param(state, owner_param).await?;
+        let building_ids = get_owned_building_entity_ids(state, owner_id).await?;
 
         // Filter houses that match these IDs
         house_query = house_query.filter(
@@ -128,6 +128,12 @@ pub(crate) async fn find_houses(
         );
     }
 
+    if let Some((column, order)) = sort {
+        house_query = house_query.order_by(column, order);
+    }
+    // Pages are only stable with a total order
+    house_query = house_query.order_by_asc(::entity::player_housing_state::Column::EntityId);
+
     // Pagination
     let paginator = house_query.paginate(&state.conn, per_page);
     let total = paginator
@@ -142,26 +148,108 @@ pub(crate) async fn find_houses(
     let mut houses_response = Vec::with_capacity(houses_models.len());
 
     for house in houses_models {
-        let owner_id = get_owner_entity_id_for_house(&state, &house).await;
-        let resp = build_house_response(&state, house, owner_id).await?;
+        let owner_id = get_owner_entity_id_for_house(state, &house).await;
+        let resp = build_house_response(state, house, owner_id).await?;
         houses_response.push(resp.0);
     }
 
+    Ok((houses_response, total))
+}
+
+/// GET /houses?owner={owner}&page={page}&per_page={per_page}
+///
+/// Finds houses, optionally filtering by owner. Supports pagination.
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/houses",
+    tag = "houses",
+    params(FindHousesQuery),
+    responses(
+        (status = OK, body = HousesResponse),
+    )
+)]
+pub(crate) async fn find_houses(
+    state: State<AppState>,
+    query: axum::extract::Query<FindHousesQuery>,
+) -> Result<axum_codec::Codec<HousesResponse>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(20);
+    // Limit per_page to avoid massive queries
+    let per_page = per_page.min(100);
+
+    let (houses, total) =
+        find_houses_page(&state, page, per_page, query.owner.as_deref(), None).await?;
+
     Ok(axum_codec::Codec(HousesResponse {
-        houses: houses_response,
+        houses,
         page,
         per_page,
         total,
     }))
 }
 
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct HouseFilters {
+    /// Username or entity id of the owner.
+    pub owner: Option<String>,
+}
+
+/// GET /api/v1/houses
+///
+/// Like `/houses`, but sortable by `entity_id` or `rank`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/houses",
+    tag = "houses",
+    params(ListParams, HouseFilters),
+    responses(
+        (status = OK, body = Paginated<HouseResponse>),
+        (status = BAD_REQUEST, body = ErrorResponse),
+    )
+)]
+pub(crate) async fn list_houses(
+    state: State<AppState>,
+    list: ListQuery,
+    axum::extract::Query(filters): axum::extract::Query<HouseFilters>,
+) -> Result<axum_codec::Codec<Paginated<HouseResponse>>, ApiError> {
+    list.no_search()?;
+    let sort = list.sort_by(&[
+        (
+            "entity_id",
+            ::entity::player_housing_state::Column::EntityId,
+        ),
+        ("rank", ::entity::player_housing_state::Column::Rank),
+    ])?;
+
+    let (houses, total) = find_houses_page(
+        &state,
+        list.page,
+        list.per_page,
+        filters.owner.as_deref(),
+        sort,
+    )
+    .await?;
+
+    Ok(axum_codec::Codec(Paginated::new(houses, &list, total)))
+}
+
 /// GET /houses/by_owner/{id}
 ///
 /// Finds houses by owner player entity ID.
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/houses/by_owner/{id}",
+    tag = "houses",
+    params(("id" = i64, Path, description = "Owner player entity id")),
+    responses(
+        (status = OK, body = Vec<HouseResponse>),
+    )
+)]
 pub(crate) async fn find_houses_by_owner_id(
     State(state): State<AppState>,
     Path(owner_id): Path<i64>,
-) -> Result<axum_codec::Codec<Vec<HouseResponse>>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<Vec<HouseResponse>>, ApiError> {
     let building_ids = get_owned_building_entity_ids(&state, owner_id).await?;
 
     // 2. Find Houses that have these entrance buildings OR are the houses themselves
@@ -190,10 +278,20 @@ pub(crate) async fn find_houses_by_owner_id(
 /// GET /houses/{id}
 ///
 /// Returns the house details for the given house entity id.
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/houses/{id}",
+    tag = "houses",
+    params(("id" = i64, Path, description = "House entity id")),
+    responses(
+        (status = OK, body = HouseResponse),
+        (status = NOT_FOUND, description = "House not found"),
+    )
+)]
 pub(crate) async fn find_house(
     state: State<AppState>,
     Path(id): Path<i64>,
-) -> Result<axum_codec::Codec<HouseResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<HouseResponse>, ApiError> {
     // Look up the housing state by entity_id
     let house = ::entity::player_housing_state::Entity::find_by_id(id)
         .one(&state.conn)
@@ -208,10 +306,7 @@ pub(crate) async fn find_house(
 
 // ─── Helpers ─────────────────────────────────────────────────────────────────
 
-async fn resolve_owner_id_by_param(
-    state: &AppState,
-    owner_param: &str,
-) -> Result<i64, (StatusCode, &'static str)> {
+async fn resolve_owner_id_by_param(state: &AppState, owner_param: &str) -> Result<i64, ApiError> {
     if let Ok(id) = owner_param.parse::<i64>() {
         Ok(id)
     } else {
@@ -229,7 +324,7 @@ async fn resolve_owner_id_by_param(
 async fn get_owned_building_entity_ids(
     state: &AppState,
     owner_id: i64,
-) -> Result<Vec<i64>, (StatusCode, &'static str)> {
+) -> Result<Vec<i64>, ApiError> {
     let ownerships = ::entity::permission_state::Entity::find()
         .filter(::entity::permission_state::Column::AllowedEntityId.eq(owner_id))
         .filter(::entity::permission_state::Column::Rank.eq(7)) // Permission::Owner matches rank 7
@@ -268,7 +363,7 @@ async fn build_house_response(
     state: &AppState,
     house: ::entity::player_housing_state::Model,
     owner_id: i64,
-) -> Result<axum_codec::Codec<HouseResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<HouseResponse>, ApiError> {
     // Look up permissions where ordained_entity_id matches the house's
     // entrance_building_entity_id (the building that is the house).
     let permission_models = ::entity::permission_state::Entity::find()
@@ -332,10 +427,20 @@ async fn build_house_response(
 ///
 /// Resolves the interior dimension for the house and returns all inventories
 /// in that dimension.
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/houses/{id}/inventories",
+    tag = "houses",
+    params(("id" = i64, Path, description = "House entity id")),
+    responses(
+        (status = OK, body = HouseInventoriesResponse),
+        (status = NOT_FOUND, description = "House not found"),
+    )
+)]
 pub(crate) async fn find_house_inventories(
     state: State<AppState>,
     Path(id): Path<i64>,
-) -> Result<axum_codec::Codec<HouseInventoriesResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<HouseInventoriesResponse>, ApiError> {
     // 1. Find the house
     let house = ::entity::player_housing_state::Entity::find_by_id(id)
         .one(&state.conn)
@@ -391,7 +496,7 @@ pub(crate) async fn find_house_inventories(
 async fn get_dimension_id_for_house(
     state: &AppState,
     house: &::entity::player_housing_state::Model,
-) -> Result<Option<i64>, (StatusCode, &'static str)> {
+) -> Result<Option<i64>, ApiError> {
     let portal_exit = ::entity::portal_state::Entity::find_by_id(house.exit_portal_entity_id)
         .one(&state.conn)
         .await
@@ -415,7 +520,7 @@ async fn get_building_entity_ids_in_dimension(
     state: &AppState,
     house: &::entity::player_housing_state::Model,
     dim_id: i64,
-) -> Result<Vec<i64>, (StatusCode, &'static str)> {
+) -> Result<Vec<i64>, ApiError> {
     // dimension_id is only unique within a region
     let region = house.region_index as entity::shared::Region;
 
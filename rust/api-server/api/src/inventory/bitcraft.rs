@@ -1,22 +1,21 @@
 use crate::AppState;
+use crate::claims::events::{ClaimEvent, publish_claim_event};
 use crate::inventory::resolve_pocket;
+use crate::reducer_event_handler::inventory::classify_reducer;
 use crate::websocket::batched_worker::BatchedWorker;
-use crate::websocket::{SpacetimeUpdateMessages, WebSocketMessages};
+use crate::websocket::ingest_queue::{self, Checkpoint, IngestReceiver, IngestSender};
+use crate::websocket::{ReducerCall, SpacetimeUpdateMessages, WebSocketMessages};
 use chrono::DateTime;
 
 use entity::inventory::ResolvedInventory;
-use entity::inventory_changelog::TypeOfChange;
+use entity::inventory_changelog::{ChangeCause, TypeOfChange};
 use game_module::module_bindings::InventoryState;
 use migration::{OnConflict, sea_query};
 use sea_orm::QueryFilter;
-use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, NotSet, Set};
-use spacetimedb_sdk::__codegen::Reducer;
-use spacetimedb_sdk::Event;
+use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, NotSet, Set, TryIntoModel};
 use std::collections::HashMap;
 use std::time::Duration;
-use tokio::sync::mpsc::{
-    Receiver, Sender, UnboundedReceiver, UnboundedSender, channel, unbounded_channel,
-};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
 
 enum InventoryDbOperation {
     Upsert(Vec<::entity::inventory::ActiveModel>),
@@ -26,11 +25,12 @@ enum InventoryDbOperation {
         ids: Vec<i64>,
         region: entity::shared::Region,
     },
+    Commit(Checkpoint),
 }
 
 pub(crate) struct InventoryStateWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<InventoryState>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<InventoryState>>,
+    rx: IngestReceiver<InventoryState>,
+    tx: IngestSender<InventoryState>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -44,7 +44,7 @@ pub(crate) struct InventoryStateWorker {
 impl InventoryStateWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_inventory_state_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("inventory_state");
         let on_conflict = sea_query::OnConflict::column(::entity::inventory::Column::EntityId)
             .update_columns([
                 ::entity::inventory::Column::Pockets,
@@ -156,9 +156,10 @@ impl InventoryStateWorker {
                 old,
                 event,
                 database_name,
-                ..
+                reducer_name,
             } => {
-                self.handle_update(new, old, event, database_name).await;
+                self.handle_update(new, old, event, reducer_name, database_name)
+                    .await;
             }
             SpacetimeUpdateMessages::Remove {
                 delete,
@@ -270,6 +271,9 @@ impl InventoryStateWorker {
             }
         }
 
+        self.global_app_state
+            .pending_inventory
+            .insert(model.entity_id, Some(model.clone()));
         let _ = self
             .global_app_state
             .tx
@@ -285,6 +289,7 @@ impl InventoryStateWorker {
                     claim: None,
                 },
                 player_owner_id,
+                region: model.region,
             });
 
         if let Some(index) = self
@@ -310,24 +315,13 @@ impl InventoryStateWorker {
         &mut self,
         new: InventoryState,
         old: InventoryState,
-        event: Option<
-            Box<spacetimedb_sdk::__codegen::Event<game_module::module_bindings::Reducer>>,
-        >,
+        event: Option<ReducerCall>,
+        reducer_name: Option<&'static str>,
         database_name: entity::shared::Region,
     ) {
-        let mut caller_identity = None;
-        let mut timestamp = None;
-        if let Some(event) = &event {
-            if let Event::Reducer(event) = &**event {
-                match event.reducer.reducer_name() {
-                    "inventory_sort" => {}
-                    _ => {
-                        caller_identity = Some(event.caller_identity);
-                        timestamp = Some(event.timestamp);
-                    }
-                }
-            }
-        }
+        let caller_identity = event.map(|event| event.caller_identity);
+        let timestamp = event.map(|event| event.timestamp);
+        let cause = reducer_name.map_or(ChangeCause::Unknown, classify_reducer);
 
         let new_model = new.clone();
         let model: ::entity::inventory::Model = ::entity::inventory::ModelBuilder::new(new)
@@ -358,20 +352,41 @@ impl InventoryStateWorker {
             ));
         }
 
+        let resolved_inventory = ResolvedInventory {
+            entity_id: model.entity_id,
+            pockets,
+            inventory_index: model.inventory_index,
+            cargo_index: model.cargo_index,
+            owner_entity_id: model.owner_entity_id,
+            player_owner_entity_id: model.player_owner_entity_id,
+            nickname: None,
+            claim: None,
+        };
+
+        if let Some(claim_id) = self
+            .global_app_state
+            .building_claim_id
+            .get(&model.owner_entity_id)
+            .map(|claim_id| *claim_id)
+        {
+            publish_claim_event(
+                &self.global_app_state,
+                claim_id,
+                ClaimEvent::StorageChanged {
+                    resolved_inventory: resolved_inventory.clone(),
+                },
+            );
+        }
+
+        self.global_app_state
+            .pending_inventory
+            .insert(model.entity_id, Some(model.clone()));
         let _ = self
             .global_app_state
             .tx
             .send(WebSocketMessages::InventoryUpdate {
-                resolved_inventory: ResolvedInventory {
-                    entity_id: model.entity_id,
-                    pockets,
-                    inventory_index: model.inventory_index,
-                    cargo_index: model.cargo_index,
-                    owner_entity_id: model.owner_entity_id,
-                    player_owner_entity_id: model.player_owner_entity_id,
-                    nickname: None,
-                    claim: None,
-                },
+                resolved_inventory,
+                region: model.region,
             });
 
         self.messages.push(model.into_active_model());
@@ -440,6 +455,7 @@ impl InventoryStateWorker {
                         new_item_type: Set(new_item_type),
                         new_item_quantity: Set(new_item_quantity),
                         type_of_change: Set(type_of_change),
+                        cause: Set(cause),
                         timestamp: Set(DateTime::from_timestamp_micros(
                             timestamp.unwrap().to_micros_since_unix_epoch(),
                         )
@@ -472,6 +488,7 @@ impl InventoryStateWorker {
             ));
         }
 
+        self.global_app_state.pending_inventory.insert(id, None);
         let _ = self
             .global_app_state
             .tx
@@ -486,6 +503,7 @@ impl InventoryStateWorker {
                     nickname: None,
                     claim: None,
                 },
+                region: model.region,
             });
 
         if let Some(index) = self
@@ -537,11 +555,11 @@ impl InventoryStateWorker {
 impl BatchedWorker for InventoryStateWorker {
     type Entity = InventoryState;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -576,6 +594,14 @@ impl BatchedWorker for InventoryStateWorker {
         self.flush_changes().await;
         self.flush_deletes().await;
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(InventoryDbOperation::Commit(checkpoint))
+            .await;
+    }
 }
 
 fn start_inventory_state_db_worker(global_app_state: AppState) -> Sender<InventoryDbOperation> {
@@ -605,20 +631,42 @@ fn start_inventory_state_db_worker(global_app_state: AppState) -> Sender<Invento
         ::entity::inventory_changelog::Column::NewItemType,
         ::entity::inventory_changelog::Column::NewItemQuantity,
         ::entity::inventory_changelog::Column::TypeOfChange,
+        ::entity::inventory_changelog::Column::Cause,
         ::entity::inventory_changelog::Column::Timestamp,
     ])
     .to_owned();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                InventoryDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 InventoryDbOperation::Upsert(messages) => {
+                    let models = messages
+                        .iter()
+                        .filter_map(|message| message.clone().try_into_model().ok())
+                        .collect::<Vec<_>>();
                     let mut messages = messages;
-                    insert_multiple_inventory(&global_app_state, &on_conflict, &mut messages).await;
+                    if !insert_multiple_inventory(&global_app_state, &on_conflict, &mut messages)
+                        .await
+                    {
+                        written = false;
+                        continue;
+                    }
+                    // Unless a newer change is already pending again
+                    for model in models {
+                        global_app_state
+                            .pending_inventory
+                            .remove_if(&model.entity_id, |_, pending| {
+                                pending.as_ref() == Some(&model)
+                            });
+                    }
                 }
                 InventoryDbOperation::UpsertChangelog(messages) => {
                     let mut messages = messages;
-                    insert_multiple_inventory_changelog(
+                    written &= insert_multiple_inventory_changelog(
                         &global_app_state,
                         &on_conflict_changelog,
                         &mut messages,
@@ -626,10 +674,19 @@ fn start_inventory_state_db_worker(global_app_state: AppState) -> Sender<Invento
                     .await;
                 }
                 InventoryDbOperation::Delete(ids) => {
-                    delete_multiple_inventory(&global_app_state, ids).await;
+                    if !delete_multiple_inventory(&global_app_state, &ids).await {
+                        written = false;
+                        continue;
+                    }
+                    for id in ids {
+                        global_app_state
+                            .pending_inventory
+                            .remove_if(&id, |_, pending| pending.is_none());
+                    }
                 }
                 InventoryDbOperation::DeleteForRegion { ids, region } => {
-                    delete_multiple_inventory_for_region(&global_app_state, ids, region).await;
+                    written &=
+                        delete_multiple_inventory_for_region(&global_app_state, ids, region).await;
                 }
             }
         }
@@ -638,7 +695,9 @@ fn start_inventory_state_db_worker(global_app_state: AppState) -> Sender<Invento
     tx
 }
 
-async fn delete_multiple_inventory(global_app_state: &AppState, ids: Vec<i64>) {
+/// Returns whether every row was deleted.
+async fn delete_multiple_inventory(global_app_state: &AppState, ids: &[i64]) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::inventory::Entity::delete_many()
@@ -652,15 +711,20 @@ async fn delete_multiple_inventory(global_app_state: &AppState, ids: Vec<i64>) {
                 error = error.to_string(),
                 "Could not delete InventoryState"
             );
+            deleted = false;
         }
     }
+
+    deleted
 }
 
+/// Returns whether every row was deleted.
 async fn delete_multiple_inventory_for_region(
     global_app_state: &AppState,
     ids: Vec<i64>,
     region: entity::shared::Region,
-) {
+) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::inventory::Entity::delete_many()
@@ -675,17 +739,21 @@ async fn delete_multiple_inventory_for_region(
                 error = error.to_string(),
                 "Could not delete Inventory"
             );
+            deleted = false;
         }
     }
+
+    deleted
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_inventory(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::inventory::ActiveModel>,
-) {
+) -> bool {
     if messages.is_empty() {
-        return;
+        return true;
     }
 
     let insert = ::entity::inventory::Entity::insert_many(messages.clone())
@@ -693,20 +761,22 @@ async fn insert_multiple_inventory(
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(e) = insert {
+    if let Err(e) = &insert {
         tracing::error!("Error inserting InventoryState chunk: {}", e);
     }
 
     messages.clear();
+    insert.is_ok()
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_inventory_changelog(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: &mut Vec<::entity::inventory_changelog::ActiveModel>,
-) {
+) -> bool {
     if messages.is_empty() {
-        return;
+        return true;
     }
 
     let insert = ::entity::inventory_changelog::Entity::insert_many(messages.clone())
@@ -714,9 +784,115 @@ async fn insert_multiple_inventory_changelog(
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(e) = insert {
+    if let Err(e) = &insert {
         tracing::error!("Error inserting InventoryChangelog chunk: {}", e);
     }
 
     messages.clear();
+    insert.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GraphqlConfig, IngestConfig, RateLimitConfig};
+    use crate::freshness::Freshness;
+    use crate::websocket::recording::RecordedMessage;
+    use game_module::module_bindings::{ItemStack, ItemType, Pocket};
+    use sea_orm::DatabaseConnection;
+    use spacetimedb_sdk::{Identity, Timestamp};
+    use std::sync::Arc;
+
+    fn inventory(quantity: i32) -> InventoryState {
+        InventoryState {
+            entity_id: 1,
+            pockets: vec![Pocket {
+                volume: 100,
+                contents: Some(ItemStack {
+                    item_id: 7,
+                    quantity,
+                    item_type: ItemType::Item,
+                    durability: None,
+                }),
+                locked: false,
+            }],
+            inventory_index: 0,
+            cargo_index: 1,
+            owner_entity_id: 2,
+            player_owner_entity_id: 3,
+        }
+    }
+
+    #[tokio::test]
+    async fn replayed_updates_produce_the_changelog() {
+        ingest_queue::configure(
+            &IngestConfig {
+                log_path: None,
+                ..IngestConfig::default()
+            },
+            Arc::new(Freshness::default()),
+        );
+        let state = AppState::new(
+            DatabaseConnection::Disconnected,
+            tokio::sync::mpsc::unbounded_channel().0,
+            HashMap::new(),
+            RateLimitConfig::default(),
+            &GraphqlConfig::default(),
+        );
+        let caller_identity = Identity::from_byte_array([1; 32]);
+        state.user_state.insert(caller_identity, 42);
+        state.item_desc.insert(
+            7,
+            entity::item_desc::Model {
+                id: 7,
+                name: "Rough Wood Log".to_string(),
+                description: String::new(),
+                volume: 100,
+                durability: 0,
+                convert_to_on_durability_zero: 0,
+                secondary_knowledge_id: 0,
+                model_asset_name: String::new(),
+                icon_asset_name: String::new(),
+                tier: 1,
+                tag: String::new(),
+                rarity: entity::shared::JsonRarity::Common,
+                compendium_entry: false,
+                item_list_id: 0,
+            },
+        );
+        let mut worker = InventoryStateWorker::new(state, 100, Duration::from_secs(1));
+
+        let message = SpacetimeUpdateMessages::Update {
+            event: Some(ReducerCall {
+                caller_identity,
+                timestamp: Timestamp::from_micros_since_unix_epoch(1_704_067_200_000_000),
+            }),
+            old: inventory(1),
+            new: inventory(5),
+            database_name: 2,
+            reducer_name: Some("item_pick_up"),
+        };
+        let line = serde_json::to_string(
+            &RecordedMessage::encode("inventory_state", Duration::ZERO, &message).unwrap(),
+        )
+        .unwrap();
+        let replayed = serde_json::from_str::<RecordedMessage>(&line)
+            .unwrap()
+            .decode::<InventoryState>()
+            .unwrap();
+
+        worker.process_message(replayed).await;
+
+        assert_eq!(worker.messages_changed.len(), 1);
+        let change = &worker.messages_changed[0];
+        assert_eq!(change.user_id, Set(Some(42)));
+        assert_eq!(change.cause, Set(ChangeCause::PickUp));
+        assert_eq!(change.type_of_change, Set(TypeOfChange::Update));
+        assert_eq!(change.old_item_quantity, Set(Some(1)));
+        assert_eq!(change.new_item_quantity, Set(Some(5)));
+        assert_eq!(
+            change.timestamp,
+            Set(DateTime::from_timestamp(1_704_067_200, 0).unwrap())
+        );
+    }
 }
@@ -1,5 +1,6 @@
 pub(crate) mod bitcraft;
 
+use crate::error::ApiError;
 use crate::{AppRouter, AppState};
 use axum::Router;
 use axum::extract::{Path, Query, State};
@@ -19,6 +20,7 @@ use std::fs::File;
 use std::ops::AddAssign;
 use std::sync::Arc;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new()
@@ -44,17 +46,28 @@ pub(crate) fn get_routes() -> AppRouter {
         )
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct InventoryChangesParams {
     pub item_id: Option<i32>,
     pub item_type: Option<inventory_changelog::ItemType>,
     pub user_id: Option<i64>,
+    pub cause: Option<inventory_changelog::ChangeCause>,
 }
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/inventorys/changes/{id}",
+    tag = "inventory",
+    params(("id" = i64, Path, description = "Inventory entity id"), InventoryChangesParams),
+    responses(
+        (status = OK, body = Vec<inventory_changelog::Model>),
+    )
+)]
 pub(crate) async fn read_inventory_changes(
     state: State<AppState>,
     Path(id): Path<i64>,
     Query(params): Query<InventoryChangesParams>,
-) -> Result<axum_codec::Codec<Vec<inventory_changelog::Model>>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<Vec<inventory_changelog::Model>>, ApiError> {
     let (inventory_changes, _num_pages) = QueryCore::find_inventory_changes_by_entity_ids(
         &state.conn,
         vec![id],
@@ -62,6 +75,7 @@ pub(crate) async fn read_inventory_changes(
         params.item_id,
         params.item_type,
         params.user_id,
+        params.cause,
     )
     .await
     .map_err(|e| {
@@ -72,10 +86,20 @@ pub(crate) async fn read_inventory_changes(
     Ok(axum_codec::Codec(inventory_changes))
 }
 
+#[utoipa::path(
+    get,
+    path = "/inventory/{id}",
+    tag = "inventory",
+    params(("id" = i64, Path, description = "Inventory entity id")),
+    responses(
+        (status = OK, body = inventory::Model),
+        (status = NOT_FOUND, description = "Inventory not found"),
+    )
+)]
 pub(crate) async fn find_inventory_by_id(
     state: State<AppState>,
     Path(id): Path<i64>,
-) -> Result<axum_codec::Codec<inventory::Model>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<inventory::Model>, ApiError> {
     let inventory = QueryCore::find_inventory_by_id(&state.conn, id)
         .await
         .map_err(|e| {
@@ -86,11 +110,11 @@ pub(crate) async fn find_inventory_by_id(
 
     match inventory {
         Some(inventory) => Ok(axum_codec::Codec(inventory)),
-        None => Err((StatusCode::NOT_FOUND, "Inventory not found")),
+        None => Err(ApiError::new(StatusCode::NOT_FOUND, "Inventory not found")),
     }
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct InventorysResponse {
     inventorys: Vec<ResolvedInventory>,
@@ -100,10 +124,19 @@ pub(crate) struct InventorysResponse {
     per_page: i64,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/inventorys/owner_entity_id/{id}",
+    tag = "inventory",
+    params(("id" = i64, Path, description = "Owner entity id")),
+    responses(
+        (status = OK, body = InventorysResponse),
+    )
+)]
 pub(crate) async fn find_inventory_by_owner_entity_id(
     state: State<AppState>,
     Path(id): Path<i64>,
-) -> Result<axum_codec::Codec<InventorysResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<InventorysResponse>, ApiError> {
     let mut inventory_ids = vec![id];
     let player = QueryCore::find_player_by_id(&state.conn, id)
         .await
@@ -259,16 +292,24 @@ pub(crate) async fn find_inventory_by_owner_entity_id(
     }))
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct AllInventoryStatsResponse {
     items: Vec<(i64, Option<::entity::item_desc::Model>)>,
     cargo: Vec<(i64, Option<::entity::cargo_desc::Model>)>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/inventory/all_inventory_stats",
+    tag = "inventory",
+    responses(
+        (status = OK, body = AllInventoryStatsResponse),
+    )
+)]
 pub(crate) async fn all_inventory_stats(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<AllInventoryStatsResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<AllInventoryStatsResponse>, ApiError> {
     // if state.inventory_state.is_empty() {
     //     let inventorys = ::entity::inventory::Entity::find()
     //         .all(&state.conn)
@@ -1,23 +1,36 @@
+use crate::error::{ApiError, ErrorResponse};
+use crate::pagination::{ListParams, ListQuery, Paginated};
 use crate::{AppState, Params};
 use axum::extract::{Query, State};
 use axum::http::StatusCode;
 use entity::item_desc;
+use log::error;
+use sea_orm::IntoSimpleExpr;
 use serde_json::{Value, json};
 use service::Query as QueryCore;
 use std::collections::HashMap;
 
 pub(crate) mod bitcraft;
 
+#[utoipa::path(
+    get,
+    path = "/items",
+    tag = "items",
+    params(Params),
+    responses(
+        (status = OK, body = serde_json::Value),
+    )
+)]
 pub async fn list_items(
     state: State<AppState>,
     Query(params): Query<Params>,
-) -> Result<axum_codec::Codec<Value>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<Value>, ApiError> {
     let page = params.page.unwrap_or(1);
     let posts_per_page = params.per_page.unwrap_or(5);
     let search = params.search;
 
     let (items, tags, tiers) = tokio::join!(
-        QueryCore::find_items(&state.conn, page, posts_per_page, search),
+        QueryCore::find_items(&state.conn, page, posts_per_page, search, None),
         QueryCore::find_unique_item_tags(&state.conn),
         QueryCore::find_unique_item_tiers(&state.conn),
     );
@@ -36,9 +49,60 @@ pub async fn list_items(
     })))
 }
 
+/// Item descriptions sorted by `id` unless `sort` is `name` or `tier`, the tags and tiers to
+/// filter by are in `/api/v1/items-and-cargo/meta`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/items",
+    tag = "items",
+    params(ListParams),
+    responses(
+        (status = OK, body = Paginated<item_desc::Model>),
+        (status = BAD_REQUEST, body = ErrorResponse),
+    )
+)]
+pub(crate) async fn list_items_v1(
+    state: State<AppState>,
+    list: ListQuery,
+) -> Result<axum_codec::Codec<Paginated<item_desc::Model>>, ApiError> {
+    let sort = list.sort_by(&[
+        ("id", item_desc::Column::Id.into_simple_expr()),
+        ("name", item_desc::Column::Name.into_simple_expr()),
+        ("tier", item_desc::Column::Tier.into_simple_expr()),
+    ])?;
+
+    let (items, num_pages) = QueryCore::find_items(
+        &state.conn,
+        list.page,
+        list.per_page,
+        list.search.clone(),
+        sort,
+    )
+    .await
+    .map_err(|error| {
+        error!("Error: {error}");
+
+        (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+    })?;
+
+    Ok(axum_codec::Codec(Paginated::new(
+        items,
+        &list,
+        num_pages.number_of_items,
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/items/world",
+    tag = "items",
+    responses(
+        (status = OK, body = HashMap<i32, item_desc::Model>),
+    )
+)]
 pub async fn list_world_items(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<HashMap<i32, item_desc::Model>>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<HashMap<i32, item_desc::Model>>, ApiError> {
     let items: HashMap<i32, item_desc::Model> = state
         .item_desc
         .iter()
@@ -1,14 +1,18 @@
 use std::collections::HashMap;
 
+use crate::desc_cache::{CachedRequest, DescTable};
+use crate::error::{ApiError, ErrorResponse};
+use crate::pagination::{ListParams, ListQuery, Paginated};
 use crate::{AppRouter, AppState};
 use axum::Router;
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::response::Response;
 use entity::cargo_desc;
 use entity::item_desc;
 use serde::{Deserialize, Serialize};
 use service::Query as QueryCore;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new()
@@ -22,18 +26,19 @@ pub(crate) fn get_routes() -> AppRouter {
         )
         .route(
             "/api/bitcraft/itemsAndCargo/all",
-            axum_codec::routing::get(get_all).into(),
+            axum::routing::get(get_all),
         )
 }
 
-#[derive(Clone, Serialize, Deserialize, TS)]
+#[derive(Clone, Serialize, Deserialize, TS, ToSchema)]
 #[serde(tag = "type")]
-enum ItemCargo {
+pub(crate) enum ItemCargo {
     Item(item_desc::Model),
     Cargo(cargo_desc::Model),
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub(crate) struct ItemsAndCargoParams {
     page: Option<u64>,
     per_page: Option<u64>,
@@ -43,7 +48,7 @@ pub(crate) struct ItemsAndCargoParams {
     no_item_list: Option<bool>,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct ItemsAndCargoResponse {
     items: Vec<ItemCargo>,
@@ -53,45 +58,74 @@ pub(crate) struct ItemsAndCargoResponse {
     pages: u64,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct MetaResponse {
     tags: Vec<String>,
     tiers: Vec<i64>,
 }
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct ItemsAndCargollResponse {
     cargo_desc: HashMap<i32, cargo_desc::Model>,
     item_desc: HashMap<i32, item_desc::Model>,
 }
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/itemsAndCargo/all",
+    tag = "items",
+    operation_id = "get_all_items_and_cargo",
+    responses(
+        (status = OK, body = ItemsAndCargollResponse),
+        (status = NOT_MODIFIED, description = "The `If-None-Match` ETag is still current"),
+    )
+)]
 pub(crate) async fn get_all(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<ItemsAndCargollResponse>, (StatusCode, &'static str)> {
-    Ok(axum_codec::Codec(ItemsAndCargollResponse {
-        cargo_desc: state
-            .cargo_desc
-            .iter()
-            .map(|value| (*value.key(), value.clone()))
-            .collect(),
-        item_desc: state
-            .item_desc
-            .iter()
-            .filter_map(|value| {
-                if value.item_list_id > 0 {
-                    return None;
-                }
-
-                Some((*value.key(), value.clone()))
-            })
-            .collect(),
-    }))
+    request: CachedRequest,
+) -> Result<Response, ApiError> {
+    state
+        .desc_cache
+        .respond(
+            request,
+            &[DescTable::CargoDesc, DescTable::ItemDesc],
+            || async {
+                Ok(ItemsAndCargollResponse {
+                    cargo_desc: state
+                        .cargo_desc
+                        .iter()
+                        .map(|value| (*value.key(), value.clone()))
+                        .collect(),
+                    item_desc: state
+                        .item_desc
+                        .iter()
+                        .filter_map(|value| {
+                            if value.item_list_id > 0 {
+                                return None;
+                            }
+
+                            Some((*value.key(), value.clone()))
+                        })
+                        .collect(),
+                })
+            },
+        )
+        .await
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/itemsAndCargo",
+    tag = "items",
+    params(ItemsAndCargoParams),
+    responses(
+        (status = OK, body = ItemsAndCargoResponse),
+    )
+)]
 pub(crate) async fn list_items_and_cargo(
     state: State<AppState>,
     Query(params): Query<ItemsAndCargoParams>,
-) -> Result<axum_codec::Codec<ItemsAndCargoResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<ItemsAndCargoResponse>, ApiError> {
     let page = params.page.unwrap_or(1);
     let posts_per_page = params.per_page.unwrap_or(5);
     let search = params.search.map(|search| search.to_lowercase());
@@ -282,9 +316,64 @@ pub(crate) async fn list_items_and_cargo(
     }))
 }
 
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ItemsAndCargoFilters {
+    tier: Option<i32>,
+    tag: Option<String>,
+    no_item_list: Option<bool>,
+}
+
+/// Items and cargo by tier, tiers below 1 last.
+#[utoipa::path(
+    get,
+    path = "/api/v1/items-and-cargo",
+    tag = "items",
+    params(ListParams, ItemsAndCargoFilters),
+    responses(
+        (status = OK, body = Paginated<ItemCargo>),
+        (status = BAD_REQUEST, body = ErrorResponse),
+    )
+)]
+pub(crate) async fn list_items_and_cargo_v1(
+    state: State<AppState>,
+    list: ListQuery,
+    Query(filters): Query<ItemsAndCargoFilters>,
+) -> Result<axum_codec::Codec<Paginated<ItemCargo>>, ApiError> {
+    list.no_sort()?;
+
+    let axum_codec::Codec(response) = list_items_and_cargo(
+        state,
+        Query(ItemsAndCargoParams {
+            page: Some(list.page),
+            per_page: Some(list.per_page),
+            search: list.search.clone(),
+            tier: filters.tier,
+            tag: filters.tag,
+            no_item_list: filters.no_item_list,
+        }),
+    )
+    .await?;
+
+    Ok(axum_codec::Codec(Paginated::new(
+        response.items,
+        &list,
+        response.total,
+    )))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/itemsAndCargo/meta",
+    tag = "items",
+    operation_id = "get_items_and_cargo_meta",
+    responses(
+        (status = OK, body = MetaResponse),
+    )
+)]
 pub(crate) async fn meta(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<MetaResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<MetaResponse>, ApiError> {
     if state.cargo_tags.is_empty()
         || state.cargo_tiers.is_empty()
         || state.item_tags.is_empty()
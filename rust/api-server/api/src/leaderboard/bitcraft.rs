@@ -1,6 +1,7 @@
 use crate::AppState;
-use crate::leaderboard::{Leaderboard, experience_to_level};
+use crate::leaderboard::{EXCLUDED_USERS_FROM_LEADERBOARD, Leaderboard, experience_to_level};
 use crate::websocket::batched_worker::BatchedWorker;
+use crate::websocket::ingest_queue::{self, Checkpoint, IngestReceiver, IngestSender};
 use crate::websocket::{SpacetimeUpdateMessages, WebSocketMessages, record_worker_received};
 use chrono::DateTime;
 use game_module::module_bindings::ExperienceState;
@@ -12,9 +13,7 @@ use spacetimedb_sdk::Event;
 use std::collections::HashMap;
 use std::ops::AddAssign;
 use std::time::Duration;
-use tokio::sync::mpsc::{
-    Receiver, Sender, UnboundedReceiver, UnboundedSender, channel, unbounded_channel,
-};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
 use tokio::time::sleep;
 
 enum ExperienceStateDbOperation {
@@ -24,11 +23,12 @@ enum ExperienceStateDbOperation {
         ids: Vec<String>,
         region: entity::shared::Region,
     },
+    Commit(Checkpoint),
 }
 
 pub(crate) struct ExperienceStateWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<ExperienceState>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<ExperienceState>>,
+    rx: IngestReceiver<ExperienceState>,
+    tx: IngestSender<ExperienceState>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -53,11 +53,16 @@ fn start_experience_state_db_worker(
     .to_owned();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                ExperienceStateDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 ExperienceStateDbOperation::Upsert(messages) => {
-                    insert_multiple_experience_state(&global_app_state, &on_conflict, messages)
-                        .await;
+                    written &=
+                        insert_multiple_experience_state(&global_app_state, &on_conflict, messages)
+                            .await;
                 }
                 ExperienceStateDbOperation::Delete(ids) => {
                     tracing::debug!("ExperienceState::Remove");
@@ -86,6 +91,7 @@ fn start_experience_state_db_worker(
                                 error = error.to_string(),
                                 "Could not delete ExperienceState"
                             );
+                            written = false;
                         }
                     }
                 }
@@ -128,7 +134,7 @@ fn start_experience_state_db_worker(
 impl ExperienceStateWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_experience_state_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("experience_state");
 
         Self {
             rx,
@@ -194,6 +200,50 @@ impl ExperienceStateWorker {
         }
     }
 
+    fn update_region_rankings(
+        &self,
+        experience_state: &ExperienceState,
+        region: entity::shared::Region,
+    ) {
+        let player_id = experience_state.entity_id as i64;
+        if EXCLUDED_USERS_FROM_LEADERBOARD.contains(&player_id) {
+            return;
+        }
+
+        let mut total_exp = 0;
+        let mut total_level = 0;
+        let mut skills = Vec::with_capacity(experience_state.experience_stacks.len());
+        for es in experience_state.experience_stacks.iter() {
+            total_exp.add_assign(es.quantity as i64);
+            total_level.add_assign(experience_to_level(es.quantity as i64) as i64);
+
+            if self
+                .global_app_state
+                .skill_desc
+                .get(&(es.skill_id as i64))
+                .is_some_and(|skill| skill.skill_category != 0)
+            {
+                skills.push((es.skill_id as i64, es.quantity as i64));
+            }
+        }
+
+        let mut xp_per_hour = 0;
+        if let Some(player_state) = self.global_app_state.player_state.get(&player_id) {
+            if player_state.time_signed_in >= 3600 {
+                xp_per_hour = total_exp / (player_state.time_signed_in as i64 / 3600);
+            }
+        }
+
+        self.global_app_state.ranking_system.update_region(
+            region,
+            player_id,
+            skills,
+            total_exp,
+            total_level,
+            xp_per_hour,
+        );
+    }
+
     async fn process_message(&mut self, msg: SpacetimeUpdateMessages<ExperienceState>) {
         match msg {
             SpacetimeUpdateMessages::Initial {
@@ -257,6 +307,8 @@ impl ExperienceStateWorker {
         for model in data
             .into_iter()
             .flat_map(|value| {
+                self.update_region_rankings(&value, database_name);
+
                 let id = value.entity_id;
                 let mut total_exp = 0;
                 let model: Vec<::entity::experience_state::Model> = value
@@ -355,6 +407,8 @@ impl ExperienceStateWorker {
     }
 
     async fn handle_insert(&mut self, new: ExperienceState, database_name: entity::shared::Region) {
+        self.update_region_rankings(&new, database_name);
+
         let id = new.entity_id as i64;
         let mut total_exp = 0;
         new.experience_stacks.iter().for_each(|es| {
@@ -478,6 +532,8 @@ impl ExperienceStateWorker {
         //     }
         // }
 
+        self.update_region_rankings(&new, database_name);
+
         let id = new.entity_id as i64;
         let mut new_total_exp = 0;
         let mut new_total_level = 0;
@@ -779,11 +835,11 @@ impl ExperienceStateWorker {
 impl BatchedWorker for ExperienceStateWorker {
     type Entity = ExperienceState;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -815,22 +871,33 @@ impl BatchedWorker for ExperienceStateWorker {
         self.flush_messages().await;
         self.flush_deletes().await;
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(ExperienceStateDbOperation::Commit(checkpoint))
+            .await;
+    }
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_experience_state(
     global_app_state: &AppState,
     on_conflict: &OnConflict,
     messages: Vec<::entity::experience_state::ActiveModel>,
-) {
+) -> bool {
     let result = ::entity::experience_state::Entity::insert_many(messages)
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(error) = result {
+    if let Err(error) = &result {
         tracing::error!(
             error = error.to_string(),
             "Error while saving experience_state"
         );
     }
+
+    result.is_ok()
 }
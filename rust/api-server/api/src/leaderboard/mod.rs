@@ -1,12 +1,16 @@
 pub(crate) mod bitcraft;
+pub(crate) mod snapshot;
 
+use crate::error::ApiError;
 use crate::{AppRouter, AppState, leaderboard};
 use axum::Router;
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query as AxumQuery, State};
 use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
 use crossbeam_channel::{Receiver, Sender, unbounded};
 use crossbeam_skiplist::SkipMap;
 use dashmap::DashMap;
+use entity::shared::Region;
 use log::error;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
@@ -16,6 +20,7 @@ use std::sync::Arc;
 use std::sync::LazyLock;
 use std::sync::atomic::{AtomicUsize, Ordering};
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 #[macro_export]
 macro_rules! generate_mysql_sum_level_sql_statement {
@@ -165,9 +170,17 @@ pub(crate) fn get_routes() -> AppRouter {
             "/api/bitcraft/leaderboard/claims/{claim_id}",
             axum_codec::routing::get(get_claim_leaderboard).into(),
         )
+        .route(
+            "/leaderboard/history/{player_id}",
+            axum_codec::routing::get(player_rank_history).into(),
+        )
+        .route(
+            "/leaderboard/movers",
+            axum_codec::routing::get(get_leaderboard_movers).into(),
+        )
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 #[serde(untagged)]
 pub(crate) enum RankType {
     Experience(LeaderboardExperience),
@@ -518,7 +531,7 @@ impl Leaderboard {
         Some(higher_count + above_same_bucket_higher_xp + tie_rank)
     }
 
-    pub(super) fn remove(&self, user_id: i64) {
+    pub(crate) fn remove(&self, user_id: i64) {
         if let Some((_, xp)) = self.scores.remove(&user_id) {
             let _ = self.tx.send(LeaderboardOp::Remove { user_id, xp });
         }
@@ -601,6 +614,9 @@ pub struct RankingSystem {
     pub level_leaderboard: Leaderboard,
     pub time_played: Leaderboard,
     pub time_signed_in: Leaderboard,
+    pub regions: DashMap<Region, Arc<RegionRankingSystem>>,
+    // Player ID -> Region the player is currently ranked in
+    player_regions: DashMap<i64, Region>,
 }
 
 impl Default for RankingSystem {
@@ -612,11 +628,85 @@ impl Default for RankingSystem {
             xp_per_hour: Leaderboard::default(),
             time_played: Leaderboard::default(),
             time_signed_in: Leaderboard::default(),
+            regions: DashMap::new(),
+            player_regions: DashMap::new(),
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS)]
+impl RankingSystem {
+    pub(crate) fn region(&self, region: Region) -> Option<Arc<RegionRankingSystem>> {
+        self.regions
+            .get(&region)
+            .map(|boards| Arc::clone(boards.value()))
+    }
+
+    /// Updates the region scoped boards of a player. A player is only ranked in one
+    /// region at a time, so when they show up in a new region they are removed from
+    /// the boards of the previous one.
+    pub(crate) fn update_region(
+        &self,
+        region: Region,
+        player_id: i64,
+        skills: impl IntoIterator<Item = (i64, i64)>,
+        total_experience: i64,
+        total_level: i64,
+        xp_per_hour: i64,
+    ) {
+        if let Some(previous_region) = self.player_regions.insert(player_id, region)
+            && previous_region != region
+            && let Some(previous_boards) = self.region(previous_region)
+        {
+            previous_boards.remove_player(player_id);
+        }
+
+        let boards = Arc::clone(self.regions.entry(region).or_default().value());
+
+        boards
+            .global_leaderboard
+            .update(player_id, total_experience);
+        boards.level_leaderboard.update(player_id, total_level);
+        boards.xp_per_hour.update(player_id, xp_per_hour);
+
+        for (skill_id, experience) in skills {
+            boards
+                .skill_leaderboards
+                .entry(skill_id)
+                .or_default()
+                .update(player_id, experience);
+        }
+    }
+}
+
+/// The experience based boards of [`RankingSystem`] scoped to a single region.
+/// Time played and time signed in are only tracked globally.
+#[derive(Default)]
+pub struct RegionRankingSystem {
+    pub skill_leaderboards: DashMap<i64, Leaderboard>,
+    pub global_leaderboard: Leaderboard,
+    pub xp_per_hour: Leaderboard,
+    pub level_leaderboard: Leaderboard,
+}
+
+impl RegionRankingSystem {
+    fn remove_player(&self, player_id: i64) {
+        self.global_leaderboard.remove(player_id);
+        self.level_leaderboard.remove(player_id);
+        self.xp_per_hour.remove(player_id);
+
+        for skill_leaderboard in self.skill_leaderboards.iter() {
+            skill_leaderboard.remove(player_id);
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct LeaderboardRegionParams {
+    region: Option<Region>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct LeaderboardSkill {
     pub(crate) player_id: i64,
@@ -626,7 +716,7 @@ pub(crate) struct LeaderboardSkill {
     pub(crate) rank: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 pub(crate) struct LeaderboardLevel {
     pub(crate) player_id: i64,
     pub(crate) player_name: Option<String>,
@@ -634,7 +724,7 @@ pub(crate) struct LeaderboardLevel {
     pub(crate) rank: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 pub(crate) struct LeaderboardExperiencePerHour {
     pub(crate) player_id: i64,
     pub(crate) player_name: Option<String>,
@@ -642,7 +732,7 @@ pub(crate) struct LeaderboardExperiencePerHour {
     pub(crate) rank: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 pub(crate) struct LeaderboardExperience {
     pub(crate) player_id: i64,
     pub(crate) player_name: Option<String>,
@@ -651,7 +741,7 @@ pub(crate) struct LeaderboardExperience {
     pub(crate) rank: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, TS)]
+#[derive(Debug, Serialize, Deserialize, Clone, TS, ToSchema)]
 pub(crate) struct LeaderboardTime {
     pub(crate) player_id: i64,
     pub(crate) player_name: Option<String>,
@@ -660,53 +750,92 @@ pub(crate) struct LeaderboardTime {
 }
 
 type LeaderboardRankTypeTasks =
-    Vec<tokio::task::JoinHandle<Result<(String, Vec<RankType>), (StatusCode, &'static str)>>>;
-
+    Vec<tokio::task::JoinHandle<Result<(String, Vec<RankType>), ApiError>>>;
+
+#[utoipa::path(
+    get,
+    path = "/leaderboard",
+    tag = "leaderboard",
+    params(LeaderboardRegionParams),
+    responses(
+        (status = OK, body = GetTop100Response),
+    )
+)]
 pub(crate) async fn get_top_100(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<GetTop100Response>, (StatusCode, &'static str)> {
+    AxumQuery(params): AxumQuery<LeaderboardRegionParams>,
+) -> Result<axum_codec::Codec<GetTop100Response>, ApiError> {
     let skills = state
         .skill_desc
         .iter()
         .map(|skill_desc| skill_desc.clone())
         .collect::<Vec<_>>();
 
+    let region_rankings = match params.region {
+        Some(region) => Some(
+            state
+                .ranking_system
+                .region(region)
+                .ok_or((StatusCode::NOT_FOUND, "Region not found"))?,
+        ),
+        None => None,
+    };
+
+    let (global_leaderboard, level_leaderboard, xp_per_hour, skill_leaderboards) =
+        match &region_rankings {
+            Some(region_rankings) => (
+                &region_rankings.global_leaderboard,
+                &region_rankings.level_leaderboard,
+                &region_rankings.xp_per_hour,
+                &region_rankings.skill_leaderboards,
+            ),
+            None => (
+                &state.ranking_system.global_leaderboard,
+                &state.ranking_system.level_leaderboard,
+                &state.ranking_system.xp_per_hour,
+                &state.ranking_system.skill_leaderboards,
+            ),
+        };
+
     let mut leaderboard_result: BTreeMap<String, Vec<RankType>> = BTreeMap::new();
 
     // let generated_level_sql = generate_mysql_sum_level_sql_statement!(EXPERIENCE_PER_LEVEL);
 
     let mut results = vec![];
 
-    let entries_time_played = state.ranking_system.time_played.get_range(0, 100);
-    let mut leaderboard: Vec<RankType> = Vec::new();
+    // Time played and time online are not tracked per region
+    if region_rankings.is_none() {
+        let entries_time_played = state.ranking_system.time_played.get_range(0, 100);
+        let mut leaderboard: Vec<RankType> = Vec::new();
 
-    for (i, entry) in entries_time_played.into_iter().enumerate() {
-        let rank = i + 1;
-        leaderboard.push(RankType::Time(LeaderboardTime {
-            player_id: entry.user_id,
-            player_name: None,
-            time_played: entry.xp as u64,
-            rank: rank as u64,
-        }));
-    }
-    results.push(("Time Played".to_string(), leaderboard));
+        for (i, entry) in entries_time_played.into_iter().enumerate() {
+            let rank = i + 1;
+            leaderboard.push(RankType::Time(LeaderboardTime {
+                player_id: entry.user_id,
+                player_name: None,
+                time_played: entry.xp as u64,
+                rank: rank as u64,
+            }));
+        }
+        results.push(("Time Played".to_string(), leaderboard));
 
-    let entries_time_signed_in = state.ranking_system.time_signed_in.get_range(0, 100);
-    let mut leaderboard: Vec<RankType> = Vec::new();
+        let entries_time_signed_in = state.ranking_system.time_signed_in.get_range(0, 100);
+        let mut leaderboard: Vec<RankType> = Vec::new();
 
-    for (i, entry) in entries_time_signed_in.into_iter().enumerate() {
-        let rank = i + 1;
-        leaderboard.push(RankType::Time(LeaderboardTime {
-            player_id: entry.user_id,
-            player_name: None,
-            time_played: entry.xp as u64,
-            rank: rank as u64,
-        }));
-    }
+        for (i, entry) in entries_time_signed_in.into_iter().enumerate() {
+            let rank = i + 1;
+            leaderboard.push(RankType::Time(LeaderboardTime {
+                player_id: entry.user_id,
+                player_name: None,
+                time_played: entry.xp as u64,
+                rank: rank as u64,
+            }));
+        }
 
-    results.push(("Time Online".to_string(), leaderboard));
+        results.push(("Time Online".to_string(), leaderboard));
+    }
 
-    let entries_per_hour_xp = state.ranking_system.xp_per_hour.get_range(0, 100);
+    let entries_per_hour_xp = xp_per_hour.get_range(0, 100);
     let mut leaderboard: Vec<RankType> = Vec::new();
 
     for (i, entry) in entries_per_hour_xp.into_iter().enumerate() {
@@ -721,7 +850,7 @@ pub(crate) async fn get_top_100(
 
     results.push(("Experience Per Hour".to_string(), leaderboard));
 
-    let entries_total_level = state.ranking_system.level_leaderboard.get_range(0, 100);
+    let entries_total_level = level_leaderboard.get_range(0, 100);
     let mut leaderboard: Vec<RankType> = Vec::new();
 
     for (i, entry) in entries_total_level.into_iter().enumerate() {
@@ -736,7 +865,7 @@ pub(crate) async fn get_top_100(
 
     results.push(("Level".to_string(), leaderboard));
 
-    let entries_total_xp = state.ranking_system.global_leaderboard.get_range(0, 100);
+    let entries_total_xp = global_leaderboard.get_range(0, 100);
     let mut leaderboard: Vec<RankType> = Vec::new();
 
     for (i, entry) in entries_total_xp.into_iter().enumerate() {
@@ -758,12 +887,10 @@ pub(crate) async fn get_top_100(
         }
 
         // let db = state.conn.clone();
-        let entries = state
-            .ranking_system
-            .skill_leaderboards
-            .get(&skill.id)
-            .unwrap()
-            .get_range(0, 100);
+        let Some(skill_leaderboard) = skill_leaderboards.get(&skill.id) else {
+            continue;
+        };
+        let entries = skill_leaderboard.get_range(0, 100);
 
         let mut leaderboard: Vec<RankType> = Vec::new();
 
@@ -879,7 +1006,7 @@ pub(crate) async fn get_top_100(
     }))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct GetTop100Response {
     pub player_map: HashMap<i64, entity::player_state::Model>,
@@ -900,14 +1027,25 @@ pub(crate) fn experience_to_level(experience: i64) -> i32 {
     100i32
 }
 
-#[derive(Serialize, TS)]
+#[derive(Serialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct PlayerLeaderboardResponse(BTreeMap<String, RankType>);
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/experience/{player_id}",
+    tag = "leaderboard",
+    params(("player_id" = i64, Path, description = "Player entity id"), LeaderboardRegionParams),
+    responses(
+        (status = OK, body = PlayerLeaderboardResponse),
+        (status = NOT_FOUND, description = "Player is not ranked"),
+    )
+)]
 pub(crate) async fn player_leaderboard(
     state: State<AppState>,
     Path(player_id): Path<i64>,
-) -> Result<axum_codec::Codec<PlayerLeaderboardResponse>, (StatusCode, &'static str)> {
+    AxumQuery(params): AxumQuery<LeaderboardRegionParams>,
+) -> Result<axum_codec::Codec<PlayerLeaderboardResponse>, ApiError> {
     let skills = Query::skill_descriptions(&state.conn)
         .await
         .map_err(|error| {
@@ -921,9 +1059,36 @@ pub(crate) async fn player_leaderboard(
     if !has_player {
         tracing::warn!(player_id, "Player leaderboard with no player");
 
-        return Err((StatusCode::NOT_FOUND, "Not found"));
+        return Err(ApiError::new(StatusCode::NOT_FOUND, "Not found"));
     };
 
+    let region_rankings = match params.region {
+        Some(region) => Some(
+            state
+                .ranking_system
+                .region(region)
+                .ok_or((StatusCode::NOT_FOUND, "Region not found"))?,
+        ),
+        None => None,
+    };
+
+    let (global_leaderboard, level_leaderboard, skill_leaderboards) = match &region_rankings {
+        Some(region_rankings) => (
+            &region_rankings.global_leaderboard,
+            &region_rankings.level_leaderboard,
+            &region_rankings.skill_leaderboards,
+        ),
+        None => (
+            &state.ranking_system.global_leaderboard,
+            &state.ranking_system.level_leaderboard,
+            &state.ranking_system.skill_leaderboards,
+        ),
+    };
+
+    if region_rankings.is_some() && !global_leaderboard.has(&player_id) {
+        return Err(ApiError::new(StatusCode::NOT_FOUND, "Not found"));
+    }
+
     let mut leaderboard_result: BTreeMap<String, RankType> = BTreeMap::new();
 
     let mut results = vec![];
@@ -935,23 +1100,17 @@ pub(crate) async fn player_leaderboard(
 
         let player_name = None;
 
-        let (skill_exp, rank) = if let Some(rank) = state
-            .ranking_system
-            .skill_leaderboards
+        let skill_rank = skill_leaderboards
             .get(&skill.id)
-            .unwrap()
-            .get_rank(player_id)
-        {
-            let a = state
-                .ranking_system
-                .skill_leaderboards
-                .get(&skill.id)
-                .unwrap()
-                .scores
-                .get(&player_id)
-                .map(|r| *r.value());
+            .and_then(|skill_leaderboard| {
+                Some((
+                    skill_leaderboard.get_value(&player_id)?,
+                    skill_leaderboard.get_rank(player_id)?,
+                ))
+            });
 
-            (a.unwrap(), rank)
+        let (skill_exp, rank) = if let Some(skill_rank) = skill_rank {
+            skill_rank
         } else {
             tracing::warn!(
                 player_id,
@@ -973,7 +1132,7 @@ pub(crate) async fn player_leaderboard(
         ));
     }
 
-    let rank = if let Some(rank) = state.ranking_system.global_leaderboard.get_rank(player_id) {
+    let rank = if let Some(rank) = global_leaderboard.get_rank(player_id) {
         rank
     } else {
         tracing::warn!(player_id, "Could not find total experience rank for player");
@@ -981,11 +1140,7 @@ pub(crate) async fn player_leaderboard(
         0
     };
 
-    let total_experience = if let Some(total_experience) = state
-        .ranking_system
-        .global_leaderboard
-        .scores
-        .get(&player_id)
+    let total_experience = if let Some(total_experience) = global_leaderboard.scores.get(&player_id)
     {
         *total_experience.value()
     } else {
@@ -1005,13 +1160,8 @@ pub(crate) async fn player_leaderboard(
         }),
     ));
 
-    let rank = state.ranking_system.level_leaderboard.get_rank(player_id);
-    let level = state
-        .ranking_system
-        .level_leaderboard
-        .scores
-        .get(&player_id)
-        .map(|r| *r.value());
+    let rank = level_leaderboard.get_rank(player_id);
+    let level = level_leaderboard.get_value(&player_id);
 
     results.push((
         "Level".to_string(),
@@ -1086,10 +1236,19 @@ pub(crate) async fn player_leaderboard(
     )))
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/leaderboard/claims/{claim_id}",
+    tag = "leaderboard",
+    params(("claim_id" = i64, Path, description = "Claim entity id")),
+    responses(
+        (status = OK, body = GetTop100Response),
+    )
+)]
 pub(crate) async fn get_claim_leaderboard(
     state: State<AppState>,
     Path(claim_id): Path<i64>,
-) -> Result<axum_codec::Codec<GetTop100Response>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<GetTop100Response>, ApiError> {
     let skills = Query::skill_descriptions(&state.conn)
         .await
         .map_err(|error| {
@@ -1107,7 +1266,7 @@ pub(crate) async fn get_claim_leaderboard(
         })?;
 
     if claim_member.is_empty() {
-        return Err((StatusCode::NOT_FOUND, ""));
+        return Err(ApiError::new(StatusCode::NOT_FOUND, ""));
     }
 
     let player_ids = claim_member
@@ -1370,3 +1529,177 @@ pub(crate) async fn get_claim_leaderboard(
         leaderboard: leaderboard_result,
     }))
 }
+
+/// Resolves the snapshot board name, `skill_id` takes precedence over `board`.
+fn snapshot_board_name(board: Option<String>, skill_id: Option<i64>) -> Option<String> {
+    match skill_id {
+        Some(skill_id) => Some(format!("skill_{skill_id}")),
+        None => board,
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct RankHistoryParams {
+    board: Option<String>,
+    skill_id: Option<i64>,
+    region: Option<Region>,
+    start: Option<DateTime<Utc>>,
+    end: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct RankHistoryPoint {
+    timestamp: DateTime<Utc>,
+    rank: i32,
+    value: i64,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct RankHistoryResponse {
+    player_id: i64,
+    region: Option<Region>,
+    boards: BTreeMap<String, Vec<RankHistoryPoint>>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/leaderboard/history/{player_id}",
+    tag = "leaderboard",
+    params(("player_id" = i64, Path, description = "Player entity id"), RankHistoryParams),
+    responses(
+        (status = OK, body = RankHistoryResponse),
+    )
+)]
+pub(crate) async fn player_rank_history(
+    state: State<AppState>,
+    Path(player_id): Path<i64>,
+    AxumQuery(params): AxumQuery<RankHistoryParams>,
+) -> Result<axum_codec::Codec<RankHistoryResponse>, ApiError> {
+    let end = params.end.unwrap_or_else(Utc::now);
+    let start = params.start.unwrap_or(end - chrono::Duration::days(30));
+
+    if start > end {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "start must be before end",
+        ));
+    }
+
+    let snapshots = Query::get_leaderboard_rank_history(
+        &state.conn,
+        player_id,
+        snapshot_board_name(params.board, params.skill_id),
+        params.region,
+        start,
+        end,
+    )
+    .await
+    .map_err(|error| {
+        error!("Error: {error}");
+
+        (StatusCode::INTERNAL_SERVER_ERROR, "")
+    })?;
+
+    let mut boards: BTreeMap<String, Vec<RankHistoryPoint>> = BTreeMap::new();
+
+    for snapshot in snapshots {
+        boards
+            .entry(snapshot.board)
+            .or_default()
+            .push(RankHistoryPoint {
+                timestamp: snapshot.timestamp,
+                rank: snapshot.rank,
+                value: snapshot.value,
+            });
+    }
+
+    Ok(axum_codec::Codec(RankHistoryResponse {
+        player_id,
+        region: params.region,
+        boards,
+    }))
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct LeaderboardMoversParams {
+    board: Option<String>,
+    skill_id: Option<i64>,
+    region: Option<Region>,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    limit: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct LeaderboardMoversResponse {
+    board: String,
+    region: Option<Region>,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    movers: Vec<entity::leaderboard_snapshot::LeaderboardMover>,
+    player_names: HashMap<i64, String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/leaderboard/movers",
+    tag = "leaderboard",
+    params(LeaderboardMoversParams),
+    responses(
+        (status = OK, body = LeaderboardMoversResponse),
+    )
+)]
+pub(crate) async fn get_leaderboard_movers(
+    state: State<AppState>,
+    AxumQuery(params): AxumQuery<LeaderboardMoversParams>,
+) -> Result<axum_codec::Codec<LeaderboardMoversResponse>, ApiError> {
+    let board = snapshot_board_name(params.board, params.skill_id)
+        .unwrap_or_else(|| "global_experience".to_string());
+    let to = params.to.unwrap_or_else(Utc::now);
+    let from = params.from.unwrap_or(to - chrono::Duration::days(7));
+    let limit = params.limit.unwrap_or(50).min(500);
+
+    if from > to {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "from must be before to",
+        ));
+    }
+
+    let movers =
+        Query::get_leaderboard_movers(&state.conn, board.clone(), params.region, from, to, limit)
+            .await
+            .map_err(|error| {
+                error!("Error: {error}");
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "")
+            })?;
+
+    let player_names = Query::find_player_username_by_ids(
+        &state.conn,
+        movers.iter().map(|mover| mover.player_entity_id).collect(),
+    )
+    .await
+    .map_err(|error| {
+        error!("Error: {error}");
+
+        (StatusCode::INTERNAL_SERVER_ERROR, "")
+    })?
+    .into_iter()
+    .map(|x| (x.entity_id, x.username))
+    .collect::<HashMap<i64, String>>();
+
+    Ok(axum_codec::Codec(LeaderboardMoversResponse {
+        board,
+        region: params.region,
+        from,
+        to,
+        movers,
+        player_names,
+    }))
+}
@@ -0,0 +1,159 @@
+use crate::AppState;
+use crate::config::LeaderboardSnapshotConfig;
+use crate::leaderboard::{Leaderboard, RankingSystem};
+use chrono::{DateTime, Utc};
+use entity::leaderboard_snapshot;
+use sea_orm::{EntityTrait, NotSet, Set};
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+// Keeps a single insert below the postgres bind parameter limit
+const INSERT_CHUNK_SIZE: usize = 5000;
+
+pub(crate) fn start_leaderboard_snapshot_worker(
+    global_app_state: AppState,
+    config: LeaderboardSnapshotConfig,
+) {
+    tokio::spawn(async move {
+        let period = Duration::from_secs(config.interval.max(60));
+        // The first tick is delayed so the region boards are filled by the initial subscription
+        let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        loop {
+            interval.tick().await;
+
+            let timestamp = Utc::now();
+            let tmp_global_app_state = global_app_state.clone();
+            let tmp_config = config.clone();
+            let rows = match tokio::task::spawn_blocking(move || {
+                collect_snapshot(&tmp_global_app_state.ranking_system, &tmp_config, timestamp)
+            })
+            .await
+            {
+                Ok(rows) => rows,
+                Err(error) => {
+                    tracing::error!(
+                        error = error.to_string(),
+                        "Could not collect leaderboard snapshot"
+                    );
+                    continue;
+                }
+            };
+
+            let row_count = rows.len();
+            insert_snapshot(&global_app_state, rows).await;
+
+            tracing::debug!(rows = row_count, "Stored leaderboard snapshot");
+        }
+    });
+}
+
+fn collect_snapshot(
+    ranking_system: &RankingSystem,
+    config: &LeaderboardSnapshotConfig,
+    timestamp: DateTime<Utc>,
+) -> Vec<leaderboard_snapshot::ActiveModel> {
+    let mut rows = Vec::new();
+    let global_limit = config.global_top_n.unwrap_or(usize::MAX);
+
+    let global_boards = [
+        ("global_experience", &ranking_system.global_leaderboard),
+        ("level", &ranking_system.level_leaderboard),
+        ("xp_per_hour", &ranking_system.xp_per_hour),
+        ("time_played", &ranking_system.time_played),
+        ("time_signed_in", &ranking_system.time_signed_in),
+    ];
+
+    for (board_name, board) in global_boards {
+        push_board(&mut rows, board_name, None, board, global_limit, timestamp);
+    }
+
+    for entry in ranking_system.skill_leaderboards.iter() {
+        push_board(
+            &mut rows,
+            &format!("skill_{}", entry.key()),
+            None,
+            entry.value(),
+            global_limit,
+            timestamp,
+        );
+    }
+
+    for region_entry in ranking_system.regions.iter() {
+        let region = *region_entry.key();
+        let region_rankings = region_entry.value();
+
+        let region_boards = [
+            ("global_experience", &region_rankings.global_leaderboard),
+            ("level", &region_rankings.level_leaderboard),
+            ("xp_per_hour", &region_rankings.xp_per_hour),
+        ];
+
+        for (board_name, board) in region_boards {
+            push_board(
+                &mut rows,
+                board_name,
+                Some(region),
+                board,
+                config.region_top_n,
+                timestamp,
+            );
+        }
+
+        for entry in region_rankings.skill_leaderboards.iter() {
+            push_board(
+                &mut rows,
+                &format!("skill_{}", entry.key()),
+                Some(region),
+                entry.value(),
+                config.region_top_n,
+                timestamp,
+            );
+        }
+    }
+
+    rows
+}
+
+fn push_board(
+    rows: &mut Vec<leaderboard_snapshot::ActiveModel>,
+    board_name: &str,
+    region: Option<entity::shared::Region>,
+    board: &Leaderboard,
+    limit: usize,
+    timestamp: DateTime<Utc>,
+) {
+    rows.extend(board.get_range(0, limit).into_iter().map(|entry| {
+        leaderboard_snapshot::ActiveModel {
+            id: NotSet,
+            board: Set(board_name.to_string()),
+            region: Set(region),
+            player_entity_id: Set(entry.user_id),
+            rank: Set(entry.rank as i32),
+            value: Set(entry.xp),
+            timestamp: Set(timestamp),
+        }
+    }));
+}
+
+async fn insert_snapshot(
+    global_app_state: &AppState,
+    mut rows: Vec<leaderboard_snapshot::ActiveModel>,
+) {
+    while !rows.is_empty() {
+        let chunk = rows
+            .drain(..rows.len().min(INSERT_CHUNK_SIZE))
+            .collect::<Vec<_>>();
+
+        if let Err(error) = leaderboard_snapshot::Entity::insert_many(chunk)
+            .exec(&global_app_state.conn)
+            .await
+        {
+            tracing::error!(
+                error = error.to_string(),
+                "Error while saving leaderboard_snapshot"
+            );
+        }
+    }
+}
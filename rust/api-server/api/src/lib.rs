@@ -1,3 +1,4 @@
+mod accounts;
 mod auction_listing_state;
 mod buildings;
 mod cargo_desc;
@@ -9,7 +10,11 @@ mod config;
 mod crafting;
 mod crafting_recipe_desc;
 mod deployable_state;
+mod desc_cache;
+mod error;
 mod extraction_recipe_desc;
+mod freshness;
+mod graphql;
 mod houses;
 mod inventory;
 mod item_list_desc;
@@ -23,28 +28,36 @@ mod location_state;
 mod locations;
 mod mobile_entity_state;
 mod npc_desc;
+mod openapi;
+mod pagination;
 mod player_state;
+mod rate_limit;
 mod recipes;
 mod reducer_event_handler;
 mod resource_desc;
 mod skill_descriptions;
+mod status;
 mod trading_orders;
 mod traveler_task_desc;
 mod traveler_task_state;
 mod traveler_tasks;
 mod user_state;
+mod v1;
 mod vault_state;
 mod websocket;
 
-use crate::config::{Config, TechTierResearchMap};
+use crate::config::{Config, GraphqlConfig, RateLimitConfig, TechTierResearchMap};
 use crate::leaderboard::{
     EXCLUDED_SKILLS_FROM_GLOBAL_LEADERBOARD_SKILLS_CATEGORY, EXCLUDED_USERS_FROM_LEADERBOARD,
     Leaderboard, RankingSystem,
 };
 use crate::websocket::WebSocketMessages;
+use crate::websocket::filter::{MAX_FILTERS_PER_CLIENT, SubscriptionFilter, parse_topic};
+use crate::websocket::outbox::{CLIENT_QUEUE_CAPACITY, ClientOutbox};
+use crate::websocket::snapshot::topic_snapshot;
 use axum::extract::{
     MatchedPath, Query, Request, State,
-    ws::{Message, WebSocket, WebSocketUpgrade},
+    ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade, close_code},
 };
 use axum::http::{HeaderValue, Version};
 use axum::middleware::Next;
@@ -58,7 +71,6 @@ use axum::{
 };
 use clap::{Parser, Subcommand};
 use futures::{SinkExt, StreamExt};
-use kanal::AsyncSender;
 use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
 use metrics_process::Collector;
 use migration::{Migrator, MigratorTrait};
@@ -71,9 +83,10 @@ use spacetimedb_sdk::unstable::CLIENT_METRICS;
 use std::collections::{HashMap, HashSet};
 use std::env;
 use std::fmt::{Display, Write};
-use std::ops::{AddAssign, SubAssign};
+use std::net::SocketAddr;
 use std::process::exit;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
@@ -84,23 +97,22 @@ use tower_http::services::ServeDir;
 use tracing::error;
 use tracing_subscriber::layer::SubscriberExt as _;
 use tracing_subscriber::util::SubscriberInitExt;
+use utoipa::IntoParams;
 
 async fn start(database_connection: DatabaseConnection, config: Config) -> anyhow::Result<()> {
     tracing::info!("Starting up server");
     let prometheus = setup_metrics_recorder();
 
-    Migrator::up(&database_connection, None).await?;
-
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<WebSocketMessages>();
 
     let state = AppState::new(
         database_connection.clone(),
         tx.clone(),
         config.tech_tier_research_map.clone(),
+        config.rate_limit.clone(),
+        &config.graphql,
     );
 
-    state.fill_state_from_db().await;
-
     let server_url = config.server_url();
 
     let server_url = if server_url.is_err() {
@@ -110,6 +122,38 @@ async fn start(database_connection: DatabaseConnection, config: Config) -> anyho
         server_url?
     };
 
+    // Serving starts before migrations and the cache fill, `/readyz` reports when they are done
+    // and every other route answers 503 until then
+    let app = create_app(&config, state.clone(), prometheus);
+    let listener = tokio::net::TcpListener::bind(&server_url).await?;
+    tracing::info!("Server started on http://{}", server_url);
+    let server = tokio::spawn(async move {
+        axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .await
+    });
+
+    Migrator::up(&database_connection, None).await?;
+    state.status.migrations_applied();
+
+    state.fill_state_from_db().await;
+    state.status.caches_loaded();
+
+    if config.rate_limit.enabled {
+        rate_limit::start_rate_limit_pruning(state.clone());
+    }
+
+    accounts::start_session_cleanup(state.clone());
+
+    if config.leaderboard_snapshot.enabled {
+        leaderboard::snapshot::start_leaderboard_snapshot_worker(
+            state.clone(),
+            config.leaderboard_snapshot.clone(),
+        );
+    }
+
     if config.live_updates_ws {
         if config.spacetimedb.databases.is_empty() {
             tracing::warn!("You need to set spacetimedb databases");
@@ -120,26 +164,24 @@ async fn start(database_connection: DatabaseConnection, config: Config) -> anyho
 
             let tmp_config = config.clone();
 
+            let required_databases = config.spacetimedb.required_databases.as_ref();
             config
                 .spacetimedb
                 .databases
                 .iter()
-                .for_each(|connection_state| {
-                    state
-                        .connection_state
-                        .insert(connection_state.clone(), false);
+                .filter(|database| !database.trim().is_empty())
+                .for_each(|database| {
+                    state.status.register(
+                        database,
+                        required_databases.is_none_or(|required| required.contains(database)),
+                    );
                 });
 
             websocket::start_websocket_bitcraft_logic(tmp_config, state.clone());
         }
     }
 
-    let app = create_app(&config, state.clone(), prometheus);
-
-    tracing::info!("Server started on http://{}", server_url);
-
-    let listener = tokio::net::TcpListener::bind(&server_url).await?;
-    axum::serve(listener, app).await?;
+    server.await??;
 
     Ok(())
 }
@@ -271,6 +313,16 @@ struct QueryWebsocketOptions {
     encoding: Option<WebsocketEncoding>,
 }
 
+/// Upgrades to the live update websocket, messages are encoded as `encoding` (JSON by default).
+#[utoipa::path(
+    get,
+    path = "/websocket",
+    tag = "meta",
+    params(("encoding" = Option<String>, Query, description = "Json, Toml, Yaml or MessagePack")),
+    responses(
+        (status = SWITCHING_PROTOCOLS, description = "Upgraded to a websocket connection"),
+    )
+)]
 async fn websocket_handler(
     ws: WebSocketUpgrade,
     version: Version,
@@ -297,40 +349,21 @@ async fn websocket(stream: WebSocket, state: AppState, websocket_options: QueryW
 
     let id = nanoid::nanoid!();
 
-    let (tx, rx) = kanal::bounded_async::<WebSocketMessages>(20);
-
-    state
-        .clients_state
-        .add_client(
-            id.clone(),
-            tx.clone(),
-            websocket_options
-                .encoding
-                .map_or(WebsocketEncoding::Json, |value| value),
-        )
-        .await;
+    let encoding = websocket_options
+        .encoding
+        .map_or(WebsocketEncoding::Json, |value| value);
+    let outbox = state.clients_state.add_client(id.clone()).await;
 
     // Now send the "joined" message to all subscribers.
     let msg = format!("{id} joined.");
-    let _ = tx.send(WebSocketMessages::Message(msg)).await;
+    outbox.push(WebSocketMessages::Message(msg), "message");
 
-    let internal_id = id.clone();
-    let inner_state = state.clone();
+    let send_outbox = outbox.clone();
     // Spawn the first task that will receive broadcast messages and send text
     // messages over the websocket to our client.
     let mut send_task = tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
+        while let Some(msg) = send_outbox.recv().await {
             let a = axum_codec::Codec(msg.clone());
-            let encoding = inner_state
-                .clients_state
-                .get_encode_format_for_client(&internal_id)
-                .await;
-
-            if encoding.is_none() {
-                tracing::warn!("Could not find encoding for {internal_id}")
-            };
-
-            let encoding = encoding.unwrap();
 
             let send_result = if encoding == WebsocketEncoding::Json {
                 sender
@@ -356,9 +389,17 @@ async fn websocket(stream: WebSocket, state: AppState, websocket_options: QueryW
             // In any websocket error, break loop.
             if let Err(error) = send_result {
                 tracing::error!("Error sending message to client: {error}");
-                break;
+                return;
             }
         }
+
+        // The outbox only closes on its own when the client could not keep up.
+        let _ = sender
+            .send(Message::Close(Some(CloseFrame {
+                code: close_code::POLICY,
+                reason: "Slow consumer".into(),
+            })))
+            .await;
     });
 
     let inner_id = id.clone();
@@ -367,39 +408,92 @@ async fn websocket(stream: WebSocket, state: AppState, websocket_options: QueryW
         while let Some(Ok(Message::Text(text))) = receiver.next().await {
             match serde_json::from_str::<WebSocketMessages>(&text) {
                 Ok(message) => match message {
-                    WebSocketMessages::Subscribe { topics } => {
-                        for topic in topics {
-                            let possible_topic = topic.split_once(".");
+                    WebSocketMessages::Subscribe { topics, snapshot } => {
+                        let snapshot = snapshot.unwrap_or(false);
+                        if snapshot {
+                            inner_state.clients_state.set_client_sequenced(&inner_id);
+                        }
 
-                            if let Some((topic, id)) = possible_topic {
-                                let id = id.parse::<i64>().unwrap();
+                        for full_topic in topics {
+                            let (topic, id) = match parse_topic(&full_topic) {
+                                Ok(parsed) => parsed,
+                                Err(error) => {
+                                    outbox.push(
+                                        WebSocketMessages::SubscriptionError {
+                                            topic: full_topic.clone(),
+                                            error: error.to_string(),
+                                        },
+                                        "subscription_error",
+                                    );
+                                    continue;
+                                }
+                            };
+
+                            inner_state
+                                .clients_state
+                                .add_topic_to_client(&inner_id, topic, id)
+                                .await;
+
+                            if snapshot && let Some(id) = id {
+                                // Read after registering, so every delta with a higher
+                                // sequence reaches the client.
+                                let sequence = inner_state.clients_state.current_sequence();
+                                let messages = topic_snapshot(&inner_state, topic, id).await;
+                                outbox.push(
+                                    WebSocketMessages::Snapshot {
+                                        topic: full_topic.clone(),
+                                        sequence,
+                                        messages,
+                                    },
+                                    topic,
+                                );
+                            }
+                        }
+                    }
+                    WebSocketMessages::Unsubscribe { topic: full_topic } => {
+                        match parse_topic(&full_topic) {
+                            Ok((topic, id)) => {
                                 inner_state
                                     .clients_state
-                                    .add_topic_to_client(&inner_id, &topic.to_string(), Some(id))
+                                    .remove_topic_from_client(&inner_id, &topic.to_string(), id)
                                     .await;
-                            } else {
+                            }
+                            Err(error) => {
+                                outbox.push(
+                                    WebSocketMessages::SubscriptionError {
+                                        topic: full_topic.clone(),
+                                        error: error.to_string(),
+                                    },
+                                    "subscription_error",
+                                );
+                            }
+                        }
+                    }
+                    WebSocketMessages::SubscribeFilter { id, filter } => {
+                        let result = match filter.validate() {
+                            Ok(()) => {
                                 inner_state
                                     .clients_state
-                                    .add_topic_to_client(&inner_id, &topic.to_string(), None)
-                                    .await;
+                                    .add_filter_to_client(&inner_id, id.clone(), filter)
+                                    .await
                             }
-                        }
+                            Err(error) => Err(error),
+                        };
+
+                        let message = match result {
+                            Ok(()) => WebSocketMessages::SubscribedFilter { id },
+                            Err(error) => WebSocketMessages::SubscriptionError {
+                                topic: id,
+                                error: error.to_string(),
+                            },
+                        };
+                        outbox.push(message, "subscribe_filter");
                     }
-                    WebSocketMessages::Unsubscribe { topic } => {
-                        let possible_topic = topic.split_once(".");
-
-                        if let Some((topic, id)) = possible_topic {
-                            let id = id.parse::<i64>().unwrap();
-                            inner_state
-                                .clients_state
-                                .remove_topic_from_client(&inner_id, &topic.to_string(), Some(id))
-                                .await;
-                        } else {
-                            inner_state
-                                .clients_state
-                                .remove_topic_from_client(&inner_id, &topic.to_string(), None)
-                                .await;
-                        }
+                    WebSocketMessages::UnsubscribeFilter { id } => {
+                        inner_state
+                            .clients_state
+                            .remove_filter_from_client(&inner_id, &id)
+                            .await;
                     }
                     WebSocketMessages::ListSubscribedTopics => {
                         let topics = inner_state
@@ -412,7 +506,10 @@ async fn websocket(stream: WebSocket, state: AppState, websocket_options: QueryW
                         }
 
                         let topics = topics.unwrap();
-                        let _ = tx.send(WebSocketMessages::SubscribedTopics(topics)).await;
+                        outbox.push(
+                            WebSocketMessages::SubscribedTopics(topics),
+                            "subscribed_topics",
+                        );
                     }
                     _ => {}
                 },
@@ -448,24 +545,55 @@ async fn broadcast_message(state: AppState, mut rx: UnboundedReceiver<WebSocketM
             }
 
             let topics = message.topics().unwrap();
-            let mut senders: Vec<AsyncSender<WebSocketMessages>> = vec![];
-
-            for (topic_name, topic_id) in topics {
-                senders.extend(
-                    state
-                        .clients_state
-                        .clients_listen_to_topic(&topic_name, topic_id)
-                        .await,
-                );
+
+            if state.graphql_events.receiver_count() > 0 {
+                let _ = state.graphql_events.send(message.clone());
             }
 
-            futures::future::join_all(senders.into_iter().map(|sender| {
-                let message = message.clone();
-                async move {
-                    let _ = sender.send(message).await;
+            let sequence = state.clients_state.next_sequence();
+            // A client listening to several of the message's topics still only gets it once
+            let mut delivered_to = HashSet::new();
+            let mut deliver = |client_id: String, outbox: Arc<ClientOutbox>, topic_name: &str| {
+                if !delivered_to.insert(client_id) {
+                    return;
                 }
-            }))
-            .await;
+
+                let message = if outbox.is_sequenced() {
+                    WebSocketMessages::Sequenced {
+                        sequence,
+                        message: Box::new(message.clone()),
+                    }
+                } else {
+                    message.clone()
+                };
+                outbox.push(message, topic_name);
+            };
+
+            let mut ids_per_topic: HashMap<&str, Vec<Option<i64>>> = HashMap::new();
+            for (topic_name, topic_id) in &topics {
+                ids_per_topic
+                    .entry(topic_name.as_str())
+                    .or_default()
+                    .push(*topic_id);
+
+                for (client_id, outbox) in state
+                    .clients_state
+                    .clients_listen_to_topic(topic_name, *topic_id)
+                {
+                    deliver(client_id, outbox, topic_name);
+                }
+            }
+
+            for (topic_name, topic_ids) in ids_per_topic {
+                for (client_id, outbox) in state
+                    .clients_state
+                    .clients_with_matching_filter(topic_name, |filter| {
+                        filter.matches(&state, &message, &topic_ids)
+                    })
+                {
+                    deliver(client_id, outbox, topic_name);
+                }
+            }
         }
     }
 }
@@ -473,16 +601,6 @@ async fn broadcast_message(state: AppState, mut rx: UnboundedReceiver<WebSocketM
 pub(crate) type AppRouter = Router<AppState>;
 
 fn create_app(config: &Config, state: AppState, prometheus: PrometheusHandle) -> Router {
-    let desc_router = Router::new()
-        .route(
-            "/buildings/{id}",
-            axum_codec::routing::get(buildings::find_claim_description).into(),
-        )
-        .route(
-            "/buildings",
-            axum_codec::routing::get(buildings::find_building_descriptions).into(),
-        );
-
     let collector = Collector::default();
     // Call `describe()` method to register help string.
     collector.describe();
@@ -510,7 +628,20 @@ fn create_app(config: &Config, state: AppState, prometheus: PrometheusHandle) ->
         .merge(traveler_tasks::get_routes())
         .merge(auction_listing_state::get_routes())
         .merge(houses::get_routes())
-        .nest("/desc", desc_router)
+        .merge(accounts::get_routes())
+        .merge(graphql::get_routes())
+        .merge(openapi::get_routes())
+        .merge(v1::get_routes())
+        .merge(freshness::get_routes())
+        .merge(status::get_routes())
+        .route(
+            "/desc/buildings/{id}",
+            axum_codec::routing::get(buildings::find_claim_description).into(),
+        )
+        .route(
+            "/desc/buildings",
+            get(buildings::find_building_descriptions),
+        )
         .nest_service(
             "/static",
             get_service(ServeDir::new(concat!(
@@ -601,6 +732,65 @@ fn create_app(config: &Config, state: AppState, prometheus: PrometheusHandle) ->
                     );
                 }
 
+                let _ = writeln!(
+                    leaderboard_buckets,
+                    "# HELP leaderboard_region_bucket_count Bucket distribution for region leaderboards"
+                );
+                let _ = writeln!(
+                    leaderboard_buckets,
+                    "# TYPE leaderboard_region_bucket_count gauge"
+                );
+
+                let append_region_bucket_metrics = |output: &mut String,
+                                                    name: &str,
+                                                    region: entity::shared::Region,
+                                                    board: &Leaderboard| {
+                    for bucket in board.bucket_distribution() {
+                        let _ = writeln!(
+                            output,
+                            "leaderboard_region_bucket_count{{leaderboard=\"{name}\",region=\"{region}\",bucket=\"{}\",min_xp=\"{}\",max_xp=\"{}\"}} {}",
+                            bucket.bucket,
+                            bucket.min_xp,
+                            bucket.max_xp,
+                            bucket.count
+                        );
+                    }
+                };
+
+                for region_entry in app_state.ranking_system.regions.iter() {
+                    let region = *region_entry.key();
+                    let region_rankings = region_entry.value();
+
+                    append_region_bucket_metrics(
+                        &mut leaderboard_buckets,
+                        "global_experience",
+                        region,
+                        &region_rankings.global_leaderboard,
+                    );
+                    append_region_bucket_metrics(
+                        &mut leaderboard_buckets,
+                        "level",
+                        region,
+                        &region_rankings.level_leaderboard,
+                    );
+                    append_region_bucket_metrics(
+                        &mut leaderboard_buckets,
+                        "xp_per_hour",
+                        region,
+                        &region_rankings.xp_per_hour,
+                    );
+
+                    for entry in region_rankings.skill_leaderboards.iter() {
+                        let skill_id = entry.key();
+                        append_region_bucket_metrics(
+                            &mut leaderboard_buckets,
+                            &format!("skill_{skill_id}"),
+                            region,
+                            entry.value(),
+                        );
+                    }
+                }
+
                 if let Err(err) = prometheus_body {
                     error!("Error: {:?}", err);
 
@@ -613,6 +803,14 @@ fn create_app(config: &Config, state: AppState, prometheus: PrometheusHandle) ->
                 }
             }),
         )
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit::rate_limit,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            status::starting_up,
+        ))
         .layer(CookieManagerLayer::new())
         .layer(
             CorsLayer::new()
@@ -627,6 +825,11 @@ fn create_app(config: &Config, state: AppState, prometheus: PrometheusHandle) ->
                 .allow_methods(Any),
         )
         .layer(CompressionLayer::new())
+        .route_layer(middleware::from_fn(v1::deprecate_legacy_routes))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            freshness::data_freshness,
+        ))
         .route_layer(middleware::from_fn(track_metrics))
         .with_state(state)
 }
@@ -638,6 +841,8 @@ fn record_app_state_metrics(app_state: &AppState) {
         .set(app_state.player_state.len() as f64);
     metrics::gauge!("app_state_cache_size", &[("cache", "claim_member_state")])
         .set(app_state.claim_member_state.len() as f64);
+    metrics::gauge!("app_state_cache_size", &[("cache", "building_claim_id")])
+        .set(app_state.building_claim_id.len() as f64);
     metrics::gauge!(
         "app_state_cache_size",
         &[("cache", "claim_member_state_total")]
@@ -734,7 +939,7 @@ struct AppState {
     tech_tier_research_map: TechTierResearchMap,
     conn: DatabaseConnection,
     tx: UnboundedSender<WebSocketMessages>,
-    connection_state: Arc<dashmap::DashMap<String, bool>>,
+    status: Arc<status::Status>,
     clients_state: Arc<ClientsState>,
     mobile_entity_state: Arc<dashmap::DashMap<u64, entity::mobile_entity_state::Model>>,
     player_state: Arc<dashmap::DashMap<i64, entity::player_state::Model>>,
@@ -744,6 +949,8 @@ struct AppState {
     claim_local_state: Arc<dashmap::DashMap<u64, entity::claim_local_state::Model>>,
     claim_state: Arc<dashmap::DashMap<i64, entity::claim_state::Model>>,
     claim_tile_state: Arc<dashmap::DashMap<u64, entity::claim_tile_state::Model>>,
+    // Building entity id -> claim id, only for buildings inside a claim
+    building_claim_id: Arc<dashmap::DashMap<i64, i64>>,
     player_action_state: Arc<dashmap::DashMap<u64, entity::player_action_state::Model>>,
     crafting_recipe_desc: Arc<dashmap::DashMap<i32, entity::crafting_recipe::Model>>,
     claim_tech_desc: Arc<dashmap::DashMap<i32, entity::claim_tech_desc::Model>>,
@@ -760,6 +967,8 @@ struct AppState {
     action_state: Arc<dashmap::DashMap<u64, dashmap::DashMap<u64, entity::action_state::Model>>>,
     location_state: Arc<dashmap::DashMap<i64, entity::location::Model>>,
     // inventory_state: Arc<dashmap::DashMap<i64, ::entity::inventory::Model>>,
+    // Inventory changes that were broadcast but are not in Postgres yet, `None` for deletes
+    pending_inventory: Arc<dashmap::DashMap<i64, Option<entity::inventory::Model>>>,
     #[allow(dead_code)]
     connected_user_map: Arc<dashmap::DashMap<String, i64>>,
     traveler_task_desc: Arc<dashmap::DashMap<i32, entity::traveler_task_desc::Model>>,
@@ -770,6 +979,15 @@ struct AppState {
     sell_order_state:
         Arc<dashmap::DashMap<i64, entity::auction_listing_state::AuctionListingState>>,
     npc_desc: Arc<dashmap::DashMap<i32, entity::npc_desc::Model>>,
+    // Account id -> pending player link
+    player_link_challenges: Arc<dashmap::DashMap<i64, accounts::PlayerLinkChallenge>>,
+    rate_limiter: Arc<rate_limit::RateLimiter>,
+    desc_cache: Arc<desc_cache::DescCache>,
+    crafting_planner: Arc<recipes::CraftingPlannerCache>,
+    freshness: Arc<freshness::Freshness>,
+    graphql_schema: graphql::GraphqlSchema,
+    // Every broadcast websocket message, for GraphQL subscriptions
+    graphql_events: tokio::sync::broadcast::Sender<WebSocketMessages>,
     metrics_registry: prometheus::Registry,
     ranking_system: Arc<RankingSystem>,
 }
@@ -779,6 +997,8 @@ impl AppState {
         conn: DatabaseConnection,
         tx: UnboundedSender<WebSocketMessages>,
         tech_tier_research_map: TechTierResearchMap,
+        rate_limit: RateLimitConfig,
+        graphql: &GraphqlConfig,
     ) -> Self {
         let metrics_registry = prometheus::Registry::new();
 
@@ -791,7 +1011,7 @@ impl AppState {
             conn,
             tx,
             metrics_registry,
-            connection_state: Arc::new(dashmap::DashMap::new()),
+            status: Arc::new(status::Status::default()),
             player_state: Arc::new(dashmap::DashMap::new()),
             clients_state: Arc::new(ClientsState::new()),
             mobile_entity_state: Arc::new(dashmap::DashMap::new()),
@@ -799,6 +1019,7 @@ impl AppState {
             player_to_claim_id_cache: Arc::new(dashmap::DashMap::new()),
             claim_local_state: Arc::new(dashmap::DashMap::new()),
             claim_tile_state: Arc::new(dashmap::DashMap::new()),
+            building_claim_id: Arc::new(dashmap::DashMap::new()),
             claim_state: Arc::new(dashmap::DashMap::new()),
             player_action_state: Arc::new(dashmap::DashMap::new()),
             crafting_recipe_desc: Arc::new(dashmap::DashMap::new()),
@@ -816,6 +1037,7 @@ impl AppState {
             action_state: Arc::new(dashmap::DashMap::new()),
             location_state: Arc::new(dashmap::DashMap::new()),
             // inventory_state: Arc::new(dashmap::DashMap::new()),
+            pending_inventory: Arc::new(dashmap::DashMap::new()),
             connected_user_map: Arc::new(dashmap::DashMap::new()),
             traveler_task_desc: Arc::new(dashmap::DashMap::new()),
             trade_order_state: Arc::new(dashmap::DashMap::new()),
@@ -824,6 +1046,13 @@ impl AppState {
             user_state: Arc::new(dashmap::DashMap::new()),
             deployable_state: Arc::new(dashmap::DashMap::new()),
             npc_desc: Arc::new(dashmap::DashMap::new()),
+            player_link_challenges: Arc::new(dashmap::DashMap::new()),
+            rate_limiter: Arc::new(rate_limit::RateLimiter::new(rate_limit)),
+            desc_cache: Arc::new(desc_cache::DescCache::default()),
+            crafting_planner: Arc::new(recipes::CraftingPlannerCache::default()),
+            freshness: Arc::new(freshness::Freshness::default()),
+            graphql_schema: graphql::build_schema(graphql),
+            graphql_events: tokio::sync::broadcast::channel(graphql::EVENTS_CAPACITY).0,
             ranking_system: Arc::new(RankingSystem::default()),
         }
     }
@@ -1170,64 +1399,89 @@ impl Display for WebsocketEncoding {
 }
 
 type WebsocketClient = (
-    AsyncSender<crate::websocket::WebSocketMessages>,
     HashMap<String, HashSet<i64>>,
-    WebsocketEncoding,
     HashSet<String>,
+    HashMap<String, SubscriptionFilter>,
 );
 
+type TopicKey = (String, Option<i64>);
+
 struct ClientsState {
     clients: Arc<RwLock<HashMap<String, WebsocketClient>>>,
-    topics_listen_to: Arc<RwLock<HashMap<String, HashMap<i64, u64>>>>,
+    // Which clients listen to a topic, so a broadcast only looks at its subscribers
+    topic_index: dashmap::DashMap<TopicKey, HashSet<String>>,
+    outboxes: dashmap::DashMap<String, Arc<ClientOutbox>>,
+    // Topic -> (client id, filter id) -> filter
+    filter_index: dashmap::DashMap<String, HashMap<(String, String), SubscriptionFilter>>,
+    sequence: AtomicU64,
 }
 
 impl ClientsState {
     fn new() -> Self {
         Self {
             clients: Arc::new(RwLock::new(HashMap::new())),
-            topics_listen_to: Arc::new(RwLock::new(HashMap::new())),
+            topic_index: dashmap::DashMap::new(),
+            outboxes: dashmap::DashMap::new(),
+            filter_index: dashmap::DashMap::new(),
+            sequence: AtomicU64::new(0),
         }
     }
 
-    pub(crate) async fn add_client(
-        &self,
-        id: String,
-        tx: AsyncSender<WebSocketMessages>,
-        encoding: WebsocketEncoding,
-    ) {
+    pub(crate) fn next_sequence(&self) -> u64 {
+        self.sequence.fetch_add(1, Ordering::SeqCst) + 1
+    }
+
+    pub(crate) fn current_sequence(&self) -> u64 {
+        self.sequence.load(Ordering::SeqCst)
+    }
+
+    pub(crate) fn set_client_sequenced(&self, id: &String) {
+        if let Some(outbox) = self.outboxes.get(id) {
+            outbox.set_sequenced();
+        }
+    }
+
+    pub(crate) async fn add_client(&self, id: String) -> Arc<ClientOutbox> {
+        let outbox = Arc::new(ClientOutbox::new(CLIENT_QUEUE_CAPACITY));
+        self.outboxes.insert(id.clone(), outbox.clone());
         self.clients
             .write()
             .await
-            .insert(id, (tx, HashMap::new(), encoding, HashSet::new()));
+            .insert(id, (HashMap::new(), HashSet::new(), HashMap::new()));
         metrics::gauge!("websocket_clients_connected_total").increment(1);
+
+        outbox
     }
 
     pub(crate) async fn remove_client(&self, id: &String) {
-        let current_topics = self.clients.read().await.get(id).unwrap().1.clone();
-        for (topic, topic_ids) in current_topics {
-            for topic_id in topic_ids {
-                self.remove_topic_from_client(id, &topic, Some(topic_id))
-                    .await;
+        if let Some((topics, topics_without_id, filters)) = self.clients.write().await.remove(id) {
+            for (topic, topic_ids) in topics {
+                for topic_id in topic_ids {
+                    self.unindex_topic(id, topic.clone(), Some(topic_id));
+                }
+            }
+
+            for topic in topics_without_id {
+                self.unindex_topic(id, topic, None);
+            }
+
+            for (filter_id, filter) in filters {
+                self.unindex_filter(id, filter_id, &filter.topic);
             }
         }
 
-        self.clients.write().await.remove(id);
+        if let Some((_, outbox)) = self.outboxes.remove(id) {
+            outbox.close();
+        }
         metrics::gauge!("websocket_clients_connected_total").decrement(1);
     }
 
-    pub(crate) async fn get_encode_format_for_client(
-        &self,
-        id: &String,
-    ) -> Option<WebsocketEncoding> {
-        self.clients.read().await.get(id).map(|client| client.2)
-    }
-
     pub(crate) async fn get_topics_for_client(&self, id: &String) -> Option<Vec<String>> {
         let clients = self.clients.read().await;
         if let Some(client) = clients.get(id) {
             return Some(
                 client
-                    .1
+                    .0
                     .iter()
                     .flat_map(|(topic, ids)| {
                         ids.iter()
@@ -1244,43 +1498,35 @@ impl ClientsState {
     pub(crate) async fn add_topic_to_client(
         &self,
         id: &String,
-        topic: &String,
+        topic: &str,
         topic_id: Option<i64>,
     ) {
         if self
-            .client_listen_to_topics(id, vec![(topic.clone(), topic_id)])
+            .client_listen_to_topics(id, vec![(topic.to_owned(), topic_id)])
             .await
         {
             return;
         }
 
         let mut clients = self.clients.write().await;
-        if let Some(client) = clients.get_mut(id) {
-            if let Some(topic_id) = topic_id {
-                if let Some(topics) = client.1.get_mut(topic) {
-                    topics.insert(topic_id);
-                } else {
-                    client.1.insert(topic.clone(), HashSet::from([topic_id]));
-                }
-            } else {
-                client.3.insert(topic.clone());
-            }
-        }
-
-        if topic_id.is_none() {
+        let Some(client) = clients.get_mut(id) else {
             return;
-        }
+        };
 
-        let mut topics_listen_to = self.topics_listen_to.write().await;
-        if let Some(found_topic) = topics_listen_to.get_mut(topic) {
-            if let Some(current_topic) = found_topic.get_mut(&topic_id.unwrap()) {
-                current_topic.add_assign(1);
-            } else {
-                found_topic.insert(topic_id.unwrap(), 1);
-            }
+        if let Some(topic_id) = topic_id {
+            client
+                .0
+                .entry(topic.to_owned())
+                .or_default()
+                .insert(topic_id);
         } else {
-            topics_listen_to.insert(topic.clone(), HashMap::from([(topic_id.unwrap(), 1)]));
+            client.1.insert(topic.to_owned());
         }
+
+        self.topic_index
+            .entry((topic.to_owned(), topic_id))
+            .or_default()
+            .insert(id.clone());
     }
 
     pub(crate) async fn remove_topic_from_client(
@@ -1291,46 +1537,108 @@ impl ClientsState {
     ) {
         let mut clients = self.clients.write().await;
         if let Some(client) = clients.get_mut(id) {
-            if topic_id.is_none() && client.3.contains(topic) {
-                client.3.remove(topic);
-            } else if let Some(topics) = client.1.get_mut(topic) {
-                topics.remove(&topic_id.unwrap());
+            if topic_id.is_none() && client.1.contains(topic) {
+                client.1.remove(topic);
+            } else if let Some(topic_id) = topic_id
+                && let Some(topics) = client.0.get_mut(topic)
+            {
+                topics.remove(&topic_id);
             }
         }
 
-        if topic_id.is_none() {
-            return;
+        self.unindex_topic(id, topic.clone(), topic_id);
+    }
+
+    fn unindex_topic(&self, id: &String, topic: String, topic_id: Option<i64>) {
+        self.topic_index
+            .remove_if_mut(&(topic, topic_id), |_, subscribers| {
+                subscribers.remove(id);
+                subscribers.is_empty()
+            });
+    }
+
+    /// Adds or replaces the client's filter `filter_id`.
+    pub(crate) async fn add_filter_to_client(
+        &self,
+        id: &String,
+        filter_id: String,
+        filter: SubscriptionFilter,
+    ) -> Result<(), &'static str> {
+        let mut clients = self.clients.write().await;
+        let Some(client) = clients.get_mut(id) else {
+            return Err("Unknown client");
+        };
+
+        if !client.2.contains_key(&filter_id) && client.2.len() >= MAX_FILTERS_PER_CLIENT {
+            return Err("Too many filters");
         }
 
-        let mut topics_listen_to = self.topics_listen_to.write().await;
-        if let Some(found_topic) = topics_listen_to.get_mut(topic) {
-            if let Some(current_topic) = found_topic.get_mut(&topic_id.unwrap()) {
-                if current_topic > &mut 0u64 {
-                    current_topic.sub_assign(1);
-                }
-            }
+        if let Some(previous) = client.2.insert(filter_id.clone(), filter.clone()) {
+            self.unindex_filter(id, filter_id.clone(), &previous.topic);
+        }
+
+        self.filter_index
+            .entry(filter.topic.clone())
+            .or_default()
+            .insert((id.clone(), filter_id), filter);
+
+        Ok(())
+    }
+
+    pub(crate) async fn remove_filter_from_client(&self, id: &String, filter_id: &String) {
+        let mut clients = self.clients.write().await;
+        if let Some(client) = clients.get_mut(id)
+            && let Some(filter) = client.2.remove(filter_id)
+        {
+            self.unindex_filter(id, filter_id.clone(), &filter.topic);
         }
     }
 
-    pub(crate) async fn clients_listen_to_topic(
+    fn unindex_filter(&self, id: &str, filter_id: String, topic: &String) {
+        self.filter_index.remove_if_mut(topic, |_, filters| {
+            filters.remove(&(id.to_owned(), filter_id));
+            filters.is_empty()
+        });
+    }
+
+    /// Clients with at least one filter on `topic` for which `matches` holds.
+    pub(crate) fn clients_with_matching_filter(
         &self,
-        topic: &String,
-        topic_id: Option<i64>,
-    ) -> Vec<AsyncSender<crate::websocket::WebSocketMessages>> {
-        let mut senders = vec![];
-        let clients = self.clients.read().await;
+        topic: &str,
+        matches: impl Fn(&SubscriptionFilter) -> bool,
+    ) -> Vec<(String, Arc<ClientOutbox>)> {
+        let Some(filters) = self.filter_index.get(topic) else {
+            return vec![];
+        };
 
-        for (_, (tx, topics, _, topic_with_out_id)) in clients.iter() {
-            if topic_id.is_none() && topic_with_out_id.contains(topic) {
-                senders.push(tx.clone());
-            } else if let Some(found_topic) = topics.get(topic) {
-                if found_topic.contains(&topic_id.unwrap()) {
-                    senders.push(tx.clone());
-                }
-            }
-        }
+        filters
+            .iter()
+            .filter(|(_, filter)| matches(filter))
+            .filter_map(|((id, _), _)| {
+                self.outboxes
+                    .get(id)
+                    .map(|outbox| (id.clone(), outbox.clone()))
+            })
+            .collect()
+    }
 
-        senders
+    pub(crate) fn clients_listen_to_topic(
+        &self,
+        topic: &str,
+        topic_id: Option<i64>,
+    ) -> Vec<(String, Arc<ClientOutbox>)> {
+        let Some(subscribers) = self.topic_index.get(&(topic.to_owned(), topic_id)) else {
+            return vec![];
+        };
+
+        subscribers
+            .iter()
+            .filter_map(|id| {
+                self.outboxes
+                    .get(id)
+                    .map(|outbox| (id.clone(), outbox.clone()))
+            })
+            .collect()
     }
 
     pub(crate) async fn client_listen_to_topics(
@@ -1341,10 +1649,10 @@ impl ClientsState {
         if let Some(client) = self.clients.read().await.get(id) {
             for (topic, id) in topics {
                 if id.is_none() {
-                    return client.3.contains(&topic);
+                    return client.1.contains(&topic);
                 }
 
-                if let Some(found_topic) = client.1.get(&topic) {
+                if let Some(found_topic) = client.0.get(&topic) {
                     return found_topic.contains(&id.unwrap());
                 }
             }
@@ -1354,23 +1662,15 @@ impl ClientsState {
     }
 
     #[allow(dead_code)]
-    pub(crate) async fn listeners_for_topic(&self, topics: Vec<(String, i64)>) -> bool {
-        let topics_listen_to = self.topics_listen_to.read().await;
-        for (topic, id) in topics {
-            if let Some(found_topic) = topics_listen_to.get(&topic) {
-                if let Some(current_topic) = found_topic.get(&id) {
-                    if current_topic > &0u64 {
-                        return true;
-                    }
-                }
-            }
-        }
-
-        false
+    pub(crate) fn listeners_for_topic(&self, topics: Vec<(String, i64)>) -> bool {
+        topics
+            .into_iter()
+            .any(|(topic, id)| self.topic_index.contains_key(&(topic, Some(id))))
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct Params {
     page: Option<u64>,
     per_page: Option<u64>,
@@ -1,12 +1,69 @@
 use crate::AppState;
+use crate::websocket::ingest_queue::IngestReceiver;
 use crate::websocket::{WebSocketMessages, record_worker_received};
 use entity::mobile_entity_state;
 use game_module::module_bindings::MobileEntityState;
-use tokio::sync::mpsc::UnboundedReceiver;
+
+/// Publishes the claim enter/exit events when a player crosses into a chunk of another claim.
+fn send_claim_transition(
+    global_app_state: &AppState,
+    user_id: i64,
+    old_chunk_index: u64,
+    new_chunk_index: u64,
+) {
+    if old_chunk_index == new_chunk_index {
+        return;
+    }
+
+    let old_claim_id = global_app_state
+        .claim_tile_state
+        .get(&old_chunk_index)
+        .map(|claim_tile| claim_tile.claim_id);
+    let new_claim_id = global_app_state
+        .claim_tile_state
+        .get(&new_chunk_index)
+        .map(|claim_tile| claim_tile.claim_id);
+
+    if old_claim_id == new_claim_id {
+        return;
+    }
+
+    if let Some(claim_id) = old_claim_id {
+        let _ = global_app_state
+            .tx
+            .send(WebSocketMessages::MovedOutOfClaim {
+                user_id,
+                chunk_index: new_chunk_index,
+                claim_id,
+            });
+        let _ = global_app_state
+            .tx
+            .send(WebSocketMessages::PlayerMovedOutOfClaim {
+                user_id,
+                chunk_index: new_chunk_index,
+                claim_id,
+            });
+    }
+
+    if let Some(claim_id) = new_claim_id {
+        let _ = global_app_state.tx.send(WebSocketMessages::MovedIntoClaim {
+            user_id,
+            chunk_index: new_chunk_index,
+            claim_id,
+        });
+        let _ = global_app_state
+            .tx
+            .send(WebSocketMessages::PlayerMovedIntoClaim {
+                user_id,
+                chunk_index: new_chunk_index,
+                claim_id,
+            });
+    }
+}
 
 pub(crate) fn start_worker_mobile_entity_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<crate::websocket::SpacetimeUpdateMessages<MobileEntityState>>,
+    mut rx: IngestReceiver<MobileEntityState>,
 ) {
     tokio::spawn(async move {
         let mut buffer = Vec::with_capacity(500);
@@ -48,16 +105,26 @@ pub(crate) fn start_worker_mobile_entity_state(
                                 .with_region(database_name)
                                 .build();
 
-                        global_app_state
+                        let previous = global_app_state
                             .mobile_entity_state
                             .insert(model.entity_id, model.clone());
 
+                        if let Some(previous) = previous {
+                            send_claim_transition(
+                                &global_app_state,
+                                model.entity_id as i64,
+                                previous.chunk_index,
+                                model.chunk_index,
+                            );
+                        }
+
                         let _ = global_app_state
                             .tx
                             .send(WebSocketMessages::MobileEntityState(model));
                     }
                     crate::websocket::SpacetimeUpdateMessages::Update {
                         new,
+                        old,
                         database_name,
                         ..
                     } => {
@@ -70,6 +137,13 @@ pub(crate) fn start_worker_mobile_entity_state(
                             .mobile_entity_state
                             .insert(model.entity_id, model.clone());
 
+                        send_claim_transition(
+                            &global_app_state,
+                            model.entity_id as i64,
+                            old.chunk_index,
+                            model.chunk_index,
+                        );
+
                         let _ = global_app_state
                             .tx
                             .send(WebSocketMessages::MobileEntityState(model));
@@ -0,0 +1,370 @@
+use crate::accounts::SESSION_COOKIE;
+use crate::v1::LEGACY_ROUTES;
+use crate::{
+    AppRouter, accounts, auction_listing_state, buildings, claims, extraction_recipe_desc,
+    freshness, graphql, houses, inventory, items, items_and_cargo, leaderboard, player_state,
+    recipes, status, trading_orders, traveler_tasks,
+};
+use axum::Router;
+use axum::http::header;
+use axum::response::IntoResponse;
+use std::sync::LazyLock;
+use utoipa::openapi::path::{HttpMethod, Operation, OperationBuilder, ParameterIn, PathItem};
+use utoipa::openapi::security::{ApiKey, ApiKeyValue, HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{ContentBuilder, Deprecated, ResponseBuilder};
+use utoipa::{Modify, OpenApi};
+
+pub(crate) fn get_routes() -> AppRouter {
+    Router::new().route(
+        "/api/bitcraft/openapi.json",
+        axum::routing::get(get_openapi),
+    )
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "BitCraft Hub API"),
+    paths(
+        crate::websocket_handler,
+        get_openapi,
+        accounts::create_account,
+        accounts::create_session,
+        accounts::delete_session,
+        accounts::get_me,
+        accounts::list_api_keys,
+        accounts::create_api_key,
+        accounts::revoke_api_key,
+        accounts::start_player_link,
+        accounts::verify_player_link,
+        accounts::unlink_player,
+        auction_listing_state::get_market_order_stats,
+        auction_listing_state::find_market_place_order,
+        auction_listing_state::market_item_cargo_desc,
+        auction_listing_state::get_market_price_history,
+        auction_listing_state::get_market_depth,
+        buildings::find_building_descriptions,
+        buildings::find_claim_description,
+        buildings::find_building_states,
+        buildings::find_building_state,
+        buildings::list_buildings,
+        buildings::list_building_descriptions,
+        claims::list_claims,
+        claims::get_claim_names,
+        claims::get_claim,
+        claims::get_claim_auction_listings,
+        claims::get_claim_tiles,
+        claims::get_claim_inventory_change_log,
+        claims::craft_check::get_claim_craft_check,
+        claims::list_claims_v1,
+        extraction_recipe_desc::get_all,
+        freshness::get_freshness,
+        graphql::graphiql,
+        graphql::graphql,
+        graphql::graphql_ws,
+        houses::find_houses,
+        houses::find_houses_by_owner_id,
+        houses::find_house,
+        houses::find_house_inventories,
+        houses::list_houses,
+        inventory::read_inventory_changes,
+        inventory::find_inventory_by_id,
+        inventory::find_inventory_by_owner_entity_id,
+        inventory::all_inventory_stats,
+        items::list_items,
+        items::list_world_items,
+        items::list_items_v1,
+        items_and_cargo::get_all,
+        items_and_cargo::list_items_and_cargo,
+        items_and_cargo::meta,
+        items_and_cargo::list_items_and_cargo_v1,
+        leaderboard::get_top_100,
+        leaderboard::player_leaderboard,
+        leaderboard::get_claim_leaderboard,
+        leaderboard::player_rank_history,
+        leaderboard::get_leaderboard_movers,
+        player_state::get_all,
+        player_state::list_players,
+        player_state::find_player_by_id,
+        player_state::list_players_v1,
+        recipes::get_all,
+        recipes::get_plan,
+        status::get_healthz,
+        status::get_readyz,
+        status::get_status,
+        trading_orders::get_trade_orders,
+        trading_orders::list_trade_orders,
+        traveler_tasks::get_all,
+        traveler_tasks::get_npc_all,
+    ),
+    modifiers(&SecuritySchemes),
+)]
+struct ApiDoc;
+
+/// Older paths that serve the same handler as a documented one, as `(alias, documented path)`.
+const ALIASES: &[(&str, &str)] = &[
+    ("/buildings", "/api/bitcraft/buildings"),
+    ("/buildings/{id}", "/api/bitcraft/buildings/{id}"),
+    ("/desc/buildings", "/api/bitcraft/desc/buildings"),
+    ("/claims", "/api/bitcraft/claims"),
+    (
+        "/claims/{id}/auction_listings",
+        "/api/bitcraft/claims/{id}/auction_listings",
+    ),
+    (
+        "/experience/{player_id}",
+        "/api/bitcraft/experience/{player_id}",
+    ),
+    ("/houses", "/api/bitcraft/houses"),
+    (
+        "/houses/by_owner/{id}",
+        "/api/bitcraft/houses/by_owner/{id}",
+    ),
+    ("/houses/{id}", "/api/bitcraft/houses/{id}"),
+    (
+        "/houses/{id}/inventories",
+        "/api/bitcraft/houses/{id}/inventories",
+    ),
+    (
+        "/inventorys/changes/{id}",
+        "/api/bitcraft/inventorys/changes/{id}",
+    ),
+    ("/players", "/api/bitcraft/players"),
+    ("/players/{id}", "/api/bitcraft/players/{id}"),
+];
+
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+
+        components.add_security_scheme(
+            "api_key",
+            SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::new("X-API-Key"))),
+        );
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+        components.add_security_scheme(
+            "session",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new(SESSION_COOKIE))),
+        );
+    }
+}
+
+fn operations_mut(path_item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut path_item.get,
+        &mut path_item.post,
+        &mut path_item.put,
+        &mut path_item.patch,
+        &mut path_item.delete,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Operation ids have to be unique within the document.
+fn suffix_operation_ids(path_item: &mut PathItem, suffix: &str) {
+    for operation in operations_mut(path_item) {
+        operation.operation_id = operation
+            .operation_id
+            .take()
+            .map(|operation_id| format!("{operation_id}_{suffix}"));
+    }
+}
+
+fn path_parameters(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+}
+
+pub(crate) fn openapi() -> utoipa::openapi::OpenApi {
+    let mut openapi = ApiDoc::openapi();
+
+    // `/api/v1` routes that reuse a legacy handler are documented like it
+    for (legacy, successor) in LEGACY_ROUTES {
+        if openapi.paths.paths.contains_key(*successor) {
+            continue;
+        }
+        let Some(mut path_item) = openapi.paths.paths.get(*legacy).cloned() else {
+            continue;
+        };
+
+        suffix_operation_ids(&mut path_item, "v1");
+
+        let renames = path_parameters(legacy)
+            .zip(path_parameters(successor))
+            .collect::<Vec<_>>();
+        for operation in operations_mut(&mut path_item) {
+            for parameter in operation.parameters.iter_mut().flatten() {
+                if parameter.parameter_in != ParameterIn::Path {
+                    continue;
+                }
+
+                if let Some((_, name)) = renames.iter().find(|(old, _)| *old == parameter.name) {
+                    parameter.name = name.to_string();
+                }
+            }
+        }
+
+        openapi.paths.paths.insert(successor.to_string(), path_item);
+    }
+
+    for (alias, documented) in ALIASES {
+        let Some(mut path_item) = openapi.paths.paths.get(*documented).cloned() else {
+            continue;
+        };
+
+        suffix_operation_ids(&mut path_item, "alias");
+
+        openapi.paths.paths.insert(alias.to_string(), path_item);
+    }
+
+    for (legacy, _) in LEGACY_ROUTES {
+        if let Some(path_item) = openapi.paths.paths.get_mut(*legacy) {
+            for operation in operations_mut(path_item) {
+                operation.deprecated = Some(Deprecated::True);
+            }
+        }
+    }
+
+    // Registered as a closure in `create_app`, so there is no handler to annotate
+    openapi.paths.paths.insert(
+        "/metrics".to_string(),
+        PathItem::new(
+            HttpMethod::Get,
+            OperationBuilder::new()
+                .operation_id(Some("get_metrics"))
+                .tag("meta")
+                .response(
+                    "200",
+                    ResponseBuilder::new()
+                        .description("Prometheus metrics in the text exposition format")
+                        .content("text/plain", ContentBuilder::new().build()),
+                )
+                .build(),
+        ),
+    );
+
+    openapi
+}
+
+static OPENAPI_JSON: LazyLock<String> = LazyLock::new(|| {
+    openapi()
+        .to_pretty_json()
+        .expect("OpenAPI document should serialize")
+});
+
+/// The OpenAPI 3 document describing this API.
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/openapi.json",
+    tag = "meta",
+    responses(
+        (status = OK, description = "This document", content_type = "application/json"),
+    )
+)]
+pub(crate) async fn get_openapi() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "application/json")],
+        OPENAPI_JSON.as_str(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// The paths passed to `.route(` in a source file, commented out routes are skipped.
+    fn routes_in(source: &str) -> Vec<String> {
+        let code = source
+            .lines()
+            .filter(|line| !line.trim_start().starts_with("//"))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        code.split(".route(")
+            .skip(1)
+            .filter_map(|rest| {
+                let rest = rest.trim_start().strip_prefix('"')?;
+                rest.split('"')
+                    .next()
+                    .filter(|path| path.starts_with('/'))
+                    .map(str::to_string)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn every_route_is_documented() {
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let lib = std::fs::read_to_string(src.join("lib.rs")).unwrap();
+
+        let modules = lib
+            .split(".merge(")
+            .skip(1)
+            .filter_map(|rest| rest.split("::get_routes()").next())
+            .filter(|module| module.chars().all(|c| c.is_ascii_lowercase() || c == '_'))
+            .collect::<Vec<_>>();
+        assert!(!modules.is_empty());
+
+        let mut routes = routes_in(&lib);
+        for module in modules {
+            let module_path = src.join(module).join("mod.rs");
+            let module_path = if module_path.exists() {
+                module_path
+            } else {
+                src.join(format!("{module}.rs"))
+            };
+
+            routes.extend(routes_in(&std::fs::read_to_string(module_path).unwrap()));
+        }
+
+        let openapi = openapi();
+        let missing = routes
+            .into_iter()
+            .filter(|route| !openapi.paths.paths.contains_key(route))
+            .collect::<Vec<_>>();
+
+        assert!(
+            missing.is_empty(),
+            "Routes missing from the OpenAPI document: {missing:?}"
+        );
+    }
+
+    #[test]
+    fn legacy_routes_have_documented_successors() {
+        let openapi = openapi();
+
+        for (legacy, successor) in LEGACY_ROUTES {
+            let legacy_item = &openapi.paths.paths[*legacy];
+            assert!(
+                legacy_item
+                    .get
+                    .iter()
+                    .chain(&legacy_item.post)
+                    .all(|operation| operation.deprecated == Some(Deprecated::True)),
+                "{legacy} is not marked as deprecated"
+            );
+            assert!(
+                openapi.paths.paths.contains_key(*successor),
+                "{legacy} has undocumented successor {successor}"
+            );
+        }
+    }
+
+    #[test]
+    fn aliases_point_at_documented_paths() {
+        let openapi = ApiDoc::openapi();
+
+        for (alias, documented) in ALIASES {
+            assert!(
+                openapi.paths.paths.contains_key(*documented),
+                "{alias} is an alias of undocumented {documented}"
+            );
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use crate::error::ApiError;
+use axum::extract::{FromRequestParts, Query};
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use sea_orm::Order;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
+
+const DEFAULT_PER_PAGE: u64 = 24;
+const MAX_PER_PAGE: u64 = 100;
+
+/// The query parameters every `/api/v1` list shares, resource specific filters are separate.
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct ListParams {
+    /// Starts at 1.
+    page: Option<u64>,
+    /// At most 100, defaults to 24.
+    per_page: Option<u64>,
+    /// The field to sort by, prefixed with `-` to sort descending.
+    sort: Option<String>,
+    /// Only entries whose name contains this, ignoring case.
+    search: Option<String>,
+}
+
+/// The validated [`ListParams`] of a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ListQuery {
+    pub(crate) page: u64,
+    pub(crate) per_page: u64,
+    pub(crate) sort: Option<String>,
+    pub(crate) search: Option<String>,
+}
+
+impl ListQuery {
+    fn from_params(params: ListParams) -> Result<Self, ApiError> {
+        let page = params.page.unwrap_or(1);
+        if page == 0 {
+            return Err(ApiError::new(StatusCode::BAD_REQUEST, "page starts at 1"));
+        }
+
+        let per_page = params.per_page.unwrap_or(DEFAULT_PER_PAGE);
+        if !(1..=MAX_PER_PAGE).contains(&per_page) {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "per_page must be between 1 and 100",
+            ));
+        }
+
+        Ok(Self {
+            page,
+            per_page,
+            sort: params.sort.filter(|sort| !sort.is_empty()),
+            search: params.search.filter(|search| !search.is_empty()),
+        })
+    }
+
+    /// Resolves `sort` against the fields a resource can be sorted by.
+    pub(crate) fn sort_by<T: Clone>(
+        &self,
+        fields: &[(&str, T)],
+    ) -> Result<Option<(T, Order)>, ApiError> {
+        let Some(sort) = self.sort.as_deref() else {
+            return Ok(None);
+        };
+        let (field, order) = match sort.strip_prefix('-') {
+            Some(field) => (field, Order::Desc),
+            None => (sort, Order::Asc),
+        };
+
+        fields
+            .iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, value)| Some((value.clone(), order)))
+            .ok_or(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "sort is not a field this resource can be sorted by",
+            ))
+    }
+
+    /// For resources that are always in the same order.
+    pub(crate) fn no_sort(&self) -> Result<(), ApiError> {
+        self.sort_by::<()>(&[]).map(|_| ())
+    }
+
+    /// For resources without a name to search by.
+    pub(crate) fn no_search(&self) -> Result<(), ApiError> {
+        match self.search {
+            Some(_) => Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                "search is not supported for this resource",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Pages a list that is already filtered and sorted in memory.
+    pub(crate) fn paginate<T>(&self, items: Vec<T>) -> Paginated<T> {
+        let total = items.len() as u64;
+        let items = items
+            .into_iter()
+            .skip(((self.page - 1) * self.per_page) as usize)
+            .take(self.per_page as usize)
+            .collect();
+
+        Paginated::new(items, self, total)
+    }
+}
+
+impl<S: Send + Sync> FromRequestParts<S> for ListQuery {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(params) = Query::<ListParams>::from_request_parts(parts, state)
+            .await
+            .map_err(|_| ApiError::new(StatusCode::BAD_REQUEST, "Invalid list parameters"))?;
+
+        Self::from_params(params)
+    }
+}
+
+/// The envelope of every `/api/v1` list.
+#[derive(Debug, Clone, Serialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct Paginated<T> {
+    pub items: Vec<T>,
+    pub page: u64,
+    pub per_page: u64,
+    pub total: u64,
+    pub total_pages: u64,
+}
+
+impl<T> Paginated<T> {
+    pub(crate) fn new(items: Vec<T>, query: &ListQuery, total: u64) -> Self {
+        Self {
+            items,
+            page: query.page,
+            per_page: query.per_page,
+            total,
+            total_pages: total.div_ceil(query.per_page),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(sort: Option<&str>) -> ListQuery {
+        ListQuery::from_params(ListParams {
+            page: Some(2),
+            per_page: Some(2),
+            sort: sort.map(str::to_string),
+            search: None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn rejects_out_of_range_pages() {
+        for (page, per_page) in [(Some(0), None), (None, Some(0)), (None, Some(101))] {
+            let params = ListParams {
+                page,
+                per_page,
+                sort: None,
+                search: None,
+            };
+
+            assert_eq!(
+                ListQuery::from_params(params).unwrap_err().status,
+                StatusCode::BAD_REQUEST
+            );
+        }
+    }
+
+    #[test]
+    fn sorts_only_by_allowed_fields() {
+        let fields = [("name", 1), ("tier", 2)];
+
+        assert_eq!(query(None).sort_by(&fields).unwrap(), None);
+        assert_eq!(
+            query(Some("-tier")).sort_by(&fields).unwrap(),
+            Some((2, Order::Desc))
+        );
+        assert!(query(Some("owner")).sort_by(&fields).is_err());
+    }
+
+    #[test]
+    fn paginates_in_memory() {
+        let page = query(None).paginate(vec![1, 2, 3, 4, 5]);
+
+        assert_eq!(page.items, vec![3, 4]);
+        assert_eq!((page.total, page.total_pages), (5, 3));
+    }
+}
@@ -1,22 +1,22 @@
 use crate::AppState;
 use crate::websocket::batched_worker::BatchedWorker;
+use crate::websocket::ingest_queue::{self, Checkpoint, IngestReceiver, IngestSender};
+use crate::websocket::reconcile::{ReconciledTable, diff_known, reconcile_table};
 use crate::websocket::{SpacetimeUpdateMessages, WebSocketMessages};
 use game_module::module_bindings::{PlayerState, PlayerUsernameState};
 use sea_orm::QueryFilter;
 use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, sea_query};
 use std::collections::HashMap;
 use std::time::Duration;
-use tokio::sync::mpsc::{
-    Receiver, Sender, UnboundedReceiver, UnboundedSender, channel, unbounded_channel,
-};
+use tokio::sync::mpsc::{Receiver, Sender, channel};
+use tokio::sync::oneshot;
 
 enum PlayerStateDbOperation {
     Upsert(Vec<::entity::player_state::ActiveModel>),
     Delete(Vec<i64>),
-    DeleteForRegion {
-        ids: Vec<i64>,
-        region: entity::shared::Region,
-    },
+    Commit(Checkpoint),
+    /// Answered once every operation queued before it ran.
+    Flushed(oneshot::Sender<()>),
 }
 
 enum PlayerUsernameStateDbOperation {
@@ -26,11 +26,12 @@ enum PlayerUsernameStateDbOperation {
         ids: Vec<i64>,
         region: entity::shared::Region,
     },
+    Commit(Checkpoint),
 }
 
 pub(crate) struct PlayerStateWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<PlayerState>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<PlayerState>>,
+    rx: IngestReceiver<PlayerState>,
+    tx: IngestSender<PlayerState>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -43,7 +44,7 @@ pub(crate) struct PlayerStateWorker {
 impl PlayerStateWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_player_state_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("player_state");
 
         Self {
             rx,
@@ -88,23 +89,6 @@ impl PlayerStateWorker {
         }
     }
 
-    async fn queue_region_deletes(&self, ids: Vec<i64>, region: entity::shared::Region) {
-        if ids.is_empty() {
-            return;
-        }
-
-        if let Err(error) = self
-            .db_tx
-            .send(PlayerStateDbOperation::DeleteForRegion { ids, region })
-            .await
-        {
-            tracing::error!(
-                error = error.to_string(),
-                "Could not queue PlayerState region deletes"
-            );
-        }
-    }
-
     async fn process_message(&mut self, msg: SpacetimeUpdateMessages<PlayerState>) {
         match msg {
             SpacetimeUpdateMessages::Initial {
@@ -144,87 +128,15 @@ impl PlayerStateWorker {
         data: Vec<PlayerState>,
         database_name: entity::shared::Region,
     ) {
-        let mut local_messages = Vec::with_capacity(self.batch_size + 10);
-        let mut currently_known_player_state = ::entity::player_state::Entity::find()
-            .filter(::entity::player_state::Column::Region.eq(database_name))
-            .all(&self.global_app_state.conn)
-            .await
-            .unwrap_or_else(|error| {
-                tracing::error!(
-                    error = error.to_string(),
-                    "Error while query whole player_state state"
-                );
-                vec![]
-            })
+        let snapshot = data
             .into_iter()
-            .map(|value| (value.entity_id, value))
-            .collect::<HashMap<_, _>>();
-
-        let mut online = 0;
-        let mut offline = 0;
-
-        for mut model in data.into_iter().map(|value| {
-            let model: ::entity::player_state::Model =
+            .map(|value| {
                 ::entity::player_state::ModelBuilder::new(value)
                     .with_region(database_name)
-                    .build();
-
-            if model.signed_in {
-                online += 1;
-            } else {
-                offline += 1;
-            }
-
-            model
-        }) {
-            use std::collections::hash_map::Entry;
-            match currently_known_player_state.entry(model.entity_id) {
-                Entry::Occupied(entry) => {
-                    let existing_model = entry.get();
-                    if &model != existing_model {
-                        if model.sign_in_timestamp == 0 {
-                            model.sign_in_timestamp = existing_model.sign_in_timestamp;
-                        }
-                        self.global_app_state
-                            .player_state
-                            .insert(model.entity_id, model.clone());
-                        self.global_app_state
-                            .ranking_system
-                            .time_played
-                            .update(model.entity_id, model.time_played as i64);
-                        self.global_app_state
-                            .ranking_system
-                            .time_signed_in
-                            .update(model.entity_id, model.time_signed_in as i64);
-                        local_messages.push(model.into_active_model());
-                    }
-                    entry.remove();
-                }
-                Entry::Vacant(_entry) => {
-                    self.global_app_state
-                        .player_state
-                        .insert(model.entity_id, model.clone());
-                    self.global_app_state
-                        .ranking_system
-                        .time_played
-                        .update(model.entity_id, model.time_played as i64);
-                    self.global_app_state
-                        .ranking_system
-                        .time_signed_in
-                        .update(model.entity_id, model.time_signed_in as i64);
-                    local_messages.push(model.into_active_model());
-                }
-            }
-            if local_messages.len() >= self.batch_size {
-                self.queue_upserts(std::mem::take(&mut local_messages))
-                    .await;
-                local_messages = Vec::with_capacity(self.batch_size + 10);
-            }
-        }
-
-        if !local_messages.is_empty() {
-            self.queue_upserts(local_messages).await;
-        }
+                    .build()
+            })
+            .collect::<Vec<_>>();
+        let online = snapshot.iter().filter(|model| model.signed_in).count();
 
         metrics::gauge!(
             "players_current_state",
@@ -233,7 +145,7 @@ impl PlayerStateWorker {
                 ("region", database_name.to_string())
             ]
         )
-        .set(offline);
+        .set((snapshot.len() - online) as f64);
 
         metrics::gauge!(
             "players_current_state",
@@ -242,15 +154,77 @@ impl PlayerStateWorker {
                 ("region", database_name.to_string())
             ]
         )
-        .set(online);
+        .set(online as f64);
+
+        // Batched writes have to land first, or they would bring back rows the snapshot removed
+        self.flush().await;
+        let (flushed, wait) = oneshot::channel();
+        if self
+            .db_tx
+            .send(PlayerStateDbOperation::Flushed(flushed))
+            .await
+            .is_ok()
+        {
+            let _ = wait.await;
+        }
 
-        for chunk_ids in currently_known_player_state
-            .into_keys()
-            .collect::<Vec<_>>()
-            .chunks(1000)
+        let reconciliation = match reconcile_table::<::entity::player_state::Entity>(
+            &self.global_app_state.conn,
+            database_name,
+            snapshot.clone(),
+            &player_state_on_conflict(),
+        )
+        .await
         {
-            self.queue_region_deletes(chunk_ids.to_vec(), database_name)
-                .await;
+            Ok(reconciliation) => reconciliation,
+            Err(error) => {
+                tracing::error!(
+                    error = error.to_string(),
+                    "Could not reconcile player_state"
+                );
+                let known = self
+                    .global_app_state
+                    .player_state
+                    .iter()
+                    .filter(|player_state| player_state.region == database_name)
+                    .map(|player_state| player_state.clone())
+                    .collect::<Vec<_>>();
+                diff_known::<::entity::player_state::Entity>(known, snapshot)
+            }
+        };
+
+        for model in reconciliation.upserted() {
+            self.global_app_state
+                .player_state
+                .insert(model.entity_id, model.clone());
+            self.global_app_state
+                .ranking_system
+                .time_played
+                .update(model.entity_id, model.time_played as i64);
+            self.global_app_state
+                .ranking_system
+                .time_signed_in
+                .update(model.entity_id, model.time_signed_in as i64);
+            let _ = self
+                .global_app_state
+                .tx
+                .send(WebSocketMessages::PlayerState(model.clone()));
+        }
+
+        for model in reconciliation.deleted {
+            self.global_app_state.player_state.remove(&model.entity_id);
+            self.global_app_state
+                .ranking_system
+                .time_played
+                .remove(model.entity_id);
+            self.global_app_state
+                .ranking_system
+                .time_signed_in
+                .remove(model.entity_id);
+            let _ = self
+                .global_app_state
+                .tx
+                .send(WebSocketMessages::PlayerStateDelete(model));
         }
     }
 
@@ -492,11 +466,11 @@ impl PlayerStateWorker {
 impl BatchedWorker for PlayerStateWorker {
     type Entity = PlayerState;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -528,68 +502,59 @@ impl BatchedWorker for PlayerStateWorker {
         self.flush_messages().await;
         self.flush_deletes().await;
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(PlayerStateDbOperation::Commit(checkpoint))
+            .await;
+    }
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_player_state(
     global_app_state: &AppState,
     on_conflict: &sea_query::OnConflict,
     messages: Vec<::entity::player_state::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::player_state::Entity::insert_many(messages)
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting PlayerState: {}", err)
     }
-}
 
-async fn delete_multiple_player_state(global_app_state: &AppState, ids: Vec<i64>) {
-    for chunk_ids in ids.chunks(1000) {
-        let chunk_ids = chunk_ids.to_vec();
-        if let Err(error) = ::entity::player_state::Entity::delete_many()
-            .filter(::entity::player_state::Column::EntityId.is_in(chunk_ids.clone()))
-            .exec(&global_app_state.conn)
-            .await
-        {
-            let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-            tracing::error!(
-                PlayerState = chunk_ids_str.join(","),
-                error = error.to_string(),
-                "Could not delete PlayerState"
-            );
-        }
-    }
+    insert.is_ok()
 }
 
-async fn delete_multiple_player_state_for_region(
-    global_app_state: &AppState,
-    ids: Vec<i64>,
-    region: entity::shared::Region,
-) {
+/// Returns whether every row was deleted.
+async fn delete_multiple_player_state(global_app_state: &AppState, ids: Vec<i64>) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::player_state::Entity::delete_many()
             .filter(::entity::player_state::Column::EntityId.is_in(chunk_ids.clone()))
-            .filter(::entity::player_state::Column::Region.eq(region.clone()))
             .exec(&global_app_state.conn)
             .await
         {
             let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
             tracing::error!(
                 PlayerState = chunk_ids_str.join(","),
-                region,
                 error = error.to_string(),
                 "Could not delete PlayerState"
             );
+            deleted = false;
         }
     }
+
+    deleted
 }
 
-fn start_player_state_db_worker(global_app_state: AppState) -> Sender<PlayerStateDbOperation> {
-    let (tx, mut rx) = channel(5);
-    let on_conflict = sea_query::OnConflict::column(::entity::player_state::Column::EntityId)
+fn player_state_on_conflict() -> sea_query::OnConflict {
+    sea_query::OnConflict::column(::entity::player_state::Column::EntityId)
         .update_columns([
             ::entity::player_state::Column::TimePlayed,
             ::entity::player_state::Column::SessionStartTimestamp,
@@ -600,19 +565,30 @@ fn start_player_state_db_worker(global_app_state: AppState) -> Sender<PlayerStat
             ::entity::player_state::Column::TravelerTasksExpiration,
             ::entity::player_state::Column::Region,
         ])
-        .to_owned();
+        .to_owned()
+}
+
+fn start_player_state_db_worker(global_app_state: AppState) -> Sender<PlayerStateDbOperation> {
+    let (tx, mut rx) = channel(5);
+    let on_conflict = player_state_on_conflict();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                PlayerStateDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 PlayerStateDbOperation::Upsert(messages) => {
-                    insert_multiple_player_state(&global_app_state, &on_conflict, messages).await;
+                    written &=
+                        insert_multiple_player_state(&global_app_state, &on_conflict, messages)
+                            .await;
                 }
                 PlayerStateDbOperation::Delete(ids) => {
-                    delete_multiple_player_state(&global_app_state, ids).await;
+                    written &= delete_multiple_player_state(&global_app_state, ids).await;
                 }
-                PlayerStateDbOperation::DeleteForRegion { ids, region } => {
-                    delete_multiple_player_state_for_region(&global_app_state, ids, region).await;
+                PlayerStateDbOperation::Flushed(flushed) => {
+                    let _ = flushed.send(());
                 }
             }
         }
@@ -622,8 +598,8 @@ fn start_player_state_db_worker(global_app_state: AppState) -> Sender<PlayerStat
 }
 
 pub(crate) struct PlayerUsernameStateWorker {
-    rx: UnboundedReceiver<SpacetimeUpdateMessages<PlayerUsernameState>>,
-    tx: UnboundedSender<SpacetimeUpdateMessages<PlayerUsernameState>>,
+    rx: IngestReceiver<PlayerUsernameState>,
+    tx: IngestSender<PlayerUsernameState>,
     global_app_state: AppState,
     batch_size: usize,
     time_limit: Duration,
@@ -636,7 +612,7 @@ pub(crate) struct PlayerUsernameStateWorker {
 impl PlayerUsernameStateWorker {
     pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
         let db_tx = start_player_username_state_db_worker(global_app_state.clone());
-        let (tx, rx) = unbounded_channel();
+        let (tx, rx) = ingest_queue::channel("player_username_state");
 
         Self {
             rx,
@@ -862,11 +838,11 @@ impl PlayerUsernameStateWorker {
 
 impl BatchedWorker for PlayerUsernameStateWorker {
     type Entity = PlayerUsernameState;
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>> {
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
         &mut self.rx
     }
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>> {
+    fn tx(&self) -> IngestSender<Self::Entity> {
         self.tx.clone()
     }
 
@@ -898,24 +874,37 @@ impl BatchedWorker for PlayerUsernameStateWorker {
         self.flush_messages().await;
         self.flush_deletes().await;
     }
+
+    async fn commit(&mut self) {
+        let checkpoint = self.rx.checkpoint();
+        let _ = self
+            .db_tx
+            .send(PlayerUsernameStateDbOperation::Commit(checkpoint))
+            .await;
+    }
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_player_username_state(
     global_app_state: &AppState,
     on_conflict: &sea_query::OnConflict,
     messages: Vec<::entity::player_username_state::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::player_username_state::Entity::insert_many(messages)
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting PlayerUsernameState: {}", err)
     }
+
+    insert.is_ok()
 }
 
-async fn delete_multiple_player_username_state(global_app_state: &AppState, ids: Vec<i64>) {
+/// Returns whether every row was deleted.
+async fn delete_multiple_player_username_state(global_app_state: &AppState, ids: Vec<i64>) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::player_username_state::Entity::delete_many()
@@ -929,15 +918,20 @@ async fn delete_multiple_player_username_state(global_app_state: &AppState, ids:
                 error = error.to_string(),
                 "Could not delete PlayerUsernameState"
             );
+            deleted = false;
         }
     }
+
+    deleted
 }
 
+/// Returns whether every row was deleted.
 async fn delete_multiple_player_username_state_for_region(
     global_app_state: &AppState,
     ids: Vec<i64>,
     region: entity::shared::Region,
-) {
+) -> bool {
+    let mut deleted = true;
     for chunk_ids in ids.chunks(1000) {
         let chunk_ids = chunk_ids.to_vec();
         if let Err(error) = ::entity::player_username_state::Entity::delete_many()
@@ -953,8 +947,11 @@ async fn delete_multiple_player_username_state_for_region(
                 error = error.to_string(),
                 "Could not delete PlayerUsernameState"
             );
+            deleted = false;
         }
     }
+
+    deleted
 }
 
 fn start_player_username_state_db_worker(
@@ -970,10 +967,14 @@ fn start_player_username_state_db_worker(
             .to_owned();
 
     tokio::spawn(async move {
+        let mut written = true;
         while let Some(operation) = rx.recv().await {
             match operation {
+                PlayerUsernameStateDbOperation::Commit(checkpoint) => {
+                    checkpoint.commit_if(std::mem::replace(&mut written, true))
+                }
                 PlayerUsernameStateDbOperation::Upsert(messages) => {
-                    insert_multiple_player_username_state(
+                    written &= insert_multiple_player_username_state(
                         &global_app_state,
                         &on_conflict,
                         messages,
@@ -981,10 +982,10 @@ fn start_player_username_state_db_worker(
                     .await;
                 }
                 PlayerUsernameStateDbOperation::Delete(ids) => {
-                    delete_multiple_player_username_state(&global_app_state, ids).await;
+                    written &= delete_multiple_player_username_state(&global_app_state, ids).await;
                 }
                 PlayerUsernameStateDbOperation::DeleteForRegion { ids, region } => {
-                    delete_multiple_player_username_state_for_region(
+                    written &= delete_multiple_player_username_state_for_region(
                         &global_app_state,
                         ids,
                         region,
@@ -997,3 +998,32 @@ fn start_player_username_state_db_worker(
 
     tx
 }
+
+impl ReconciledTable for ::entity::player_state::Entity {
+    const NAME: &'static str = "player_state";
+
+    type Key = i64;
+
+    fn key(model: &Self::Model) -> Self::Key {
+        model.entity_id
+    }
+
+    fn key_column() -> Self::Column {
+        ::entity::player_state::Column::EntityId
+    }
+
+    fn region_column() -> Option<Self::Column> {
+        Some(::entity::player_state::Column::Region)
+    }
+
+    fn region(model: &Self::Model) -> Option<entity::shared::Region> {
+        Some(model.region)
+    }
+
+    /// Players that are signed out have no sign in timestamp, keep the last one.
+    fn merge(snapshot: &mut Self::Model, persisted: &Self::Model) {
+        if snapshot.sign_in_timestamp == 0 {
+            snapshot.sign_in_timestamp = persisted.sign_in_timestamp;
+        }
+    }
+}
@@ -1,5 +1,7 @@
 pub(crate) mod bitcraft;
 
+use crate::error::{ApiError, ErrorResponse};
+use crate::pagination::{ListParams, ListQuery, Paginated};
 use crate::{AppRouter, AppState};
 use axum::Router;
 use axum::extract::{Path, Query, State};
@@ -8,12 +10,14 @@ use entity::player_state::TeleportLocation;
 use entity::vault_state_collectibles::VaultStateCollectibleWithDesc;
 use entity::{mobile_entity_state, player_state, player_username_state};
 use log::error;
-use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::sea_query::SimpleExpr;
+use sea_orm::{ColumnTrait, EntityTrait, IntoSimpleExpr, Order, QueryFilter};
 use serde::{Deserialize, Serialize};
 use service::Query as QueryCore;
 use std::collections::HashMap;
 use std::ops::Deref;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new()
@@ -36,7 +40,8 @@ pub(crate) fn get_routes() -> AppRouter {
         )
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct ListPlayersParams {
     page: Option<u64>,
     per_page: Option<u64>,
@@ -44,14 +49,23 @@ pub struct ListPlayersParams {
     online: Option<bool>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct PlayerUsernameStateResponse {
     username_state: HashMap<String, String>,
 }
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/players/all",
+    tag = "players",
+    operation_id = "get_all_players",
+    responses(
+        (status = OK, body = PlayerUsernameStateResponse),
+    )
+)]
 pub(crate) async fn get_all(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<PlayerUsernameStateResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<PlayerUsernameStateResponse>, ApiError> {
     let currently_known_player_username_state = ::entity::player_username_state::Entity::find()
         .all(&state.conn)
         .await
@@ -64,19 +78,23 @@ pub(crate) async fn get_all(
     }))
 }
 
-pub async fn list_players(
-    state: State<AppState>,
-    Query(params): Query<ListPlayersParams>,
-) -> Result<axum_codec::Codec<PlayersResponse>, (StatusCode, &'static str)> {
-    let page = params.page.unwrap_or(1);
-    let posts_per_page = params.per_page.unwrap_or(5);
-    let search = params.search;
-    let online = params.online;
-
+/// A page of players merged with their usernames, and the number of matching players.
+async fn find_merged_players(
+    state: &AppState,
+    page: u64,
+    per_page: u64,
+    search: Option<String>,
+    online: Option<bool>,
+    sort: Option<(SimpleExpr, Order)>,
+) -> Result<(Vec<player_state::PlayerStateMerged>, u64), ApiError> {
     let (player, player_usernames, num_pages) =
-        QueryCore::find_players(&state.conn, page, posts_per_page, search, online)
+        QueryCore::find_players(&state.conn, page, per_page, search, online, sort)
             .await
-            .expect("Cannot find player_state in page");
+            .map_err(|error| {
+                error!("Error: {error}");
+
+                (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+            })?;
 
     let merged_player = player
         .into_iter()
@@ -102,15 +120,95 @@ pub async fn list_players(
         })
         .collect::<Vec<player_state::PlayerStateMerged>>();
 
+    Ok((merged_player, num_pages.number_of_items))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/players",
+    tag = "players",
+    params(ListPlayersParams),
+    responses(
+        (status = OK, body = PlayersResponse),
+    )
+)]
+pub async fn list_players(
+    state: State<AppState>,
+    Query(params): Query<ListPlayersParams>,
+) -> Result<axum_codec::Codec<PlayersResponse>, ApiError> {
+    let page = params.page.unwrap_or(1);
+    let posts_per_page = params.per_page.unwrap_or(5);
+
+    let (players, total) = find_merged_players(
+        &state,
+        page,
+        posts_per_page,
+        params.search,
+        params.online,
+        None,
+    )
+    .await?;
+
     Ok(axum_codec::Codec(PlayersResponse {
-        players: merged_player,
+        players,
         per_page: posts_per_page,
-        total: num_pages.number_of_items,
+        total,
         page,
     }))
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct PlayerFilters {
+    online: Option<bool>,
+}
+
+/// Players sorted by `username` unless `sort` is one of `username`, `time_played` or
+/// `time_signed_in`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/players",
+    tag = "players",
+    params(ListParams, PlayerFilters),
+    responses(
+        (status = OK, body = Paginated<player_state::PlayerStateMerged>),
+        (status = BAD_REQUEST, body = ErrorResponse),
+    )
+)]
+pub(crate) async fn list_players_v1(
+    state: State<AppState>,
+    list: ListQuery,
+    Query(filters): Query<PlayerFilters>,
+) -> Result<axum_codec::Codec<Paginated<player_state::PlayerStateMerged>>, ApiError> {
+    let sort = list.sort_by(&[
+        (
+            "username",
+            player_username_state::Column::Username.into_simple_expr(),
+        ),
+        (
+            "time_played",
+            player_state::Column::TimePlayed.into_simple_expr(),
+        ),
+        (
+            "time_signed_in",
+            player_state::Column::TimeSignedIn.into_simple_expr(),
+        ),
+    ])?;
+
+    let (players, total) = find_merged_players(
+        &state,
+        list.page,
+        list.per_page,
+        list.search.clone(),
+        filters.online,
+        sort,
+    )
+    .await?;
+
+    Ok(axum_codec::Codec(Paginated::new(players, &list, total)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct PlayersResponse {
     pub players: Vec<player_state::PlayerStateMerged>,
@@ -119,7 +217,7 @@ pub struct PlayersResponse {
     pub total: u64,
     pub page: u64,
 }
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct FindPlayerByIdResponse {
     pub teleport_location: TeleportLocation,
@@ -141,17 +239,27 @@ pub(crate) struct FindPlayerByIdResponse {
     pub current_action_state: Option<entity::player_action_state::Model>,
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/players/{id}",
+    tag = "players",
+    params(("id" = i64, Path, description = "Player entity id")),
+    responses(
+        (status = OK, body = FindPlayerByIdResponse),
+        (status = NOT_FOUND, description = "Player not found"),
+    )
+)]
 pub async fn find_player_by_id(
     State(state): State<AppState>,
     Path(id): Path<i64>,
-) -> Result<axum_codec::Codec<FindPlayerByIdResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<FindPlayerByIdResponse>, ApiError> {
     let player = player_state::Entity::find_by_id(id)
         .one(&state.conn)
         .await
         .expect("Cannot find player");
 
     if player.is_none() {
-        return Err((StatusCode::NOT_FOUND, "Player not found"));
+        return Err(ApiError::new(StatusCode::NOT_FOUND, "Player not found"));
     }
 
     let player = player.unwrap();
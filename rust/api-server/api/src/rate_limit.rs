@@ -0,0 +1,272 @@
+use crate::AppState;
+use crate::accounts::AuthenticatedAccount;
+use crate::config::RateLimitConfig;
+use crate::error::ApiError;
+use axum::extract::{
+    ConnectInfo, FromRequestParts, MatchedPath, OptionalFromRequestParts, Request, State,
+};
+use axum::http::request::Parts;
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+struct TokenBucket {
+    tokens: f64,
+    capacity: f64,
+    per_second: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    fn new(capacity: f64, per_second: f64, now: Instant) -> Self {
+        Self {
+            tokens: capacity,
+            capacity,
+            per_second,
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.updated_at = now;
+    }
+
+    /// Takes `cost` tokens, or returns how long until there are enough of them.
+    fn take(&mut self, cost: f64, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+
+        // A route costing more than the whole bucket could never be requested otherwise
+        let cost = cost.min(self.capacity);
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            return Ok(());
+        }
+
+        if self.per_second <= 0.0 {
+            return Err(Duration::MAX);
+        }
+
+        Err(Duration::from_secs_f64(
+            (cost - self.tokens) / self.per_second,
+        ))
+    }
+}
+
+/// Token buckets per client, keyed by account for authenticated requests and by IP otherwise.
+pub(crate) struct RateLimiter {
+    config: RateLimitConfig,
+    buckets: DashMap<String, TokenBucket>,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(config: RateLimitConfig) -> Self {
+        Self {
+            config,
+            buckets: DashMap::new(),
+        }
+    }
+
+    fn cost(&self, route: &str) -> u32 {
+        self.config.route_costs.get(route).copied().unwrap_or(1)
+    }
+
+    fn check(
+        &self,
+        key: String,
+        authenticated: bool,
+        cost: u32,
+        now: Instant,
+    ) -> Result<(), Duration> {
+        let (capacity, per_second) = if authenticated {
+            (
+                self.config.authenticated_burst,
+                self.config.authenticated_per_second,
+            )
+        } else {
+            (self.config.burst, self.config.per_second)
+        };
+
+        self.buckets
+            .entry(key)
+            .or_insert_with(|| TokenBucket::new(capacity as f64, per_second, now))
+            .take(cost as f64, now)
+    }
+
+    /// Checked on top of the route costs, so one IP cannot mass create accounts.
+    pub(crate) fn check_account_creation(&self, ip: &str) -> Result<(), Duration> {
+        if !self.config.enabled {
+            return Ok(());
+        }
+
+        let now = Instant::now();
+        let per_hour = self.config.account_creations_per_hour as f64;
+        self.buckets
+            .entry(format!("account_creation:{ip}"))
+            .or_insert_with(|| TokenBucket::new(per_hour, per_hour / 3600.0, now))
+            .take(1.0, now)
+    }
+
+    /// Forgets buckets that refilled completely, they behave the same as a new one.
+    pub(crate) fn prune(&self) {
+        let now = Instant::now();
+        self.buckets.retain(|_, bucket| {
+            bucket.refill(now);
+            bucket.tokens < bucket.capacity
+        });
+
+        metrics::gauge!("rate_limit_buckets").set(self.buckets.len() as f64);
+    }
+}
+
+pub(crate) fn start_rate_limit_pruning(state: AppState) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(60));
+
+        loop {
+            interval.tick().await;
+            state.rate_limiter.prune();
+        }
+    });
+}
+
+/// The IP the request came from, the same one the rate limiter keys anonymous clients by.
+pub(crate) struct ClientIp(pub(crate) String);
+
+impl FromRequestParts<AppState> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, Infallible> {
+        Ok(Self(client_ip(
+            parts,
+            state.rate_limiter.config.trust_forwarded_for,
+        )))
+    }
+}
+
+fn client_ip(parts: &Parts, trust_forwarded_for: bool) -> String {
+    if trust_forwarded_for
+        && let Some(forwarded_for) = parts.headers.get("x-forwarded-for")
+        && let Ok(forwarded_for) = forwarded_for.to_str()
+        && let Some(ip) = forwarded_for.split(',').next()
+    {
+        return ip.trim().to_string();
+    }
+
+    parts
+        .extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map_or_else(|| "unknown".to_string(), |info| info.0.ip().to_string())
+}
+
+pub(crate) async fn rate_limit(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.rate_limiter.config.enabled {
+        return next.run(request).await;
+    }
+
+    let (mut parts, body) = request.into_parts();
+    let route = parts.extensions.get::<MatchedPath>().map_or_else(
+        || parts.uri.path().to_owned(),
+        |path| path.as_str().to_owned(),
+    );
+
+    let cost = state.rate_limiter.cost(&route);
+    if cost == 0 {
+        return next.run(Request::from_parts(parts, body)).await;
+    }
+
+    // An invalid key or session falls back to the IP, the handler still rejects it if it needs one
+    let account = <AuthenticatedAccount as OptionalFromRequestParts<AppState>>::from_request_parts(
+        &mut parts, &state,
+    )
+    .await
+    .ok()
+    .flatten();
+
+    let (key, key_type) = match &account {
+        Some(account) => (format!("account:{}", account.account.id), "account"),
+        None => (
+            format!(
+                "ip:{}",
+                client_ip(&parts, state.rate_limiter.config.trust_forwarded_for)
+            ),
+            "ip",
+        ),
+    };
+    let result = state
+        .rate_limiter
+        .check(key, account.is_some(), cost, Instant::now());
+
+    if let Some(account) = account {
+        parts.extensions.insert(account);
+    }
+
+    match result {
+        Ok(()) => {
+            metrics::counter!("rate_limit_requests_total", "outcome" => "allowed", "key_type" => key_type)
+                .increment(1);
+
+            next.run(Request::from_parts(parts, body)).await
+        }
+        Err(retry_after) => {
+            metrics::counter!("rate_limit_requests_total", "outcome" => "limited", "key_type" => key_type)
+                .increment(1);
+            metrics::counter!("rate_limit_limited_total", "path" => route).increment(1);
+
+            let retry_after = retry_after.as_secs_f64().ceil().min(u32::MAX as f64) as u32;
+            let mut response =
+                ApiError::new(StatusCode::TOO_MANY_REQUESTS, "Too many requests").into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after.max(1)));
+
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bucket_refills_over_time() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, 1.0, now);
+
+        assert!(bucket.take(2.0, now).is_ok());
+        assert_eq!(bucket.take(1.0, now), Err(Duration::from_secs(1)));
+        assert!(bucket.take(1.0, now + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn routes_cost_at_most_the_whole_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(5.0, 1.0, now);
+
+        assert!(bucket.take(20.0, now).is_ok());
+        assert_eq!(bucket.take(20.0, now), Err(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn account_creations_are_limited_per_ip() {
+        let limiter = RateLimiter::new(RateLimitConfig {
+            account_creations_per_hour: 2,
+            ..RateLimitConfig::default()
+        });
+
+        assert!(limiter.check_account_creation("10.0.0.1").is_ok());
+        assert!(limiter.check_account_creation("10.0.0.1").is_ok());
+        assert!(limiter.check_account_creation("10.0.0.1").is_err());
+        assert!(limiter.check_account_creation("10.0.0.2").is_ok());
+    }
+}
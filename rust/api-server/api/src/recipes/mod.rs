@@ -1,17 +1,31 @@
+pub(crate) mod planner;
+
+use crate::desc_cache::{CachedRequest, DescTable};
+use crate::error::ApiError;
+use crate::recipes::planner::{CraftingPlan, CraftingPlanner, MAX_PLAN_QUANTITY};
 use crate::{AppRouter, AppState};
 use axum::Router;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::StatusCode;
+use axum::response::Response;
+use axum::routing::get;
+use entity::inventory::ItemType;
 use entity::{cargo_desc, crafting_recipe, item_desc, item_list_desc};
+use log::error;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use service::Query as QueryCore;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
-    Router::new().route("/recipes/get_all", axum_codec::routing::get(get_all).into())
+    Router::new()
+        .route("/recipes/get_all", get(get_all))
+        .route("/recipes/plan", axum_codec::routing::get(get_plan).into())
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct RecipesAllResponse {
     recipes: HashMap<i32, crafting_recipe::Model>,
@@ -19,29 +33,179 @@ pub(crate) struct RecipesAllResponse {
     item_desc: HashMap<i32, item_desc::Model>,
     item_list_desc: HashMap<i32, item_list_desc::Model>,
 }
+#[utoipa::path(
+    get,
+    path = "/recipes/get_all",
+    tag = "recipes",
+    operation_id = "get_all_recipes",
+    responses(
+        (status = OK, body = RecipesAllResponse),
+        (status = NOT_MODIFIED, description = "The `If-None-Match` ETag is still current"),
+    )
+)]
 pub(crate) async fn get_all(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<RecipesAllResponse>, (StatusCode, &'static str)> {
-    Ok(axum_codec::Codec(RecipesAllResponse {
-        recipes: state
-            .crafting_recipe_desc
-            .iter()
-            .map(|value| (*value.key(), value.clone()))
-            .collect(),
-        cargo_desc: state
-            .cargo_desc
-            .iter()
-            .map(|value| (*value.key(), value.clone()))
-            .collect(),
-        item_desc: state
-            .item_desc
-            .iter()
-            .map(|value| (*value.key(), value.clone()))
-            .collect(),
-        item_list_desc: state
-            .item_list_desc
-            .iter()
-            .map(|value| (*value.key(), value.clone()))
-            .collect(),
-    }))
+    request: CachedRequest,
+) -> Result<Response, ApiError> {
+    const TABLES: &[DescTable] = &[
+        DescTable::CraftingRecipeDesc,
+        DescTable::CargoDesc,
+        DescTable::ItemDesc,
+        DescTable::ItemListDesc,
+    ];
+
+    state
+        .desc_cache
+        .respond(request, TABLES, || async {
+            Ok(RecipesAllResponse {
+                recipes: state
+                    .crafting_recipe_desc
+                    .iter()
+                    .map(|value| (*value.key(), value.clone()))
+                    .collect(),
+                cargo_desc: state
+                    .cargo_desc
+                    .iter()
+                    .map(|value| (*value.key(), value.clone()))
+                    .collect(),
+                item_desc: state
+                    .item_desc
+                    .iter()
+                    .map(|value| (*value.key(), value.clone()))
+                    .collect(),
+                item_list_desc: state
+                    .item_list_desc
+                    .iter()
+                    .map(|value| (*value.key(), value.clone()))
+                    .collect(),
+            })
+        })
+        .await
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct CraftingPlanParams {
+    pub item_id: i32,
+    pub item_type: Option<ItemType>,
+    pub quantity: Option<i64>,
+    /// Comma separated recipe ids to use whenever an item has alternative recipes.
+    pub prefer: Option<String>,
+}
+
+pub(crate) fn parse_preferred_recipes(prefer: Option<&str>) -> Option<HashSet<i32>> {
+    let Some(prefer) = prefer else {
+        return Some(HashSet::new());
+    };
+
+    prefer
+        .split(',')
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(|value| value.parse().ok())
+        .collect()
+}
+
+/// The planner for the current descriptions, it is only rebuilt once one of them changed.
+#[derive(Default)]
+pub(crate) struct CraftingPlannerCache {
+    planner: tokio::sync::Mutex<Option<(Vec<u64>, Arc<CraftingPlanner>)>>,
+}
+
+const PLANNER_TABLES: &[DescTable] = &[
+    DescTable::CraftingRecipeDesc,
+    DescTable::ExtractionRecipeDesc,
+    DescTable::ItemDesc,
+    DescTable::ItemListDesc,
+];
+
+pub(crate) async fn crafting_planner(state: &AppState) -> Result<Arc<CraftingPlanner>, ApiError> {
+    // Held while building, so concurrent requests wait for one rebuild instead of each doing one
+    let mut cached = state.crafting_planner.planner.lock().await;
+    let versions = state.desc_cache.versions(PLANNER_TABLES);
+
+    if let Some((_, planner)) = cached
+        .as_ref()
+        .filter(|(cached_versions, _)| *cached_versions == versions)
+    {
+        return Ok(planner.clone());
+    }
+
+    let planner = Arc::new(build_crafting_planner(state).await?);
+    *cached = Some((versions, planner.clone()));
+
+    Ok(planner)
+}
+
+async fn build_crafting_planner(state: &AppState) -> Result<CraftingPlanner, ApiError> {
+    let extraction_recipes = QueryCore::all_extraction_recipe_desc(&state.conn)
+        .await
+        .map_err(|error| {
+            error!("Error loading extraction recipes: {error}");
+
+            (StatusCode::INTERNAL_SERVER_ERROR, "Unexpected error")
+        })?;
+
+    let item_desc: HashMap<i32, item_desc::Model> = state
+        .item_desc
+        .iter()
+        .map(|value| (*value.key(), value.clone()))
+        .collect();
+    let item_list_desc: HashMap<i32, item_list_desc::Model> = state
+        .item_list_desc
+        .iter()
+        .map(|value| (*value.key(), value.clone()))
+        .collect();
+
+    Ok(CraftingPlanner::new(
+        state.crafting_recipe_desc.iter().map(|value| value.clone()),
+        extraction_recipes,
+        &item_desc,
+        &item_list_desc,
+    ))
+}
+
+#[utoipa::path(
+    get,
+    path = "/recipes/plan",
+    tag = "recipes",
+    params(CraftingPlanParams),
+    responses(
+        (status = OK, body = CraftingPlan),
+        (status = BAD_REQUEST, description = "Invalid quantity or recipe id"),
+        (status = NOT_FOUND, description = "Item not found"),
+    )
+)]
+pub(crate) async fn get_plan(
+    state: State<AppState>,
+    Query(params): Query<CraftingPlanParams>,
+) -> Result<axum_codec::Codec<CraftingPlan>, ApiError> {
+    let quantity = params.quantity.unwrap_or(1);
+    if !(1..=MAX_PLAN_QUANTITY).contains(&quantity) {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "Quantity must be between 1 and 1000000",
+        ));
+    }
+
+    let item_type = params.item_type.unwrap_or(ItemType::Item);
+    let known_item = match item_type {
+        ItemType::Item => state.item_desc.contains_key(&params.item_id),
+        ItemType::Cargo => state.cargo_desc.contains_key(&params.item_id),
+    };
+    if !known_item {
+        return Err(ApiError::new(StatusCode::NOT_FOUND, "Item not found"));
+    }
+
+    let preferred_recipes = parse_preferred_recipes(params.prefer.as_deref())
+        .ok_or((StatusCode::BAD_REQUEST, "Invalid recipe id"))?;
+
+    let planner = crafting_planner(&state).await?;
+
+    Ok(axum_codec::Codec(planner.plan(
+        item_type,
+        params.item_id,
+        quantity,
+        &preferred_recipes,
+    )))
 }
@@ -0,0 +1,548 @@
+use entity::crafting_recipe::{BuildingRequirement, LevelRequirement, ToolRequirement};
+use entity::inventory::ItemType;
+use entity::{crafting_recipe, extraction_recipe_desc, item_desc, item_list_desc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+const MAX_DEPTH: usize = 32;
+/// The most of an item a single plan resolves, every quantity below it is derived from it.
+pub(crate) const MAX_PLAN_QUANTITY: i64 = 1_000_000;
+
+type ItemKey = (ItemType, i32);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) enum CraftingPlanSource {
+    /// Crafted with `recipe_id`.
+    Recipe,
+    /// Gathered through one of the `extraction_recipe_ids`.
+    Extraction,
+    /// Neither crafted nor extracted, it has to be bought or looted.
+    Raw,
+    /// Every recipe for this item needs one of its own ancestors.
+    Cycle,
+    /// The tree got deeper than the planner is willing to go.
+    TooDeep,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct CraftingPlanNode {
+    pub item_id: i32,
+    pub item_type: ItemType,
+    pub quantity: i64,
+    pub source: CraftingPlanSource,
+    pub recipe_id: Option<i32>,
+    pub alternative_recipe_ids: Vec<i32>,
+    pub extraction_recipe_ids: Vec<i32>,
+    pub crafts: i64,
+    pub surplus: i64,
+    #[schema(no_recursion)]
+    pub children: Vec<CraftingPlanNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct CraftingPlanMaterial {
+    pub item_id: i32,
+    pub item_type: ItemType,
+    pub quantity: i64,
+    pub source: CraftingPlanSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct CraftingPlan {
+    pub tree: CraftingPlanNode,
+    pub raw_materials: Vec<CraftingPlanMaterial>,
+    pub total_stamina: f32,
+    pub total_time: f32,
+    pub tools: Vec<ToolRequirement>,
+    pub buildings: Vec<BuildingRequirement>,
+    pub skills: Vec<LevelRequirement>,
+    pub recipe_ids: Vec<i32>,
+}
+
+/// Amount of an item a single craft of a recipe yields, including what the crafted item lists
+/// unpack into when they only have a single possibility.
+struct RecipeOutput {
+    recipe_id: i32,
+    quantity: i64,
+}
+
+struct ExtractionOutput {
+    extraction_recipe_id: i32,
+    expected_quantity: f32,
+}
+
+/// Indexes the recipe descriptions by what they produce so that a plan can be resolved
+/// without scanning every recipe for every node.
+pub(crate) struct CraftingPlanner {
+    recipes: HashMap<i32, crafting_recipe::Model>,
+    extraction_recipes: HashMap<i32, extraction_recipe_desc::Model>,
+    producers: HashMap<ItemKey, Vec<RecipeOutput>>,
+    extractors: HashMap<ItemKey, Vec<ExtractionOutput>>,
+}
+
+impl CraftingPlanner {
+    pub(crate) fn new(
+        recipes: impl IntoIterator<Item = crafting_recipe::Model>,
+        extraction_recipes: impl IntoIterator<Item = extraction_recipe_desc::Model>,
+        item_desc: &HashMap<i32, item_desc::Model>,
+        item_list_desc: &HashMap<i32, item_list_desc::Model>,
+    ) -> Self {
+        let recipes: HashMap<i32, crafting_recipe::Model> = recipes
+            .into_iter()
+            .map(|recipe| (recipe.id, recipe))
+            .collect();
+        let extraction_recipes: HashMap<i32, extraction_recipe_desc::Model> = extraction_recipes
+            .into_iter()
+            .map(|recipe| (recipe.id, recipe))
+            .collect();
+
+        let mut producers: HashMap<ItemKey, Vec<RecipeOutput>> = HashMap::new();
+        for recipe in recipes.values() {
+            let mut outputs: HashMap<ItemKey, i64> = HashMap::new();
+            for stack in &recipe.crafted_item_stacks {
+                *outputs
+                    .entry((stack.item_type.clone(), stack.item_id))
+                    .or_default() += stack.quantity as i64;
+
+                if stack.item_type != ItemType::Item {
+                    continue;
+                }
+
+                let Some(item_list) = item_desc
+                    .get(&stack.item_id)
+                    .filter(|item| item.item_list_id != 0)
+                    .and_then(|item| item_list_desc.get(&item.item_list_id))
+                else {
+                    continue;
+                };
+
+                if let [possibility] = item_list.possibilities.as_slice() {
+                    for item in &possibility.items {
+                        *outputs
+                            .entry((item.item_type.clone(), item.item_id))
+                            .or_default() += item.quantity as i64 * stack.quantity as i64;
+                    }
+                }
+            }
+
+            for (key, quantity) in outputs {
+                if quantity > 0 {
+                    producers.entry(key).or_default().push(RecipeOutput {
+                        recipe_id: recipe.id,
+                        quantity,
+                    });
+                }
+            }
+        }
+        for outputs in producers.values_mut() {
+            outputs.sort_by_key(|output| output.recipe_id);
+        }
+
+        let mut extractors: HashMap<ItemKey, Vec<ExtractionOutput>> = HashMap::new();
+        for recipe in extraction_recipes.values() {
+            for stack in &recipe.extracted_item_stacks {
+                let Some(item_stack) = &stack.item_stack else {
+                    continue;
+                };
+
+                let expected_quantity = item_stack.quantity as f32 * stack.probability;
+                if expected_quantity <= 0.0 {
+                    continue;
+                }
+
+                extractors
+                    .entry((item_stack.item_type.clone(), item_stack.item_id))
+                    .or_default()
+                    .push(ExtractionOutput {
+                        extraction_recipe_id: recipe.id,
+                        expected_quantity,
+                    });
+            }
+        }
+        for outputs in extractors.values_mut() {
+            outputs.sort_by_key(|output| output.extraction_recipe_id);
+        }
+
+        Self {
+            recipes,
+            extraction_recipes,
+            producers,
+            extractors,
+        }
+    }
+
+    /// Resolves the full crafting tree for `quantity` of the given item.
+    ///
+    /// `preferred_recipes` picks a recipe whenever an item can be made in more than one way, items
+    /// without a preference are extracted when possible and otherwise use the lowest recipe id.
+    pub(crate) fn plan(
+        &self,
+        item_type: ItemType,
+        item_id: i32,
+        quantity: i64,
+        preferred_recipes: &HashSet<i32>,
+    ) -> CraftingPlan {
+        let mut totals = PlanTotals::default();
+        let mut path = Vec::new();
+
+        let tree = self.resolve(
+            (item_type, item_id),
+            quantity,
+            preferred_recipes,
+            &mut path,
+            &mut totals,
+        );
+
+        let mut raw_materials: Vec<CraftingPlanMaterial> = totals
+            .raw_materials
+            .into_iter()
+            .map(
+                |((item_type, item_id), (quantity, source))| CraftingPlanMaterial {
+                    item_id,
+                    item_type,
+                    quantity,
+                    source,
+                },
+            )
+            .collect();
+        raw_materials
+            .sort_by_key(|material| (material.item_type == ItemType::Cargo, material.item_id));
+
+        CraftingPlan {
+            tree,
+            raw_materials,
+            total_stamina: totals.stamina,
+            total_time: totals.time,
+            tools: totals
+                .tools
+                .into_iter()
+                .map(|(tool_type, (level, power))| ToolRequirement {
+                    tool_type,
+                    level,
+                    power,
+                })
+                .collect(),
+            buildings: totals
+                .buildings
+                .into_iter()
+                .map(|(building_type, tier)| BuildingRequirement {
+                    building_type,
+                    tier,
+                })
+                .collect(),
+            skills: totals
+                .skills
+                .into_iter()
+                .map(|(skill_id, level)| LevelRequirement { skill_id, level })
+                .collect(),
+            recipe_ids: totals.recipe_ids.into_iter().collect(),
+        }
+    }
+
+    fn resolve(
+        &self,
+        key: ItemKey,
+        quantity: i64,
+        preferred_recipes: &HashSet<i32>,
+        path: &mut Vec<ItemKey>,
+        totals: &mut PlanTotals,
+    ) -> CraftingPlanNode {
+        let (item_type, item_id) = key.clone();
+        let mut node = CraftingPlanNode {
+            item_id,
+            item_type,
+            quantity,
+            source: CraftingPlanSource::Raw,
+            recipe_id: None,
+            alternative_recipe_ids: vec![],
+            extraction_recipe_ids: vec![],
+            crafts: 0,
+            surplus: 0,
+            children: vec![],
+        };
+
+        let producers = self
+            .producers
+            .get(&key)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let extractors = self
+            .extractors
+            .get(&key)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+
+        node.alternative_recipe_ids = producers.iter().map(|output| output.recipe_id).collect();
+        node.extraction_recipe_ids = extractors
+            .iter()
+            .map(|output| output.extraction_recipe_id)
+            .collect();
+
+        if path.len() >= MAX_DEPTH {
+            node.source = CraftingPlanSource::TooDeep;
+            totals.add_material(&node);
+            return node;
+        }
+
+        let preferred = producers
+            .iter()
+            .find(|output| preferred_recipes.contains(&output.recipe_id));
+
+        if preferred.is_none() && !extractors.is_empty() {
+            node.source = CraftingPlanSource::Extraction;
+            self.add_extraction_costs(&extractors[0], quantity, totals);
+            totals.add_material(&node);
+            return node;
+        }
+
+        path.push(key);
+        let chosen = preferred
+            .into_iter()
+            .chain(producers.iter())
+            .find(|output| {
+                self.recipes.get(&output.recipe_id).is_some_and(|recipe| {
+                    !recipe
+                        .consumed_item_stacks
+                        .iter()
+                        .any(|stack| path.contains(&(stack.item_type.clone(), stack.item_id)))
+                })
+            });
+
+        let Some(chosen) = chosen else {
+            path.pop();
+            if !producers.is_empty() {
+                node.source = CraftingPlanSource::Cycle;
+            }
+            totals.add_material(&node);
+            return node;
+        };
+
+        let recipe = &self.recipes[&chosen.recipe_id];
+        // Quantities multiply with every level of the tree, none of this may overflow
+        let crafts = quantity / chosen.quantity + i64::from(quantity % chosen.quantity != 0);
+
+        node.source = CraftingPlanSource::Recipe;
+        node.recipe_id = Some(recipe.id);
+        node.crafts = crafts;
+        node.surplus = crafts
+            .checked_mul(chosen.quantity)
+            .and_then(|produced| produced.checked_sub(quantity))
+            .unwrap_or(0);
+
+        totals.add_recipe(recipe, crafts);
+
+        for stack in &recipe.consumed_item_stacks {
+            let chance = stack.consumption_chance.clamp(0.0, 1.0);
+            // Float to int casts saturate
+            let needed = (stack.quantity as f64 * crafts as f64 * chance as f64).ceil() as i64;
+            if needed <= 0 {
+                continue;
+            }
+
+            node.children.push(self.resolve(
+                (stack.item_type.clone(), stack.item_id),
+                needed,
+                preferred_recipes,
+                path,
+                totals,
+            ));
+        }
+        path.pop();
+
+        node
+    }
+
+    fn add_extraction_costs(
+        &self,
+        output: &ExtractionOutput,
+        quantity: i64,
+        totals: &mut PlanTotals,
+    ) {
+        let Some(recipe) = self.extraction_recipes.get(&output.extraction_recipe_id) else {
+            return;
+        };
+
+        let actions = (quantity as f32 / output.expected_quantity).ceil();
+        totals.stamina += recipe.stamina_requirement * actions;
+        totals.time += recipe.time_requirement * actions;
+        for tool in &recipe.tool_requirements {
+            totals.add_tool(tool);
+        }
+    }
+}
+
+#[derive(Default)]
+struct PlanTotals {
+    raw_materials: HashMap<ItemKey, (i64, CraftingPlanSource)>,
+    stamina: f32,
+    time: f32,
+    tools: BTreeMap<i32, (i32, i32)>,
+    buildings: BTreeMap<i32, i32>,
+    skills: BTreeMap<i32, i32>,
+    recipe_ids: BTreeSet<i32>,
+}
+
+impl PlanTotals {
+    fn add_material(&mut self, node: &CraftingPlanNode) {
+        let material = self
+            .raw_materials
+            .entry((node.item_type.clone(), node.item_id))
+            .or_insert((0, node.source));
+        material.0 = material.0.saturating_add(node.quantity);
+    }
+
+    fn add_recipe(&mut self, recipe: &crafting_recipe::Model, crafts: i64) {
+        let actions = recipe.actions_required.max(1) as f32 * crafts as f32;
+        self.stamina += recipe.stamina_requirement * actions;
+        self.time += recipe.time_requirement * actions;
+        self.recipe_ids.insert(recipe.id);
+
+        for tool in &recipe.tool_requirements {
+            self.add_tool(tool);
+        }
+        if let Some(building) = &recipe.building_requirement {
+            let tier = self.buildings.entry(building.building_type).or_default();
+            *tier = (*tier).max(building.tier);
+        }
+        for skill in &recipe.level_requirements {
+            let level = self.skills.entry(skill.skill_id).or_default();
+            *level = (*level).max(skill.level);
+        }
+    }
+
+    fn add_tool(&mut self, tool: &ToolRequirement) {
+        let (level, power) = self.tools.entry(tool.tool_type).or_default();
+        *level = (*level).max(tool.level);
+        *power = (*power).max(tool.power);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use entity::crafting_recipe::ConsumedItemStack;
+    use entity::shared::item_stack::ItemStack;
+
+    fn recipe(id: i32, consumed: &[(i32, i32)], crafted: &[(i32, i32)]) -> crafting_recipe::Model {
+        crafting_recipe::Model {
+            id,
+            name: format!("recipe {id}"),
+            time_requirement: 1.0,
+            stamina_requirement: 1.0,
+            tool_durability_lost: 0,
+            building_requirement: None,
+            level_requirements: vec![],
+            tool_requirements: vec![],
+            consumed_item_stacks: consumed
+                .iter()
+                .map(|(item_id, quantity)| ConsumedItemStack {
+                    item_id: *item_id,
+                    quantity: *quantity,
+                    item_type: ItemType::Item,
+                    discovery_score: 0,
+                    consumption_chance: 1.0,
+                })
+                .collect(),
+            discovery_triggers: vec![],
+            required_knowledges: vec![],
+            required_claim_tech_id: 0,
+            full_discovery_score: 0,
+            experience_per_progress: vec![],
+            allow_use_hands: true,
+            crafted_item_stacks: crafted
+                .iter()
+                .map(|(item_id, quantity)| ItemStack {
+                    item_id: *item_id,
+                    quantity: *quantity,
+                    item_type: ItemType::Item,
+                    durability: None,
+                })
+                .collect(),
+            is_passive: false,
+            actions_required: 1,
+            tool_mesh_index: 0,
+            recipe_performance_id: 0,
+        }
+    }
+
+    fn planner_with(recipes: Vec<crafting_recipe::Model>) -> CraftingPlanner {
+        CraftingPlanner::new(recipes, vec![], &HashMap::new(), &HashMap::new())
+    }
+
+    fn raw_quantity(plan: &CraftingPlan, item_id: i32) -> Option<(i64, CraftingPlanSource)> {
+        plan.raw_materials
+            .iter()
+            .find(|material| material.item_id == item_id)
+            .map(|material| (material.quantity, material.source))
+    }
+
+    #[test]
+    fn crafts_round_up_and_report_the_surplus() {
+        // 3 planks from 2 logs
+        let planner = planner_with(vec![recipe(1, &[(10, 2)], &[(20, 3)])]);
+
+        let plan = planner.plan(ItemType::Item, 20, 7, &HashSet::new());
+
+        assert_eq!(plan.tree.source, CraftingPlanSource::Recipe);
+        assert_eq!(plan.tree.crafts, 3);
+        assert_eq!(plan.tree.surplus, 2);
+        assert_eq!(raw_quantity(&plan, 10), Some((6, CraftingPlanSource::Raw)));
+    }
+
+    #[test]
+    fn multi_output_recipes_count_every_output() {
+        // One craft yields both a plank and sawdust
+        let planner = planner_with(vec![recipe(1, &[(10, 1)], &[(20, 2), (21, 5)])]);
+
+        let planks = planner.plan(ItemType::Item, 20, 4, &HashSet::new());
+        let sawdust = planner.plan(ItemType::Item, 21, 4, &HashSet::new());
+
+        assert_eq!((planks.tree.crafts, planks.tree.surplus), (2, 0));
+        assert_eq!((sawdust.tree.crafts, sawdust.tree.surplus), (1, 1));
+        assert_eq!(planks.recipe_ids, vec![1]);
+    }
+
+    #[test]
+    fn cycles_fall_back_to_other_recipes_or_stop() {
+        // 20 can be made from 21 and 21 from 20, only 20 also has a recipe from a raw item
+        let planner = planner_with(vec![
+            recipe(1, &[(21, 1)], &[(20, 1)]),
+            recipe(2, &[(20, 1)], &[(21, 1)]),
+            recipe(3, &[(10, 1)], &[(20, 1)]),
+        ]);
+
+        let plan = planner.plan(ItemType::Item, 21, 1, &HashSet::new());
+        let child = &plan.tree.children[0];
+        assert_eq!(plan.tree.recipe_id, Some(2));
+        assert_eq!(child.recipe_id, Some(3));
+        assert_eq!(raw_quantity(&plan, 10), Some((1, CraftingPlanSource::Raw)));
+
+        let planner = planner_with(vec![
+            recipe(1, &[(21, 1)], &[(20, 1)]),
+            recipe(2, &[(20, 1)], &[(21, 1)]),
+        ]);
+        let plan = planner.plan(ItemType::Item, 21, 1, &HashSet::new());
+        assert_eq!(plan.tree.children[0].source, CraftingPlanSource::Cycle);
+    }
+
+    #[test]
+    fn huge_quantities_saturate_instead_of_overflowing() {
+        let planner = planner_with(vec![
+            recipe(1, &[(10, i32::MAX)], &[(20, 1)]),
+            recipe(2, &[(20, i32::MAX)], &[(30, 1)]),
+        ]);
+
+        let plan = planner.plan(ItemType::Item, 30, MAX_PLAN_QUANTITY, &HashSet::new());
+
+        assert_eq!(
+            raw_quantity(&plan, 10).map(|(quantity, _)| quantity),
+            Some(i64::MAX)
+        );
+    }
+}
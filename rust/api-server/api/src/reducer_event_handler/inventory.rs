@@ -1,295 +1,102 @@
-// use entity::inventory_changelog::Model as InventoryChangelogModel;
+use entity::inventory_changelog::ChangeCause;
 
-// #[allow(dead_code)]
-// fn get_cargo_pick_up_change(
-//     transaction: &TransactionUpdate,
-//     _user_id: Option<i64>,
-// ) -> Option<InventoryChangelogModel> {
-//     if transaction.reducer_call.reducer_name.as_ref() != "cargo_pick_up" {
-//         return None;
-//     }
-//
-//     None
-// }
-//
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//     use crate::websocket::TransactionUpdate;
-//
-//     #[test]
-//     fn handle_cargo_pick_up() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107532,[[6000,{\"0\":[1694138707,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[101348134,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080001,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2080018,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3140008,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1966633278,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080017,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2080002,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2080001,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2070004,7,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107529,0]"}],"inserts":[{"Text":"[19107532,[[6000,{\"0\":[1694138707,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[101348134,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080001,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2080018,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3140008,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1966633278,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080017,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2080002,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2080001,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2070004,7,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,19107529,0]"}]}]}},"timestamp":{"microseconds":1733689251424014},"caller_identity":{"__identity_bytes":"68a275bbc4f8c25adc6240fa16ebd28c8cc86ac6fea338c5e2497ed7f5b1f469"},"caller_address":{"__address_bytes":"1e7c3ed79a57891b689110daa5466110"},"reducer_call":{"reducer_name":"cargo_pick_up","reducer_id":50,"args":{"Text":"[[48799045]]"},"request_id":7427},"energy_quanta_used":{"quanta":1079163615},"host_execution_duration_micros":1107}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_barter_stall_order_accept() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108686,[[6000,{\"0\":[2170009,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2044491604,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[828731292,1,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"0\":[5,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,112,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108683,0]"},{"Text":"[19108685,[[6000,{\"0\":[1,8052,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],2,15,19108683,0]"}],"inserts":[{"Text":"[19108685,[[6000,{\"0\":[1,3052,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],2,15,19108683,0]"},{"Text":"[19108686,[[6000,{\"0\":[2170009,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2044491604,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[828731292,1,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"0\":[5,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,112,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[555274167,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108683,0]"}]}]}},"timestamp":{"microseconds":1733688404310616},"caller_identity":{"__identity_bytes":"807eb14cf2c6cdcf0c5d00d23c3a330446c885621729ac5952cd130caf5a70a2"},"caller_address":{"__address_bytes":"49a6c9fcdb6b64337131d2fb50ac9bae"},"reducer_call":{"reducer_name":"barter_stall_order_accept","reducer_id":35,"args":{"Text":"[[48748848,1688228]]"},"request_id":975},"energy_quanta_used":{"quanta":44093793615},"host_execution_duration_micros":6842}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_building_deconstruct() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19237960,[[6000,{\"0\":[1170019,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2050003,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3070004,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2130003,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1814625731,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2050002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2050001,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[362614434,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[362614434,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19237957,0]"}],"inserts":[{"Text":"[19237960,[[6000,{\"0\":[1170019,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2050003,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3070004,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2130003,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1814625731,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2050002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2050001,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[362614434,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[362614434,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1002,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,19237957,0]"}]},{"table_id":4318,"table_name":"BuildingState","deletes":[{"Text":"[33075705,23609682,4,1162978214,19237957,\"\"]"}],"inserts":[]}]}},"timestamp":{"microseconds":1733687710839034},"caller_identity":{"__identity_bytes":"752ce133766fee7086ef6bc80a6312fff5c719b79f14c024679dc9eb8aa88a51"},"caller_address":{"__address_bytes":"78d35ecedbcf9853c29727bb08477b20"},"reducer_call":{"reducer_name":"building_deconstruct","reducer_id":41,"args":{"Text":"[[33075705]]"},"request_id":8191},"energy_quanta_used":{"quanta":1228085615},"host_execution_duration_micros":1250}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_cargo_spawn() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19258841,[[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170017,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170017,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1990669797,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[6,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1990669797,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1000,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,19258838,0]"}],"inserts":[{"Text":"[19258841,[[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170017,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170017,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1990669797,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[6,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1990669797,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19258838,0]"}]}]}},"timestamp":{"microseconds":1733688994046718},"caller_identity":{"__identity_bytes":"c5b7b5a0fc495d24f93ba21b69983c4f5c242aa98fb03131033c9dba107bedb0"},"caller_address":{"__address_bytes":"a0dbc0b1bc3cfa57eef9aaddf105b8ad"},"reducer_call":{"reducer_name":"cargo_spawn","reducer_id":52,"args":{"Text":"[[[4106,6087,1],0,0,0,0]]"},"request_id":11837},"energy_quanta_used":{"quanta":1700965615},"host_execution_duration_micros":1193}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_claim_resupply() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4127,"table_name":"ClaimDescriptionState","deletes":[{"Text":"[19935594,19163301,19935585,\"Shovel\",2179,0.0,[[19163301,\"ManlyMen\",true,true,true,true],[19107589,\"pastassium\",true,true,true,true],[19115284,\"Grayson\",true,true,true,true],[19259967,\"Crab\",true,true,true,false],[19162652,\"Zixen\",true,false,false,false],[19260021,\"Axie\",true,true,true,false],[19108518,\"Arteliese\",true,true,true,true],[19237979,\"Daka\",true,true,true,true],[19108547,\"ZeizhE\",true,true,true,true],[19474584,\"Kyle\",true,false,false,false],[19107511,\"Avery\",true,false,false,false],[19343463,\"Farrowe\",true,false,false,false],[19107709,\"JT\",true,false,false,false],[19631546,\"bhtmann\",true,false,false,false],[20678355,\"Kohlan\",true,false,false,false],[19259759,\"Yuura\",false,false,false,false],[21859415,\"Prim\",true,true,true,false],[19338202,\"Ambussing\",true,false,false,false],[19343433,\"PrimeThyme\",true,true,true,true],[19744809,\"Wulfgar\",true,false,false,false],[19107499,\"SpamWingDuck\",true,false,false,false],[19191706,\"Seryn\",true,true,false,false],[23007002,\"Bris\",true,false,false,false],[23691632,\"AmberGoldHeart\",false,false,false,false],[19107217,\"Thorne\",true,false,false,false],[19237643,\"Athelle\",true,true,false,false],[27652990,\"Jynxyy\",true,true,false,false],[23017634,\"Hershey\",true,true,true,false],[19631608,\"Greywar\",true,false,false,false],[19107438,\"Karthsiden\",true,true,false,false],[33708932,\"DeckleBall\",true,false,false,false],[36940568,\"GraysonSon\",true,true,true,false],[28483004,\"Allusion\",true,false,false,false],[28723754,\"Otaku40k\",true,false,false,false],[19107247,\"gon\",true,false,false,false]],1469,8444,0,false,{\"0\":[5940,2464,1]},1750,128,0,0.0,405]"}],"inserts":[{"Text":"[19935594,19163301,19935585,\"Shovel\",3179,0.0,[[19163301,\"ManlyMen\",true,true,true,true],[19107589,\"pastassium\",true,true,true,true],[19115284,\"Grayson\",true,true,true,true],[19259967,\"Crab\",true,true,true,false],[19162652,\"Zixen\",true,false,false,false],[19260021,\"Axie\",true,true,true,false],[19108518,\"Arteliese\",true,true,true,true],[19237979,\"Daka\",true,true,true,true],[19108547,\"ZeizhE\",true,true,true,true],[19474584,\"Kyle\",true,false,false,false],[19107511,\"Avery\",true,false,false,false],[19343463,\"Farrowe\",true,false,false,false],[19107709,\"JT\",true,false,false,false],[19631546,\"bhtmann\",true,false,false,false],[20678355,\"Kohlan\",true,false,false,false],[19259759,\"Yuura\",false,false,false,false],[21859415,\"Prim\",true,true,true,false],[19338202,\"Ambussing\",true,false,false,false],[19343433,\"PrimeThyme\",true,true,true,true],[19744809,\"Wulfgar\",true,false,false,false],[19107499,\"SpamWingDuck\",true,false,false,false],[19191706,\"Seryn\",true,true,false,false],[23007002,\"Bris\",true,false,false,false],[23691632,\"AmberGoldHeart\",false,false,false,false],[19107217,\"Thorne\",true,false,false,false],[19237643,\"Athelle\",true,true,false,false],[27652990,\"Jynxyy\",true,true,false,false],[23017634,\"Hershey\",true,true,true,false],[19631608,\"Greywar\",true,false,false,false],[19107438,\"Karthsiden\",true,true,false,false],[33708932,\"DeckleBall\",true,false,false,false],[36940568,\"GraysonSon\",true,true,true,false],[28483004,\"Allusion\",true,false,false,false],[28723754,\"Otaku40k\",true,false,false,false],[19107247,\"gon\",true,false,false,false]],1469,8444,0,false,{\"0\":[5940,2464,1]},1750,128,0,0.0,405]"}]},{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19115287,[[6000,{\"0\":[3170009,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1020003,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1955158156,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,19115284,0]"}],"inserts":[{"Text":"[19115287,[[6000,{\"0\":[3170009,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1020003,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19115284,0]"}]}]}},"timestamp":{"microseconds":1733689669296418},"caller_identity":{"__identity_bytes":"0091b7bf3d47f46d2eb2bde3c657879d16622f9a8a187af792bf9eb4fcd8639b"},"caller_address":{"__address_bytes":"b5c4fd2aa5b602d08d3160847218aa19"},"reducer_call":{"reducer_name":"claim_resupply","reducer_id":98,"args":{"Text":"[[19935585,1955158156]]"},"request_id":2789},"energy_quanta_used":{"quanta":1185120615},"host_execution_duration_micros":1579}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_claim_withdraw_from_treasury() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4127,"table_name":"ClaimDescriptionState","deletes":[{"Text":"[25099391,19583932,25099382,\"UMB Foragers Rest\",880,0.0,[[19583932,\"DroneTecH\",true,true,true,true],[19107679,\"Jobox05\",true,true,true,false],[19237631,\"Polymorphism\",true,true,true,false],[19108373,\"Mara129\",true,true,false,false],[19107764,\"Anna\",true,true,true,false],[19107727,\"Kenny\",true,true,true,false],[19108469,\"Zaetia\",true,true,false,false],[19107223,\"SnowPhoenix\",true,true,false,false],[19107661,\"MatlasH\",true,true,false,false],[20024167,\"Haywire\",true,true,false,false],[26175625,\"Maxi\",true,false,false,false],[19108764,\"Dogodve\",true,false,false,false],[19177895,\"GameOver\",true,true,true,false],[19237973,\"Phynyx\",true,true,false,false],[19108584,\"Ecop\",true,true,false,false],[38974128,\"ThinGlizzy\",true,false,false,false],[38974303,\"ORNNHUB\",true,false,false,false],[20187230,\"Leosure\",false,false,false,false],[39130195,\"MethosShadow\",false,false,false,false],[38976936,\"Godless\",false,false,false,false]],2418,13894,0,false,{\"0\":[6573,4915,1]},14596,813,1,10001.0,405]"}],"inserts":[{"Text":"[25099391,19583932,25099382,\"UMB Foragers Rest\",880,0.0,[[19583932,\"DroneTecH\",true,true,true,true],[19107679,\"Jobox05\",true,true,true,false],[19237631,\"Polymorphism\",true,true,true,false],[19108373,\"Mara129\",true,true,false,false],[19107764,\"Anna\",true,true,true,false],[19107727,\"Kenny\",true,true,true,false],[19108469,\"Zaetia\",true,true,false,false],[19107223,\"SnowPhoenix\",true,true,false,false],[19107661,\"MatlasH\",true,true,false,false],[20024167,\"Haywire\",true,true,false,false],[26175625,\"Maxi\",true,false,false,false],[19108764,\"Dogodve\",true,false,false,false],[19177895,\"GameOver\",true,true,true,false],[19237973,\"Phynyx\",true,true,false,false],[19108584,\"Ecop\",true,true,false,false],[38974128,\"ThinGlizzy\",true,false,false,false],[38974303,\"ORNNHUB\",true,false,false,false],[20187230,\"Leosure\",false,false,false,false],[39130195,\"MethosShadow\",false,false,false,false],[38976936,\"Godless\",false,false,false,false]],2418,13894,0,false,{\"0\":[6573,4915,1]},14595,813,1,10001.0,405]"}]},{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19583935,[[6000,{\"0\":[2170019,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3030002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,17,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11012,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1142832553,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1030001,41,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1130003,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1985074940,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,1,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19583932,0]"}],"inserts":[{"Text":"[19583935,[[6000,{\"0\":[2170019,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3030002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,17,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11012,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1142832553,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1030001,41,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,1,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"0\":[1130003,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1985074940,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,1,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19583932,0]"}]}]}},"timestamp":{"microseconds":1733694035809108},"caller_identity":{"__identity_bytes":"06c056f6ea03e912eac243d166824343458a3995afad9d41e0561529340ce2bd"},"caller_address":{"__address_bytes":"a21685bdf81ca3f43cd7fbd71daa8dca"},"reducer_call":{"reducer_name":"claim_withdraw_from_treasury","reducer_id":108,"args":{"Text":"[[25099391,1]]"},"request_id":3424},"energy_quanta_used":{"quanta":131688615},"host_execution_duration_micros":322}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_complete_onboarding_state() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[48589221,[[6000,{\"0\":[36,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2140013,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110003,500,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170009,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1548924604,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[471537919,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[442339538,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1861261493,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1437965200,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1846788956,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,48589218,0]"}],"inserts":[{"Text":"[48589221,[[6000,{\"0\":[36,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2140013,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110003,500,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170009,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[181402733,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1548924604,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[471537919,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[442339538,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1861261493,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1437965200,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1846788956,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,48589218,0]"}]}]}},"timestamp":{"microseconds":1733688491230414},"caller_identity":{"__identity_bytes":"806ef5ec3db2d5057fa2fd79e798560b26ed94380ef6eb378bc69d0b7cd5405e"},"caller_address":{"__address_bytes":"b2c5e82f1518b83e16f435f06dd3f44f"},"reducer_call":{"reducer_name":"complete_onboarding_state","reducer_id":113,"args":{"Text":"[27]"},"request_id":5770},"energy_quanta_used":{"quanta":25224308615},"host_execution_duration_micros":7804}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_convert_collectible_to_deed() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108321,[[6000,{\"0\":[3170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4170009,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[356873769,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1686060207,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1473664712,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108318,0]"}],"inserts":[{"Text":"[19108321,[[6000,{\"0\":[3170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1710782741,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4170009,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[356873769,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1686060207,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1473664712,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108318,0]"}]},{"table_id":4328,"table_name":"VaultState","deletes":[{"Text":"[19108318,[[100,false,1],[101,false,1],[102,false,1],[103,false,1],[104,false,1],[105,false,1],[106,false,1],[107,false,1],[110,false,1],[111,false,1],[112,true,1],[113,false,1],[200,false,1],[201,false,1],[202,false,1],[203,false,1],[205,true,1],[206,false,1],[207,false,1],[208,false,1],[300,true,1],[400,true,1],[401,false,1],[402,false,1],[403,false,1],[404,false,1],[500,true,1],[501,false,1],[502,false,1],[503,false,1],[504,false,1],[1586273539,false,1],[16000500,false,1],[16001100,false,1],[16000300,false,1],[16000800,false,1],[16000600,false,1],[16000400,false,1],[16001101,false,1],[16000200,false,1],[16000000,false,1],[16000100,false,1],[16001000,false,1],[16000801,false,1],[16001200,false,1],[16000301,false,1],[16000201,false,1],[16000900,false,1],[16000700,false,1],[16000501,false,1],[16000101,false,1],[16000001,false,1],[16000401,false,1],[16000701,false,1],[340356319,false,1],[16000601,false,1],[16001102,false,1],[16000202,false,1],[16001001,false,1],[1861687868,false,1],[16000302,false,1],[16000002,false,1],[16000102,false,1],[16000901,false,1],[16000802,false,1],[16000003,false,1],[10020001,false,1],[10020002,false,1],[10020003,false,1],[10020004,false,1],[10020005,false,1],[10020006,false,1],[16000902,false,1],[16000903,false,1],[10110001,false,1],[10110002,false,1],[10110003,false,1],[10110004,false,1],[10110005,false,1],[10110006,false,1],[1143486079,true,1],[16001002,false,1],[16000803,false,1],[10100001,false,1],[10100002,false,1],[10100003,false,1],[10100004,false,1],[10100005,false,1],[10100006,false,1],[16000502,false,1],[16000203,false,1],[10040001,false,1],[10040002,false,1],[10040003,false,1],[10040004,false,1],[10040005,false,1],[10040006,false,1],[16000702,false,1],[16001201,false,1],[16001103,false,1],[10130001,false,1],[10130002,false,1],[10130003,false,1],[10130004,false,1],[10130005,false,1],[10130006,false,1],[2098468505,true,1],[16000402,false,1],[16000303,false,1],[10050001,false,1],[10050002,false,1],[10050003,false,1],[10050004,false,1],[10050005,false,1],[10050006,false,1],[16000403,false,1],[10060001,false,1],[10060002,false,1],[10060003,false,1],[10060004,false,1],[10060005,false,1],[10060006,false,1],[1616030221,false,1],[16000703,false,1],[10090001,false,1],[10090002,false,1],[10090003,false,1],[10090004,false,1],[10090005,false,1],[10090006,false,1],[1826106885,false,1],[16000602,false,1],[16000103,false,1],[10030001,false,1],[10030002,false,1],[10030003,false,1],[10030004,false,1],[10030005,false,1],[10030006,false,1],[16001003,false,1],[10120001,false,1],[10120002,false,1],[10120003,false,1],[10120004,false,1],[10120005,false,1],[10120006,false,1],[16000804,false,1],[16001202,false,1],[16000603,false,1],[10080001,false,1],[10080002,false,1],[10080003,false,1],[10080004,false,1],[10080005,false,1],[10080006,false,1],[16001104,false,1],[16000503,false,1],[10070001,false,1],[10070002,false,1],[10070003,false,1],[10070004,false,1],[10070005,false,1],[10070006,false,1],[16000204,false,1],[16000004,false,1],[131,false,1],[16000304,false,1],[16000104,false,1],[16000805,false,1],[12100005,false,1],[12100004,false,1],[12100002,false,1],[12100003,false,1],[12100001,false,1],[12100006,false,1],[16000404,false,1],[1973259219,false,1],[16001203,false,1],[10140001,false,1],[10140002,false,1],[10140003,false,1],[10140004,false,1],[10140005,false,1],[10140006,false,1],[16000704,false,1],[16000504,false,1],[16001105,false,1],[12130005,false,1],[12130004,false,1],[12130002,false,1],[12130003,false,1],[12130001,false,1],[12130006,false,1],[16001004,false,1],[16000604,false,1],[16000205,false,1],[12040005,false,1],[12040004,false,1],[12040002,false,1],[12040003,false,1],[12040001,false,1],[12040006,false,1],[16000305,false,1],[12050005,false,1],[12050004,false,1],[12050002,false,1],[12050003,false,1],[12050001,false,1],[12050006,false,1],[1602358537,false,1],[1866035960,false,1],[951721187,false,1],[593976627,false,1],[16000005,false,1],[12020005,true,1],[12020004,true,1],[12020002,true,1],[12020003,true,1],[12020001,true,1],[12020006,true,1],[16000105,false,1],[12030005,false,1],[12030004,false,1],[12030002,false,1],[12030003,false,1],[12030001,false,1],[12030006,false,1],[16001204,false,1],[129,false,1],[16000904,false,1],[2064148043,false,1],[1894463345,false,1],[1596305274,false,1],[766390251,false,1],[1713518934,false,1],[884186004,false,1],[988633300,false,1],[616488870,false,1],[370941841,false,1]],13500]"}],"inserts":[{"Text":"[19108318,[[100,false,1],[101,false,1],[102,false,1],[103,false,1],[104,false,1],[105,false,1],[106,false,1],[107,false,1],[110,false,1],[111,false,1],[112,true,1],[113,false,1],[200,false,1],[201,false,1],[202,false,1],[203,false,1],[205,true,1],[206,false,1],[207,false,1],[208,false,1],[300,true,1],[400,true,1],[401,false,1],[402,false,1],[403,false,1],[404,false,1],[500,true,1],[501,false,1],[502,false,1],[503,false,1],[504,false,1],[1586273539,false,1],[16000500,false,1],[16001100,false,1],[16000300,false,1],[16000800,false,1],[16000600,false,1],[16000400,false,1],[16001101,false,1],[16000200,false,1],[16000000,false,1],[16000100,false,1],[16001000,false,1],[16000801,false,1],[16001200,false,1],[16000301,false,1],[16000201,false,1],[16000900,false,1],[16000700,false,1],[16000501,false,1],[16000101,false,1],[16000001,false,1],[16000401,false,1],[16000701,false,1],[340356319,false,1],[16000601,false,1],[16001102,false,1],[16000202,false,1],[16001001,false,1],[1861687868,false,1],[16000302,false,1],[16000002,false,1],[16000102,false,1],[16000901,false,1],[16000802,false,1],[16000003,false,1],[10020001,false,1],[10020002,false,1],[10020003,false,1],[10020004,false,1],[10020005,false,1],[10020006,false,1],[16000902,false,1],[16000903,false,1],[10110001,false,1],[10110002,false,1],[10110003,false,1],[10110004,false,1],[10110005,false,1],[10110006,false,1],[1143486079,true,1],[16001002,false,1],[16000803,false,1],[10100001,false,1],[10100002,false,1],[10100003,false,1],[10100004,false,1],[10100005,false,1],[10100006,false,1],[16000502,false,1],[16000203,false,1],[10040001,false,1],[10040002,false,1],[10040003,false,1],[10040004,false,1],[10040005,false,1],[10040006,false,1],[16000702,false,1],[16001201,false,1],[16001103,false,1],[10130001,false,1],[10130002,false,1],[10130003,false,1],[10130004,false,1],[10130005,false,1],[10130006,false,1],[2098468505,true,1],[16000402,false,1],[16000303,false,1],[10050001,false,1],[10050002,false,1],[10050003,false,1],[10050004,false,1],[10050005,false,1],[10050006,false,1],[16000403,false,1],[10060001,false,1],[10060002,false,1],[10060003,false,1],[10060004,false,1],[10060005,false,1],[10060006,false,1],[1616030221,false,1],[16000703,false,1],[10090001,false,1],[10090002,false,1],[10090003,false,1],[10090004,false,1],[10090005,false,1],[10090006,false,1],[1826106885,false,1],[16000602,false,1],[16000103,false,1],[10030001,false,1],[10030002,false,1],[10030003,false,1],[10030004,false,1],[10030005,false,1],[10030006,false,1],[16001003,false,1],[10120001,false,1],[10120002,false,1],[10120003,false,1],[10120004,false,1],[10120005,false,1],[10120006,false,1],[16000804,false,1],[16001202,false,1],[16000603,false,1],[10080001,false,1],[10080002,false,1],[10080003,false,1],[10080004,false,1],[10080005,false,1],[10080006,false,1],[16001104,false,1],[16000503,false,1],[10070001,false,1],[10070002,false,1],[10070003,false,1],[10070004,false,1],[10070005,false,1],[10070006,false,1],[16000204,false,1],[16000004,false,1],[131,false,1],[16000304,false,1],[16000104,false,1],[16000805,false,1],[12100005,false,1],[12100004,false,1],[12100002,false,1],[12100003,false,1],[12100001,false,1],[12100006,false,1],[16000404,false,1],[1973259219,false,1],[16001203,false,1],[10140001,false,1],[10140002,false,1],[10140003,false,1],[10140004,false,1],[10140005,false,1],[10140006,false,1],[16000704,false,1],[16000504,false,1],[16001105,false,1],[12130005,false,1],[12130004,false,1],[12130002,false,1],[12130003,false,1],[12130001,false,1],[12130006,false,1],[16001004,false,1],[16000604,false,1],[16000205,false,1],[12040005,false,1],[12040004,false,1],[12040002,false,1],[12040003,false,1],[12040001,false,1],[12040006,false,1],[16000305,false,1],[12050005,false,1],[12050004,false,1],[12050002,false,1],[12050003,false,1],[12050001,false,1],[12050006,false,1],[1602358537,false,1],[1866035960,false,1],[951721187,false,1],[593976627,false,1],[16000005,false,1],[12020005,true,1],[12020004,true,1],[12020002,true,1],[12020003,true,1],[12020001,true,1],[12020006,true,1],[16000105,false,1],[12030005,false,1],[12030004,false,1],[12030002,false,1],[12030003,false,1],[12030001,false,1],[12030006,false,1],[16001204,false,1],[129,false,1],[16000904,false,1],[2064148043,false,1],[1894463345,false,1],[766390251,false,1],[1713518934,false,1],[884186004,false,1],[988633300,false,1],[616488870,false,1],[370941841,false,1]],13500]"}]}]}},"timestamp":{"microseconds":1733688640071677},"caller_identity":{"__identity_bytes":"598b5c6acb3d11533a22d91038a437cedde7ee5e3d23af4fa52a1993dbef7277"},"caller_address":{"__address_bytes":"a0b5ce174d35699bffe34480ee550738"},"reducer_call":{"reducer_name":"convert_collectible_to_deed","reducer_id":114,"args":{"Text":"[[232]]"},"request_id":12408},"energy_quanta_used":{"quanta":957604615},"host_execution_duration_micros":866}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_convert_deed_to_collectible() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108662,[[6000,{\"0\":[197900802,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[588329371,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2008582834,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[131074151,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1854175100,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1800930316,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[3170006,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170007,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1230493129,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108659,0]"}],"inserts":[{"Text":"[19108662,[[6000,{\"1\":[]},false],[6000,{\"0\":[588329371,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2008582834,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[131074151,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1854175100,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1800930316,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[3170006,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170007,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1230493129,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108659,0]"}]},{"table_id":4328,"table_name":"VaultState","deletes":[{"Text":"[19108659,[[100,false,1],[101,false,1],[102,false,1],[103,false,1],[104,false,1],[105,false,1],[106,false,1],[107,false,1],[110,true,1],[111,false,1],[112,false,1],[113,false,1],[200,false,1],[201,false,1],[202,false,1],[203,true,1],[205,false,1],[206,false,1],[207,false,1],[208,false,1],[300,true,1],[400,true,1],[401,false,1],[402,false,1],[403,false,1],[404,false,1],[500,false,1],[501,false,1],[502,false,1],[503,false,1],[504,true,1],[1586273539,false,1],[1150019,false,1],[1817687586,false,1],[1150061,false,1],[16000500,false,1],[16000000,false,1],[16001100,false,1],[16000700,false,1],[16000900,false,1],[16000300,false,1],[16000600,false,1],[16001101,false,1],[16000200,false,1],[16000201,false,1],[16000901,false,1],[16000301,false,1],[16000400,false,1],[16000902,false,1],[16001000,false,1],[16000100,false,1],[16000302,false,1],[16000202,false,1],[16000203,false,1],[10040001,false,1],[10040002,false,1],[10040003,false,1],[10040004,false,1],[10040005,false,1],[10040006,false,1],[537052696,false,1],[340356319,false,1],[1861687868,false,1],[16001001,false,1],[16000001,false,1],[16000101,false,1],[16000401,false,1],[1150003,false,1],[1616030221,false,1],[16000601,false,1],[16000501,false,1],[239291956,false,1],[1618696480,false,1],[1143486079,true,1],[30210745,false,1],[528360283,false,1],[16001102,false,1],[16000701,false,1],[16000002,false,1],[16000402,false,1],[16000303,false,1],[10050001,false,1],[10050002,false,1],[10050003,false,1],[10050004,false,1],[10050005,false,1],[10050006,false,1],[16000800,false,1],[1499869022,false,1],[1150086,false,1],[1150006,false,1],[1675603582,false,1],[1150070,false,1],[16000801,false,1],[16001200,false,1],[16000102,false,1],[16001002,false,1],[16000903,false,1],[10110001,false,1],[10110002,false,1],[10110003,false,1],[10110004,false,1],[10110005,false,1],[10110006,false,1],[16000003,false,1],[10020001,false,1],[10020002,false,1],[10020003,false,1],[10020004,false,1],[10020005,false,1],[10020006,false,1],[16001103,false,1],[10130001,false,1],[10130002,false,1],[10130003,false,1],[10130004,false,1],[10130005,false,1],[10130006,false,1],[16000802,false,1],[16001201,false,1],[16000204,false,1],[449783647,false,1],[16001202,false,1],[16000602,false,1],[678484014,false,1],[1344875852,false,1],[16000702,false,1],[16000304,false,1],[1826106885,false,1],[16000502,false,1],[16000403,false,1],[10060001,false,1],[10060002,false,1],[10060003,false,1],[10060004,false,1],[10060005,false,1],[10060006,false,1],[16000103,false,1],[10030001,false,1],[10030002,false,1],[10030003,false,1],[10030004,false,1],[10030005,false,1],[10030006,false,1],[1399244559,false,1],[16001104,false,1],[16000205,false,1],[12040005,false,1],[12040004,false,1],[12040002,false,1],[12040003,false,1],[12040001,false,1],[12040006,false,1],[16000803,false,1],[10100001,true,1],[10100002,false,1],[10100003,false,1],[10100004,false,1],[10100005,false,1],[10100006,false,1],[16001003,false,1],[10120001,false,1],[10120002,false,1],[10120003,false,1],[10120004,false,1],[10120005,false,1],[10120006,false,1],[131,false,1],[16000503,false,1],[10070001,false,1],[10070002,false,1],[10070003,false,1],[10070004,false,1],[10070005,false,1],[10070006,false,1],[16000603,false,1],[10080001,false,1],[10080002,true,1],[10080003,false,1],[10080004,false,1],[10080005,false,1],[10080006,false,1],[16000703,false,1],[10090001,false,1],[10090002,false,1],[10090003,false,1],[10090004,false,1],[10090005,false,1],[10090006,false,1],[16000904,false,1],[16000404,false,1],[16000305,false,1],[12050005,true,1],[12050004,true,1],[12050002,false,1],[12050003,true,1],[12050001,false,1],[12050006,false,1],[1335845104,false,1],[814576041,false,1],[267950940,false,1],[16000004,false,1],[16000405,false,1],[12060005,false,1],[12060004,false,1],[12060002,false,1],[12060003,false,1],[12060001,false,1],[12060006,true,1],[600,true,1],[16001203,false,1],[10140001,false,1],[10140002,false,1],[10140003,false,1],[10140004,false,1],[10140005,false,1],[10140006,false,1],[16000104,false,1],[16001004,false,1],[2098468505,true,1],[16000804,false,1],[16001204,false,1],[16000504,false,1],[1096887657,false,1],[147983415,false,1],[129,true,1],[1951915597,false,1],[1016448960,false,1],[168316208,false,1],[1023315422,false,1]],0]"}],"inserts":[{"Text":"[19108659,[[100,false,1],[101,false,1],[102,false,1],[103,false,1],[104,false,1],[105,false,1],[106,false,1],[107,false,1],[110,true,1],[111,false,1],[112,false,1],[113,false,1],[200,false,1],[201,false,1],[202,false,1],[203,true,1],[205,false,1],[206,false,1],[207,false,1],[208,false,1],[300,true,1],[400,true,1],[401,false,1],[402,false,1],[403,false,1],[404,false,1],[500,false,1],[501,false,1],[502,false,1],[503,false,1],[504,true,1],[1586273539,false,1],[1150019,false,1],[1817687586,false,1],[1150061,false,1],[16000500,false,1],[16000000,false,1],[16001100,false,1],[16000700,false,1],[16000900,false,1],[16000300,false,1],[16000600,false,1],[16001101,false,1],[16000200,false,1],[16000201,false,1],[16000901,false,1],[16000301,false,1],[16000400,false,1],[16000902,false,1],[16001000,false,1],[16000100,false,1],[16000302,false,1],[16000202,false,1],[16000203,false,1],[10040001,false,1],[10040002,false,1],[10040003,false,1],[10040004,false,1],[10040005,false,1],[10040006,false,1],[537052696,false,1],[340356319,false,1],[1861687868,false,1],[16001001,false,1],[16000001,false,1],[16000101,false,1],[16000401,false,1],[1150003,false,1],[1616030221,false,1],[16000601,false,1],[16000501,false,1],[239291956,false,1],[1618696480,false,1],[1143486079,true,1],[30210745,false,1],[528360283,false,1],[16001102,false,1],[16000701,false,1],[16000002,false,1],[16000402,false,1],[16000303,false,1],[10050001,false,1],[10050002,false,1],[10050003,false,1],[10050004,false,1],[10050005,false,1],[10050006,false,1],[16000800,false,1],[1499869022,false,1],[1150086,false,1],[1150006,false,1],[1675603582,false,1],[1150070,false,1],[16000801,false,1],[16001200,false,1],[16000102,false,1],[16001002,false,1],[16000903,false,1],[10110001,false,1],[10110002,false,1],[10110003,false,1],[10110004,false,1],[10110005,false,1],[10110006,false,1],[16000003,false,1],[10020001,false,1],[10020002,false,1],[10020003,false,1],[10020004,false,1],[10020005,false,1],[10020006,false,1],[16001103,false,1],[10130001,false,1],[10130002,false,1],[10130003,false,1],[10130004,false,1],[10130005,false,1],[10130006,false,1],[16000802,false,1],[16001201,false,1],[16000204,false,1],[449783647,false,1],[16001202,false,1],[16000602,false,1],[678484014,false,1],[1344875852,false,1],[16000702,false,1],[16000304,false,1],[1826106885,false,1],[16000502,false,1],[16000403,false,1],[10060001,false,1],[10060002,false,1],[10060003,false,1],[10060004,false,1],[10060005,false,1],[10060006,false,1],[16000103,false,1],[10030001,false,1],[10030002,false,1],[10030003,false,1],[10030004,false,1],[10030005,false,1],[10030006,false,1],[1399244559,false,1],[16001104,false,1],[16000205,false,1],[12040005,false,1],[12040004,false,1],[12040002,false,1],[12040003,false,1],[12040001,false,1],[12040006,false,1],[16000803,false,1],[10100001,true,1],[10100002,false,1],[10100003,false,1],[10100004,false,1],[10100005,false,1],[10100006,false,1],[16001003,false,1],[10120001,false,1],[10120002,false,1],[10120003,false,1],[10120004,false,1],[10120005,false,1],[10120006,false,1],[131,false,1],[16000503,false,1],[10070001,false,1],[10070002,false,1],[10070003,false,1],[10070004,false,1],[10070005,false,1],[10070006,false,1],[16000603,false,1],[10080001,false,1],[10080002,true,1],[10080003,false,1],[10080004,false,1],[10080005,false,1],[10080006,false,1],[16000703,false,1],[10090001,false,1],[10090002,false,1],[10090003,false,1],[10090004,false,1],[10090005,false,1],[10090006,false,1],[16000904,false,1],[16000404,false,1],[16000305,false,1],[12050005,true,1],[12050004,true,1],[12050002,false,1],[12050003,true,1],[12050001,false,1],[12050006,false,1],[1335845104,false,1],[814576041,false,1],[267950940,false,1],[16000004,false,1],[16000405,false,1],[12060005,false,1],[12060004,false,1],[12060002,false,1],[12060003,false,1],[12060001,false,1],[12060006,true,1],[600,true,1],[16001203,false,1],[10140001,false,1],[10140002,false,1],[10140003,false,1],[10140004,false,1],[10140005,false,1],[10140006,false,1],[16000104,false,1],[16001004,false,1],[2098468505,true,1],[16000804,false,1],[16001204,false,1],[16000504,false,1],[1096887657,false,1],[147983415,false,1],[129,true,1],[1951915597,false,1],[1016448960,false,1],[168316208,false,1],[1023315422,false,1],[1378653455,false,1]],0]"}]}]}},"timestamp":{"microseconds":1733687672351968},"caller_identity":{"__identity_bytes":"44eff1c6641ff31c17966a7dc21b6ca78a114e5cd912a58233c217b0953c3b9f"},"caller_address":{"__address_bytes":"4c1f35f2f821f8da79eaea5bdc07c19d"},"reducer_call":{"reducer_name":"convert_deed_to_collectible","reducer_id":115,"args":{"Text":"[[0]]"},"request_id":69},"energy_quanta_used":{"quanta":263211615},"host_execution_duration_micros":755}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_craft_cancel() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[23007005,[[6000,{\"0\":[1519076834,7,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1058474234,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[214500918,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[492461636,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1216682773,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[192409188,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,23007002,0]"},{"Text":"[48791276,[[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[2130001,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2130001,1000,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2130001,1000,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2130001,1000,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2130001,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[225094001,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1519076834,16,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false]],0,24,44292204,0]"}],"inserts":[{"Text":"[23007005,[[6000,{\"0\":[1519076834,7,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1058474234,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[214500918,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[492461636,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1216682773,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2130001,100,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[192409188,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,23007002,0]"},{"Text":"[48791276,[[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[2130001,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2130001,1000,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2130001,1000,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2130001,1000,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2130001,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[225094001,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1519076834,16,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[600000,{\"0\":[192409188,59,{\"1\":[]},{\"1\":[]}]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false]],0,24,44292204,0]"}]}]}},"timestamp":{"microseconds":1733690959598697},"caller_identity":{"__identity_bytes":"b31dfa33bb5d18cb1cec5f4a2df7e566439e9e5f223147f8df83f08263cd4ec0"},"caller_address":{"__address_bytes":"abae5affc62aeb617f6529885bd512a5"},"reducer_call":{"reducer_name":"craft_cancel","reducer_id":116,"args":{"Text":"[[48797870]]"},"request_id":28797},"energy_quanta_used":{"quanta":1768453615},"host_execution_duration_micros":520}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_craft_collect() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107226,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1170019,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170017,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19107223,0]"}],"inserts":[{"Text":"[19107226,[[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1170019,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170017,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19107223,0]"}]}]}},"timestamp":{"microseconds":1733694243860759},"caller_identity":{"__identity_bytes":"b5d1a8c07dd5dbb0a6a164a618eafef721cbaf381a48dc4c99f98ea104d1bbe4"},"caller_address":{"__address_bytes":"9e66a56697666017032e284e1a57a8c9"},"reducer_call":{"reducer_name":"craft_collect","reducer_id":117,"args":{"Text":"[[48846212,202009]]"},"request_id":22962},"energy_quanta_used":{"quanta":1109409615},"host_execution_duration_micros":322}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_craft_collect_all() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107398,[[6000,{\"0\":[1800930316,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[11012,32,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,13,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1142832553,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107395,0]"}],"inserts":[{"Text":"[19107398,[[6000,{\"0\":[1800930316,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11012,32,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,13,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1142832553,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107395,0]"}]}]}},"timestamp":{"microseconds":1733687111485602},"caller_identity":{"__identity_bytes":"d8b02dc95079c398239b259260ebaa808acb9563d8a3c6fb9b755de071216e25"},"caller_address":{"__address_bytes":"022eb0ee86b5051ba4d7a6469650cbc2"},"reducer_call":{"reducer_name":"craft_collect_all","reducer_id":118,"args":{"Text":"[[36041108]]"},"request_id":14091},"energy_quanta_used":{"quanta":918965615},"host_execution_duration_micros":766}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_craft_initiate_start() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19237960,[[6000,{\"0\":[1170019,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3070004,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1090002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1090002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100014,22,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1090002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2050002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100017,40,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1090002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1464553255,30,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1464553255,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19237957,0]"}],"inserts":[{"Text":"[19237960,[[6000,{\"0\":[1170019,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3070004,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1090002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1090002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100014,22,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1090002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2050002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1090002,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1464553255,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19237957,0]"}]}]}},"timestamp":{"microseconds":1733694250409751},"caller_identity":{"__identity_bytes":"752ce133766fee7086ef6bc80a6312fff5c719b79f14c024679dc9eb8aa88a51"},"caller_address":{"__address_bytes":"78d35ecedbcf9853c29727bb08477b20"},"reducer_call":{"reducer_name":"craft_initiate_start","reducer_id":122,"args":{"Text":"[[109001,33088760,8]]"},"request_id":15401},"energy_quanta_used":{"quanta":438220615},"host_execution_duration_micros":827}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_deployable_deploy() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4114,"table_name":"DeployableState","deletes":[{"Text":"[34002124,28549932,0,7,1307265899,\"Eagleson's Expanded Cart\",false]"}],"inserts":[{"Text":"[34002124,28549932,0,10,1307265899,\"Eagleson's Expanded Cart\",false]"}]},{"table_id":4328,"table_name":"VaultState","deletes":[{"Text":"[28549932,[[100,false,1],[101,false,1],[102,false,1],[103,false,1],[104,false,1],[105,false,1],[106,false,1],[107,false,1],[110,false,1],[111,false,1],[112,false,1],[113,true,1],[200,false,1],[201,false,1],[202,false,1],[203,true,1],[205,false,1],[206,false,1],[207,false,1],[208,false,1],[300,true,1],[400,false,1],[401,false,1],[402,false,1],[403,true,1],[404,false,1],[500,true,1],[501,false,1],[502,false,1],[503,false,1],[504,false,1],[1586273539,false,1],[16000900,false,1],[16001100,false,1],[1150019,false,4],[1817687586,false,4],[1150061,false,3],[16001200,false,1],[16000200,false,1],[16000800,false,1],[16000700,false,1],[16000400,false,1],[16000500,false,1],[16000600,false,1],[16001101,false,1],[16000300,false,1],[16000000,false,1],[16001201,false,1],[790337329,false,1],[16000301,false,1],[16000201,false,1],[16001000,false,1],[16000100,false,1],[16000901,false,1],[16000202,false,1],[582754682,false,1],[16000601,false,1],[16000401,false,1],[16000001,false,1],[16000101,false,1],[16000501,false,1],[16000302,false,1],[340356319,false,1],[16000701,false,1],[16001102,false,1],[16000801,false,1],[16000402,false,1],[2098468505,true,1],[16000702,false,1],[1861687868,false,1],[16001001,false,1],[16000203,false,1],[10040001,false,1],[10040002,false,1],[10040003,false,1],[10040004,false,1],[10040005,false,1],[10040006,false,1],[1499869022,false,1],[1675603582,false,1],[1150006,false,1],[1150070,false,1],[1150086,false,1],[2142554450,false,1],[16000802,false,1],[68781971,false,1],[1226450761,true,1],[449783647,false,2],[16001202,false,1],[16000002,false,1],[16000502,false,1],[16000602,false,1],[528360283,false,1],[16000303,false,1],[10050001,false,1],[10050002,false,1],[10050003,false,1],[10050004,false,1],[10050005,false,1],[10050006,false,1],[1482110378,false,2],[16001103,false,1],[10130001,false,1],[10130002,false,1],[10130003,false,1],[10130004,false,1],[10130005,false,1],[10130006,false,1],[16000902,false,1],[16001203,false,1],[10140001,false,1],[10140002,false,1],[10140003,false,1],[10140004,false,1],[10140005,false,1],[10140006,false,1],[16000903,false,1],[10110001,false,1],[10110002,false,1],[10110003,false,1],[10110004,false,1],[10110005,false,1],[10110006,false,1],[16001002,false,1],[16000403,false,1],[10060001,false,1],[10060002,false,1],[10060003,false,1],[10060004,false,1],[10060005,false,1],[10060006,false,1],[1616030221,false,1],[16000703,false,1],[10090001,false,1],[10090002,false,1],[10090003,false,1],[10090004,false,1],[10090005,false,1],[10090006,false,1],[16000204,true,1],[593894401,false,1],[16000102,false,1],[16001104,false,1],[16000003,false,1],[10020001,false,1],[10020002,false,1],[10020003,false,1],[10020004,false,1],[10020005,false,1],[10020006,false,1],[239291956,false,2],[30210745,false,2],[1150003,false,2],[267950940,false,1],[814576041,false,1],[1335845104,false,1],[1420016450,false,1],[16000103,false,1],[10030001,false,1],[10030002,false,1],[10030003,false,1],[10030004,false,1],[10030005,false,1],[10030006,false,1],[1618696480,false,2],[16000803,false,1],[10100001,false,1],[10100002,false,1],[10100003,false,1],[10100004,false,1],[10100005,false,1],[10100006,false,1],[16000304,false,1],[1754004451,false,1],[16000503,false,1],[10070001,true,1],[10070002,true,1],[10070003,true,1],[10070004,true,1],[10070005,true,1],[10070006,true,1],[1123935222,false,1],[1973310032,false,1],[16000603,false,1],[10080001,false,1],[10080002,false,1],[10080003,false,1],[10080004,false,1],[10080005,false,1],[10080006,false,1],[16000004,false,1]],9500]"}],"inserts":[{"Text":"[28549932,[[100,false,1],[101,false,1],[102,false,1],[103,false,1],[104,false,1],[105,false,1],[106,false,1],[107,false,1],[110,false,1],[111,false,1],[112,false,1],[113,true,1],[200,false,1],[201,false,1],[202,false,1],[203,true,1],[205,false,1],[206,false,1],[207,false,1],[208,false,1],[300,true,1],[400,false,1],[401,false,1],[402,false,1],[403,true,1],[404,false,1],[500,true,1],[501,false,1],[502,false,1],[503,false,1],[504,false,1],[1586273539,false,1],[16000900,false,1],[16001100,false,1],[1150019,false,4],[1817687586,false,4],[1150061,false,3],[16001200,false,1],[16000200,false,1],[16000800,false,1],[16000700,false,1],[16000400,false,1],[16000500,false,1],[16000600,false,1],[16001101,false,1],[16000300,false,1],[16000000,false,1],[16001201,false,1],[790337329,false,1],[16000301,false,1],[16000201,false,1],[16001000,false,1],[16000100,false,1],[16000901,false,1],[16000202,false,1],[582754682,false,1],[16000601,false,1],[16000401,false,1],[16000001,false,1],[16000101,false,1],[16000501,false,1],[16000302,false,1],[340356319,false,1],[16000701,false,1],[16001102,false,1],[16000801,false,1],[16000402,false,1],[2098468505,true,1],[16000702,false,1],[1861687868,false,1],[16001001,false,1],[16000203,false,1],[10040001,false,1],[10040002,false,1],[10040003,false,1],[10040004,false,1],[10040005,false,1],[10040006,false,1],[1499869022,false,1],[1675603582,false,1],[1150006,false,1],[1150070,false,1],[1150086,false,1],[2142554450,false,1],[16000802,false,1],[68781971,true,1],[1226450761,true,1],[449783647,false,2],[16001202,false,1],[16000002,false,1],[16000502,false,1],[16000602,false,1],[528360283,false,1],[16000303,false,1],[10050001,false,1],[10050002,false,1],[10050003,false,1],[10050004,false,1],[10050005,false,1],[10050006,false,1],[1482110378,false,2],[16001103,false,1],[10130001,false,1],[10130002,false,1],[10130003,false,1],[10130004,false,1],[10130005,false,1],[10130006,false,1],[16000902,false,1],[16001203,false,1],[10140001,false,1],[10140002,false,1],[10140003,false,1],[10140004,false,1],[10140005,false,1],[10140006,false,1],[16000903,false,1],[10110001,false,1],[10110002,false,1],[10110003,false,1],[10110004,false,1],[10110005,false,1],[10110006,false,1],[16001002,false,1],[16000403,false,1],[10060001,false,1],[10060002,false,1],[10060003,false,1],[10060004,false,1],[10060005,false,1],[10060006,false,1],[1616030221,false,1],[16000703,false,1],[10090001,false,1],[10090002,false,1],[10090003,false,1],[10090004,false,1],[10090005,false,1],[10090006,false,1],[16000204,true,1],[593894401,false,1],[16000102,false,1],[16001104,false,1],[16000003,false,1],[10020001,false,1],[10020002,false,1],[10020003,false,1],[10020004,false,1],[10020005,false,1],[10020006,false,1],[239291956,false,2],[30210745,false,2],[1150003,false,2],[267950940,false,1],[814576041,false,1],[1335845104,false,1],[1420016450,false,1],[16000103,false,1],[10030001,false,1],[10030002,false,1],[10030003,false,1],[10030004,false,1],[10030005,false,1],[10030006,false,1],[1618696480,false,2],[16000803,false,1],[10100001,false,1],[10100002,false,1],[10100003,false,1],[10100004,false,1],[10100005,false,1],[10100006,false,1],[16000304,false,1],[1754004451,false,1],[16000503,false,1],[10070001,true,1],[10070002,true,1],[10070003,true,1],[10070004,true,1],[10070005,true,1],[10070006,true,1],[1123935222,false,1],[1973310032,false,1],[16000603,false,1],[10080001,false,1],[10080002,false,1],[10080003,false,1],[10080004,false,1],[10080005,false,1],[10080006,false,1],[16000004,false,1]],9500]"}]},{"table_id":4250,"table_name":"InventoryState","deletes":[],"inserts":[{"Text":"[48848262,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false]],0,24,34002124,0]"}]}]}},"timestamp":{"microseconds":1733694310523666},"caller_identity":{"__identity_bytes":"4b8d68a9119b31e5060f5d180d75feb82a092067e6ad19e9c6cde55eda9c777a"},"caller_address":{"__address_bytes":"5303d484e03b6a106636521f6236c57f"},"reducer_call":{"reducer_name":"deployable_deploy","reducer_id":126,"args":{"Text":"[[85,[5631,5544,1],10]]"},"request_id":3221},"energy_quanta_used":{"quanta":2708702615},"host_execution_duration_micros":2313}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_deployable_store() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[48490601,[[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false]],0,10,48490556,0]"}],"inserts":[]},{"table_id":4328,"table_name":"VaultState","deletes":[{"Text":"[46430174,[[100,true,1],[101,false,1],[102,false,1],[103,false,1],[104,false,1],[105,false,1],[106,false,1],[107,false,1],[110,false,1],[111,false,1],[112,false,1],[113,false,1],[200,false,1],[201,false,1],[202,false,1],[203,false,1],[205,false,1],[206,true,1],[207,false,1],[208,false,1],[300,true,1],[400,false,1],[401,false,1],[402,false,1],[403,false,1],[404,true,1],[500,false,1],[501,false,1],[502,true,1],[503,false,1],[504,false,1],[1586273539,true,1],[1150019,false,1],[1817687586,false,1],[1150061,false,1],[1618696480,false,1],[1150003,false,1],[16001100,false,1],[16000400,false,1],[139,true,1],[16000000,false,1],[1399244559,true,1],[16000100,false,1]],1500]"}],"inserts":[{"Text":"[46430174,[[100,true,1],[101,false,1],[102,false,1],[103,false,1],[104,false,1],[105,false,1],[106,false,1],[107,false,1],[110,false,1],[111,false,1],[112,false,1],[113,false,1],[200,false,1],[201,false,1],[202,false,1],[203,false,1],[205,false,1],[206,true,1],[207,false,1],[208,false,1],[300,true,1],[400,false,1],[401,false,1],[402,false,1],[403,false,1],[404,true,1],[500,false,1],[501,false,1],[502,true,1],[503,false,1],[504,false,1],[1586273539,true,1],[1150019,false,1],[1817687586,false,1],[1150061,false,1],[1618696480,false,1],[1150003,false,1],[16001100,false,1],[16000400,false,1],[139,false,1],[16000000,false,1],[1399244559,true,1],[16000100,false,1]],1500]"}]},{"table_id":4114,"table_name":"DeployableState","deletes":[{"Text":"[48490556,46430174,18118302,0,14,\"TheWazzzzzup's Trader Stand\",false]"}],"inserts":[{"Text":"[48490556,46430174,0,0,14,\"TheWazzzzzup's Trader Stand\",false]"}]}]}},"timestamp":{"microseconds":1733688995529202},"caller_identity":{"__identity_bytes":"27022f4276d28cfbed9ed0f792ece8bbe64d78b3b59484c4385efeaa9ead8517"},"caller_address":{"__address_bytes":"b26976b08d7e9f935fc247246467c176"},"reducer_call":{"reducer_name":"deployable_store","reducer_id":133,"args":{"Text":"[[48490556,false]]"},"request_id":1891},"energy_quanta_used":{"quanta":423990615},"host_execution_duration_micros":348}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_eat() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108686,[[6000,{\"0\":[2170009,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2044491604,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[828731292,1,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108683,0]"}],"inserts":[{"Text":"[19108686,[[6000,{\"0\":[2170009,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2044491604,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[828731292,1,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108683,0]"}]}]}},"timestamp":{"microseconds":1733689237792531},"caller_identity":{"__identity_bytes":"807eb14cf2c6cdcf0c5d00d23c3a330446c885621729ac5952cd130caf5a70a2"},"caller_address":{"__address_bytes":"49a6c9fcdb6b64337131d2fb50ac9bae"},"reducer_call":{"reducer_name":"eat","reducer_id":138,"args":{"Text":"[[0]]"},"request_id":2512},"energy_quanta_used":{"quanta":1820536615},"host_execution_duration_micros":2079}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_empire_collect_hexite_capsule() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108662,[[6000,{\"0\":[3020003,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[767727086,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[3110026,11,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1800930316,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170000,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4110003,156,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170002,7,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108659,0]"}],"inserts":[{"Text":"[19108662,[[6000,{\"0\":[3020003,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[767727086,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[3110026,11,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1800930316,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170000,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4110003,156,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170002,7,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2000000,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,19108659,0]"}]}]}},"timestamp":{"microseconds":1733760994238545},"caller_identity":{"__identity_bytes":"44eff1c6641ff31c17966a7dc21b6ca78a114e5cd912a58233c217b0953c3b9f"},"caller_address":{"__address_bytes":"1e9ca4395ad6bbc9e3e70891566bbc26"},"reducer_call":{"reducer_name":"empire_collect_hexite_capsule","reducer_id":143,"args":{"Text":"[[41863682]]"},"request_id":3760},"energy_quanta_used":{"quanta":1006962615},"host_execution_duration_micros":1053}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_equipment_add() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108662,[[6000,{\"0\":[7039060,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1800930316,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1854175100,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[3170007,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1230493129,7,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108659,0]"}],"inserts":[{"Text":"[19108662,[[6000,{\"0\":[1744965402,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1800930316,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1854175100,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[3170007,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1230493129,7,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108659,0]"}]}]}},"timestamp":{"microseconds":1733689060904925},"caller_identity":{"__identity_bytes":"44eff1c6641ff31c17966a7dc21b6ca78a114e5cd912a58233c217b0953c3b9f"},"caller_address":{"__address_bytes":"4c1f35f2f821f8da79eaea5bdc07c19d"},"reducer_call":{"reducer_name":"equipment_add","reducer_id":177,"args":{"Text":"[[{\"0\":[19108662,0]}]]"},"request_id":2251},"energy_quanta_used":{"quanta":806791615},"host_execution_duration_micros":876}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_equipment_remove() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19326142,[[6000,{\"0\":[1170009,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[6,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2124266002,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[261514602,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[739189753,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1973037841,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1793055245,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19326139,0]"}],"inserts":[{"Text":"[19326142,[[6000,{\"0\":[1170009,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[6,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1813380695,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2124266002,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[261514602,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[739189753,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1973037841,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1793055245,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19326139,0]"}]}]}},"timestamp":{"microseconds":1733688449704894},"caller_identity":{"__identity_bytes":"1cdbde17d2405065a78e4ca42392de3f905b8407f7385b00d959004a27ac40ac"},"caller_address":{"__address_bytes":"5bfc55861d79af699bfe6411b762d4a8"},"reducer_call":{"reducer_name":"equipment_remove","reducer_id":178,"args":{"Text":"[[2,{\"0\":[19326142,5]}]]"},"request_id":4762},"energy_quanta_used":{"quanta":719602615},"host_execution_duration_micros":1046}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_extract() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4151,"table_name":"ExperienceState","deletes":[{"Text":"[19107661,[[1,0],[3,650867],[15,3943],[13,302555],[16,0],[11,1299252],[12,1443769],[14,1629808],[2,735827],[9,160865],[8,281694],[20,0],[4,451341],[5,240184],[7,3025487],[18,49443],[6,63171],[10,1019617],[17,0],[19,0]]]"}],"inserts":[{"Text":"[19107661,[[1,0],[3,650867],[15,3943],[13,302555],[16,0],[11,1299252],[12,1443769],[14,1629808],[2,735860],[9,160865],[8,281694],[20,0],[4,451341],[5,240184],[7,3025487],[18,49443],[6,63171],[10,1019617],[17,0],[19,0]]]"}]},{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[48791042,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[60000,{\"0\":[1002,10,{\"1\":[]},{\"1\":[]}]},false],[60000,{\"0\":[1002,5,{\"1\":[]},{\"1\":[]}]},false]],0,18,19682518,0]"}],"inserts":[{"Text":"[48791042,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[60000,{\"0\":[1002,10,{\"1\":[]},{\"1\":[]}]},false],[60000,{\"0\":[1002,6,{\"1\":[]},{\"1\":[]}]},false]],0,18,19682518,0]"}]}]}},"timestamp":{"microseconds":1733688397360609},"caller_identity":{"__identity_bytes":"99a62f8bee6001fc759a1442f848da6f16927a48cc441b2c54d756483e8d6011"},"caller_address":{"__address_bytes":"a2e293584fdefa0a0ba420bce984fb2e"},"reducer_call":{"reducer_name":"extract","reducer_id":179,"args":{"Text":"[[27,44791943]]"},"request_id":7506},"energy_quanta_used":{"quanta":2191729615},"host_execution_duration_micros":481}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_interior_set_collapsed_scheduled() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[45147156,[[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147154,0]"},{"Text":"[45147168,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147166,0]"},{"Text":"[45147171,[[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147169,0]"},{"Text":"[45147159,[[6000,{\"0\":[1,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147157,0]"},{"Text":"[45147180,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147178,0]"},{"Text":"[45147177,[[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147175,0]"},{"Text":"[45147150,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147148,0]"},{"Text":"[45147174,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147172,0]"},{"Text":"[45147147,[[6000,{\"0\":[1,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147145,0]"},{"Text":"[45147165,[[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147163,0]"},{"Text":"[45147144,[[6000,{\"0\":[1,18,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147142,0]"},{"Text":"[45147153,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147151,0]"},{"Text":"[45147132,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147130,0]"},{"Text":"[45147186,[[6000,{\"0\":[1,14,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147184,0]"},{"Text":"[45147183,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147181,0]"},{"Text":"[45147189,[[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147187,0]"},{"Text":"[45147141,[[6000,{\"0\":[1,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147139,0]"},{"Text":"[45147195,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147193,0]"},{"Text":"[45147162,[[6000,{\"0\":[1,25,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147160,0]"},{"Text":"[45147138,[[6000,{\"0\":[1,20,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147136,0]"},{"Text":"[45147135,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,45147133,0]"}],"inserts":[{"Text":"[49316495,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316493,0]"},{"Text":"[49316498,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316496,0]"},{"Text":"[49316501,[[6000,{\"0\":[1,20,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316499,0]"},{"Text":"[49316504,[[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316502,0]"},{"Text":"[49316507,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316505,0]"},{"Text":"[49316510,[[6000,{\"0\":[1,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316508,0]"},{"Text":"[49316513,[[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316511,0]"},{"Text":"[49316516,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316514,0]"},{"Text":"[49316519,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316517,0]"},{"Text":"[49316522,[[6000,{\"0\":[1,14,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316520,0]"},{"Text":"[49316525,[[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316523,0]"},{"Text":"[49316528,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316526,0]"},{"Text":"[49316531,[[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316529,0]"},{"Text":"[49316534,[[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316532,0]"},{"Text":"[49316537,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316535,0]"},{"Text":"[49316540,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316538,0]"},{"Text":"[49316543,[[6000,{\"0\":[34,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316541,0]"},{"Text":"[49316546,[[6000,{\"0\":[1,14,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316544,0]"},{"Text":"[49316549,[[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316547,0]"},{"Text":"[49316552,[[6000,{\"0\":[1,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316550,0]"},{"Text":"[49316558,[[6000,{\"0\":[5,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,49316556,0]"}]}]}},"timestamp":{"microseconds":1733763549281448},"caller_identity":{"__identity_bytes":"074c0e57570cc6cc5101906fa0cdd697ff5ffa5aa76d1c0860ab3c3738e141fa"},"caller_address":{"__address_bytes":"00000000000000000000000000000000"},"reducer_call":{"reducer_name":"interior_set_collapsed_scheduled","reducer_id":346,"args":{"Text":"[[5055393,false,12717,{\"0\":1733763549279632}]]"},"request_id":0},"energy_quanta_used":{"quanta":8432862615},"host_execution_duration_micros":23386}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_inventory_create_personal() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[],"inserts":[{"Text":"[48791419,[[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false]],0,24,18096863,48782790]"}]}]}},"timestamp":{"microseconds":1733688013574078},"caller_identity":{"__identity_bytes":"fb49f82ef494286cb521b67ccaf1db2bbf31a472ceda0e8e6a2df8bc1fa5058b"},"caller_address":{"__address_bytes":"b8e05e1e7e66b1cd63cc784fd41a577f"},"reducer_call":{"reducer_name":"inventory_create_personal","reducer_id":347,"args":{"Text":"[18096863]"},"request_id":2063},"energy_quanta_used":{"quanta":638644615},"host_execution_duration_micros":123}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_inventory_sort() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108721,[[6000,{\"0\":[2029842732,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[570558336,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[51880722,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,21,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108718,0]"}],"inserts":[{"Text":"[19108721,[[6000,{\"0\":[51880722,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2029842732,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[570558336,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,21,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108718,0]"}]}]}},"timestamp":{"microseconds":1733694258312317},"caller_identity":{"__identity_bytes":"1b6fc16d6955969e905d98ea55e4e4dd1b9834d419fa648509dd6deedd04c936"},"caller_address":{"__address_bytes":"2ec275b95f0fac2e14ddcfa84539de95"},"reducer_call":{"reducer_name":"inventory_sort","reducer_id":348,"args":{"Text":"[19108721]"},"request_id":14307},"energy_quanta_used":{"quanta":856966615},"host_execution_duration_micros":926}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_item_convert() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19316203,[[6000,{\"0\":[4170016,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170000,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2120006,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170012,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[646142864,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2100010,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170000,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[7039060,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19316200,0]"}],"inserts":[{"Text":"[19316203,[[6000,{\"0\":[4170016,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170000,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2120006,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170012,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[646142864,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2100010,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170004,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170000,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1974958555,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[7039060,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19316200,0]"}]}]}},"timestamp":{"microseconds":1733694282649619},"caller_identity":{"__identity_bytes":"ae9507d2218823323c7e1d5287b8b75f9965e421057287d32b76a05c5c3a929d"},"caller_address":{"__address_bytes":"7d099dceb57afe51987885a742ed39e3"},"reducer_call":{"reducer_name":"item_convert","reducer_id":349,"args":{"Text":"[[705122582,0,10]]"},"request_id":6676},"energy_quanta_used":{"quanta":43569385615},"host_execution_duration_micros":9625}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_item_drop() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108749,[[6000,{\"0\":[570558336,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[385876987,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[385876987,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[385876987,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170022,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170022,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170022,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1795386038,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1795386038,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[321993696,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[321993696,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108746,0]"}],"inserts":[{"Text":"[19108749,[[6000,{\"0\":[570558336,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[385876987,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[385876987,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[385876987,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170022,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170022,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3170022,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1795386038,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1795386038,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[321993696,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[321993696,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108746,0]"}]}]}},"timestamp":{"microseconds":1733758353372057},"caller_identity":{"__identity_bytes":"78fea2e7d03ade2a626e9dcdc8d7c9dc5033b4d33086823ec75b98e3f23d487c"},"caller_address":{"__address_bytes":"d5be30bf33fece4735c647bb48dc36e5"},"reducer_call":{"reducer_name":"item_drop","reducer_id":351,"args":{"Text":"[[[19108749,17]]]"},"request_id":5739},"energy_quanta_used":{"quanta":751928615},"host_execution_duration_micros":643}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_item_pick_up() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108611,[[6000,{\"0\":[35341746,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[639786510,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1089048538,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1158434858,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[781210816,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,46,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108608,0]"}],"inserts":[{"Text":"[19108611,[[6000,{\"0\":[35341746,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[639786510,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1089048538,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1158434858,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[781210816,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,46,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108608,0]"}]}]}},"timestamp":{"microseconds":1733758379955335},"caller_identity":{"__identity_bytes":"5c28286006f216a6ed31c595e45780ca0d3662d3743caac16f6da08b0a528a8d"},"caller_address":{"__address_bytes":"7d77237f7535fe144c4dd00b92b30076"},"reducer_call":{"reducer_name":"item_pick_up","reducer_id":352,"args":{"Text":"[[1010001]]"},"request_id":288},"energy_quanta_used":{"quanta":950117615},"host_execution_duration_micros":634}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_item_stack_move() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19182430,[[6000,{\"0\":[2030012,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2030008,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2030016,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2030020,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220000,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220002,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220003,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220004,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[131074151,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[197900802,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2008582834,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[588329371,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170009,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19182427,0]"},{"Text":"[47183713,[[60000,{\"0\":[4220000,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4220002,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4220003,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3220002,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3220000,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3220004,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2220000,4,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[2220003,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2220004,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1220000,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1220002,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1220003,6,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1220004,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[365034169,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2105517822,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1304761590,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1790380706,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[786636466,1,{\"0\":[]},{\"1\":[]}]},false],[60000,{\"0\":[1278688272,3,{\"0\":[]},{\"1\":[]}]},false],[60000,{\"0\":[1455673331,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1030016,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false]],0,70,47183648,0]"}],"inserts":[{"Text":"[19182430,[[6000,{\"0\":[2030012,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2030008,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2030016,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2030020,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220000,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220002,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220003,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220004,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2220000,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[131074151,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[197900802,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2008582834,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[588329371,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170009,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19182427,0]"},{"Text":"[47183713,[[60000,{\"0\":[4220000,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4220002,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4220003,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3220002,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3220000,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3220004,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2220000,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[2220003,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2220004,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1220000,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1220002,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1220003,6,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1220004,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[365034169,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2105517822,3,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1304761590,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1790380706,2,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[786636466,1,{\"0\":[]},{\"1\":[]}]},false],[60000,{\"0\":[1278688272,3,{\"0\":[]},{\"1\":[]}]},false],[60000,{\"0\":[1455673331,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1030016,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false]],0,70,47183648,0]"}]}]}},"timestamp":{"microseconds":1733688980002295},"caller_identity":{"__identity_bytes":"8e0480030ce09e6aab28e6388c350e4e1b5dbf0791bdf90e25cce1df02683616"},"caller_address":{"__address_bytes":"5df48b46622b29aaab70a278a017bde4"},"reducer_call":{"reducer_name":"item_stack_move","reducer_id":355,"args":{"Text":"[[[47183713,6],[19182430,0],1]]"},"request_id":33887},"energy_quanta_used":{"quanta":991699615},"host_execution_duration_micros":237}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_item_stack_move_all() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107226,[[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2020003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1170019,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170017,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19107223,0]"},{"Text":"[40584779,[[60000,{\"0\":[6010002,23,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010002,146,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4010002,132,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010002,116,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2010002,279,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,262,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,142,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010001,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010001,17,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4010001,65,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010001,5,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010001,64,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[1020003,30,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1103116665,70,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1103116665,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[362614434,25,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[362614434,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[2020003,15,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2020003,100,{\"0\":[]},{\"0\":0}]},false]],0,70,40584564,0]"}],"inserts":[{"Text":"[19107226,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1170019,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170017,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19107223,0]"},{"Text":"[40584779,[[60000,{\"0\":[6010002,23,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010002,146,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4010002,132,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010002,116,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2010002,279,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010002,262,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,500,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[11012,142,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010001,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010001,17,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4010001,65,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010001,5,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1010001,64,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2020003,15,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[1020003,30,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1103116665,70,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1103116665,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[362614434,25,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[362614434,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[2020003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2020003,100,{\"0\":[]},{\"0\":0}]},false]],0,70,40584564,0]"}]}]}},"timestamp":{"microseconds":1733694246286568},"caller_identity":{"__identity_bytes":"b5d1a8c07dd5dbb0a6a164a618eafef721cbaf381a48dc4c99f98ea104d1bbe4"},"caller_address":{"__address_bytes":"9e66a56697666017032e284e1a57a8c9"},"reducer_call":{"reducer_name":"item_stack_move_all","reducer_id":356,"args":{"Text":"[[[19107226,6],[40584779,0]]]"},"request_id":22963},"energy_quanta_used":{"quanta":578339615},"host_execution_duration_micros":566}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_item_stack_split() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107292,[[6000,{\"0\":[5170009,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1338355262,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[265562740,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[523199222,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1484950256,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[800115571,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1017477842,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[154468670,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[893965087,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1869772193,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[653230956,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3050001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3050001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4050003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5050003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107289,0]"}],"inserts":[{"Text":"[19107292,[[6000,{\"0\":[5170009,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1338355262,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[265562740,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[523199222,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1484950256,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[800115571,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1017477842,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[154468670,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[893965087,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1869772193,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[653230956,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3050001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3050001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4050003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5050003,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5050003,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107289,0]"}]}]}},"timestamp":{"microseconds":1733689017782712},"caller_identity":{"__identity_bytes":"5ebb546b9de3cd70f235cad4f0a689ed1b583983f092a1e9303c910d1a4efcd0"},"caller_address":{"__address_bytes":"53b8ad83f72a0d3060a875681a64ecca"},"reducer_call":{"reducer_name":"item_stack_split","reducer_id":357,"args":{"Text":"[[[19107292,14],5050003,5]]"},"request_id":45},"energy_quanta_used":{"quanta":136047615},"host_execution_duration_micros":263}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_loot_chest_spawn() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[],"inserts":[{"Text":"[48786258,[[6000,{\"0\":[601,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,15,48786256,0]"}]}]}},"timestamp":{"microseconds":1733687121744052},"caller_identity":{"__identity_bytes":"074c0e57570cc6cc5101906fa0cdd697ff5ffa5aa76d1c0860ab3c3738e141fa"},"caller_address":{"__address_bytes":"00000000000000000000000000000000"},"reducer_call":{"reducer_name":"loot_chest_spawn","reducer_id":366,"args":{"Text":"[[24,5887097,84875,{\"0\":1733687121742836}]]"},"request_id":0},"energy_quanta_used":{"quanta":165237615},"host_execution_duration_micros":329}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_passive_craft_collect() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[26816424,[[6000,{\"0\":[1210037,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110003,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1050001,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1030025,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1070001,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[253606511,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11002,20,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110012,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110010,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1030001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100007,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100005,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1239191446,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1210003,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1050001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010010,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,11,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11007,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100002,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1140008,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170002,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,26816421,0]"}],"inserts":[{"Text":"[26816424,[[6000,{\"0\":[1210037,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110003,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1050001,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1030025,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1070001,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[253606511,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11002,20,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110012,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110010,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1030001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100007,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100005,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1239191446,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1210003,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1050001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010010,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,11,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11007,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1100002,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11009,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1140008,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170002,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,26816421,0]"}]}]}},"timestamp":{"microseconds":1733689574635052},"caller_identity":{"__identity_bytes":"7dc152e66904a7881b471bae5a3f08ff16126f72fa7fd4e1678f46834079f2fc"},"caller_address":{"__address_bytes":"b2904b8a11ddab3b808ac5c3a60c2e01"},"reducer_call":{"reducer_name":"passive_craft_collect","reducer_id":369,"args":{"Text":"[34529661]"},"request_id":106},"energy_quanta_used":{"quanta":27064363615},"host_execution_duration_micros":5951}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_passive_craft_collect_all() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107292,[[6000,{\"0\":[5170009,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1338355262,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[265562740,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[523199222,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1484950256,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[800115571,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1017477842,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[154468670,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[893965087,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1869772193,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107289,0]"}],"inserts":[{"Text":"[19107292,[[6000,{\"0\":[5170009,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1338355262,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[265562740,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[523199222,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1484950256,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[800115571,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1017477842,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[154468670,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[893965087,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1869772193,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1442354536,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107289,0]"}]}]}},"timestamp":{"microseconds":1733688980032165},"caller_identity":{"__identity_bytes":"5ebb546b9de3cd70f235cad4f0a689ed1b583983f092a1e9303c910d1a4efcd0"},"caller_address":{"__address_bytes":"53b8ad83f72a0d3060a875681a64ecca"},"reducer_call":{"reducer_name":"passive_craft_collect_all","reducer_id":370,"args":{"Text":"[29032324]"},"request_id":22},"energy_quanta_used":{"quanta":8048929615},"host_execution_duration_micros":1309}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_passive_craft_queue() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107238,[[6000,{\"0\":[1100014,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[570558336,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[356873769,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[129627414,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[653230956,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19107235,0]"},{"Text":"[44804807,[[60000,{\"0\":[5050003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5050003,39,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4050003,85,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3050003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3050003,87,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2050003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2050003,18,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4050001,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3050001,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010001,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010001,99,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5170009,30,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4040003,75,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4040003,14,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010001,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010001,56,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2040003,86,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[5040003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5040003,56,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"0\":[550006,3,{\"1\":[]},{\"1\":[]}]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false]],0,24,44804787,0]"}],"inserts":[{"Text":"[19107238,[[6000,{\"0\":[1100014,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[570558336,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[356873769,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[129627414,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3040003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[653230956,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19107235,0]"},{"Text":"[44804807,[[60000,{\"0\":[5050003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5050003,39,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4050003,85,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3050003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3050003,87,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2050003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2050003,18,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4050001,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3050001,1,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010001,99,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[3010001,99,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5170009,30,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4040003,75,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[4040003,14,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010001,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5010001,56,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[2040003,86,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"0\":[5040003,100,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[5040003,56,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false],[600000,{\"0\":[550006,3,{\"1\":[]},{\"1\":[]}]},false],[600000,{\"1\":[]},false],[600000,{\"1\":[]},false]],0,24,44804787,0]"}]}]}},"timestamp":{"microseconds":1733758331410406},"caller_identity":{"__identity_bytes":"f3687be6904d812d70d41c2ecf59299ed5d76faaf856833853fb75fa0fb36799"},"caller_address":{"__address_bytes":"683219815f99ea9454008ed7a5fb23e7"},"reducer_call":{"reducer_name":"passive_craft_queue","reducer_id":372,"args":{"Text":"[[305000,34282006]]"},"request_id":18216},"energy_quanta_used":{"quanta":561031615},"host_execution_duration_micros":438}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_paving_place_tile() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107250,[[6000,{\"0\":[3170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4030001,100,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5030001,100,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[11001,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19107247,0]"}],"inserts":[{"Text":"[19107250,[[6000,{\"0\":[3170019,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4030001,100,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5030001,99,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5130003,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[11001,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19107247,0]"}]}]}},"timestamp":{"microseconds":1733762867916748},"caller_identity":{"__identity_bytes":"7679cb9a2d18ce1600b2866df6981dfb66db6f49e09c4002c23c4b908662e789"},"caller_address":{"__address_bytes":"6d8236cba9766c953b3e6c235c9f105c"},"reducer_call":{"reducer_name":"paving_place_tile","reducer_id":376,"args":{"Text":"[[[5927,7771,1],6]]"},"request_id":443},"energy_quanta_used":{"quanta":900802615},"host_execution_duration_micros":573}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_player_death_start() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[21859418,[[6000,{\"0\":[1110003,500,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2110003,177,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[552455373,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2130005,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1170006,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170006,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,21859415,0]"}],"inserts":[{"Text":"[21859418,[[6000,{\"0\":[1110003,500,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2110003,177,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[552455373,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2130005,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1170006,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170006,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,21859415,0]"}]}]}},"timestamp":{"microseconds":1733695976999750},"caller_identity":{"__identity_bytes":"074c0e57570cc6cc5101906fa0cdd697ff5ffa5aa76d1c0860ab3c3738e141fa"},"caller_address":{"__address_bytes":"00000000000000000000000000000000"},"reducer_call":{"reducer_name":"player_death_start","reducer_id":383,"args":{"Text":"[[21859415,25703,{\"0\":1733695976994307}]]"},"request_id":0},"energy_quanta_used":{"quanta":1566292615},"host_execution_duration_micros":1737}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_pocket_swap_contents() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108638,[[6000,{\"1\":[]},false],[6000,{\"0\":[767727086,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1142832553,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1724476397,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2063037992,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[7039060,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4050001,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4050001,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[51880722,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4020003,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4090004,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108635,0]"}],"inserts":[{"Text":"[19108638,[[6000,{\"1\":[]},false],[6000,{\"0\":[767727086,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2063037992,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1142832553,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1724476397,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[7039060,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4050001,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4050001,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[51880722,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4020003,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4090004,8,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19108635,0]"}]}]}},"timestamp":{"microseconds":1733758347319762},"caller_identity":{"__identity_bytes":"2619ed23cf18c234522d1a3d37391b2f9f21f46a915193655973d5a8f4d2b41a"},"caller_address":{"__address_bytes":"b6431e004546f5a0862e6b5476aebb1c"},"reducer_call":{"reducer_name":"pocket_swap_contents","reducer_id":397,"args":{"Text":"[[[19108638,15],[19108638,12],3]]"},"request_id":2174},"energy_quanta_used":{"quanta":948849615},"host_execution_duration_micros":612}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_project_site_add_materials() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107532,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2516328,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[101348134,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[3000,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,19107529,0]"}],"inserts":[{"Text":"[19107532,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2516328,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[101348134,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[3000,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,19107529,0]"}]}]}},"timestamp":{"microseconds":1733694265680811},"caller_identity":{"__identity_bytes":"68a275bbc4f8c25adc6240fa16ebd28c8cc86ac6fea338c5e2497ed7f5b1f469"},"caller_address":{"__address_bytes":"1e7c3ed79a57891b689110daa5466110"},"reducer_call":{"reducer_name":"project_site_add_materials","reducer_id":399,"args":{"Text":"[[48848119,[[19107532,3]]]]"},"request_id":14997},"energy_quanta_used":{"quanta":263192615},"host_execution_duration_micros":509}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_project_site_advance_project() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4151,"table_name":"ExperienceState","deletes":[{"Text":"[27852559,[[1,0],[3,56947],[15,6987],[13,40565],[16,0],[11,37575],[12,72818],[14,266828],[2,155604],[9,82210],[8,87062],[20,0],[4,136549],[5,153018],[7,89555],[18,41266],[6,101676],[10,32762],[17,0],[19,0]]]"}],"inserts":[{"Text":"[27852559,[[1,0],[3,56947],[15,6993],[13,40565],[16,0],[11,37575],[12,72818],[14,266828],[2,155604],[9,82210],[8,87062],[20,0],[4,136549],[5,153018],[7,89555],[18,41266],[6,101676],[10,32762],[17,0],[19,0]]]"}]},{"table_id":4318,"table_name":"BuildingState","deletes":[],"inserts":[{"Text":"[48848421,48837462,10,2036,27852559,\"\"]"}]},{"table_id":4250,"table_name":"InventoryState","deletes":[],"inserts":[{"Text":"[48848468,[[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false],[60000,{\"1\":[]},false]],0,30,48848421,0]"}]}]}},"timestamp":{"microseconds":1733694383587189},"caller_identity":{"__identity_bytes":"cdeacfb6d73ec643c95238bd9f811044e91887d38586759275534c133dd14582"},"caller_address":{"__address_bytes":"b8278ef47eb734dda71c6c7f886950bd"},"reducer_call":{"reducer_name":"project_site_advance_project","reducer_id":400,"args":{"Text":"[[48848421]]"},"request_id":1597},"energy_quanta_used":{"quanta":1078355615},"host_execution_duration_micros":454}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_project_site_place() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[27886807,[[6000,{\"0\":[6,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[520255004,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2022077499,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[893927592,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1256522022,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1522116038,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[225094001,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130002,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130002,44,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,33,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,27886804,0]"}],"inserts":[{"Text":"[27886807,[[6000,{\"0\":[6,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[36,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[520255004,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2022077499,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[893927592,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1256522022,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1522116038,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[225094001,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[11001,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130002,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4130002,44,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[3130005,33,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010002,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1010001,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,27886804,0]"}]},{"table_id":4318,"table_name":"BuildingState","deletes":[],"inserts":[{"Text":"[48848126,0,0,11,27886804,\"\"]"}]}]}},"timestamp":{"microseconds":1733694271019517},"caller_identity":{"__identity_bytes":"cc61c4de03311b6ace45921fbf7c9c278a920b6d3813e9eb0774b3efbe3b422c"},"caller_address":{"__address_bytes":"74adc8c820435710c3b7a7a6d6dc3ec0"},"reducer_call":{"reducer_name":"project_site_place","reducer_id":403,"args":{"Text":"[[[5731,7893,1],1044,0,0]]"},"request_id":21048},"energy_quanta_used":{"quanta":1319958615},"host_execution_duration_micros":2164}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_scroll_read() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108721,[[6000,{\"0\":[51880722,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2029842732,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[570558336,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5170004,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1369637069,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108718,0]"}],"inserts":[{"Text":"[19108721,[[6000,{\"0\":[51880722,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2029842732,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[570558336,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1170019,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,50,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5100010,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5170004,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108718,0]"}]}]}},"timestamp":{"microseconds":1733694278305928},"caller_identity":{"__identity_bytes":"1b6fc16d6955969e905d98ea55e4e4dd1b9834d419fa648509dd6deedd04c936"},"caller_address":{"__address_bytes":"2ec275b95f0fac2e14ddcfa84539de95"},"reducer_call":{"reducer_name":"scroll_read","reducer_id":424,"args":{"Text":"[[23]]"},"request_id":14326},"energy_quanta_used":{"quanta":44514153615},"host_execution_duration_micros":11626}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_trade_accept() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19108321,[[6000,{\"0\":[3170009,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[197900802,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2008582834,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[588329371,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[131074151,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[51880722,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[998429872,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[356873769,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1473664712,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1877971246,1,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1735478313,1,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[578594209,1,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1686060207,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[241830765,1,{\"0\":[]},{\"0\":0}]},true],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108318,0]"},{"Text":"[29110099,[[6000,{\"0\":[4110003,342,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[4170006,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[36,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,29110096,0]"}],"inserts":[{"Text":"[19108321,[[6000,{\"0\":[3170009,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[197900802,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2008582834,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[588329371,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[131074151,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[51880722,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[998429872,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[356873769,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1473664712,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2082692656,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1686060207,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1182839338,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19108318,0]"},{"Text":"[29110099,[[6000,{\"0\":[4110003,342,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1877971246,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170006,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1735478313,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[241830765,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[578594209,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[36,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[601,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,29110096,0]"}]}]}},"timestamp":{"microseconds":1733688984025763},"caller_identity":{"__identity_bytes":"bcf1f8e470145826e3eab0dce1d889d725d8e37ff16bc8c271c7f2d147dd626f"},"caller_address":{"__address_bytes":"aba95a0a2fe8f5dbf05f6ee1404e471a"},"reducer_call":{"reducer_name":"trade_accept","reducer_id":520,"args":{"Text":"[[48797471]]"},"request_id":11519},"energy_quanta_used":{"quanta":30263054615},"host_execution_duration_micros":9844}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_trade_add_item() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19319932,[[6000,{\"0\":[1519076834,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1843741445,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[591497586,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19319929,0]"}],"inserts":[{"Text":"[19319932,[[6000,{\"0\":[1519076834,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1519076834,9,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[5170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1843741445,1,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[591497586,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19319929,0]"}]}]}},"timestamp":{"microseconds":1733689108110754},"caller_identity":{"__identity_bytes":"286d31281b6bee68a5255a65a5e137207a58307353d989945f14f73d747fe8e0"},"caller_address":{"__address_bytes":"08fd74516e7e7c7cef79393a13fdcee7"},"reducer_call":{"reducer_name":"trade_add_item","reducer_id":522,"args":{"Text":"[[48798049,-1,3,0]]"},"request_id":14933},"energy_quanta_used":{"quanta":99912615},"host_execution_duration_micros":350}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_trade_decline() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107532,[[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[101348134,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,4,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1020003,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1464553255,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[349530101,1,{\"0\":[]},{\"0\":0}]},true],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107529,0]"}],"inserts":[{"Text":"[19107532,[[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[101348134,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1020003,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1464553255,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[349530101,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107529,0]"}]}]}},"timestamp":{"microseconds":1733695238685957},"caller_identity":{"__identity_bytes":"68a275bbc4f8c25adc6240fa16ebd28c8cc86ac6fea338c5e2497ed7f5b1f469"},"caller_address":{"__address_bytes":"1e7c3ed79a57891b689110daa5466110"},"reducer_call":{"reducer_name":"trade_decline","reducer_id":524,"args":{"Text":"[[48853210]]"},"request_id":16585},"energy_quanta_used":{"quanta":386882615},"host_execution_duration_micros":276}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_trade_remove_item() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19107532,[[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[101348134,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,4,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1020003,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1464553255,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[349530101,1,{\"0\":[]},{\"0\":0}]},true],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107529,0]"}],"inserts":[{"Text":"[19107532,[[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2516328,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[101348134,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[767727086,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1040002,4,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[1020003,6,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1464553255,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[349530101,1,{\"0\":[]},{\"0\":0}]},true],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,19107529,0]"}]}]}},"timestamp":{"microseconds":1733695251906741},"caller_identity":{"__identity_bytes":"68a275bbc4f8c25adc6240fa16ebd28c8cc86ac6fea338c5e2497ed7f5b1f469"},"caller_address":{"__address_bytes":"1e7c3ed79a57891b689110daa5466110"},"reducer_call":{"reducer_name":"trade_remove_item","reducer_id":527,"args":{"Text":"[[48853246,5]]"},"request_id":16598},"energy_quanta_used":{"quanta":122245615},"host_execution_duration_micros":138}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_trade_sessions_agent_loop() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[19326142,[[6000,{\"0\":[2170009,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,7,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"0\":[767727086,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},true],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2124266002,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[261514602,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[739189753,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1973037841,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1793055245,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19326139,0]"}],"inserts":[{"Text":"[19326142,[[6000,{\"0\":[2170009,3,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2170009,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[4170009,7,{\"0\":[]},{\"1\":[]}]},false],[6000,{\"0\":[767727086,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[2010001,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[2124266002,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[261514602,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[739189753,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1973037841,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1793055245,1,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false]],0,25,19326139,0]"}]}]}},"timestamp":{"microseconds":1733763178570094},"caller_identity":{"__identity_bytes":"074c0e57570cc6cc5101906fa0cdd697ff5ffa5aa76d1c0860ab3c3738e141fa"},"caller_address":{"__address_bytes":"00000000000000000000000000000000"},"reducer_call":{"reducer_name":"trade_sessions_agent_loop","reducer_id":528,"args":{"Text":"[[1,{\"1\":5000000}]]"},"request_id":0},"energy_quanta_used":{"quanta":251351615},"host_execution_duration_micros":252}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-//
-//     #[test]
-//     fn handle_user_create() {
-//         let raw_event_data = r#"{"status":{"Committed":{"tables":[{"table_id":4250,"table_name":"InventoryState","deletes":[{"Text":"[24931948,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false]],0,25,24931945,0]"},{"Text":"[49246566,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1110016,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110016,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110016,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1130005,5,{\"0\":[]},{\"0\":0}]},false],[60000,{\"1\":[]},false]],0,16,48623070,0]"}],"inserts":[{"Text":"[24931948,[[6000,{\"0\":[1080002,4,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1080001,2,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1,1,{\"1\":[]},{\"1\":[]}]},false]],0,25,24931945,0]"},{"Text":"[49246566,[[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1110016,5,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110016,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"0\":[1110016,10,{\"0\":[]},{\"0\":0}]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"1\":[]},false],[6000,{\"0\":[1130005,5,{\"0\":[]},{\"0\":0}]},false],[60000,{\"0\":[1,1,{\"1\":[]},{\"1\":[]}]},false]],0,16,48623070,0]"}]}]}},"timestamp":{"microseconds":1733766085277332},"caller_identity":{"__identity_bytes":"1dd1e1f819b56f48887f610459dbe32d12475dbeaa70229a6282967d4f1b56cb"},"caller_address":{"__address_bytes":"48a9d9d656252126975c1d87dd4b47d8"},"reducer_call":{"reducer_name":"craft_cancel","reducer_id":116,"args":{"Text":"[[49328573]]"},"request_id":3894},"energy_quanta_used":{"quanta":2158209615},"host_execution_duration_micros":601}"#;
-//         let parsed_event_data: TransactionUpdate = serde_json::from_str(raw_event_data).unwrap();
-//     }
-// }
+/// Maps the name of the reducer that touched an inventory to the cause stored on the changelog.
+pub(crate) fn classify_reducer(reducer_name: &str) -> ChangeCause {
+    if reducer_name.starts_with("admin_") || reducer_name.starts_with("cheat_") {
+        return ChangeCause::Admin;
+    }
+
+    match reducer_name {
+        "barter_stall_order_accept" | "order_collect" | "closed_listing_collect" => {
+            ChangeCause::MarketFill
+        }
+        "barter_stall_order_create"
+        | "barter_stall_order_delete"
+        | "order_cancel"
+        | "order_edit_buy_order"
+        | "order_edit_sell_order"
+        | "order_post_buy_order"
+        | "order_post_sell_order" => ChangeCause::MarketOrder,
+        "item_pick_up" => ChangeCause::PickUp,
+        "item_drop" => ChangeCause::Drop,
+        "claim_treasury_deposit"
+        | "claim_resupply"
+        | "claim_resupply_start"
+        | "empire_donate_item"
+        | "empire_add_siege_supplies"
+        | "empire_resupply_node"
+        | "empire_resupply_node_start"
+        | "project_site_add_materials"
+        | "rent_deposit_coins" => ChangeCause::Deposit,
+        "claim_withdraw_from_treasury" | "empire_withdraw_item" => ChangeCause::Withdraw,
+        "building_deconstruct" | "building_deconstruct_start" => ChangeCause::Deconstruct,
+        "extract" | "extract_start" => ChangeCause::Extract,
+        "inventory_sort"
+        | "item_stack_move"
+        | "item_stack_move_all"
+        | "item_stack_split"
+        | "pocket_swap_contents" => ChangeCause::Move,
+        "eat" | "item_use" | "item_use_start" | "item_convert" | "item_convert_start" => {
+            ChangeCause::Consume
+        }
+        "project_site_place" | "project_site_advance_project" | "building_repair" => {
+            ChangeCause::Build
+        }
+        "player_complete_task" | "request_stage_reward" | "complete_quest_chain" => {
+            ChangeCause::Quest
+        }
+        name if name.starts_with("craft_") || name.starts_with("passive_craft_") => {
+            ChangeCause::Craft
+        }
+        name if name.starts_with("trade_") => ChangeCause::Trade,
+        name if name.starts_with("equipment_") => ChangeCause::Equipment,
+        name if name.starts_with("loot_chest_") || name == "enemy_spawn_loot" => ChangeCause::Loot,
+        name if name.contains("onboarding") || name.contains("quest") => ChangeCause::Quest,
+        _ => ChangeCause::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_known_reducers() {
+        assert_eq!(classify_reducer("craft_collect"), ChangeCause::Craft);
+        assert_eq!(
+            classify_reducer("passive_craft_collect_all"),
+            ChangeCause::Craft
+        );
+        assert_eq!(classify_reducer("trade_accept_session"), ChangeCause::Trade);
+        assert_eq!(classify_reducer("item_pick_up"), ChangeCause::PickUp);
+        assert_eq!(classify_reducer("item_drop"), ChangeCause::Drop);
+        assert_eq!(classify_reducer("inventory_sort"), ChangeCause::Move);
+        assert_eq!(
+            classify_reducer("barter_stall_order_accept"),
+            ChangeCause::MarketFill
+        );
+        assert_eq!(
+            classify_reducer("claim_withdraw_from_treasury"),
+            ChangeCause::Withdraw
+        );
+        assert_eq!(classify_reducer("claim_resupply"), ChangeCause::Deposit);
+        assert_eq!(
+            classify_reducer("building_deconstruct"),
+            ChangeCause::Deconstruct
+        );
+        assert_eq!(
+            classify_reducer("complete_onboarding_state"),
+            ChangeCause::Quest
+        );
+        assert_eq!(
+            classify_reducer("cheat_item_stack_grant"),
+            ChangeCause::Admin
+        );
+    }
+
+    #[test]
+    fn unknown_reducers_fall_back_to_unknown() {
+        assert_eq!(classify_reducer("enemy_spawn"), ChangeCause::Unknown);
+        assert_eq!(classify_reducer(""), ChangeCause::Unknown);
+    }
+}
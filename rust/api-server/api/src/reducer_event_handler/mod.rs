@@ -1 +1 @@
-mod inventory;
+pub(crate) mod inventory;
@@ -1,152 +1,28 @@
-use crate::AppState;
-use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
+use crate::websocket::reconcile::reconciled_table;
+use crate::websocket::table_sync::SyncedTable;
 use entity::resource_desc;
-use migration::OnConflict;
-use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter, sea_query};
-use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
-use tokio::time::sleep;
+use entity::shared::Region;
+use game_module::module_bindings::ResourceDesc;
 
-pub(crate) fn start_worker_resource_desc(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<game_module::module_bindings::ResourceDesc>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict = sea_query::OnConflict::column(resource_desc::Column::Id)
-            .update_columns([
-                resource_desc::Column::Name,
-                resource_desc::Column::Description,
-                resource_desc::Column::Tier,
-                resource_desc::Column::Tag,
-                resource_desc::Column::Rarity,
-                resource_desc::Column::OnDestroyYield,
-                resource_desc::Column::OnDestroyYieldResourceId,
-                resource_desc::Column::IconAssetName,
-            ])
-            .to_owned();
+reconciled_table!(resource_desc, id: i32, Id, shared);
 
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
+impl SyncedTable for resource_desc::Entity {
+    type Row = ResourceDesc;
 
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("resource_desc", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                // let currently_known = ::entity::resource_desc::Entity::find()
-                                //     .all(&global_app_state.conn)
-                                //     .await
-                                //     .map_or(vec![], |aa| aa)
-                                //     .into_iter()
-                                //     .map(|value| (value.id, value))
-                                //     .collect::<HashMap<_, _>>();
-
-                                for model in data.into_iter().map(|value| {
-                                    let model: ::entity::resource_desc::Model = value.into();
-                                    model
-                                }) {
-                                    local_messages.push(model.into_active_model());
-                                    if local_messages.len() >= batch_size {
-                                       insert_multiple(&global_app_state, &on_conflict, &mut local_messages).await;
-                                    }
-                                }
-                                if !local_messages.is_empty() {
-                                    insert_multiple(&global_app_state, &on_conflict, &mut local_messages).await;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, .. } => {
-                                let model: ::entity::resource_desc::Model = new.into();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.id) {
-                                    messages_delete.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, .. } => {
-                                let model: ::entity::resource_desc::Model = new.into();
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.id) {
-                                    messages_delete.remove(index);
-                                }
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete,.. } => {
-                                let model: ::entity::resource_desc::Model = delete.into();
-                                let id = model.id;
-                                if let Some(index) = messages.iter().position(|value| value.id.as_ref() == &model.id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => {
-                        break;
-                    }
-                    else => {
-                        break;
-                    }
-                }
-            }
-
-            if !messages.is_empty() {
-                insert_multiple(&global_app_state, &on_conflict, &mut messages).await;
-            }
-
-            if !messages_delete.is_empty() {
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::resource_desc::Entity::delete_many()
-                        .filter(::entity::resource_desc::Column::Id.is_in(chunk_ids.clone()))
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            ResourceDesc = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete ResourceDesc"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
-
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
-
-async fn insert_multiple(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::resource_desc::ActiveModel>,
-) {
-    let insert = ::entity::resource_desc::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await;
-
-    if let Err(err) = insert {
-        tracing::error!("Error inserting ResourceDesc: {}", err);
+    fn model(row: Self::Row, _region: Region) -> Self::Model {
+        row.into()
     }
 
-    messages.clear();
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            resource_desc::Column::Name,
+            resource_desc::Column::Description,
+            resource_desc::Column::Tier,
+            resource_desc::Column::Tag,
+            resource_desc::Column::Rarity,
+            resource_desc::Column::OnDestroyYield,
+            resource_desc::Column::OnDestroyYieldResourceId,
+            resource_desc::Column::IconAssetName,
+        ]
+    }
 }
@@ -0,0 +1,277 @@
+use crate::error::ApiError;
+use crate::{AppRouter, AppState};
+use axum::Router;
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+/// How long `/readyz` waits for Postgres before it counts as unhealthy.
+const DATABASE_PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The routes served while the instance is starting up.
+const STARTUP_ROUTES: [&str; 4] = ["/healthz", "/readyz", "/status", "/metrics"];
+
+pub(crate) fn get_routes() -> AppRouter {
+    Router::new()
+        .route("/healthz", axum::routing::get(get_healthz))
+        .route("/readyz", axum::routing::get(get_readyz))
+        .route("/status", axum_codec::routing::get(get_status).into())
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// The SpacetimeDB connection of one configured database. Timestamps are unix milliseconds.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct DatabaseStatus {
+    /// Whether `/readyz` waits for the subscription of this database.
+    required: bool,
+    connected: bool,
+    /// Whether the subscription of the current connection got its initial snapshot.
+    subscribed: bool,
+    /// Failed attempts since the connection last stayed up, the retry loop gives up after 15.
+    reconnect_attempts: u64,
+    last_error: Option<String>,
+    last_error_at: Option<u64>,
+    connected_at: Option<u64>,
+    subscription_applied_at: Option<u64>,
+    /// Rows per table in the snapshot of the last applied subscription.
+    initial_rows: BTreeMap<String, u64>,
+}
+
+/// Startup progress and the state of every SpacetimeDB connection.
+#[derive(Default)]
+pub(crate) struct Status {
+    migrations_applied: AtomicBool,
+    caches_loaded: AtomicBool,
+    databases: DashMap<String, DatabaseStatus>,
+}
+
+impl Status {
+    pub(crate) fn migrations_applied(&self) {
+        self.migrations_applied.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn caches_loaded(&self) {
+        self.caches_loaded.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the migrations ran and the caches are filled from Postgres.
+    fn started(&self) -> bool {
+        self.migrations_applied.load(Ordering::Relaxed)
+            && self.caches_loaded.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn register(&self, database: &str, required: bool) {
+        self.databases
+            .entry(database.to_string())
+            .or_default()
+            .required = required;
+    }
+
+    pub(crate) fn connected(&self, database: &str) {
+        let mut status = self.databases.entry(database.to_string()).or_default();
+        status.connected = true;
+        status.subscribed = false;
+        status.connected_at = Some(unix_millis());
+    }
+
+    pub(crate) fn disconnected(&self, database: &str, error: Option<String>) {
+        let mut status = self.databases.entry(database.to_string()).or_default();
+        status.connected = false;
+        status.subscribed = false;
+        if let Some(error) = error {
+            status.last_error = Some(error);
+            status.last_error_at = Some(unix_millis());
+        }
+    }
+
+    pub(crate) fn failed(&self, database: &str, error: String) {
+        let mut status = self.databases.entry(database.to_string()).or_default();
+        status.last_error = Some(error);
+        status.last_error_at = Some(unix_millis());
+    }
+
+    pub(crate) fn reconnect_attempts(&self, database: &str, attempts: u64) {
+        self.databases
+            .entry(database.to_string())
+            .or_default()
+            .reconnect_attempts = attempts;
+    }
+
+    pub(crate) fn subscription_applied(&self, database: &str, initial_rows: BTreeMap<String, u64>) {
+        let mut status = self.databases.entry(database.to_string()).or_default();
+        status.subscribed = true;
+        status.subscription_applied_at = Some(unix_millis());
+        status.initial_rows = initial_rows;
+    }
+
+    /// Required databases that are not connected with an applied subscription.
+    fn unsubscribed(&self) -> Vec<String> {
+        let mut databases = self
+            .databases
+            .iter()
+            .filter(|entry| entry.required && !entry.subscribed)
+            .map(|entry| entry.key().clone())
+            .collect::<Vec<_>>();
+        databases.sort();
+
+        databases
+    }
+}
+
+/// Answers `503` until [`Status::started`], except for [`STARTUP_ROUTES`]. The server is bound
+/// before the migrations, so everything else would query tables that may not exist yet or serve
+/// empty caches.
+pub(crate) async fn starting_up(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if state.status.started() || STARTUP_ROUTES.contains(&request.uri().path()) {
+        return next.run(request).await;
+    }
+
+    ApiError::new(StatusCode::SERVICE_UNAVAILABLE, "The server is starting up").into_response()
+}
+
+/// The process is up, says nothing about its dependencies.
+#[utoipa::path(
+    get,
+    path = "/healthz",
+    tag = "meta",
+    responses(
+        (status = OK, description = "The process is alive", content_type = "text/plain"),
+    )
+)]
+pub(crate) async fn get_healthz() -> &'static str {
+    "ok"
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct ReadinessResponse {
+    ready: bool,
+    migrations_applied: bool,
+    database_healthy: bool,
+    caches_loaded: bool,
+    /// Required SpacetimeDB databases still waiting for their subscription.
+    unsubscribed_databases: Vec<String>,
+}
+
+async fn readiness(state: &AppState) -> ReadinessResponse {
+    let migrations_applied = state.status.migrations_applied.load(Ordering::Relaxed);
+    let caches_loaded = state.status.caches_loaded.load(Ordering::Relaxed);
+    let database_healthy = matches!(
+        tokio::time::timeout(DATABASE_PING_TIMEOUT, state.conn.ping()).await,
+        Ok(Ok(()))
+    );
+    let unsubscribed_databases = state.status.unsubscribed();
+
+    ReadinessResponse {
+        ready: migrations_applied
+            && database_healthy
+            && caches_loaded
+            && unsubscribed_databases.is_empty(),
+        migrations_applied,
+        database_healthy,
+        caches_loaded,
+        unsubscribed_databases,
+    }
+}
+
+/// Whether the instance should get traffic, answers `503` with the failed checks otherwise.
+#[utoipa::path(
+    get,
+    path = "/readyz",
+    tag = "meta",
+    responses(
+        (status = OK, body = ReadinessResponse),
+        (status = SERVICE_UNAVAILABLE, body = ReadinessResponse),
+    )
+)]
+pub(crate) async fn get_readyz(state: State<AppState>) -> impl IntoResponse {
+    let readiness = readiness(&state).await;
+    let status = if readiness.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, axum::Json(readiness))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub(crate) struct StatusResponse {
+    readiness: ReadinessResponse,
+    databases: BTreeMap<String, DatabaseStatus>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/status",
+    tag = "meta",
+    responses(
+        (status = OK, body = StatusResponse),
+    )
+)]
+pub(crate) async fn get_status(state: State<AppState>) -> axum_codec::Codec<StatusResponse> {
+    let readiness = readiness(&state).await;
+    let databases = state
+        .status
+        .databases
+        .iter()
+        .map(|entry| (entry.key().clone(), entry.value().clone()))
+        .collect();
+
+    axum_codec::Codec(StatusResponse {
+        readiness,
+        databases,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn required_databases_need_an_applied_subscription() {
+        let status = Status::default();
+        status.register("bitcraft-2", true);
+        status.register("bitcraft-3", false);
+        assert_eq!(status.unsubscribed(), vec!["bitcraft-2".to_string()]);
+
+        status.connected("bitcraft-2");
+        assert_eq!(status.unsubscribed(), vec!["bitcraft-2".to_string()]);
+
+        status.subscription_applied("bitcraft-2", BTreeMap::new());
+        assert!(status.unsubscribed().is_empty());
+
+        status.disconnected("bitcraft-2", Some("connection reset".to_string()));
+        assert_eq!(status.unsubscribed(), vec!["bitcraft-2".to_string()]);
+    }
+
+    #[test]
+    fn started_once_migrated_and_loaded() {
+        let status = Status::default();
+        status.migrations_applied();
+        assert!(!status.started());
+
+        status.caches_loaded();
+        assert!(status.started());
+    }
+}
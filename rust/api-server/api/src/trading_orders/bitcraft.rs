@@ -1,16 +1,16 @@
 use crate::AppState;
+use crate::websocket::ingest_queue::IngestReceiver;
 use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
 use game_module::module_bindings::TradeOrderState;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 use sea_orm::{EntityTrait, sea_query};
 use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::sleep;
 
 pub(crate) fn start_worker_trade_order_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<TradeOrderState>>,
+    mut rx: IngestReceiver<TradeOrderState>,
     _batch_size: usize,
     time_limit: Duration,
 ) {
@@ -162,6 +162,8 @@ pub(crate) fn start_worker_trade_order_state(
             // Your batch processing logic here
             // }
 
+            rx.commit();
+
             // If the channel is closed and we processed the last batch, exit the outer loop
             // if messages.is_empty() && rx.is_closed() {
             if rx.is_closed() {
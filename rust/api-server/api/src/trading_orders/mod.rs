@@ -1,13 +1,16 @@
 pub(crate) mod bitcraft;
 
+use crate::error::{ApiError, ErrorResponse};
 use crate::inventory::resolve_contents;
+use crate::pagination::{ListParams, ListQuery, Paginated};
 use crate::{AppRouter, AppState};
 use axum::Router;
-use axum::extract::State;
-use axum::http::StatusCode;
+use axum::extract::{Query, State};
 use entity::inventory::ExpendedRefrence;
+use sea_orm::Order;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::{IntoParams, ToSchema};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new().route(
@@ -16,40 +19,53 @@ pub(crate) fn get_routes() -> AppRouter {
     )
 }
 
-async fn get_trade_orders(
+/// Trade orders of shops with their items resolved, traveler orders are left out.
+fn resolve_trade_orders(state: &AppState) -> Vec<TradeOrdersWithExpendedRefrence> {
+    state
+        .trade_order_state
+        .iter()
+        .filter(|trade_order| trade_order.traveler_trade_order_id.is_none())
+        .map(|trade_order| TradeOrdersWithExpendedRefrence {
+            entity_id: trade_order.entity_id,
+            remaining_stock: trade_order.remaining_stock,
+            offer_items: trade_order
+                .offer_items
+                .iter()
+                .map(|offer_items| {
+                    let a = Some(offer_items.clone());
+                    resolve_contents(&a, &state.item_desc, &state.cargo_desc).unwrap()
+                })
+                .collect(),
+            offer_cargo_id: trade_order.offer_cargo_id.clone(),
+            required_items: trade_order
+                .required_items
+                .iter()
+                .map(|required_items| {
+                    let a = Some(required_items.clone());
+                    resolve_contents(&a, &state.item_desc, &state.cargo_desc).unwrap()
+                })
+                .collect(),
+            required_cargo_id: trade_order.required_cargo_id.clone(),
+            region: trade_order.region.clone(),
+            shop_entity_id: trade_order.shop_entity_id,
+            traveler_trade_order_id: trade_order.traveler_trade_order_id,
+        })
+        .collect()
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/bitcraft/trade_orders/get_trade_orders",
+    tag = "market",
+    responses(
+        (status = OK, body = TradeOrdersResponse),
+    )
+)]
+pub(crate) async fn get_trade_orders(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<TradeOrdersResponse>, (StatusCode, &'static str)> {
+) -> Result<axum_codec::Codec<TradeOrdersResponse>, ApiError> {
     Ok(axum_codec::Codec(TradeOrdersResponse {
-        trade_orders: state
-            .trade_order_state
-            .iter()
-            .filter(|trade_order| trade_order.traveler_trade_order_id.is_none())
-            .map(|trade_order| TradeOrdersWithExpendedRefrence {
-                entity_id: trade_order.entity_id,
-                remaining_stock: trade_order.remaining_stock,
-                offer_items: trade_order
-                    .offer_items
-                    .iter()
-                    .map(|offer_items| {
-                        let a = Some(offer_items.clone());
-                        resolve_contents(&a, &state.item_desc, &state.cargo_desc).unwrap()
-                    })
-                    .collect(),
-                offer_cargo_id: trade_order.offer_cargo_id.clone(),
-                required_items: trade_order
-                    .required_items
-                    .iter()
-                    .map(|required_items| {
-                        let a = Some(required_items.clone());
-                        resolve_contents(&a, &state.item_desc, &state.cargo_desc).unwrap()
-                    })
-                    .collect(),
-                required_cargo_id: trade_order.required_cargo_id.clone(),
-                region: trade_order.region.clone(),
-                shop_entity_id: trade_order.shop_entity_id,
-                traveler_trade_order_id: trade_order.traveler_trade_order_id,
-            })
-            .collect(),
+        trade_orders: resolve_trade_orders(&state),
         total: state.trade_order_state.len() as u64,
         page: 1,
         per_page: 50,
@@ -188,7 +204,61 @@ async fn get_trade_orders(
     // }))
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub(crate) struct TradeOrderFilters {
+    shop_entity_id: Option<i64>,
+}
+
+#[derive(Clone, Copy)]
+enum TradeOrderSort {
+    EntityId,
+    RemainingStock,
+}
+
+/// Trade orders sorted by `entity_id` unless `sort` is `remaining_stock`.
+#[utoipa::path(
+    get,
+    path = "/api/v1/trade-orders",
+    tag = "market",
+    params(ListParams, TradeOrderFilters),
+    responses(
+        (status = OK, body = Paginated<TradeOrdersWithExpendedRefrence>),
+        (status = BAD_REQUEST, body = ErrorResponse),
+    )
+)]
+pub(crate) async fn list_trade_orders(
+    state: State<AppState>,
+    list: ListQuery,
+    Query(filters): Query<TradeOrderFilters>,
+) -> Result<axum_codec::Codec<Paginated<TradeOrdersWithExpendedRefrence>>, ApiError> {
+    list.no_search()?;
+    let sort = list.sort_by(&[
+        ("entity_id", TradeOrderSort::EntityId),
+        ("remaining_stock", TradeOrderSort::RemainingStock),
+    ])?;
+
+    let mut trade_orders = resolve_trade_orders(&state);
+    trade_orders.retain(|trade_order| {
+        filters
+            .shop_entity_id
+            .is_none_or(|shop_entity_id| trade_order.shop_entity_id == shop_entity_id)
+    });
+    trade_orders.sort_by_key(|trade_order| trade_order.entity_id);
+
+    if let Some((field, order)) = sort {
+        if let TradeOrderSort::RemainingStock = field {
+            trade_orders.sort_by_key(|trade_order| trade_order.remaining_stock);
+        }
+        if order == Order::Desc {
+            trade_orders.reverse();
+        }
+    }
+
+    Ok(axum_codec::Codec(list.paginate(trade_orders)))
+}
+
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub struct TradeOrdersWithExpendedRefrence {
     pub entity_id: i64,
@@ -202,7 +272,7 @@ pub struct TradeOrdersWithExpendedRefrence {
     pub traveler_trade_order_id: Option<i32>,
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 #[ts(export)]
 pub(crate) struct TradeOrdersResponse {
     trade_orders: Vec<TradeOrdersWithExpendedRefrence>,
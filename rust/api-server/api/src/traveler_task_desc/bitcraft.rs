@@ -1,187 +1,38 @@
 use crate::AppState;
-use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
+use crate::desc_cache::DescTable;
+use crate::websocket::reconcile::reconciled_table;
+use crate::websocket::table_sync::SyncedTable;
+use dashmap::DashMap;
+use entity::shared::Region;
+use entity::traveler_task_desc;
 use game_module::module_bindings::TravelerTaskDesc;
-use migration::{OnConflict, sea_query};
-use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter};
-use std::collections::HashMap;
-use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
-use tokio::time::sleep;
 
-pub(crate) fn start_worker_traveler_task_desc(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<TravelerTaskDesc>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict = sea_query::OnConflict::column(::entity::traveler_task_desc::Column::Id)
-            .update_columns([
-                ::entity::traveler_task_desc::Column::SkillId,
-                ::entity::traveler_task_desc::Column::MinLevel,
-                ::entity::traveler_task_desc::Column::MaxLevel,
-                ::entity::traveler_task_desc::Column::RequiredItems,
-                ::entity::traveler_task_desc::Column::RewardedItems,
-                ::entity::traveler_task_desc::Column::RewardedExperience,
-                ::entity::traveler_task_desc::Column::Description,
-            ])
-            .to_owned();
+reconciled_table!(traveler_task_desc, id: i32, Id, shared);
 
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
+impl SyncedTable for traveler_task_desc::Entity {
+    type Row = TravelerTaskDesc;
 
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("traveler_task_desc", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                let mut currently_known_traveler_task_desc = ::entity::traveler_task_desc::Entity::find()
-                                    .all(&global_app_state.conn)
-                                    .await
-                                    .unwrap_or_else(|error| {
-                                        tracing::error!(
-                                            error = error.to_string(),
-                                            "Error while query whole traveler_task_desc state"
-                                        );
-                                        vec![]
-                                    })
-                                    .into_iter()
-                                    .map(|value| (value.id, value))
-                                    .collect::<HashMap<_, _>>();
-
-                                for model in data.into_iter().map(|value| {
-                                    let model: ::entity::traveler_task_desc::Model = value.into();
-
-                                    model
-                                }) {
-                                    global_app_state.traveler_task_desc.insert(model.id, model.clone());
-                                    use std::collections::hash_map::Entry;
-                                    match currently_known_traveler_task_desc.entry(model.id) {
-                                        Entry::Occupied(entry) => {
-                                            let existing_model = entry.get();
-                                            if &model != existing_model {
-                                                local_messages.push(model.into_active_model());
-                                            }
-                                            entry.remove();
-                                        }
-                                        Entry::Vacant(_entry) => {
-                                            local_messages.push(model.into_active_model());
-                                        }
-                                    }
-                                    if local_messages.len() >= batch_size {
-                                       insert_multiple_traveler_task_desc(&global_app_state, &on_conflict, &mut local_messages).await;
-                                    }
-                                };
-                                if !local_messages.is_empty() {
-                                    insert_multiple_traveler_task_desc(&global_app_state, &on_conflict, &mut local_messages).await;
-                                }
-
-                                for chunk_ids in currently_known_traveler_task_desc.into_keys().collect::<Vec<_>>().chunks(1000) {
-                                    let chunk_ids = chunk_ids.to_vec();
-                                    if let Err(error) = ::entity::traveler_task_desc::Entity::delete_many().filter(::entity::traveler_task_desc::Column::Id.is_in(chunk_ids.clone())).exec(&global_app_state.conn).await {
-                                        let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-                                        tracing::error!(TravelerTaskDesc = chunk_ids_str.join(","), error = error.to_string(), "Could not delete TravelerTaskDesc");
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, .. } => {
-                                let model: ::entity::traveler_task_desc::Model = new.into();
-                                global_app_state.traveler_task_desc.insert(model.id, model.clone());
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.id) {
-                                    messages_delete.remove(index);
-                                }
-
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, .. } => {
-                                let model: ::entity::traveler_task_desc::Model = new.into();
-                                global_app_state.traveler_task_desc.insert(model.id, model.clone());
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.id) {
-                                    messages_delete.remove(index);
-                                }
-
-                                messages.push(model.into_active_model());
-                                if messages.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete, .. } => {
-                                let model: ::entity::traveler_task_desc::Model = delete.into();
-                                let id = model.id;
-                                global_app_state.traveler_task_desc.remove(&id);
-                                if let Some(index) = messages.iter().position(|value| value.id.as_ref() == &model.id) {
-                                    messages.remove(index);
-                                }
-                                messages_delete.push(id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => {
-                        break;
-                    }
-                    else => {
-                        break;
-                    }
-                }
-            }
-
-            if !messages.is_empty() {
-                insert_multiple_traveler_task_desc(&global_app_state, &on_conflict, &mut messages)
-                    .await;
-            }
-
-            if !messages_delete.is_empty() {
-                tracing::debug!("TravelerTaskDesc::Remove");
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::traveler_task_desc::Entity::delete_many()
-                        .filter(::entity::traveler_task_desc::Column::Id.is_in(chunk_ids.clone()))
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            TravelerTaskDesc = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete TravelerTaskDesc"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
-
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
+    fn model(row: Self::Row, _region: Region) -> Self::Model {
+        row.into()
+    }
 
-async fn insert_multiple_traveler_task_desc(
-    global_app_state: &AppState,
-    on_conflict: &OnConflict,
-    messages: &mut Vec<::entity::traveler_task_desc::ActiveModel>,
-) {
-    let insert = ::entity::traveler_task_desc::Entity::insert_many(messages.clone())
-        .on_conflict(on_conflict.clone())
-        .exec(&global_app_state.conn)
-        .await;
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            traveler_task_desc::Column::SkillId,
+            traveler_task_desc::Column::MinLevel,
+            traveler_task_desc::Column::MaxLevel,
+            traveler_task_desc::Column::RequiredItems,
+            traveler_task_desc::Column::RewardedItems,
+            traveler_task_desc::Column::RewardedExperience,
+            traveler_task_desc::Column::Description,
+        ]
+    }
 
-    if let Err(err) = insert {
-        tracing::error!("Error inserting TravelerTaskDesc: {}", err)
+    fn cache(state: &AppState) -> Option<&DashMap<Self::Key, Self::Model>> {
+        Some(state.traveler_task_desc.as_ref())
     }
 
-    messages.clear();
+    fn desc_table() -> Option<DescTable> {
+        Some(DescTable::TravelerTaskDesc)
+    }
 }
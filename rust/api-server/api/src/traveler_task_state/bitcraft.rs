@@ -1,207 +1,36 @@
-use crate::AppState;
-use crate::websocket::{SpacetimeUpdateMessages, WebSocketMessages, record_worker_received};
+use crate::websocket::WebSocketMessages;
+use crate::websocket::reconcile::reconciled_table;
+use crate::websocket::table_sync::SyncedTable;
+use entity::shared::Region;
+use entity::traveler_task_state;
 use game_module::module_bindings::TravelerTaskState;
-use migration::sea_query;
-use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter};
-use std::collections::HashMap;
-use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
-use tokio::time::sleep;
 
-pub(crate) fn start_worker_traveler_task_state(
-    global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<TravelerTaskState>>,
-    batch_size: usize,
-    time_limit: Duration,
-) {
-    tokio::spawn(async move {
-        let on_conflict =
-            sea_query::OnConflict::column(::entity::traveler_task_state::Column::EntityId)
-                .update_columns([
-                    ::entity::traveler_task_state::Column::PlayerEntityId,
-                    ::entity::traveler_task_state::Column::TravelerId,
-                    ::entity::traveler_task_state::Column::TaskId,
-                    ::entity::traveler_task_state::Column::Completed,
-                    ::entity::traveler_task_state::Column::Region,
-                ])
-                .to_owned();
+reconciled_table!(traveler_task_state, entity_id: i64, EntityId);
 
-        loop {
-            let mut messages = Vec::with_capacity(batch_size + 10);
-            let mut messages_delete = Vec::with_capacity(batch_size + 10);
-            let timer = sleep(time_limit);
-            tokio::pin!(timer);
+impl SyncedTable for traveler_task_state::Entity {
+    type Row = TravelerTaskState;
 
-            loop {
-                tokio::select! {
-                    Some(msg) = rx.recv() => {
-                        record_worker_received("traveler_task_state", 1);
-                        match msg {
-                            SpacetimeUpdateMessages::Initial { data, database_name, .. } => {
-                                let mut local_messages = Vec::with_capacity(batch_size + 10);
-                                let mut currently_known_traveler_task_state = ::entity::traveler_task_state::Entity::find()
-                                    .filter(::entity::traveler_task_state::Column::Region.eq(database_name))
-                                    .all(&global_app_state.conn)
-                                    .await
-                                    .unwrap_or_else(|error| {
-                                        tracing::error!(
-                                            error = error.to_string(),
-                                            "Error while query whole traveler_task_state state"
-                                        );
-                                        vec![]
-                                    })
-                                    .into_iter()
-                                    .map(|value| (value.entity_id, value))
-                                    .collect::<HashMap<_, _>>();
-
-                                for model in data.into_iter().map(|value| {
-                                    let model: ::entity::traveler_task_state::Model = ::entity::traveler_task_state::ModelBuilder::new(value).with_region(database_name).build();
-
-                                    model
-                                }) {
-                                    use std::collections::hash_map::Entry;
-                                    match currently_known_traveler_task_state.entry(model.entity_id) {
-                                        Entry::Occupied(entry) => {
-                                            let existing_model = entry.get();
-                                            if &model != existing_model {
-                                                local_messages.push(model);
-                                            }
-                                            entry.remove();
-                                        }
-                                        Entry::Vacant(_entry) => {
-                                            local_messages.push(model);
-                                        }
-                                    }
-                                    if local_messages.len() >= batch_size {
-                                       insert_multiple_traveler_task_state(&global_app_state, &on_conflict, &mut local_messages).await;
-                                    }
-                                };
-                                if !local_messages.is_empty() {
-                                    insert_multiple_traveler_task_state(&global_app_state, &on_conflict, &mut local_messages).await;
-                                }
-
-                                for chunk_ids in currently_known_traveler_task_state.into_keys().collect::<Vec<_>>().chunks(1000) {
-                                    let chunk_ids = chunk_ids.to_vec();
-                                    if let Err(error) = ::entity::traveler_task_state::Entity::delete_many()
-                                        .filter(::entity::traveler_task_state::Column::EntityId.is_in(chunk_ids.clone()))
-                                        .filter(::entity::traveler_task_state::Column::Region.eq(database_name))
-                                        .exec(&global_app_state.conn).await
-                                    {
-                                        let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
-                                        tracing::error!(TravelerTaskState = chunk_ids_str.join(","), error = error.to_string(), "Could not delete TravelerTaskState");
-                                    }
-                                }
-                            }
-                            SpacetimeUpdateMessages::Insert { new, database_name, .. } => {
-                                let model: ::entity::traveler_task_state::Model = ::entity::traveler_task_state::ModelBuilder::new(new).with_region(database_name).build();
-
-                                let _ = global_app_state.tx.send(WebSocketMessages::TravelerTaskState(model.clone()));
-
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                messages.push(model);
-                                if messages.len() >= batch_size {
-                                    insert_multiple_traveler_task_state(&global_app_state, &on_conflict, &mut messages)
-                                        .await;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Update { new, database_name, .. } => {
-                                let model: ::entity::traveler_task_state::Model = ::entity::traveler_task_state::ModelBuilder::new(new).with_region(database_name).build();
-
-                                let _ = global_app_state.tx.send(WebSocketMessages::TravelerTaskState(model.clone()));
-
-                                if let Some(index) = messages.iter().position(|value| value.entity_id == model.entity_id) {
-                                    messages.remove(index);
-                                }
-
-                                if let Some(index) = messages_delete.iter().position(|value| *value == model.entity_id) {
-                                    messages_delete.remove(index);
-                                }
-                                messages.push(model);
-                                if messages.len() >= batch_size {
-                                    insert_multiple_traveler_task_state(&global_app_state, &on_conflict, &mut messages)
-                                        .await;
-                                }
-                            }
-                            SpacetimeUpdateMessages::Remove { delete, .. } => {
-                                let model: ::entity::traveler_task_state::Model = ::entity::traveler_task_state::ModelBuilder::new(delete).build();
-                                let id = model.entity_id;
-                                let _ = global_app_state.tx.send(WebSocketMessages::TravelerTaskStateDelete(model.clone()));
-
-                                if let Some(index) = messages.iter().position(|value| value.entity_id == model.entity_id) {
-                                    messages.remove(index);
-                                }
-
-                                messages_delete.push(id);
-                                if messages_delete.len() >= batch_size {
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                    _ = &mut timer => {
-                        break;
-                    }
-                    else => {
-                        break;
-                    }
-                }
-            }
-
-            if !messages.is_empty() {
-                insert_multiple_traveler_task_state(&global_app_state, &on_conflict, &mut messages)
-                    .await;
-            }
-
-            if !messages_delete.is_empty() {
-                tracing::debug!("TravelerTaskState::Remove");
-                for chunk_ids in messages_delete.chunks(1000) {
-                    let chunk_ids = chunk_ids.to_vec();
-                    if let Err(error) = ::entity::traveler_task_state::Entity::delete_many()
-                        .filter(
-                            ::entity::traveler_task_state::Column::EntityId
-                                .is_in(chunk_ids.clone()),
-                        )
-                        .exec(&global_app_state.conn)
-                        .await
-                    {
-                        let chunk_ids_str: Vec<String> =
-                            chunk_ids.iter().map(|id| id.to_string()).collect();
-                        tracing::error!(
-                            TravelerTaskState = chunk_ids_str.join(","),
-                            error = error.to_string(),
-                            "Could not delete TravelerTaskState"
-                        );
-                    }
-                }
-                messages_delete.clear();
-            }
-
-            if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
-                break;
-            }
-        }
-    });
-}
+    fn model(row: Self::Row, region: Region) -> Self::Model {
+        traveler_task_state::ModelBuilder::new(row)
+            .with_region(region)
+            .build()
+    }
 
-async fn insert_multiple_traveler_task_state(
-    global_app_state: &AppState,
-    on_conflict: &sea_query::OnConflict,
-    messages: &mut Vec<::entity::traveler_task_state::Model>,
-) {
-    let insert = ::entity::traveler_task_state::Entity::insert_many(
-        messages
-            .iter()
-            .map(|model| model.clone().into_active_model()),
-    )
-    .on_conflict(on_conflict.clone())
-    .exec(&global_app_state.conn)
-    .await;
+    fn update_columns() -> Vec<Self::Column> {
+        vec![
+            traveler_task_state::Column::PlayerEntityId,
+            traveler_task_state::Column::TravelerId,
+            traveler_task_state::Column::TaskId,
+            traveler_task_state::Column::Completed,
+            traveler_task_state::Column::Region,
+        ]
+    }
 
-    if let Err(err) = insert {
-        tracing::error!("Error inserting TravelerTaskState: {}", err)
+    fn upserted_message(model: &Self::Model) -> Option<WebSocketMessages> {
+        Some(WebSocketMessages::TravelerTaskState(model.clone()))
     }
 
-    messages.clear();
+    fn removed_message(model: &Self::Model) -> Option<WebSocketMessages> {
+        Some(WebSocketMessages::TravelerTaskStateDelete(model.clone()))
+    }
 }
@@ -1,38 +1,66 @@
+use crate::desc_cache::{CachedRequest, DescTable};
+use crate::error::ApiError;
 use std::collections::HashMap;
 
-use axum::{Router, extract::State};
+use axum::response::Response;
+use axum::{Router, extract::State, routing::get};
 use entity::{npc_desc, traveler_task_desc};
-use hyper::StatusCode;
 
 use crate::{AppRouter, AppState};
 
 pub(crate) fn get_routes() -> AppRouter {
     Router::new()
-        .route("/traveler_tasks", axum_codec::routing::get(get_all).into())
-        .route("/npc", axum_codec::routing::get(get_npc_all).into())
+        .route("/traveler_tasks", get(get_all))
+        .route("/npc", get(get_npc_all))
 }
 
+#[utoipa::path(
+    get,
+    path = "/traveler_tasks",
+    tag = "traveler_tasks",
+    operation_id = "get_all_traveler_tasks",
+    responses(
+        (status = OK, body = HashMap<i32, traveler_task_desc::Model>),
+        (status = NOT_MODIFIED, description = "The `If-None-Match` ETag is still current"),
+    )
+)]
 pub(crate) async fn get_all(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<HashMap<i32, traveler_task_desc::Model>>, (StatusCode, &'static str)>
-{
-    Ok(axum_codec::Codec(
-        state
-            .traveler_task_desc
-            .iter()
-            .map(|value| (*value.key(), value.clone()))
-            .collect(),
-    ))
+    request: CachedRequest,
+) -> Result<Response, ApiError> {
+    state
+        .desc_cache
+        .respond(request, &[DescTable::TravelerTaskDesc], || async {
+            Ok(state
+                .traveler_task_desc
+                .iter()
+                .map(|value| (*value.key(), value.clone()))
+                .collect::<HashMap<i32, traveler_task_desc::Model>>())
+        })
+        .await
 }
 
+#[utoipa::path(
+    get,
+    path = "/npc",
+    tag = "traveler_tasks",
+    responses(
+        (status = OK, body = HashMap<i32, npc_desc::Model>),
+        (status = NOT_MODIFIED, description = "The `If-None-Match` ETag is still current"),
+    )
+)]
 pub(crate) async fn get_npc_all(
     state: State<AppState>,
-) -> Result<axum_codec::Codec<HashMap<i32, npc_desc::Model>>, (StatusCode, &'static str)> {
-    Ok(axum_codec::Codec(
-        state
-            .npc_desc
-            .iter()
-            .map(|value| (*value.key(), value.clone()))
-            .collect(),
-    ))
+    request: CachedRequest,
+) -> Result<Response, ApiError> {
+    state
+        .desc_cache
+        .respond(request, &[DescTable::NpcDesc], || async {
+            Ok(state
+                .npc_desc
+                .iter()
+                .map(|value| (*value.key(), value.clone()))
+                .collect::<HashMap<i32, npc_desc::Model>>())
+        })
+        .await
 }
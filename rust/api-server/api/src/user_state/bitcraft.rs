@@ -1,11 +1,11 @@
 use crate::AppState;
+use crate::websocket::ingest_queue::IngestReceiver;
 use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
 use game_module::module_bindings::UserState;
-use tokio::sync::mpsc::UnboundedReceiver;
 
 pub(crate) fn start_worker_user_state(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<UserState>>,
+    mut rx: IngestReceiver<UserState>,
 ) {
     tokio::spawn(async move {
         while let Some(update) = rx.recv().await {
@@ -0,0 +1,450 @@
+use crate::{
+    AppRouter, accounts, auction_listing_state, buildings, claims, extraction_recipe_desc, houses,
+    inventory, items, items_and_cargo, leaderboard, player_state, recipes, trading_orders,
+    traveler_tasks,
+};
+use axum::Router;
+use axum::extract::{MatchedPath, Request};
+use axum::http::{HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// RFC 9745 date of when the legacy routes got deprecated, 2026-10-18.
+const DEPRECATION: &str = "@1792281600";
+
+/// Every route outside of `/api/v1` that has a successor in it, as `(legacy, successor)`.
+/// Path parameters are matched by position, so names may differ.
+pub(crate) const LEGACY_ROUTES: &[(&str, &str)] = &[
+    ("/players", "/api/v1/players"),
+    ("/api/bitcraft/players", "/api/v1/players"),
+    ("/api/bitcraft/players/all", "/api/v1/players/usernames"),
+    ("/players/{id}", "/api/v1/players/{id}"),
+    ("/api/bitcraft/players/{id}", "/api/v1/players/{id}"),
+    ("/experience/{player_id}", "/api/v1/players/{id}/experience"),
+    (
+        "/api/bitcraft/experience/{player_id}",
+        "/api/v1/players/{id}/experience",
+    ),
+    (
+        "/leaderboard/history/{player_id}",
+        "/api/v1/players/{id}/rank-history",
+    ),
+    ("/claims", "/api/v1/claims"),
+    ("/api/bitcraft/claims", "/api/v1/claims"),
+    ("/claims/names", "/api/v1/claims/names"),
+    ("/api/bitcraft/claims/{id}", "/api/v1/claims/{id}"),
+    (
+        "/claims/{id}/auction_listings",
+        "/api/v1/claims/{id}/auction-listings",
+    ),
+    (
+        "/api/bitcraft/claims/{id}/auction_listings",
+        "/api/v1/claims/{id}/auction-listings",
+    ),
+    ("/claims/tiles/{id}", "/api/v1/claims/{id}/tiles"),
+    (
+        "/claims/inventory_changelog/{id}",
+        "/api/v1/claims/{id}/inventory-changes",
+    ),
+    (
+        "/claims/{id}/craft_check",
+        "/api/v1/claims/{id}/craft-check",
+    ),
+    (
+        "/api/bitcraft/leaderboard/claims/{claim_id}",
+        "/api/v1/claims/{id}/leaderboard",
+    ),
+    ("/buildings", "/api/v1/buildings"),
+    ("/api/bitcraft/buildings", "/api/v1/buildings"),
+    ("/buildings/{id}", "/api/v1/buildings/{id}"),
+    ("/api/bitcraft/buildings/{id}", "/api/v1/buildings/{id}"),
+    ("/desc/buildings", "/api/v1/building-descriptions"),
+    (
+        "/api/bitcraft/desc/buildings",
+        "/api/v1/building-descriptions",
+    ),
+    ("/desc/buildings/{id}", "/api/v1/building-descriptions/{id}"),
+    ("/inventory/{id}", "/api/v1/inventories/{id}"),
+    (
+        "/inventory/all_inventory_stats",
+        "/api/v1/inventories/stats",
+    ),
+    (
+        "/inventorys/changes/{id}",
+        "/api/v1/inventories/{id}/changes",
+    ),
+    (
+        "/api/bitcraft/inventorys/changes/{id}",
+        "/api/v1/inventories/{id}/changes",
+    ),
+    (
+        "/api/bitcraft/inventorys/owner_entity_id/{id}",
+        "/api/v1/inventories/by-owner/{id}",
+    ),
+    ("/items", "/api/v1/items"),
+    ("/items/world", "/api/v1/items/all"),
+    ("/api/bitcraft/itemsAndCargo", "/api/v1/items-and-cargo"),
+    (
+        "/api/bitcraft/itemsAndCargo/all",
+        "/api/v1/items-and-cargo/all",
+    ),
+    (
+        "/api/bitcraft/itemsAndCargo/meta",
+        "/api/v1/items-and-cargo/meta",
+    ),
+    ("/recipes/get_all", "/api/v1/recipes"),
+    ("/recipes/plan", "/api/v1/recipes/plan"),
+    (
+        "/api/bitcraft/extractionRecipes/all",
+        "/api/v1/extraction-recipes",
+    ),
+    ("/traveler_tasks", "/api/v1/traveler-tasks"),
+    ("/npc", "/api/v1/npcs"),
+    (
+        "/api/bitcraft/trade_orders/get_trade_orders",
+        "/api/v1/trade-orders",
+    ),
+    ("/market", "/api/v1/market"),
+    ("/market/orders", "/api/v1/market/orders"),
+    ("/market/item_cargo_desc", "/api/v1/market/items"),
+    ("/market/history", "/api/v1/market/history"),
+    ("/market/depth", "/api/v1/market/depth"),
+    ("/leaderboard", "/api/v1/leaderboards"),
+    ("/leaderboard/movers", "/api/v1/leaderboards/movers"),
+    ("/houses", "/api/v1/houses"),
+    ("/api/bitcraft/houses", "/api/v1/houses"),
+    ("/houses/by_owner/{id}", "/api/v1/houses/by-owner/{id}"),
+    (
+        "/api/bitcraft/houses/by_owner/{id}",
+        "/api/v1/houses/by-owner/{id}",
+    ),
+    ("/houses/{id}", "/api/v1/houses/{id}"),
+    ("/api/bitcraft/houses/{id}", "/api/v1/houses/{id}"),
+    (
+        "/houses/{id}/inventories",
+        "/api/v1/houses/{id}/inventories",
+    ),
+    (
+        "/api/bitcraft/houses/{id}/inventories",
+        "/api/v1/houses/{id}/inventories",
+    ),
+    ("/api/bitcraft/accounts", "/api/v1/accounts"),
+    ("/api/bitcraft/accounts/me", "/api/v1/accounts/me"),
+    (
+        "/api/bitcraft/accounts/sessions",
+        "/api/v1/accounts/sessions",
+    ),
+    (
+        "/api/bitcraft/accounts/api-keys",
+        "/api/v1/accounts/api-keys",
+    ),
+    (
+        "/api/bitcraft/accounts/api-keys/{id}",
+        "/api/v1/accounts/api-keys/{id}",
+    ),
+    (
+        "/api/bitcraft/accounts/player-link",
+        "/api/v1/accounts/player-link",
+    ),
+    (
+        "/api/bitcraft/accounts/player-link/verify",
+        "/api/v1/accounts/player-link/verify",
+    ),
+];
+
+static SUCCESSORS: LazyLock<HashMap<&str, &str>> =
+    LazyLock::new(|| LEGACY_ROUTES.iter().copied().collect());
+
+/// Plural, kebab-case resources. The searchable lists take the shared `ListParams` and answer
+/// with `Paginated`, change logs and market orders keep the filters of their legacy routes and
+/// reference data (recipes, extraction recipes, traveler tasks, npcs) is sent whole.
+pub(crate) fn get_routes() -> AppRouter {
+    Router::new()
+        .route(
+            "/api/v1/players",
+            axum_codec::routing::get(player_state::list_players_v1).into(),
+        )
+        .route(
+            "/api/v1/players/usernames",
+            axum_codec::routing::get(player_state::get_all).into(),
+        )
+        .route(
+            "/api/v1/players/{id}",
+            axum_codec::routing::get(player_state::find_player_by_id).into(),
+        )
+        .route(
+            "/api/v1/players/{id}/experience",
+            axum_codec::routing::get(leaderboard::player_leaderboard).into(),
+        )
+        .route(
+            "/api/v1/players/{id}/rank-history",
+            axum_codec::routing::get(leaderboard::player_rank_history).into(),
+        )
+        .route(
+            "/api/v1/claims",
+            axum_codec::routing::get(claims::list_claims_v1).into(),
+        )
+        .route(
+            "/api/v1/claims/names",
+            axum_codec::routing::get(claims::get_claim_names).into(),
+        )
+        .route(
+            "/api/v1/claims/{id}",
+            axum_codec::routing::get(claims::get_claim).into(),
+        )
+        .route(
+            "/api/v1/claims/{id}/auction-listings",
+            axum_codec::routing::get(claims::get_claim_auction_listings).into(),
+        )
+        .route(
+            "/api/v1/claims/{id}/tiles",
+            axum_codec::routing::get(claims::get_claim_tiles).into(),
+        )
+        .route(
+            "/api/v1/claims/{id}/inventory-changes",
+            axum_codec::routing::get(claims::get_claim_inventory_change_log).into(),
+        )
+        .route(
+            "/api/v1/claims/{id}/craft-check",
+            axum_codec::routing::get(claims::craft_check::get_claim_craft_check).into(),
+        )
+        .route(
+            "/api/v1/claims/{id}/leaderboard",
+            axum_codec::routing::get(leaderboard::get_claim_leaderboard).into(),
+        )
+        .route(
+            "/api/v1/buildings",
+            axum_codec::routing::get(buildings::list_buildings).into(),
+        )
+        .route(
+            "/api/v1/buildings/{id}",
+            axum_codec::routing::get(buildings::find_building_state).into(),
+        )
+        .route(
+            "/api/v1/building-descriptions",
+            axum_codec::routing::get(buildings::list_building_descriptions).into(),
+        )
+        .route(
+            "/api/v1/building-descriptions/{id}",
+            axum_codec::routing::get(buildings::find_claim_description).into(),
+        )
+        .route(
+            "/api/v1/inventories/stats",
+            axum_codec::routing::get(inventory::all_inventory_stats).into(),
+        )
+        .route(
+            "/api/v1/inventories/by-owner/{id}",
+            axum_codec::routing::get(inventory::find_inventory_by_owner_entity_id).into(),
+        )
+        .route(
+            "/api/v1/inventories/{id}",
+            axum_codec::routing::get(inventory::find_inventory_by_id).into(),
+        )
+        .route(
+            "/api/v1/inventories/{id}/changes",
+            axum_codec::routing::get(inventory::read_inventory_changes).into(),
+        )
+        .route(
+            "/api/v1/items",
+            axum_codec::routing::get(items::list_items_v1).into(),
+        )
+        .route(
+            "/api/v1/items/all",
+            axum_codec::routing::get(items::list_world_items).into(),
+        )
+        .route(
+            "/api/v1/items-and-cargo",
+            axum_codec::routing::get(items_and_cargo::list_items_and_cargo_v1).into(),
+        )
+        .route(
+            "/api/v1/items-and-cargo/all",
+            axum::routing::get(items_and_cargo::get_all),
+        )
+        .route(
+            "/api/v1/items-and-cargo/meta",
+            axum_codec::routing::get(items_and_cargo::meta).into(),
+        )
+        .route("/api/v1/recipes", axum::routing::get(recipes::get_all))
+        .route(
+            "/api/v1/recipes/plan",
+            axum_codec::routing::get(recipes::get_plan).into(),
+        )
+        .route(
+            "/api/v1/extraction-recipes",
+            axum::routing::get(extraction_recipe_desc::get_all),
+        )
+        .route(
+            "/api/v1/traveler-tasks",
+            axum::routing::get(traveler_tasks::get_all),
+        )
+        .route(
+            "/api/v1/npcs",
+            axum::routing::get(traveler_tasks::get_npc_all),
+        )
+        .route(
+            "/api/v1/trade-orders",
+            axum_codec::routing::get(trading_orders::list_trade_orders).into(),
+        )
+        .route(
+            "/api/v1/market",
+            axum_codec::routing::get(auction_listing_state::get_market_order_stats).into(),
+        )
+        .route(
+            "/api/v1/market/orders",
+            axum_codec::routing::get(auction_listing_state::find_market_place_order).into(),
+        )
+        .route(
+            "/api/v1/market/items",
+            axum_codec::routing::get(auction_listing_state::market_item_cargo_desc).into(),
+        )
+        .route(
+            "/api/v1/market/history",
+            axum_codec::routing::get(auction_listing_state::get_market_price_history).into(),
+        )
+        .route(
+            "/api/v1/market/depth",
+            axum_codec::routing::get(auction_listing_state::get_market_depth).into(),
+        )
+        .route(
+            "/api/v1/leaderboards",
+            axum_codec::routing::get(leaderboard::get_top_100).into(),
+        )
+        .route(
+            "/api/v1/leaderboards/movers",
+            axum_codec::routing::get(leaderboard::get_leaderboard_movers).into(),
+        )
+        .route(
+            "/api/v1/houses",
+            axum_codec::routing::get(houses::list_houses).into(),
+        )
+        .route(
+            "/api/v1/houses/by-owner/{id}",
+            axum_codec::routing::get(houses::find_houses_by_owner_id).into(),
+        )
+        .route(
+            "/api/v1/houses/{id}",
+            axum_codec::routing::get(houses::find_house).into(),
+        )
+        .route(
+            "/api/v1/houses/{id}/inventories",
+            axum_codec::routing::get(houses::find_house_inventories).into(),
+        )
+        .route(
+            "/api/v1/accounts",
+            axum_codec::routing::post(accounts::create_account).into(),
+        )
+        .route(
+            "/api/v1/accounts/me",
+            axum_codec::routing::get(accounts::get_me).into(),
+        )
+        .route(
+            "/api/v1/accounts/sessions",
+            axum::routing::MethodRouter::from(axum_codec::routing::post(accounts::create_session))
+                .delete(accounts::delete_session),
+        )
+        .route(
+            "/api/v1/accounts/api-keys",
+            axum_codec::routing::get(accounts::list_api_keys)
+                .post(accounts::create_api_key)
+                .into(),
+        )
+        .route(
+            "/api/v1/accounts/api-keys/{id}",
+            axum::routing::delete(accounts::revoke_api_key),
+        )
+        .route(
+            "/api/v1/accounts/player-link",
+            axum::routing::MethodRouter::from(axum_codec::routing::post(
+                accounts::start_player_link,
+            ))
+            .delete(accounts::unlink_player),
+        )
+        .route(
+            "/api/v1/accounts/player-link/verify",
+            axum_codec::routing::post(accounts::verify_player_link).into(),
+        )
+}
+
+/// Fills the path parameters of `path`, which matched `legacy`, into `successor`.
+fn successor_path(legacy: &str, successor: &str, path: &str) -> String {
+    let mut values = legacy
+        .split('/')
+        .zip(path.split('/'))
+        .filter(|(segment, _)| segment.starts_with('{'))
+        .map(|(_, value)| value);
+
+    successor
+        .split('/')
+        .map(|segment| {
+            if segment.starts_with('{') {
+                values.next().unwrap_or(segment)
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Marks responses of legacy routes with `Deprecation` and a `Link` to their `/api/v1` successor.
+pub(crate) async fn deprecate_legacy_routes(
+    matched_path: Option<MatchedPath>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let successor = matched_path.and_then(|matched_path| {
+        let legacy = matched_path.as_str();
+
+        SUCCESSORS.get(legacy).map(|successor| {
+            metrics::counter!("deprecated_route_requests_total", "path" => legacy.to_string())
+                .increment(1);
+
+            successor_path(legacy, successor, request.uri().path())
+        })
+    });
+
+    let mut response = next.run(request).await;
+
+    if let Some(successor) = successor {
+        let headers = response.headers_mut();
+        headers.insert("deprecation", HeaderValue::from_static(DEPRECATION));
+        if let Ok(link) =
+            HeaderValue::from_str(&format!("<{successor}>; rel=\"successor-version\""))
+        {
+            headers.insert(header::LINK, link);
+        }
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_path_parameters_into_the_successor() {
+        assert_eq!(
+            successor_path(
+                "/experience/{player_id}",
+                "/api/v1/players/{id}/experience",
+                "/experience/42"
+            ),
+            "/api/v1/players/42/experience"
+        );
+        assert_eq!(
+            successor_path(
+                "/claims/tiles/{id}",
+                "/api/v1/claims/{id}/tiles",
+                "/claims/tiles/7"
+            ),
+            "/api/v1/claims/7/tiles"
+        );
+        assert_eq!(
+            successor_path("/npc", "/api/v1/npcs", "/npc"),
+            "/api/v1/npcs"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::AppState;
+use crate::websocket::ingest_queue::IngestReceiver;
 use crate::websocket::{SpacetimeUpdateMessages, record_worker_received};
 use entity::vault_state_collectibles;
 use game_module::module_bindings::VaultState;
@@ -6,12 +7,11 @@ use migration::sea_query;
 use sea_orm::{ColumnTrait, EntityTrait, IntoActiveModel, QueryFilter};
 use std::collections::HashMap;
 use std::time::Duration;
-use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::sleep;
 
 pub(crate) fn start_worker_vault_state_collectibles(
     global_app_state: AppState,
-    mut rx: UnboundedReceiver<SpacetimeUpdateMessages<VaultState>>,
+    mut rx: IngestReceiver<VaultState>,
     batch_size: usize,
     time_limit: Duration,
 ) {
@@ -30,6 +30,7 @@ pub(crate) fn start_worker_vault_state_collectibles(
         loop {
             let mut messages = Vec::with_capacity(batch_size + 10);
             let mut messages_delete = Vec::with_capacity(batch_size + 10);
+            let mut written = true;
             let timer = sleep(time_limit);
             tokio::pin!(timer);
 
@@ -74,11 +75,11 @@ pub(crate) fn start_worker_vault_state_collectibles(
                                         }
                                     }
                                     if local_messages.len() >= batch_size {
-                                       insert_multiple_vault_state_collectibles(&global_app_state, &on_conflict, &mut local_messages).await;
+                                       written &= insert_multiple_vault_state_collectibles(&global_app_state, &on_conflict, &mut local_messages).await;
                                     }
                                 };
                                 if !local_messages.is_empty() {
-                                    insert_multiple_vault_state_collectibles(&global_app_state, &on_conflict, &mut local_messages).await;
+                                    written &= insert_multiple_vault_state_collectibles(&global_app_state, &on_conflict, &mut local_messages).await;
                                 }
 
                                 for chunk_ids in currently_known_vault_state_collectibles.into_keys().collect::<Vec<_>>().chunks(1000) {
@@ -90,6 +91,7 @@ pub(crate) fn start_worker_vault_state_collectibles(
                                     {
                                         let chunk_ids_str: Vec<String> = chunk_ids.iter().map(|id| id.to_string()).collect();
                                         tracing::error!(VaultState = chunk_ids_str.join(","), error = error.to_string(), "Could not delete VaultState");
+                                        written = false;
                                     }
                                 }
                             }
@@ -160,7 +162,7 @@ pub(crate) fn start_worker_vault_state_collectibles(
 
             if !messages.is_empty() {
                 //tracing::info!("Processing {} messages in batch", messages.len());
-                insert_multiple_vault_state_collectibles(
+                written &= insert_multiple_vault_state_collectibles(
                     &global_app_state,
                     &on_conflict,
                     &mut messages,
@@ -187,11 +189,14 @@ pub(crate) fn start_worker_vault_state_collectibles(
                             error = error.to_string(),
                             "Could not delete VaultState"
                         );
+                        written = false;
                     }
                 }
                 messages_delete.clear();
             }
 
+            rx.commit_if(written);
+
             // If the channel is closed and we processed the last batch, exit the outer loop
             if messages.is_empty() && messages_delete.is_empty() && rx.is_closed() {
                 break;
@@ -200,19 +205,21 @@ pub(crate) fn start_worker_vault_state_collectibles(
     });
 }
 
+/// Returns whether the rows were written.
 async fn insert_multiple_vault_state_collectibles(
     global_app_state: &AppState,
     on_conflict: &sea_query::OnConflict,
     messages: &mut Vec<::entity::vault_state_collectibles::ActiveModel>,
-) {
+) -> bool {
     let insert = ::entity::vault_state_collectibles::Entity::insert_many(messages.clone())
         .on_conflict(on_conflict.clone())
         .exec(&global_app_state.conn)
         .await;
 
-    if let Err(err) = insert {
+    if let Err(err) = &insert {
         tracing::error!("Error inserting VaultState: {}", err)
     }
 
     messages.clear();
+    insert.is_ok()
 }
@@ -1,15 +1,16 @@
+use super::ingest_queue::{IngestReceiver, IngestSender};
 use super::{SpacetimeUpdateMessages, record_worker_received};
+use spacetimedb_sdk::__codegen::__lib;
 use std::future::Future;
-use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender};
 use tokio::time::Duration;
 use tokio::time::sleep;
 
 pub(crate) trait BatchedWorker {
-    type Entity;
+    type Entity: __lib::ser::Serialize + for<'de> __lib::de::Deserialize<'de>;
 
-    fn rx(&mut self) -> &mut UnboundedReceiver<SpacetimeUpdateMessages<Self::Entity>>;
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity>;
 
-    fn tx(&self) -> UnboundedSender<SpacetimeUpdateMessages<Self::Entity>>;
+    fn tx(&self) -> IngestSender<Self::Entity>;
 
     fn start(self)
     where
@@ -38,6 +39,13 @@ pub(crate) trait BatchedWorker {
     ) -> impl Future<Output = ()> + Send;
 
     fn flush(&mut self) -> impl Future<Output = ()> + Send;
+
+    /// Acknowledges what was received so far, after `flush` got it into Postgres. Workers that
+    /// hand their batches to a database task commit from there instead.
+    fn commit(&mut self) -> impl Future<Output = ()> + Send {
+        let checkpoint = self.rx().checkpoint();
+        async move { checkpoint.commit() }
+    }
 }
 
 pub(crate) async fn run_batched_worker<W>(worker: &mut W)
@@ -68,6 +76,7 @@ where
         }
 
         worker.flush().await;
+        worker.commit().await;
         worker.reset_batch();
 
         if worker.is_idle() && worker.rx().is_closed() {
@@ -0,0 +1,372 @@
+use crate::AppState;
+use crate::websocket::WebSocketMessages;
+use entity::inventory::{ItemExpended, ItemType, ResolvedInventory};
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+
+/// How many filters a single client may have active at once.
+pub(crate) const MAX_FILTERS_PER_CLIENT: usize = 64;
+
+/// Topics a filter can be placed on, `experience:{skill}` and `level:{skill}` are accepted as
+/// well.
+const FILTERABLE_TOPICS: &[&str] = &[
+    "experience",
+    "level",
+    "total_experience",
+    "time_played",
+    "time_signed_in",
+    "player_state",
+    "mobile_entity_state",
+    "player_action_state",
+    "player_action_state_change_name",
+    "action_state",
+    "claim_local_state",
+    "claim",
+    "moved_into_claim",
+    "moved_out_of_claim",
+    "player_moved_into_claim",
+    "player_moved_out_of_claim",
+    "traveler_task_state",
+    "traveler_task_state:player",
+    "insert_sell_order",
+    "update_sell_order",
+    "remove_sell_order",
+    "update_buy_order",
+    "remove_buy_order",
+    "inventory_insert",
+    "inventory_update",
+    "inventory_remove",
+];
+
+const ITEM_TOPICS: &[&str] = &[
+    "insert_sell_order",
+    "update_sell_order",
+    "remove_sell_order",
+    "update_buy_order",
+    "remove_buy_order",
+    "inventory_insert",
+    "inventory_update",
+    "inventory_remove",
+];
+
+/// Subscribes to every message of `topic` that matches all the given predicates.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, TS)]
+#[ts(export)]
+pub(crate) struct SubscriptionFilter {
+    pub(crate) topic: String,
+    /// The id the topic is keyed by, the same as in `topic.id`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub(crate) entity_id: Option<i64>,
+    /// Matches the claim itself or players that are a member of it.
+    #[serde(default)]
+    #[ts(optional)]
+    pub(crate) claim_id: Option<i64>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub(crate) region: Option<entity::shared::Region>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub(crate) item_id: Option<i32>,
+    /// `0` for items and `1` for cargo, only used together with `item_id`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub(crate) item_type: Option<i32>,
+    #[serde(default)]
+    #[ts(optional)]
+    pub(crate) item_tag: Option<String>,
+}
+
+impl SubscriptionFilter {
+    pub(crate) fn validate(&self) -> Result<(), &'static str> {
+        let topic = match self.topic.split_once(':') {
+            Some((topic @ ("experience" | "level"), skill_name)) if !skill_name.is_empty() => topic,
+            _ => self.topic.as_str(),
+        };
+
+        if !FILTERABLE_TOPICS.contains(&topic) {
+            return Err("Unknown topic");
+        }
+
+        let has_item_predicate = self.item_id.is_some() || self.item_tag.is_some();
+        if has_item_predicate && !ITEM_TOPICS.contains(&topic) {
+            return Err("Item filters only apply to order and inventory topics");
+        }
+
+        if self.item_type.is_some() && self.item_id.is_none() {
+            return Err("item_type requires item_id");
+        }
+
+        if self
+            .item_type
+            .is_some_and(|item_type| !(0..=1).contains(&item_type))
+        {
+            return Err("item_type has to be 0 (item) or 1 (cargo)");
+        }
+
+        if self.item_tag.as_ref().is_some_and(|tag| tag.is_empty()) {
+            return Err("item_tag must not be empty");
+        }
+
+        Ok(())
+    }
+
+    /// `topic_ids` are the ids `message` is published with for this filter's topic.
+    pub(crate) fn matches(
+        &self,
+        state: &AppState,
+        message: &WebSocketMessages,
+        topic_ids: &[Option<i64>],
+    ) -> bool {
+        if self
+            .entity_id
+            .is_some_and(|entity_id| !topic_ids.contains(&Some(entity_id)))
+        {
+            return false;
+        }
+
+        if self
+            .region
+            .is_some_and(|region| message_region(message) != Some(region))
+        {
+            return false;
+        }
+
+        if let Some(claim_id) = self.claim_id {
+            let (claim_ids, player_ids) = message_claims_and_players(message);
+            let is_member = |player_id: &i64| {
+                state
+                    .player_to_claim_id_cache
+                    .get(&(*player_id as u64))
+                    .is_some_and(|claims| claims.contains(&(claim_id as u64)))
+            };
+
+            if !claim_ids.contains(&claim_id) && !player_ids.iter().any(is_member) {
+                return false;
+            }
+        }
+
+        if self.item_id.is_some() || self.item_tag.is_some() {
+            return message_items(state, message)
+                .into_iter()
+                .any(|(item_type, item_id, tag)| {
+                    self.item_id.is_none_or(|wanted| wanted == item_id)
+                        && self.item_type.is_none_or(|wanted| wanted == item_type)
+                        && self
+                            .item_tag
+                            .as_ref()
+                            .is_none_or(|wanted| tag.as_ref() == Some(wanted))
+                });
+        }
+
+        true
+    }
+}
+
+/// Splits `topic.id` into the topic and its id.
+pub(crate) fn parse_topic(full_topic: &str) -> Result<(&str, Option<i64>), &'static str> {
+    let Some((topic, id)) = full_topic.split_once('.') else {
+        return Ok((full_topic, None));
+    };
+
+    if topic.is_empty() {
+        return Err("Topic must not be empty");
+    }
+
+    id.parse::<i64>()
+        .map(|id| (topic, Some(id)))
+        .map_err(|_| "Topic id has to be a number")
+}
+
+fn message_region(message: &WebSocketMessages) -> Option<entity::shared::Region> {
+    match message {
+        WebSocketMessages::MobileEntityState(mobile_entity_state) => {
+            Some(mobile_entity_state.region)
+        }
+        WebSocketMessages::PlayerState(player_state)
+        | WebSocketMessages::PlayerStateDelete(player_state) => Some(player_state.region),
+        WebSocketMessages::PlayerActionState(player_action_state) => {
+            Some(player_action_state.region)
+        }
+        WebSocketMessages::ActionState(action_state) => Some(action_state.region),
+        WebSocketMessages::ClaimLocalState(claim_local_state) => Some(claim_local_state.region),
+        WebSocketMessages::TravelerTaskState(traveler_task_state)
+        | WebSocketMessages::TravelerTaskStateDelete(traveler_task_state) => {
+            Some(traveler_task_state.region)
+        }
+        WebSocketMessages::InsertSellOrder(order)
+        | WebSocketMessages::UpdateSellOrder(order)
+        | WebSocketMessages::RemoveSellOrder(order)
+        | WebSocketMessages::InsertBuyOrder(order)
+        | WebSocketMessages::UpdateBuyOrder(order)
+        | WebSocketMessages::RemoveBuyOrder(order) => Some(order.region),
+        WebSocketMessages::InventoryUpdate { region, .. }
+        | WebSocketMessages::InventoryRemove { region, .. }
+        | WebSocketMessages::InventoryInsert { region, .. } => Some(*region),
+        _ => None,
+    }
+}
+
+/// The claims a message is about directly and the players it is about.
+fn message_claims_and_players(message: &WebSocketMessages) -> (Vec<i64>, Vec<i64>) {
+    match message {
+        WebSocketMessages::Experience { user_id, .. }
+        | WebSocketMessages::Level { user_id, .. }
+        | WebSocketMessages::TotalExperience { user_id, .. }
+        | WebSocketMessages::TimePlayed { user_id, .. }
+        | WebSocketMessages::TimeSignedIn { user_id, .. } => (vec![], vec![*user_id]),
+        WebSocketMessages::MovedIntoClaim {
+            user_id, claim_id, ..
+        }
+        | WebSocketMessages::MovedOutOfClaim {
+            user_id, claim_id, ..
+        }
+        | WebSocketMessages::PlayerMovedIntoClaim {
+            user_id, claim_id, ..
+        }
+        | WebSocketMessages::PlayerMovedOutOfClaim {
+            user_id, claim_id, ..
+        } => (vec![*claim_id as i64], vec![*user_id]),
+        WebSocketMessages::PlayerState(player_state)
+        | WebSocketMessages::PlayerStateDelete(player_state) => {
+            (vec![], vec![player_state.entity_id])
+        }
+        WebSocketMessages::MobileEntityState(mobile_entity_state) => {
+            (vec![], vec![mobile_entity_state.entity_id as i64])
+        }
+        WebSocketMessages::PlayerActionState(player_action_state) => {
+            (vec![], vec![player_action_state.entity_id as i64])
+        }
+        WebSocketMessages::PlayerActionStateChangeName(_, entity_id) => {
+            (vec![], vec![*entity_id as i64])
+        }
+        WebSocketMessages::ActionState(action_state) => {
+            (vec![], vec![action_state.owner_entity_id as i64])
+        }
+        WebSocketMessages::ClaimLocalState(claim_local_state) => {
+            (vec![claim_local_state.entity_id], vec![])
+        }
+        WebSocketMessages::ClaimEvent { claim_id, .. } => (vec![*claim_id], vec![]),
+        WebSocketMessages::TravelerTaskState(traveler_task_state)
+        | WebSocketMessages::TravelerTaskStateDelete(traveler_task_state) => {
+            (vec![], vec![traveler_task_state.player_entity_id])
+        }
+        WebSocketMessages::InsertSellOrder(order)
+        | WebSocketMessages::UpdateSellOrder(order)
+        | WebSocketMessages::RemoveSellOrder(order)
+        | WebSocketMessages::InsertBuyOrder(order)
+        | WebSocketMessages::UpdateBuyOrder(order)
+        | WebSocketMessages::RemoveBuyOrder(order) => (
+            vec![order.claim_entity_id as i64],
+            vec![order.owner_entity_id as i64],
+        ),
+        WebSocketMessages::InventoryUpdate {
+            resolved_inventory, ..
+        }
+        | WebSocketMessages::InventoryRemove {
+            resolved_inventory, ..
+        }
+        | WebSocketMessages::InventoryInsert {
+            resolved_inventory, ..
+        } => (vec![], vec![resolved_inventory.player_owner_entity_id]),
+        _ => (vec![], vec![]),
+    }
+}
+
+/// Every `(item_type, item_id, tag)` the message carries.
+fn message_items(state: &AppState, message: &WebSocketMessages) -> Vec<(i32, i32, Option<String>)> {
+    match message {
+        WebSocketMessages::InsertSellOrder(order)
+        | WebSocketMessages::UpdateSellOrder(order)
+        | WebSocketMessages::RemoveSellOrder(order)
+        | WebSocketMessages::InsertBuyOrder(order)
+        | WebSocketMessages::UpdateBuyOrder(order)
+        | WebSocketMessages::RemoveBuyOrder(order) => {
+            let tag = if order.item_type == 0 {
+                state
+                    .item_desc
+                    .get(&order.item_id)
+                    .map(|item| item.tag.clone())
+            } else {
+                state
+                    .cargo_desc
+                    .get(&order.item_id)
+                    .map(|cargo| cargo.tag.clone())
+            };
+
+            vec![(order.item_type, order.item_id, tag)]
+        }
+        WebSocketMessages::InventoryUpdate {
+            resolved_inventory, ..
+        }
+        | WebSocketMessages::InventoryRemove {
+            resolved_inventory, ..
+        }
+        | WebSocketMessages::InventoryInsert {
+            resolved_inventory, ..
+        } => inventory_items(resolved_inventory),
+        _ => vec![],
+    }
+}
+
+fn inventory_items(resolved_inventory: &ResolvedInventory) -> Vec<(i32, i32, Option<String>)> {
+    resolved_inventory
+        .pockets
+        .iter()
+        .filter_map(|pocket| pocket.contents.as_ref())
+        .map(|contents| {
+            let item_type = if contents.item_type == ItemType::Item {
+                0
+            } else {
+                1
+            };
+            let tag = match &contents.item {
+                ItemExpended::Item(item) => item.tag.clone(),
+                ItemExpended::Cargo(cargo) => cargo.tag.clone(),
+            };
+
+            (item_type, contents.item_id, Some(tag))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(topic: &str) -> SubscriptionFilter {
+        SubscriptionFilter {
+            topic: topic.to_string(),
+            entity_id: None,
+            claim_id: None,
+            region: None,
+            item_id: None,
+            item_type: None,
+            item_tag: None,
+        }
+    }
+
+    #[test]
+    fn validates_topics_and_item_predicates() {
+        assert!(filter("experience:Fishing").validate().is_ok());
+        assert!(filter("experiance").validate().is_err());
+
+        let mut orders = filter("insert_sell_order");
+        orders.item_tag = Some("Fish".to_string());
+        assert!(orders.validate().is_ok());
+
+        let mut experience = filter("experience");
+        experience.item_id = Some(1);
+        assert!(experience.validate().is_err());
+    }
+
+    #[test]
+    fn parses_topics_without_panicking() {
+        assert_eq!(
+            parse_topic("player_state.42"),
+            Ok(("player_state", Some(42)))
+        );
+        assert_eq!(parse_topic("time_played"), Ok(("time_played", None)));
+        assert!(parse_topic("player_state.abc").is_err());
+    }
+}
@@ -0,0 +1,1063 @@
+use crate::config::IngestConfig;
+use crate::freshness::Freshness;
+use crate::websocket::SpacetimeUpdateMessages;
+use crate::websocket::recording::RecordedMessage;
+use entity::shared::Region;
+use parking_lot::Mutex;
+use spacetimedb_sdk::__codegen::__lib;
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock, mpsc as std_mpsc};
+use std::thread::JoinHandle;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::Notify;
+use tokio::sync::mpsc::{self, error::TrySendError};
+
+const CHECKPOINT: &str = "checkpoint";
+
+static CONFIG: OnceLock<IngestConfig> = OnceLock::new();
+static FRESHNESS: OnceLock<Arc<Freshness>> = OnceLock::new();
+
+/// Has to run before the first worker is created, later calls are ignored.
+pub(crate) fn configure(config: &IngestConfig, freshness: Arc<Freshness>) {
+    let _ = CONFIG.set(config.clone());
+    let _ = FRESHNESS.set(freshness);
+}
+
+/// Creates the queue between the SpacetimeDB listeners and `worker`. Messages the worker did not
+/// acknowledge before the last shutdown are delivered first.
+pub(crate) fn channel<T>(worker: &'static str) -> (IngestSender<T>, IngestReceiver<T>) {
+    channel_with(
+        worker,
+        CONFIG.get().cloned().unwrap_or_default(),
+        true,
+        FRESHNESS.get().cloned(),
+    )
+}
+
+/// For workers that only keep their state in memory, which the next snapshot rebuilds anyway.
+/// Only messages that spill get written and nothing is replayed after a restart.
+pub(crate) fn volatile_channel<T>(worker: &'static str) -> (IngestSender<T>, IngestReceiver<T>) {
+    channel_with(
+        worker,
+        CONFIG.get().cloned().unwrap_or_default(),
+        false,
+        FRESHNESS.get().cloned(),
+    )
+}
+
+fn channel_with<T>(
+    worker: &'static str,
+    config: IngestConfig,
+    write_ahead: bool,
+    freshness: Option<Arc<Freshness>>,
+) -> (IngestSender<T>, IngestReceiver<T>) {
+    let (tx, rx) = mpsc::channel(config.capacity.max(1));
+    let (log, recovered) = Log::open(worker, &config, write_ahead);
+    let acked = log.acked;
+    let files = Arc::new(LogFiles {
+        appended: AtomicU64::new(0),
+        append_done: Notify::new(),
+        log: Mutex::new(log),
+    });
+    let writer = files
+        .log
+        .lock()
+        .dir
+        .is_some()
+        .then(|| LogWriter::start(worker, files.clone()));
+    let shared = Arc::new(Shared {
+        worker,
+        write_ahead: write_ahead && writer.is_some(),
+        sent: AtomicU64::new(recovered.next_seq - 1),
+        delivered: AtomicU64::new(acked),
+        acked: AtomicU64::new(acked),
+        held: AtomicBool::new(false),
+        queue: Mutex::new(QueueState {
+            next_seq: recovered.next_seq,
+            appends: 0,
+            spilled_from: recovered.spilled_from,
+        }),
+        files,
+        writer,
+        freshness,
+    });
+
+    (
+        IngestSender {
+            shared: shared.clone(),
+            tx,
+            tap: None,
+        },
+        IngestReceiver {
+            shared,
+            rx,
+            reader: None,
+            regions: HashSet::new(),
+        },
+    )
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+/// A log line is the sequence number followed by the message in the JSON format of recordings,
+/// with `at` being the unix time in milliseconds the message was queued at.
+fn encode_line(seq: u64, recorded: &RecordedMessage) -> serde_json::Result<Vec<u8>> {
+    let mut line = format!("{seq} ").into_bytes();
+    serde_json::to_writer(&mut line, recorded)?;
+    line.push(b'\n');
+    Ok(line)
+}
+
+fn segment_path(dir: &Path, first_seq: u64) -> PathBuf {
+    dir.join(format!("{first_seq:020}.log"))
+}
+
+fn region<T>(message: &SpacetimeUpdateMessages<T>) -> Region {
+    match message {
+        SpacetimeUpdateMessages::Initial { database_name, .. }
+        | SpacetimeUpdateMessages::Insert { database_name, .. }
+        | SpacetimeUpdateMessages::Update { database_name, .. }
+        | SpacetimeUpdateMessages::Remove { database_name, .. } => *database_name,
+    }
+}
+
+struct Queued<T> {
+    seq: u64,
+    enqueued_at: u64,
+    event_at: Option<u64>,
+    message: SpacetimeUpdateMessages<T>,
+}
+
+struct Shared {
+    worker: &'static str,
+    /// Whether every message is logged before it is queued, otherwise only spilled ones are.
+    write_ahead: bool,
+    /// The last sequence number handed out to a message.
+    sent: AtomicU64,
+    /// The last sequence number the worker received.
+    delivered: AtomicU64,
+    /// The last sequence number the worker acknowledged.
+    acked: AtomicU64,
+    /// Set once a worker could not write a batch, see [`Checkpoint::hold`].
+    held: AtomicBool,
+    /// Only held for as long as it takes to queue a message, the listeners run on the callback
+    /// thread of the SpacetimeDB connection.
+    queue: Mutex<QueueState>,
+    files: Arc<LogFiles>,
+    /// `None` when the queue is in memory only.
+    writer: Option<LogWriter>,
+    freshness: Option<Arc<Freshness>>,
+}
+
+struct QueueState {
+    next_seq: u64,
+    /// Appends handed to the log thread, compared with [`LogFiles::appended`].
+    appends: u64,
+    /// The next message that is only in the log. While set the receiver reads from the log and
+    /// new messages skip the channel, so the order is kept.
+    spilled_from: Option<u64>,
+}
+
+impl Shared {
+    fn record_depth(&self) {
+        let depth = self
+            .sent
+            .load(Ordering::Relaxed)
+            .saturating_sub(self.delivered.load(Ordering::Relaxed));
+        metrics::gauge!("ingest_queue_depth", &[("worker", self.worker.to_string())])
+            .set(depth as f64);
+    }
+
+    /// Whether the log thread took `operation`.
+    fn log(&self, operation: LogOperation) -> bool {
+        self.writer
+            .as_ref()
+            .is_some_and(|writer| writer.send(operation))
+    }
+}
+
+enum LogOperation {
+    Append {
+        seq: u64,
+        recorded: Box<RecordedMessage>,
+    },
+    Commit(u64),
+}
+
+/// The thread that owns the files of a log, so the listeners never wait on the disk. Dropping
+/// it waits for everything that was handed to it.
+struct LogWriter {
+    operations: Option<std_mpsc::Sender<LogOperation>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl LogWriter {
+    fn start(worker: &'static str, files: Arc<LogFiles>) -> Self {
+        let (operations, received) = std_mpsc::channel();
+        let thread = std::thread::Builder::new()
+            .name(format!("ingest-log-{worker}"))
+            .spawn(move || {
+                for operation in received {
+                    files.apply(operation);
+                }
+            })
+            .inspect_err(|error| {
+                tracing::error!(worker, "Could not start the ingest log thread: {error}")
+            })
+            .ok();
+
+        Self {
+            operations: thread.is_some().then_some(operations),
+            thread,
+        }
+    }
+
+    fn send(&self, operation: LogOperation) -> bool {
+        self.operations
+            .as_ref()
+            .is_some_and(|operations| operations.send(operation).is_ok())
+    }
+}
+
+impl Drop for LogWriter {
+    fn drop(&mut self) {
+        self.operations = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// What the log thread shares with the receiver, which reads spilled messages back.
+struct LogFiles {
+    /// Appends the log thread got through, whether writing them worked or not.
+    appended: AtomicU64,
+    append_done: Notify,
+    log: Mutex<Log>,
+}
+
+impl LogFiles {
+    fn apply(&self, operation: LogOperation) {
+        match operation {
+            LogOperation::Append { seq, recorded } => {
+                let mut log = self.log.lock();
+                let written = encode_line(seq, &recorded)
+                    .map_err(io::Error::other)
+                    .and_then(|line| log.append(seq, &line));
+                if let Err(error) = written {
+                    tracing::error!(
+                        worker = log.worker,
+                        seq,
+                        "Could not write ingest log: {error}"
+                    );
+                }
+                drop(log);
+
+                self.appended.fetch_add(1, Ordering::Release);
+                self.append_done.notify_one();
+            }
+            LogOperation::Commit(seq) => {
+                let mut log = self.log.lock();
+                if let Err(error) = log.commit(seq) {
+                    tracing::error!(
+                        worker = log.worker,
+                        seq,
+                        "Could not commit ingest log: {error}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Where a recovered log continues.
+struct Recovered {
+    next_seq: u64,
+    spilled_from: Option<u64>,
+}
+
+/// The append-only log of a worker, split into segments named after their first sequence
+/// number. With write-ahead every message except snapshots is logged when it is queued, so
+/// whatever was not acknowledged survives a restart. Snapshots only get written when they
+/// spill, a restart subscribes again and gets a fresh one anyway. Segments are synced to disk
+/// before a checkpoint covers them.
+struct Log {
+    worker: &'static str,
+    /// `None` when the queue is in memory only.
+    dir: Option<PathBuf>,
+    segment_bytes: u64,
+    segments: VecDeque<u64>,
+    writer: Option<File>,
+    written: u64,
+    acked: u64,
+}
+
+impl Log {
+    fn open(worker: &'static str, config: &IngestConfig, write_ahead: bool) -> (Self, Recovered) {
+        let mut log = Self {
+            worker,
+            dir: None,
+            segment_bytes: config.segment_bytes,
+            segments: VecDeque::new(),
+            writer: None,
+            written: 0,
+            acked: 0,
+        };
+        let empty = Recovered {
+            next_seq: 1,
+            spilled_from: None,
+        };
+
+        let Some(path) = &config.log_path else {
+            return (log, empty);
+        };
+
+        let dir = Path::new(path).join(worker);
+        match log.recover(&dir, write_ahead) {
+            Ok(recovered) => {
+                log.dir = Some(dir);
+                (log, recovered)
+            }
+            Err(error) => {
+                tracing::error!(
+                    worker,
+                    path = dir.display().to_string(),
+                    "Could not open ingest log, the queue drops messages once it is full: {error}"
+                );
+                log.segments.clear();
+                log.writer = None;
+                log.acked = 0;
+                (log, empty)
+            }
+        }
+    }
+
+    fn recover(&mut self, dir: &Path, write_ahead: bool) -> io::Result<Recovered> {
+        if !write_ahead && dir.exists() {
+            fs::remove_dir_all(dir)?;
+        }
+        fs::create_dir_all(dir)?;
+
+        self.acked = match fs::read_to_string(dir.join(CHECKPOINT)) {
+            Ok(acked) => acked.trim().parse().map_err(io::Error::other)?,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => 0,
+            Err(error) => return Err(error),
+        };
+
+        let mut segments = fs::read_dir(dir)?
+            .filter_map(|entry| {
+                entry
+                    .ok()?
+                    .file_name()
+                    .to_str()?
+                    .strip_suffix(".log")?
+                    .parse::<u64>()
+                    .ok()
+            })
+            .collect::<Vec<_>>();
+        segments.sort_unstable();
+        self.segments = segments.into();
+
+        let mut last_seq = self.acked;
+        if let Some(first_seq) = self.segments.back().copied() {
+            let path = segment_path(dir, first_seq);
+            let (seq, valid_bytes) = last_complete_line(&path)?;
+
+            // A crash can leave half a line behind, appending after it would corrupt the log
+            let file = OpenOptions::new().append(true).open(&path)?;
+            file.set_len(valid_bytes)?;
+
+            last_seq = last_seq
+                .max(first_seq.saturating_sub(1))
+                .max(seq.unwrap_or_default());
+            self.writer = Some(file);
+            self.written = valid_bytes;
+        }
+        let next_seq = last_seq + 1;
+
+        if self.writer.is_none() {
+            self.rotate(dir, next_seq)?;
+        }
+
+        let mut spilled_from = None;
+        if last_seq > self.acked {
+            spilled_from = Some(self.acked + 1);
+            tracing::info!(
+                worker = self.worker,
+                pending = last_seq - self.acked,
+                "Replaying unacknowledged messages from the ingest log"
+            );
+        }
+
+        Ok(Recovered {
+            next_seq,
+            spilled_from,
+        })
+    }
+
+    fn rotate(&mut self, dir: &Path, first_seq: u64) -> io::Result<()> {
+        if let Some(previous) = &self.writer {
+            previous.sync_data()?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(segment_path(dir, first_seq))?;
+
+        self.segments.push_back(first_seq);
+        self.writer = Some(file);
+        self.written = 0;
+
+        Ok(())
+    }
+
+    fn append(&mut self, seq: u64, line: &[u8]) -> io::Result<()> {
+        let Some(dir) = self.dir.clone() else {
+            return Err(io::Error::other("the queue has no log"));
+        };
+
+        if self.writer.is_none() || self.written >= self.segment_bytes {
+            self.rotate(&dir, seq)?;
+        }
+
+        let Some(writer) = self.writer.as_mut() else {
+            return Err(io::Error::other("the log has no segment"));
+        };
+        // One write per line, readers never see half of one
+        writer.write_all(line)?;
+        self.written += line.len() as u64;
+
+        Ok(())
+    }
+
+    /// Persists that everything up to `seq` is in Postgres and drops the segments that only
+    /// hold acknowledged messages.
+    fn commit(&mut self, seq: u64) -> io::Result<()> {
+        if seq <= self.acked {
+            return Ok(());
+        }
+        let Some(dir) = &self.dir else {
+            self.acked = seq;
+            return Ok(());
+        };
+
+        // The checkpoint must never get to disk before what it covers
+        if let Some(writer) = &self.writer {
+            writer.sync_data()?;
+        }
+        let temporary = dir.join(format!("{CHECKPOINT}.tmp"));
+        let mut checkpoint = File::create(&temporary)?;
+        checkpoint.write_all(seq.to_string().as_bytes())?;
+        checkpoint.sync_all()?;
+        fs::rename(&temporary, dir.join(CHECKPOINT))?;
+        File::open(dir)?.sync_all()?;
+        self.acked = seq;
+
+        while self.segments.len() > 1 && self.segments[1] <= seq + 1 {
+            if let Some(first_seq) = self.segments.pop_front() {
+                fs::remove_file(segment_path(dir, first_seq))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The sequence number of the last complete line and where that line ends.
+fn last_complete_line(path: &Path) -> io::Result<(Option<u64>, u64)> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut line = String::new();
+    let mut last_seq = None;
+    let mut valid_bytes = 0;
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        if read == 0 || !line.ends_with('\n') {
+            break;
+        }
+        let Some(seq) = line
+            .split_once(' ')
+            .and_then(|(seq, _)| seq.parse::<u64>().ok())
+        else {
+            break;
+        };
+
+        last_seq = Some(seq);
+        valid_bytes += read as u64;
+    }
+
+    Ok((last_seq, valid_bytes))
+}
+
+#[derive(Debug)]
+pub(crate) enum IngestSendError {
+    /// The worker is gone.
+    Closed,
+    /// The queue is full and the message could not be written to the log.
+    Full,
+}
+
+type Tap<T> = Arc<dyn Fn(&SpacetimeUpdateMessages<T>) + Send + Sync>;
+
+/// The listener side of a worker queue. Sending never blocks, a full queue spills to the log.
+pub(crate) struct IngestSender<T> {
+    shared: Arc<Shared>,
+    tx: mpsc::Sender<Queued<T>>,
+    tap: Option<Tap<T>>,
+}
+
+impl<T> Clone for IngestSender<T> {
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            tx: self.tx.clone(),
+            tap: self.tap.clone(),
+        }
+    }
+}
+
+impl<T> IngestSender<T> {
+    /// Calls `tap` with every message before it is queued.
+    pub(crate) fn with_tap(
+        mut self,
+        tap: impl Fn(&SpacetimeUpdateMessages<T>) + Send + Sync + 'static,
+    ) -> Self {
+        self.tap = Some(Arc::new(tap));
+        self
+    }
+}
+
+impl<T: __lib::ser::Serialize> IngestSender<T> {
+    pub(crate) fn send(&self, message: SpacetimeUpdateMessages<T>) -> Result<(), IngestSendError> {
+        self.send_at(message, None)
+    }
+
+    /// Like `send`, `event_at` being the unix time in milliseconds SpacetimeDB ran the reducer
+    /// behind the message at.
+    pub(crate) fn send_at(
+        &self,
+        message: SpacetimeUpdateMessages<T>,
+        event_at: Option<u64>,
+    ) -> Result<(), IngestSendError> {
+        if let Some(tap) = &self.tap {
+            tap(&message);
+        }
+        if self.tx.is_closed() {
+            return Err(IngestSendError::Closed);
+        }
+
+        let worker = self.shared.worker;
+        let enqueued_at = unix_millis();
+        // Only the rows get encoded here, writing the line is up to the log thread
+        let record = |message: &SpacetimeUpdateMessages<T>| {
+            RecordedMessage::encode(worker, Duration::from_millis(enqueued_at), message)
+                .map(|mut recorded| {
+                    recorded.event_at = event_at;
+                    Box::new(recorded)
+                })
+                .inspect_err(|error| {
+                    tracing::warn!(
+                        worker,
+                        "Could not encode message for the ingest log: {error}"
+                    )
+                })
+                .ok()
+        };
+
+        let recorded = match &message {
+            SpacetimeUpdateMessages::Initial { .. } => None,
+            _ if !self.shared.write_ahead => None,
+            message => record(message),
+        };
+
+        let region = region(&message);
+        let mut queue = self.shared.queue.lock();
+        let seq = queue.next_seq;
+        queue.next_seq += 1;
+        self.shared.sent.store(seq, Ordering::Relaxed);
+        if let Some(freshness) = &self.shared.freshness {
+            freshness.received(worker, region, seq, event_at);
+        }
+
+        // Handed to the log thread before the worker can get it, so the log has it before any
+        // checkpoint covers it
+        let mut logged = false;
+        if let Some(recorded) = recorded {
+            logged = self.shared.log(LogOperation::Append { seq, recorded });
+            queue.appends += logged as u64;
+        }
+
+        let message = if queue.spilled_from.is_none() {
+            match self.tx.try_send(Queued {
+                seq,
+                enqueued_at,
+                event_at,
+                message,
+            }) {
+                Ok(()) => {
+                    drop(queue);
+                    self.shared.record_depth();
+                    return Ok(());
+                }
+                Err(TrySendError::Closed(_)) => return Err(IngestSendError::Closed),
+                Err(TrySendError::Full(queued)) => queued.message,
+            }
+        } else {
+            message
+        };
+
+        if !logged {
+            let Some(recorded) = record(&message) else {
+                return Err(IngestSendError::Full);
+            };
+            if !self.shared.log(LogOperation::Append { seq, recorded }) {
+                tracing::error!(worker, seq, "Ingest queue is full, dropping message");
+                return Err(IngestSendError::Full);
+            }
+            queue.appends += 1;
+        }
+
+        if queue.spilled_from.is_none() {
+            queue.spilled_from = Some(seq);
+            tracing::warn!(worker, seq, "Ingest queue is full, spilling to the log");
+            metrics::gauge!("ingest_queue_spilling", &[("worker", worker.to_string())]).set(1);
+        }
+        drop(queue);
+
+        metrics::counter!(
+            "ingest_queue_spilled_total",
+            &[("worker", worker.to_string())]
+        )
+        .increment(1);
+        self.shared.record_depth();
+
+        Ok(())
+    }
+}
+
+/// Everything a worker received up to a point, commit it once that is in Postgres.
+pub(crate) struct Checkpoint {
+    shared: Arc<Shared>,
+    seq: u64,
+    /// The regions that got messages since the previous checkpoint.
+    regions: HashSet<Region>,
+}
+
+impl Checkpoint {
+    pub(crate) fn commit(self) {
+        let worker = self.shared.worker;
+        if self.shared.held.load(Ordering::Relaxed) {
+            return;
+        }
+
+        self.shared.log(LogOperation::Commit(self.seq));
+        let acked = self
+            .shared
+            .acked
+            .fetch_max(self.seq, Ordering::Relaxed)
+            .max(self.seq);
+        if let Some(freshness) = &self.shared.freshness {
+            for region in &self.regions {
+                freshness.committed(worker, *region);
+            }
+        }
+
+        metrics::gauge!(
+            "ingest_queue_unacknowledged",
+            &[("worker", worker.to_string())]
+        )
+        .set(
+            self.shared
+                .sent
+                .load(Ordering::Relaxed)
+                .saturating_sub(acked) as f64,
+        );
+    }
+
+    /// Leaves everything up to the checkpoint in the log, as the worker could not write it to
+    /// Postgres. Any later checkpoint would acknowledge it along with its own messages, so the
+    /// queue commits nothing anymore and the next start replays the log from the last commit.
+    pub(crate) fn hold(self) {
+        let worker = self.shared.worker;
+        if !self.shared.held.swap(true, Ordering::Relaxed) {
+            tracing::error!(
+                worker,
+                seq = self.seq,
+                "Could not write to Postgres, keeping the ingest log for the next start"
+            );
+            metrics::gauge!("ingest_queue_held", &[("worker", worker.to_string())]).set(1);
+        }
+    }
+
+    /// Commits if everything up to the checkpoint was `written`, otherwise holds it.
+    pub(crate) fn commit_if(self, written: bool) {
+        if written {
+            self.commit();
+        } else {
+            self.hold();
+        }
+    }
+}
+
+struct SegmentReader {
+    first_seq: u64,
+    lines: BufReader<File>,
+}
+
+/// The worker side of a queue, drains the channel first and then whatever spilled.
+pub(crate) struct IngestReceiver<T> {
+    shared: Arc<Shared>,
+    rx: mpsc::Receiver<Queued<T>>,
+    reader: Option<SegmentReader>,
+    regions: HashSet<Region>,
+}
+
+impl<T> IngestReceiver<T> {
+    /// Everything received so far.
+    pub(crate) fn checkpoint(&mut self) -> Checkpoint {
+        Checkpoint {
+            shared: self.shared.clone(),
+            seq: self.shared.delivered.load(Ordering::Relaxed),
+            regions: std::mem::take(&mut self.regions),
+        }
+    }
+
+    /// Acknowledges everything received so far, for workers that write to Postgres themselves.
+    pub(crate) fn commit(&mut self) {
+        self.checkpoint().commit();
+    }
+
+    /// Commits what was received so far if `written`, otherwise holds it, see
+    /// [`Checkpoint::hold`].
+    pub(crate) fn commit_if(&mut self, written: bool) {
+        self.checkpoint().commit_if(written);
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.rx.is_closed() && self.rx.is_empty() && self.shared.queue.lock().spilled_from.is_none()
+    }
+
+    /// Workers apply what they receive to the in-memory state right away, so this is when the
+    /// message counts as applied. Snapshots carry no event and count as current when queued.
+    fn deliver(
+        &mut self,
+        seq: u64,
+        enqueued_at: u64,
+        event_at: Option<u64>,
+        message: SpacetimeUpdateMessages<T>,
+    ) -> SpacetimeUpdateMessages<T> {
+        self.shared.delivered.store(seq, Ordering::Relaxed);
+        self.shared.record_depth();
+
+        let region = region(&message);
+        self.regions.insert(region);
+        if let Some(freshness) = &self.shared.freshness {
+            freshness.applied(
+                self.shared.worker,
+                region,
+                seq,
+                event_at.unwrap_or(enqueued_at),
+            );
+        }
+        metrics::gauge!(
+            "ingest_queue_lag_seconds",
+            &[("worker", self.shared.worker.to_string())]
+        )
+        .set(unix_millis().saturating_sub(enqueued_at) as f64 / 1000.0);
+
+        message
+    }
+}
+
+impl<T> IngestReceiver<T>
+where
+    T: for<'de> __lib::de::Deserialize<'de>,
+{
+    pub(crate) async fn recv(&mut self) -> Option<SpacetimeUpdateMessages<T>> {
+        loop {
+            if let Some(message) = self.try_recv() {
+                return Some(message);
+            }
+
+            if self.shared.queue.lock().spilled_from.is_some() {
+                // What spilled is not written yet, new messages skip the channel until it is
+                self.shared.files.append_done.notified().await;
+                continue;
+            }
+
+            // Nothing spilled, the log only fills up again once the channel is full
+            let queued = self.rx.recv().await?;
+            return Some(self.deliver(
+                queued.seq,
+                queued.enqueued_at,
+                queued.event_at,
+                queued.message,
+            ));
+        }
+    }
+
+    pub(crate) async fn recv_many(
+        &mut self,
+        buffer: &mut Vec<SpacetimeUpdateMessages<T>>,
+        limit: usize,
+    ) -> usize {
+        if limit == 0 {
+            return 0;
+        }
+        let Some(message) = self.recv().await else {
+            return 0;
+        };
+        buffer.push(message);
+
+        let mut count = 1;
+        while count < limit {
+            let Some(message) = self.try_recv() else {
+                break;
+            };
+            buffer.push(message);
+            count += 1;
+        }
+
+        count
+    }
+
+    pub(crate) fn try_recv(&mut self) -> Option<SpacetimeUpdateMessages<T>> {
+        if let Ok(queued) = self.rx.try_recv() {
+            return Some(self.deliver(
+                queued.seq,
+                queued.enqueued_at,
+                queued.event_at,
+                queued.message,
+            ));
+        }
+
+        self.read_spilled()
+    }
+
+    fn read_spilled(&mut self) -> Option<SpacetimeUpdateMessages<T>> {
+        let shared = self.shared.clone();
+        let worker = shared.worker;
+
+        loop {
+            let from = shared.queue.lock().spilled_from?;
+            // Loaded before reading, so an append that lands while reading is not missed
+            let appended = shared.files.appended.load(Ordering::Acquire);
+            let next = next_logged(&mut self.reader, &shared.files.log.lock(), from);
+
+            match next {
+                Ok(Some((seq, recorded))) => {
+                    shared.queue.lock().spilled_from = Some(seq + 1);
+                    let enqueued_at = recorded.at;
+                    let event_at = recorded.event_at;
+
+                    match recorded.decode::<T>() {
+                        Ok(message) => {
+                            return Some(self.deliver(seq, enqueued_at, event_at, message));
+                        }
+                        Err(error) => {
+                            tracing::error!(worker, seq, "Could not decode logged message: {error}")
+                        }
+                    }
+                }
+                Ok(None) => {
+                    let mut queue = shared.queue.lock();
+                    if queue.appends != appended {
+                        // The log thread is still writing, `recv` waits for it
+                        return None;
+                    }
+                    queue.spilled_from = None;
+                    drop(queue);
+
+                    self.reader = None;
+                    tracing::info!(worker, "Ingest queue caught up with its log");
+                    // Nobody acknowledges volatile queues, what was read from the log is done
+                    if !shared.write_ahead {
+                        shared.log(LogOperation::Commit(shared.sent.load(Ordering::Relaxed)));
+                    }
+                    metrics::gauge!("ingest_queue_spilling", &[("worker", worker.to_string())])
+                        .set(0);
+                    return None;
+                }
+                Err(error) => {
+                    shared.queue.lock().spilled_from = None;
+                    self.reader = None;
+                    tracing::error!(worker, "Could not read ingest log, skipping it: {error}");
+                    metrics::gauge!("ingest_queue_spilling", &[("worker", worker.to_string())])
+                        .set(0);
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// The first logged message at or after `from`, `None` once the reader is at the end of the log.
+fn next_logged(
+    reader: &mut Option<SegmentReader>,
+    log: &Log,
+    from: u64,
+) -> anyhow::Result<Option<(u64, RecordedMessage)>> {
+    let Some(dir) = &log.dir else {
+        return Ok(None);
+    };
+    let open = |first_seq: u64| -> io::Result<SegmentReader> {
+        Ok(SegmentReader {
+            first_seq,
+            lines: BufReader::new(File::open(segment_path(dir, first_seq))?),
+        })
+    };
+
+    let mut line = String::new();
+    loop {
+        if reader.is_none() {
+            let first_seq = log
+                .segments
+                .iter()
+                .rev()
+                .find(|first_seq| **first_seq <= from)
+                .or(log.segments.front())
+                .copied();
+            let Some(first_seq) = first_seq else {
+                return Ok(None);
+            };
+            *reader = Some(open(first_seq)?);
+        }
+        let Some(current) = reader.as_mut() else {
+            return Ok(None);
+        };
+
+        line.clear();
+        if current.lines.read_line(&mut line)? == 0 {
+            let first_seq = current.first_seq;
+            let Some(next) = log.segments.iter().find(|next| **next > first_seq).copied() else {
+                return Ok(None);
+            };
+            *reader = Some(open(next)?);
+            continue;
+        }
+
+        let Some((seq, json)) = line.trim_end().split_once(' ') else {
+            return Err(anyhow::anyhow!(
+                "Malformed line in segment {}",
+                current.first_seq
+            ));
+        };
+        let seq = seq.parse::<u64>()?;
+        if seq < from {
+            continue;
+        }
+
+        return Ok(Some((seq, serde_json::from_str(json)?)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_module::module_bindings::ClaimTileState;
+
+    fn config(name: &str, capacity: usize) -> IngestConfig {
+        let path = std::env::temp_dir().join(format!("ingest-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+
+        IngestConfig {
+            capacity,
+            log_path: Some(path.display().to_string()),
+            segment_bytes: 256,
+        }
+    }
+
+    fn insert(entity_id: u64) -> SpacetimeUpdateMessages<ClaimTileState> {
+        SpacetimeUpdateMessages::Insert {
+            event: None,
+            new: ClaimTileState {
+                entity_id,
+                claim_id: 1,
+            },
+            database_name: 2,
+            reducer_name: None,
+        }
+    }
+
+    fn entity_id(message: SpacetimeUpdateMessages<ClaimTileState>) -> u64 {
+        match message {
+            SpacetimeUpdateMessages::Insert { new, .. } => new.entity_id,
+            _ => panic!("Expected an insert"),
+        }
+    }
+
+    #[tokio::test]
+    async fn full_queue_spills_without_reordering() {
+        let (tx, mut rx) = channel_with("claim_tile_state", config("spill", 2), true, None);
+
+        for entity_id in 1..=5 {
+            tx.send(insert(entity_id)).unwrap();
+        }
+        for expected in 1..=3 {
+            assert_eq!(entity_id(rx.recv().await.unwrap()), expected);
+        }
+        tx.send(insert(6)).unwrap();
+        for expected in 4..=6 {
+            assert_eq!(entity_id(rx.recv().await.unwrap()), expected);
+        }
+        assert!(rx.try_recv().is_none());
+    }
+
+    #[tokio::test]
+    async fn unacknowledged_messages_are_replayed_after_a_restart() {
+        let config = config("replay", 10);
+
+        let (tx, mut rx) = channel_with("claim_tile_state", config.clone(), true, None);
+        for entity_id in 1..=20 {
+            tx.send(insert(entity_id)).unwrap();
+        }
+        for _ in 1..=8 {
+            rx.recv().await.unwrap();
+        }
+        rx.commit();
+        rx.recv().await.unwrap();
+        drop((tx, rx));
+
+        let (_tx, mut rx) = channel_with::<ClaimTileState>("claim_tile_state", config, true, None);
+        for expected in 9..=20 {
+            assert_eq!(entity_id(rx.recv().await.unwrap()), expected);
+        }
+        assert!(rx.try_recv().is_none());
+    }
+
+    #[tokio::test]
+    async fn held_checkpoints_are_replayed_after_a_restart() {
+        let config = config("hold", 10);
+
+        let (tx, mut rx) = channel_with("claim_tile_state", config.clone(), true, None);
+        for entity_id in 1..=5 {
+            tx.send(insert(entity_id)).unwrap();
+        }
+        for _ in 1..=2 {
+            rx.recv().await.unwrap();
+        }
+        rx.commit();
+        rx.recv().await.unwrap();
+        rx.commit_if(false);
+        // Would acknowledge the held message along with its own
+        rx.recv().await.unwrap();
+        rx.commit();
+        drop((tx, rx));
+
+        let (_tx, mut rx) = channel_with::<ClaimTileState>("claim_tile_state", config, true, None);
+        for expected in 3..=5 {
+            assert_eq!(entity_id(rx.recv().await.unwrap()), expected);
+        }
+        assert!(rx.try_recv().is_none());
+    }
+}
@@ -5,33 +5,25 @@ use crate::auction_listing_state::bitcraft::{
 use crate::buildings::bitcraft::{
     start_worker_building_desc, start_worker_building_nickname_state, start_worker_building_state,
 };
-use crate::cargo_desc::bitcraft::start_worker_cargo_desc;
 use crate::claims::bitcraft;
-use crate::collectible_desc::bitcraft::start_worker_collectible_desc;
+use crate::claims::bitcraft::{ClaimTile, start_worker_claim_tile_state};
+use crate::claims::events::ClaimEvent;
 use crate::config::Config;
-use crate::crafting_recipe_desc::bitcraft::start_worker_crafting_recipe_desc;
-use crate::deployable_state::bitcraft::start_worker_deployable_state;
-use crate::extraction_recipe_desc::bitcraft::start_worker_extraction_recipe_desc;
-use crate::houses::bitcraft::{
-    start_worker_dimension_description_state, start_worker_interior_network_desc,
-    start_worker_permission_state, start_worker_player_housing_state, start_worker_portal_state,
-};
+use crate::houses::bitcraft::start_worker_player_housing_state;
 use crate::inventory::bitcraft as inventory_bitcraft;
-use crate::item_list_desc::bitcraft::start_worker_item_list_desc;
-use crate::items::bitcraft::start_worker_item_desc;
-use crate::location_state::bitcraft::start_worker_location_state;
-use crate::resource_desc::bitcraft::start_worker_resource_desc;
 
 use crate::crafting::bitcraft::start_worker_progressive_action_state;
 use crate::mobile_entity_state::bitcraft::start_worker_mobile_entity_state;
-use crate::npc_desc::bitcraft::start_worker_npc_desc;
-use crate::skill_descriptions::bitcraft::start_worker_skill_desc;
 use crate::trading_orders::bitcraft::start_worker_trade_order_state;
-use crate::traveler_task_desc::bitcraft::start_worker_traveler_task_desc;
-use crate::traveler_task_state::bitcraft::start_worker_traveler_task_state;
 use crate::user_state::bitcraft::start_worker_user_state;
 use crate::vault_state::bitcraft::start_worker_vault_state_collectibles;
 use crate::websocket::batched_worker::BatchedWorker;
+use crate::websocket::filter::SubscriptionFilter;
+use crate::websocket::ingest_queue::IngestSender;
+use crate::websocket::outbox::DeliveryPolicy;
+use crate::websocket::recording::{Recorder, ReplaySource};
+use crate::websocket::table_sync::TableSyncWorker;
+use crate::websocket::tables::EnabledTables;
 use game_module::module_bindings::*;
 use serde::{Deserialize, Serialize};
 use spacetimedb_sdk::__codegen::Reducer;
@@ -40,26 +32,44 @@ use spacetimedb_sdk::{
     Compression, DbContext, Error, Event, Table, TableWithPrimaryKey, credentials,
 };
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::fs::{self, File};
 use std::io::BufWriter;
-use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::Duration;
 use tokio::time::Instant;
 use ts_rs::TS;
 
 pub(crate) mod batched_worker;
+pub(crate) mod filter;
+pub(crate) mod ingest_queue;
+pub(crate) mod outbox;
+pub(crate) mod reconcile;
+pub(crate) mod recording;
+pub(crate) mod snapshot;
+pub(crate) mod table_sync;
+pub(crate) mod tables;
+
+fn send_worker_message<T: __sdk::__lib::ser::Serialize>(
+    worker_name: &str,
+    tx: &IngestSender<T>,
+    message: SpacetimeUpdateMessages<T>,
+) {
+    send_worker_message_at(worker_name, tx, message, None);
+}
 
-fn send_worker_message<T>(
+/// Like `send_worker_message` for updates, `event_at` feeds the freshness of the table.
+fn send_worker_message_at<T: __sdk::__lib::ser::Serialize>(
     worker_name: &str,
-    tx: &UnboundedSender<SpacetimeUpdateMessages<T>>,
+    tx: &IngestSender<T>,
     message: SpacetimeUpdateMessages<T>,
+    event_at: Option<u64>,
 ) {
     metrics::counter!(
         "worker_queue_sent_total",
         &[("worker", worker_name.to_string())]
     )
     .increment(1);
-    if tx.send(message).is_err() {
+    if tx.send_at(message, event_at).is_err() {
         metrics::counter!(
             "worker_queue_send_errors_total",
             &[("worker", worker_name.to_string())]
@@ -68,6 +78,16 @@ fn send_worker_message<T>(
     }
 }
 
+/// Unix time in milliseconds SpacetimeDB ran the reducer behind `event` at.
+fn event_timestamp(event: &Event<game_module::module_bindings::Reducer>) -> Option<u64> {
+    match event {
+        Event::Reducer(reducer) => {
+            u64::try_from(reducer.timestamp.to_micros_since_unix_epoch() / 1000).ok()
+        }
+        _ => None,
+    }
+}
+
 pub(crate) fn record_worker_received(worker_name: &str, count: usize) {
     if count == 0 {
         return;
@@ -109,9 +129,7 @@ fn connect_to_db(
             )
             .set(1);
 
-            tmp_global_app_state
-                .connection_state
-                .insert(tmp_db_name.clone(), true);
+            tmp_global_app_state.status.connected(&tmp_db_name);
             if let Err(e) = creds_store().save(token) {
                 tracing::warn!(
                     region = tmp_db_name.clone(),
@@ -130,9 +148,10 @@ fn connect_to_db(
             )
             .set(0);
 
-            tmp_disconnect_global_app_state
-                .connection_state
-                .insert(tmp_disconnect_db_name.clone(), false);
+            tmp_disconnect_global_app_state.status.disconnected(
+                &tmp_disconnect_db_name,
+                err.as_ref().map(ToString::to_string),
+            );
             if let Some(err) = err {
                 tracing::error!(
                     region = tmp_disconnect_db_name,
@@ -221,7 +240,7 @@ macro_rules! setup_spacetime_db_listeners {
                     Event::Reducer(reducer) => Some(reducer.reducer.reducer_name()),
                     _ => None,
                 };
-                send_worker_message(
+                send_worker_message_at(
                     $worker_name,
                     &temp_tx,
                     SpacetimeUpdateMessages::Update {
@@ -231,6 +250,7 @@ macro_rules! setup_spacetime_db_listeners {
                         new: new.clone(),
                         reducer_name: reducer_name.clone(),
                     },
+                    event_timestamp(&ctx.event),
                 );
             },
         );
@@ -267,7 +287,7 @@ macro_rules! setup_spacetime_db_listeners {
                     _ => None,
                 };
 
-                send_worker_message(
+                send_worker_message_at(
                     $worker_name,
                     &temp_tx,
                     SpacetimeUpdateMessages::Insert {
@@ -276,6 +296,7 @@ macro_rules! setup_spacetime_db_listeners {
                         new: new.clone(),
                         reducer_name: reducer_name.clone(),
                     },
+                    event_timestamp(&ctx.event),
                 );
             },
         );
@@ -307,7 +328,7 @@ macro_rules! setup_spacetime_db_listeners {
                     Event::Reducer(reducer) => Some(reducer.reducer.reducer_name()),
                     _ => None,
                 };
-                send_worker_message(
+                send_worker_message_at(
                     $worker_name,
                     &temp_tx,
                     SpacetimeUpdateMessages::Remove {
@@ -316,6 +337,7 @@ macro_rules! setup_spacetime_db_listeners {
                         delete: new.clone(),
                         reducer_name: reducer_name.clone(),
                     },
+                    event_timestamp(&ctx.event),
                 );
             },
         );
@@ -354,16 +376,17 @@ macro_rules! setup_spacetime_db_listeners_event {
                     Event::Reducer(reducer) => Some(reducer.reducer.reducer_name()),
                     _ => None,
                 };
-                send_worker_message(
+                send_worker_message_at(
                     $worker_name,
                     &temp_tx,
                     SpacetimeUpdateMessages::Update {
-                        event: Some(Box::new(ctx.event.clone())),
+                        event: ReducerCall::from_event(&ctx.event),
                         database_name: $database_region,
                         old: old.clone(),
                         new: new.clone(),
                         reducer_name: reducer_name.clone(),
                     },
+                    event_timestamp(&ctx.event),
                 );
             },
         );
@@ -398,15 +421,16 @@ macro_rules! setup_spacetime_db_listeners_event {
                     Event::Reducer(reducer) => Some(reducer.reducer.reducer_name()),
                     _ => None,
                 };
-                send_worker_message(
+                send_worker_message_at(
                     $worker_name,
                     &temp_tx,
                     SpacetimeUpdateMessages::Insert {
-                        event: Some(Box::new(ctx.event.clone())),
+                        event: ReducerCall::from_event(&ctx.event),
                         database_name: $database_region,
                         new: new.clone(),
                         reducer_name: reducer_name.clone(),
                     },
+                    event_timestamp(&ctx.event),
                 );
             },
         );
@@ -438,21 +462,115 @@ macro_rules! setup_spacetime_db_listeners_event {
                     _ => None,
                 };
                 metrics::counter!("game_message_events", &labels_delete).increment(1);
-                send_worker_message(
+                send_worker_message_at(
                     $worker_name,
                     &temp_tx,
                     SpacetimeUpdateMessages::Remove {
-                        event: Some(Box::new(ctx.event.clone())),
+                        event: ReducerCall::from_event(&ctx.event),
                         database_name: $database_region,
                         delete: new.clone(),
                         reducer_name: reducer_name.clone(),
                     },
+                    event_timestamp(&ctx.event),
                 );
             },
         );
     };
 }
 
+/// `claim_tile_state` rows only carry the claim, the chunk is taken from the `location_state`
+/// rows that are subscribed alongside them and already applied to the client cache when these
+/// callbacks run.
+fn setup_claim_tile_state_listeners(
+    ctx: &DbConnection,
+    claim_tile_state_tx: &IngestSender<ClaimTile>,
+    database_region: entity::shared::Region,
+) {
+    let temp_tx = claim_tile_state_tx.clone();
+    ctx.db
+        .claim_tile_state()
+        .on_insert(move |ctx: &EventContext, new: &ClaimTileState| {
+            if let Event::SubscribeApplied = ctx.event {
+                return;
+            }
+
+            let reducer_name = match &ctx.event {
+                Event::Reducer(reducer) => Some(reducer.reducer.reducer_name()),
+                _ => None,
+            };
+            send_worker_message_at(
+                "claim_tile_state",
+                &temp_tx,
+                SpacetimeUpdateMessages::Insert {
+                    event: None,
+                    database_name: database_region,
+                    new: claim_tile_from_cache(ctx, new),
+                    reducer_name,
+                },
+                event_timestamp(&ctx.event),
+            );
+        });
+
+    let temp_tx = claim_tile_state_tx.clone();
+    ctx.db.claim_tile_state().on_update(
+        move |ctx: &EventContext, old: &ClaimTileState, new: &ClaimTileState| {
+            let reducer_name = match &ctx.event {
+                Event::Reducer(reducer) => Some(reducer.reducer.reducer_name()),
+                _ => None,
+            };
+            send_worker_message_at(
+                "claim_tile_state",
+                &temp_tx,
+                SpacetimeUpdateMessages::Update {
+                    event: None,
+                    database_name: database_region,
+                    old: claim_tile_from_cache(ctx, old),
+                    new: claim_tile_from_cache(ctx, new),
+                    reducer_name,
+                },
+                event_timestamp(&ctx.event),
+            );
+        },
+    );
+
+    let temp_tx = claim_tile_state_tx.clone();
+    ctx.db
+        .claim_tile_state()
+        .on_delete(move |ctx: &EventContext, delete: &ClaimTileState| {
+            let reducer_name = match &ctx.event {
+                Event::Reducer(reducer) => Some(reducer.reducer.reducer_name()),
+                _ => None,
+            };
+            send_worker_message_at(
+                "claim_tile_state",
+                &temp_tx,
+                SpacetimeUpdateMessages::Remove {
+                    event: None,
+                    database_name: database_region,
+                    delete: claim_tile_from_cache(ctx, delete),
+                    reducer_name,
+                },
+                event_timestamp(&ctx.event),
+            );
+        });
+}
+
+fn claim_tile_from_cache<Ctx: DbContext<DbView = RemoteTables>>(
+    ctx: &Ctx,
+    claim_tile_state: &ClaimTileState,
+) -> ClaimTile {
+    ClaimTile {
+        entity_id: claim_tile_state.entity_id,
+        claim_id: claim_tile_state.claim_id,
+        chunk_index: ctx
+            .db()
+            .location_state()
+            .entity_id()
+            .find(&claim_tile_state.entity_id)
+            .map(|location| location.chunk_index),
+    }
+}
+
 fn connect_to_db_global(
     global_app_state: AppState,
     config: &Config,
@@ -487,45 +605,45 @@ async fn connect_to_db_logic(
     config: &Config,
     database: &str,
     remove_desc: &bool,
-    mobile_entity_state_tx: &UnboundedSender<SpacetimeUpdateMessages<MobileEntityState>>,
-    player_state_tx: &UnboundedSender<SpacetimeUpdateMessages<PlayerState>>,
-    player_username_state_tx: &UnboundedSender<SpacetimeUpdateMessages<PlayerUsernameState>>,
-    experience_state_tx: &UnboundedSender<SpacetimeUpdateMessages<ExperienceState>>,
-    inventory_state_tx: &UnboundedSender<SpacetimeUpdateMessages<InventoryState>>,
-    item_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<ItemDesc>>,
-    cargo_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<CargoDesc>>,
-    vault_state_collectibles_tx: &UnboundedSender<SpacetimeUpdateMessages<VaultState>>,
-    deployable_state_tx: &UnboundedSender<SpacetimeUpdateMessages<DeployableState>>,
-    claim_state_tx: &UnboundedSender<SpacetimeUpdateMessages<ClaimState>>,
-    claim_local_state_tx: &UnboundedSender<SpacetimeUpdateMessages<ClaimLocalState>>,
-    claim_member_state_tx: &UnboundedSender<SpacetimeUpdateMessages<ClaimMemberState>>,
-    skill_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<SkillDesc>>,
-    claim_tech_state_tx: &UnboundedSender<SpacetimeUpdateMessages<ClaimTechState>>,
-    claim_tech_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<ClaimTechDesc>>,
-    building_state_tx: &UnboundedSender<SpacetimeUpdateMessages<BuildingState>>,
-    building_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<BuildingDesc>>,
-    building_nickname_state_tx: &UnboundedSender<SpacetimeUpdateMessages<BuildingNicknameState>>,
-    crafting_recipe_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<CraftingRecipeDesc>>,
-    item_list_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<ItemListDesc>>,
-    traveler_task_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<TravelerTaskDesc>>,
-    traveler_task_state_tx: &UnboundedSender<SpacetimeUpdateMessages<TravelerTaskState>>,
-    trade_order_state_tx: &UnboundedSender<SpacetimeUpdateMessages<TradeOrderState>>,
-    user_state_tx: &UnboundedSender<SpacetimeUpdateMessages<UserState>>,
-    npc_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<NpcDesc>>,
-    buy_order_state_tx: &UnboundedSender<SpacetimeUpdateMessages<AuctionListingState>>,
-    sell_order_state_tx: &UnboundedSender<SpacetimeUpdateMessages<AuctionListingState>>,
-    collectible_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<CollectibleDesc>>,
-    interior_network_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<InteriorNetworkDesc>>,
-    dimension_description_state_tx: &UnboundedSender<
-        SpacetimeUpdateMessages<DimensionDescriptionState>,
-    >,
-    player_housing_state_tx: &UnboundedSender<SpacetimeUpdateMessages<PlayerHousingState>>,
-    permission_state_tx: &UnboundedSender<SpacetimeUpdateMessages<PermissionState>>,
-    portal_state_tx: &UnboundedSender<SpacetimeUpdateMessages<PortalState>>,
-    location_state_tx: &UnboundedSender<SpacetimeUpdateMessages<LocationState>>,
-    resource_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<ResourceDesc>>,
-    extraction_recipe_desc_tx: &UnboundedSender<SpacetimeUpdateMessages<ExtractionRecipeDesc>>,
-    progressive_action_state_tx: &UnboundedSender<SpacetimeUpdateMessages<ProgressiveActionState>>,
+    enabled_tables: &EnabledTables,
+    mobile_entity_state_tx: &IngestSender<MobileEntityState>,
+    player_state_tx: &IngestSender<PlayerState>,
+    player_username_state_tx: &IngestSender<PlayerUsernameState>,
+    experience_state_tx: &IngestSender<ExperienceState>,
+    inventory_state_tx: &IngestSender<InventoryState>,
+    item_desc_tx: &IngestSender<ItemDesc>,
+    cargo_desc_tx: &IngestSender<CargoDesc>,
+    vault_state_collectibles_tx: &IngestSender<VaultState>,
+    deployable_state_tx: &IngestSender<DeployableState>,
+    claim_state_tx: &IngestSender<ClaimState>,
+    claim_local_state_tx: &IngestSender<ClaimLocalState>,
+    claim_member_state_tx: &IngestSender<ClaimMemberState>,
+    skill_desc_tx: &IngestSender<SkillDesc>,
+    claim_tech_state_tx: &IngestSender<ClaimTechState>,
+    claim_tech_desc_tx: &IngestSender<ClaimTechDesc>,
+    building_state_tx: &IngestSender<BuildingState>,
+    building_desc_tx: &IngestSender<BuildingDesc>,
+    building_nickname_state_tx: &IngestSender<BuildingNicknameState>,
+    crafting_recipe_desc_tx: &IngestSender<CraftingRecipeDesc>,
+    item_list_desc_tx: &IngestSender<ItemListDesc>,
+    traveler_task_desc_tx: &IngestSender<TravelerTaskDesc>,
+    traveler_task_state_tx: &IngestSender<TravelerTaskState>,
+    trade_order_state_tx: &IngestSender<TradeOrderState>,
+    user_state_tx: &IngestSender<UserState>,
+    npc_desc_tx: &IngestSender<NpcDesc>,
+    buy_order_state_tx: &IngestSender<AuctionListingState>,
+    sell_order_state_tx: &IngestSender<AuctionListingState>,
+    collectible_desc_tx: &IngestSender<CollectibleDesc>,
+    interior_network_desc_tx: &IngestSender<InteriorNetworkDesc>,
+    dimension_description_state_tx: &IngestSender<DimensionDescriptionState>,
+    player_housing_state_tx: &IngestSender<PlayerHousingState>,
+    permission_state_tx: &IngestSender<PermissionState>,
+    portal_state_tx: &IngestSender<PortalState>,
+    location_state_tx: &IngestSender<LocationState>,
+    resource_desc_tx: &IngestSender<ResourceDesc>,
+    extraction_recipe_desc_tx: &IngestSender<ExtractionRecipeDesc>,
+    progressive_action_state_tx: &IngestSender<ProgressiveActionState>,
+    claim_tile_state_tx: &IngestSender<ClaimTile>,
 ) -> anyhow::Result<()> {
     let ctx = connect_to_db(
         global_app_state.clone(),
@@ -549,6 +667,7 @@ async fn connect_to_db_logic(
         .unwrap()
         .parse::<entity::shared::Region>()?;
 
+    // Tables that are not subscribed to never get rows, so their listeners never fire
     setup_spacetime_db_listeners!(
         ctx,
         mobile_entity_state,
@@ -711,7 +830,8 @@ async fn connect_to_db_logic(
         region_number,
         "location_state"
     );
-    setup_spacetime_db_listeners!(
+    // Renames carry their caller, which is how players prove a link to their account
+    setup_spacetime_db_listeners_event!(
         ctx,
         building_nickname_state,
         building_nickname_state_tx,
@@ -884,182 +1004,7 @@ async fn connect_to_db_logic(
         region_number,
         "progressive_action_state"
     );
-
-    let tables_to_subscribe = vec![
-        "user_state",
-        // "mobile_entity_state",
-        // "claim_tile_state",
-        // "combat_action_desc",
-        "item_desc",
-        "item_list_desc",
-        "cargo_desc",
-        // "equipment_desc",
-        // "player_action_state",
-        "crafting_recipe_desc",
-        // "action_state",
-        "player_state",
-        // "equipment_state",
-        "skill_desc",
-        "player_username_state",
-        "building_desc",
-        "building_state",
-        "building_nickname_state",
-        "vault_state",
-        "experience_state",
-        "claim_tech_state",
-        "claim_state",
-        "claim_member_state",
-        "claim_local_state",
-        "deployable_state",
-        "inventory_state",
-        "collectible_desc",
-        "claim_tech_desc",
-        "resource_desc",
-        "extraction_recipe_desc",
-        "progressive_action_state",
-        "character_stats_state",
-        "identity_role",
-        // "claim_description_state", -> claim_state
-        // "location_state where dimension = 1", // This currently takes to much cpu to run
-        // "select location_state.* from location_state JOIN player_state ps ON player_state.entity_id = location_state.entity_id", // This currently takes to much cpu to run
-        // "select location_state.* from location_state JOIN building_state ps ON building_state.entity_id = building_state.entity_id", // This currently takes to much cpu to run
-        // "select location_state.* from location_state JOIN deployable_state ps ON deployable_state.entity_id = deployable_state.entity_id", // This currently takes to much cpu to run
-        "traveler_task_desc",
-        "traveler_task_state",
-        // "trade_order_state",
-        "buy_order_state",
-        "sell_order_state",
-        "npc_desc",
-        "interior_network_desc",
-        "dimension_description_state",
-        "player_housing_state",
-        "permission_state",
-        "portal_state",
-    ];
-
-    // "ability_custom_desc"
-    // "ability_unlock_desc"
-    // "achievement_desc"
-    // "alert_desc"
-    // "biome_desc"
-    // "buff_desc"
-    // "buff_type_desc"
-    // "building_buff_desc"
-    // "building_claim_desc"
-    // "building_desc"
-    // "building_function_type_mapping_desc"
-    // "building_portal_desc"
-    // "building_repairs_desc"
-    // "building_spawn_desc"
-    // "building_type_desc"
-    // "cargo_desc"
-    // "character_stat_desc"
-    // "chest_rarity_desc"
-    // "claim_tech_desc"
-    // "climb_requirement_desc"
-    // "clothing_desc"
-    // "collectible_desc"
-    // "combat_action_desc"
-    // "combat_action_multi_hit_desc"
-    // "construction_recipe_desc"
-    // "construction_recipe_discovery_cargo_desc"
-    // "construction_recipe_discovery_item_desc"
-    // "construction_recipe_discovery_knowledge_desc"
-    // "contribution_loot_desc"
-    // "crafting_recipe_desc"
-    // "crafting_recipe_discovery_cargo_desc"
-    // "crafting_recipe_discovery_item_desc"
-    // "crafting_recipe_discovery_knowledge_desc"
-    // "deconstruction_recipe_desc"
-    // "deployable_desc"
-    // "distant_visible_entity_desc"
-    // "elevator_desc"
-    // "emote_desc"
-    // "empire_color_desc"
-    // "empire_icon_desc"
-    // "empire_notification_desc"
-    // "empire_rank_desc"
-    // "empire_supplies_desc"
-    // "empire_territory_desc"
-    // "enemy_ai_params_desc"
-    // "enemy_desc"
-    // "enemy_scaling_desc"
-    // "environment_debuff_desc"
-    // "equipment_desc"
-    // "equipment_preset_knowledge_desc"
-    // "extraction_recipe_desc"
-    // "food_desc"
-    // "gate_desc"
-    // "hexite_exchange_entry_desc"
-    // "interior_environment_desc"
-    // "interior_instance_desc"
-    // "interior_network_desc"
-    // "interior_portal_connections_desc"
-    // "interior_shape_desc"
-    // "interior_spawn_desc"
-    // "item_conversion_recipe_desc"
-    // "item_desc"
-    // "item_list_desc"
-    // "knowledge_scroll_desc"
-    // "knowledge_scroll_type_desc"
-    // "knowledge_stat_modifier_desc"
-    // "loot_chest_desc"
-    // "loot_rarity_desc"
-    // "loot_table_desc"
-    // "npc_desc"
-    // "onboarding_reward_desc"
-    // "parameters_desc"
-    // "parameters_player_move_desc"
-    // "pathfinding_desc"
-    // "paving_recipe_discovery_cargo_desc"
-    // "paving_recipe_discovery_item_desc"
-    // "paving_recipe_discovery_knowledge_desc"
-    // "paving_tile_desc"
-    // "pillar_shaping_desc"
-    // "pillar_shaping_recipe_discovery_cargo_desc"
-    // "pillar_shaping_recipe_discovery_item_desc"
-    // "pillar_shaping_recipe_discovery_knowledge_desc"
-    // "player_action_desc"
-    // "player_housing_desc"
-    // "premium_item_desc"
-    // "premium_service_desc"
-    // "private_parameters_desc"
-    // "prospecting_desc"
-    // "quest_chain_desc"
-    // "quest_drop_desc"
-    // "quest_stage_desc"
-    // "reserved_name_desc"
-    // "resource_clump_desc"
-    // "resource_desc"
-    // "resource_growth_recipe_desc"
-    // "resource_placement_recipe_desc"
-    // "resource_placement_recipe_discovery_cargo_desc"
-    // "resource_placement_recipe_discovery_item_desc"
-    // "resource_placement_recipe_discovery_knowledge_desc"
-    // "secondary_knowledge_desc"
-    // "single_resource_to_clump_desc"
-    // "skill_desc"
-    // "targeting_matrix_desc"
-    // "teleport_item_desc"
-    // "terraform_recipe_desc"
-    // "tool_desc"
-    // "tool_type_desc"
-    // "traveler_task_desc"
-    // "traveler_task_knowledge_requirement_desc"
-    // "traveler_trade_order_desc"
-    // "wall_desc"
-    // "weapon_desc"
-    // "weapon_type_desc"
-    // "wind_dbg_desc"
-    // "wind_params_desc"
-
-    let sql_subscribe = vec![
-        "SELECT * FROM equipment_desc",
-        "SELECT * FROM equipment_state",
-        // "SELECT location_state.* FROM location_state JOIN building_state ON location_state.entity_id = building_state.entity_id",
-        // "SELECT location_state.* FROM location_state JOIN portal_state ON location_state.entity_id = portal_state.entity_id",
-        "SELECT mobile_entity_state.* FROM mobile_entity_state JOIN player_state ON mobile_entity_state.entity_id = player_state.entity_id",
-    ];
+    setup_claim_tile_state_listeners(&ctx, claim_tile_state_tx, region_number);
 
     let tmp_region_number = region_number.clone();
     let tmp_mobile_entity_state_tx = mobile_entity_state_tx.clone();
@@ -1099,16 +1044,32 @@ async fn connect_to_db_logic(
     let tmp_resource_desc_tx = resource_desc_tx.clone();
     let tmp_extraction_recipe_desc_tx = extraction_recipe_desc_tx.clone();
     let tmp_progressive_action_state_tx = progressive_action_state_tx.clone();
+    let tmp_claim_tile_state_tx = claim_tile_state_tx.clone();
+    let tmp_subscription_db_name = database.to_string();
+    let tmp_subscription_global_app_state = global_app_state.clone();
+    let tmp_error_db_name = database.to_string();
+    let tmp_error_global_app_state = global_app_state.clone();
+    let tmp_enabled_tables = enabled_tables.clone();
 
     ctx.subscription_builder()
         .on_applied(move |ctx: &SubscriptionEventContext| {
             tracing::debug!(region = region_number, "Handle Subscription response");
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "cargo_desc".to_string())]
-            )
-            .set(ctx.db.cargo_desc().count() as f64);
+            let mut initial_rows = BTreeMap::new();
+            let mut record_initial_rows = |table: &str, count: u64| {
+                if !tmp_enabled_tables.is_enabled(table) {
+                    return;
+                }
+
+                metrics::gauge!(
+                    "worker_queue_initial_batch_size",
+                    &[("worker", table.to_string())]
+                )
+                .set(count as f64);
+                initial_rows.insert(table.to_string(), count);
+            };
+
+            record_initial_rows("cargo_desc", ctx.db.cargo_desc().count());
 
             let cargo_desc = ctx.db.cargo_desc().iter().collect::<Vec<_>>();
             if !cargo_desc.is_empty() {
@@ -1122,11 +1083,10 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "player_username_state".to_string())]
-            )
-            .set(ctx.db.player_username_state().count() as f64);
+            record_initial_rows(
+                "player_username_state",
+                ctx.db.player_username_state().count(),
+            );
             let player_username_state = ctx.db.player_username_state().iter().collect::<Vec<_>>();
             if !player_username_state.is_empty() {
                 send_worker_message(
@@ -1139,11 +1099,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "claim_local_state".to_string())]
-            )
-            .set(ctx.db.claim_local_state().count() as f64);
+            record_initial_rows("claim_local_state", ctx.db.claim_local_state().count());
             let claim_local_state = ctx.db.claim_local_state().iter().collect::<Vec<_>>();
             if !claim_local_state.is_empty() {
                 send_worker_message(
@@ -1156,11 +1112,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "claim_state".to_string())]
-            )
-            .set(ctx.db.claim_state().count() as f64);
+            record_initial_rows("claim_state", ctx.db.claim_state().count());
             let claim_state = ctx.db.claim_state().iter().collect::<Vec<_>>();
             if !claim_state.is_empty() {
                 send_worker_message(
@@ -1173,11 +1125,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "deployable_state".to_string())]
-            )
-            .set(ctx.db.deployable_state().count() as f64);
+            record_initial_rows("deployable_state", ctx.db.deployable_state().count());
             let deployable_state = ctx.db.deployable_state().iter().collect::<Vec<_>>();
             if !deployable_state.is_empty() {
                 send_worker_message(
@@ -1190,11 +1138,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "item_desc".to_string())]
-            )
-            .set(ctx.db.item_desc().count() as f64);
+            record_initial_rows("item_desc", ctx.db.item_desc().count());
             let item_desc = ctx.db.item_desc().iter().collect::<Vec<_>>();
             if !item_desc.is_empty() {
                 send_worker_message(
@@ -1207,11 +1151,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "skill_desc".to_string())]
-            )
-            .set(ctx.db.skill_desc().count() as f64);
+            record_initial_rows("skill_desc", ctx.db.skill_desc().count());
             let skill_desc = ctx.db.skill_desc().iter().collect::<Vec<_>>();
             if !skill_desc.is_empty() {
                 send_worker_message(
@@ -1224,11 +1164,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "building_desc".to_string())]
-            )
-            .set(ctx.db.building_desc().count() as f64);
+            record_initial_rows("building_desc", ctx.db.building_desc().count());
             let building_desc = ctx.db.building_desc().iter().collect::<Vec<_>>();
             if !building_desc.is_empty() {
                 send_worker_message(
@@ -1241,11 +1177,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "claim_tech_desc".to_string())]
-            )
-            .set(ctx.db.claim_tech_desc().count() as f64);
+            record_initial_rows("claim_tech_desc", ctx.db.claim_tech_desc().count());
             let claim_tech_desc = ctx.db.claim_tech_desc().iter().collect::<Vec<_>>();
             if !claim_tech_desc.is_empty() {
                 send_worker_message(
@@ -1258,11 +1190,10 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "crafting_recipe_desc".to_string())]
-            )
-            .set(ctx.db.crafting_recipe_desc().count() as f64);
+            record_initial_rows(
+                "crafting_recipe_desc",
+                ctx.db.crafting_recipe_desc().count(),
+            );
             let crafting_recipe_desc = ctx.db.crafting_recipe_desc().iter().collect::<Vec<_>>();
             if !crafting_recipe_desc.is_empty() {
                 send_worker_message(
@@ -1275,11 +1206,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "item_list_desc".to_string())]
-            )
-            .set(ctx.db.item_list_desc().count() as f64);
+            record_initial_rows("item_list_desc", ctx.db.item_list_desc().count());
             let item_list_desc = ctx.db.item_list_desc().iter().collect::<Vec<_>>();
             if !item_list_desc.is_empty() {
                 send_worker_message(
@@ -1292,11 +1219,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "traveler_task_desc".to_string())]
-            )
-            .set(ctx.db.traveler_task_desc().count() as f64);
+            record_initial_rows("traveler_task_desc", ctx.db.traveler_task_desc().count());
             let traveler_task_desc = ctx.db.traveler_task_desc().iter().collect::<Vec<_>>();
             if !traveler_task_desc.is_empty() {
                 send_worker_message(
@@ -1309,11 +1232,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "npc_desc".to_string())]
-            )
-            .set(ctx.db.npc_desc().count() as f64);
+            record_initial_rows("npc_desc", ctx.db.npc_desc().count());
             let npc_desc = ctx.db.npc_desc().iter().collect::<Vec<_>>();
             if !npc_desc.is_empty() {
                 send_worker_message(
@@ -1335,11 +1254,10 @@ async fn connect_to_db_logic(
                 )
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "extraction_recipe_desc".to_string())]
-            )
-            .set(ctx.db.extraction_recipe_desc().count() as f64);
+            record_initial_rows(
+                "extraction_recipe_desc",
+                ctx.db.extraction_recipe_desc().count(),
+            );
             let extraction_recipe_desc = ctx.db.extraction_recipe_desc().iter().collect::<Vec<_>>();
             if !extraction_recipe_desc.is_empty() {
                 send_worker_message(
@@ -1352,11 +1270,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "inventory_state".to_string())]
-            )
-            .set(ctx.db.inventory_state().count() as f64);
+            record_initial_rows("inventory_state", ctx.db.inventory_state().count());
             let inventory_state = ctx.db.inventory_state().iter().collect::<Vec<_>>();
             if !inventory_state.is_empty() {
                 send_worker_message(
@@ -1369,11 +1283,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "claim_member_state".to_string())]
-            )
-            .set(ctx.db.claim_member_state().count() as f64);
+            record_initial_rows("claim_member_state", ctx.db.claim_member_state().count());
             let claim_member_state = ctx.db.claim_member_state().iter().collect::<Vec<_>>();
             if !claim_member_state.is_empty() {
                 send_worker_message(
@@ -1386,11 +1296,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "experience_state".to_string())]
-            )
-            .set(ctx.db.experience_state().count() as f64);
+            record_initial_rows("experience_state", ctx.db.experience_state().count());
             let experience_state = ctx.db.experience_state().iter().collect::<Vec<_>>();
             if !experience_state.is_empty() {
                 send_worker_message(
@@ -1403,11 +1309,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "mobile_entity_state".to_string())]
-            )
-            .set(ctx.db.mobile_entity_state().count() as f64);
+            record_initial_rows("mobile_entity_state", ctx.db.mobile_entity_state().count());
             let mobile_entity_state = ctx.db.mobile_entity_state().iter().collect::<Vec<_>>();
             if !mobile_entity_state.is_empty() {
                 send_worker_message(
@@ -1420,11 +1322,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "player_state".to_string())]
-            )
-            .set(ctx.db.player_state().count() as f64);
+            record_initial_rows("player_state", ctx.db.player_state().count());
             let player_state = ctx.db.player_state().iter().collect::<Vec<_>>();
             if !player_state.is_empty() {
                 send_worker_message(
@@ -1437,11 +1335,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "vault_state".to_string())]
-            )
-            .set(ctx.db.vault_state().count() as f64);
+            record_initial_rows("vault_state", ctx.db.vault_state().count());
             let vault_state = ctx.db.vault_state().iter().collect::<Vec<_>>();
             if !vault_state.is_empty() {
                 send_worker_message(
@@ -1454,11 +1348,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "claim_tech_state".to_string())]
-            )
-            .set(ctx.db.claim_tech_state().count() as f64);
+            record_initial_rows("claim_tech_state", ctx.db.claim_tech_state().count());
             let claim_tech_state = ctx.db.claim_tech_state().iter().collect::<Vec<_>>();
             if !claim_tech_state.is_empty() {
                 send_worker_message(
@@ -1471,11 +1361,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "building_state".to_string())]
-            )
-            .set(ctx.db.building_state().count() as f64);
+            record_initial_rows("building_state", ctx.db.building_state().count());
             let building_state = ctx.db.building_state().iter().collect::<Vec<_>>();
             if !building_state.is_empty() {
                 send_worker_message(
@@ -1488,11 +1374,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "location_state".to_string())]
-            )
-            .set(ctx.db.location_state().count() as f64);
+            record_initial_rows("location_state", ctx.db.location_state().count());
             let location_state = ctx.db.location_state().iter().collect::<Vec<_>>();
             if !location_state.is_empty() {
                 send_worker_message(
@@ -1505,11 +1387,29 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "building_nickname_state".to_string())]
-            )
-            .set(ctx.db.building_nickname_state().count() as f64);
+            record_initial_rows("claim_tile_state", ctx.db.claim_tile_state().count());
+            let claim_tile_state = ctx
+                .db
+                .claim_tile_state()
+                .iter()
+                .map(|claim_tile_state| claim_tile_from_cache(ctx, &claim_tile_state))
+                .collect::<Vec<_>>();
+            // Sent even when empty, so the worker drops the tiles of claims that are gone
+            if tmp_enabled_tables.is_enabled("claim_tile_state") {
+                send_worker_message(
+                    "claim_tile_state",
+                    &tmp_claim_tile_state_tx,
+                    SpacetimeUpdateMessages::Initial {
+                        database_name: tmp_region_number,
+                        data: claim_tile_state,
+                    },
+                );
+            }
+
+            record_initial_rows(
+                "building_nickname_state",
+                ctx.db.building_nickname_state().count(),
+            );
             let building_nickname_state =
                 ctx.db.building_nickname_state().iter().collect::<Vec<_>>();
             if !building_nickname_state.is_empty() {
@@ -1523,11 +1423,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "traveler_task_state".to_string())]
-            )
-            .set(ctx.db.traveler_task_state().count() as f64);
+            record_initial_rows("traveler_task_state", ctx.db.traveler_task_state().count());
             let traveler_task_state = ctx.db.traveler_task_state().iter().collect::<Vec<_>>();
             if !traveler_task_state.is_empty() {
                 send_worker_message(
@@ -1540,11 +1436,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "trade_order_state".to_string())]
-            )
-            .set(ctx.db.trade_order_state().count() as f64);
+            record_initial_rows("trade_order_state", ctx.db.trade_order_state().count());
             let trade_order_state = ctx.db.trade_order_state().iter().collect::<Vec<_>>();
             if !trade_order_state.is_empty() {
                 send_worker_message(
@@ -1557,11 +1449,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "user_state".to_string())]
-            )
-            .set(ctx.db.user_state().count() as f64);
+            record_initial_rows("user_state", ctx.db.user_state().count());
             let user_state = ctx.db.user_state().iter().collect::<Vec<_>>();
             if !user_state.is_empty() {
                 send_worker_message(
@@ -1574,11 +1462,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "sell_order_state".to_string())]
-            )
-            .set(ctx.db.sell_order_state().count() as f64);
+            record_initial_rows("sell_order_state", ctx.db.sell_order_state().count());
             let sell_order_state = ctx.db.sell_order_state().iter().collect::<Vec<_>>();
             if !sell_order_state.is_empty() {
                 send_worker_message(
@@ -1591,11 +1475,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "buy_order_state".to_string())]
-            )
-            .set(ctx.db.buy_order_state().count() as f64);
+            record_initial_rows("buy_order_state", ctx.db.buy_order_state().count());
             let buy_order_state = ctx.db.buy_order_state().iter().collect::<Vec<_>>();
             if !buy_order_state.is_empty() {
                 send_worker_message(
@@ -1608,11 +1488,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "collectible_desc".to_string())]
-            )
-            .set(ctx.db.collectible_desc().count() as f64);
+            record_initial_rows("collectible_desc", ctx.db.collectible_desc().count());
             let collectible_desc = ctx.db.collectible_desc().iter().collect::<Vec<_>>();
             if !collectible_desc.is_empty() {
                 send_worker_message(
@@ -1625,11 +1501,10 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "interior_network_desc".to_string())]
-            )
-            .set(ctx.db.interior_network_desc().count() as f64);
+            record_initial_rows(
+                "interior_network_desc",
+                ctx.db.interior_network_desc().count(),
+            );
             let interior_network_desc = ctx.db.interior_network_desc().iter().collect::<Vec<_>>();
             if !interior_network_desc.is_empty() {
                 send_worker_message(
@@ -1642,11 +1517,10 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "dimension_description_state".to_string())]
-            )
-            .set(ctx.db.dimension_description_state().count() as f64);
+            record_initial_rows(
+                "dimension_description_state",
+                ctx.db.dimension_description_state().count(),
+            );
             let dimension_description_state = ctx
                 .db
                 .dimension_description_state()
@@ -1663,11 +1537,10 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "player_housing_state".to_string())]
-            )
-            .set(ctx.db.player_housing_state().count() as f64);
+            record_initial_rows(
+                "player_housing_state",
+                ctx.db.player_housing_state().count(),
+            );
             let player_housing_state = ctx.db.player_housing_state().iter().collect::<Vec<_>>();
             if !player_housing_state.is_empty() {
                 tracing::info!(
@@ -1686,11 +1559,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "permission_state".to_string())]
-            )
-            .set(ctx.db.permission_state().count() as f64);
+            record_initial_rows("permission_state", ctx.db.permission_state().count());
             let permission_state = ctx.db.permission_state().iter().collect::<Vec<_>>();
             if !permission_state.is_empty() {
                 send_worker_message(
@@ -1703,11 +1572,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "portal_state".to_string())]
-            )
-            .set(ctx.db.portal_state().count() as f64);
+            record_initial_rows("portal_state", ctx.db.portal_state().count());
             let portal_state = ctx.db.portal_state().iter().collect::<Vec<_>>();
             if !portal_state.is_empty() {
                 send_worker_message(
@@ -1720,11 +1585,7 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "resource_desc".to_string())]
-            )
-            .set(ctx.db.resource_desc().count() as f64);
+            record_initial_rows("resource_desc", ctx.db.resource_desc().count());
             let resource_desc = ctx.db.resource_desc().iter().collect::<Vec<_>>();
             if !resource_desc.is_empty() {
                 send_worker_message(
@@ -1737,11 +1598,10 @@ async fn connect_to_db_logic(
                 );
             }
 
-            metrics::gauge!(
-                "worker_queue_initial_batch_size",
-                &[("worker", "progressive_action_state".to_string())]
-            )
-            .set(ctx.db.progressive_action_state().count() as f64);
+            record_initial_rows(
+                "progressive_action_state",
+                ctx.db.progressive_action_state().count(),
+            );
             let progressive_action_state =
                 ctx.db.progressive_action_state().iter().collect::<Vec<_>>();
             if !progressive_action_state.is_empty() {
@@ -1765,6 +1625,10 @@ async fn connect_to_db_logic(
             //     println!("ID: {} Name: {:?}", resource_desc.identity, resource_desc.role);
             // }
 
+            tmp_subscription_global_app_state
+                .status
+                .subscription_applied(&tmp_subscription_db_name, initial_rows);
+
             tracing::debug!(region = region_number, "Handled Subscription response");
         })
         .on_error(move |ctx: &ErrorContext, err: Error| {
@@ -1774,22 +1638,12 @@ async fn connect_to_db_logic(
                 err,
                 ctx.event
             );
+            tmp_error_global_app_state
+                .status
+                .failed(&tmp_error_db_name, format!("Subscription failed: {err}"));
             // std::process::exit(1);
         })
-        .subscribe(
-            tables_to_subscribe
-                .into_iter()
-                .filter_map(|table| {
-                    if *remove_desc && (table.contains("_desc") || table == "player_housing_state")
-                    {
-                        return None;
-                    }
-
-                    Some(format!("select * from {table}"))
-                })
-                .chain(sql_subscribe.into_iter().map(|s| s.to_string()))
-                .collect::<Vec<_>>(),
-        );
+        .subscribe(enabled_tables.queries(*remove_desc));
 
     let tmp_disconnect_db_name = database.to_string().clone();
     let tmp_disconnect_global_app_state = global_app_state.clone();
@@ -1802,18 +1656,28 @@ async fn connect_to_db_logic(
     .set(0);
 
     tmp_disconnect_global_app_state
-        .connection_state
-        .insert(tmp_disconnect_db_name.clone(), false);
+        .status
+        .disconnected(&tmp_disconnect_db_name, None);
 
     Ok(())
 }
 
 pub fn start_websocket_bitcraft_logic(config: Config, global_app_state: AppState) {
     tokio::spawn(async move {
+        ingest_queue::configure(
+            &config.spacetimedb.ingest,
+            global_app_state.freshness.clone(),
+        );
+        let enabled_tables = EnabledTables::from_config(&config.spacetimedb.tables);
+        tracing::info!(
+            tables = enabled_tables.names().join(","),
+            "Ingesting SpacetimeDB tables"
+        );
+
         let (mobile_entity_state_tx, mobile_entity_state_rx) =
-            tokio::sync::mpsc::unbounded_channel();
+            ingest_queue::volatile_channel("mobile_entity_state");
 
-        let (user_state_tx, user_state_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (user_state_tx, user_state_rx) = ingest_queue::volatile_channel("user_state");
 
         let mut player_state_worker = crate::player_state::bitcraft::PlayerStateWorker::new(
             global_app_state.clone(),
@@ -1839,413 +1703,589 @@ pub fn start_websocket_bitcraft_logic(config: Config, global_app_state: AppState
             Duration::from_millis(200),
         );
 
-        let (item_desc_tx, item_desc_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (item_list_desc_tx, item_list_desc_rx) = tokio::sync::mpsc::unbounded_channel();
-
-        let (cargo_desc_tx, cargo_desc_rx) = tokio::sync::mpsc::unbounded_channel();
-
-        let (vault_state_collectibles_tx, vault_state_collectibles_rx) =
-            tokio::sync::mpsc::unbounded_channel();
-
-        let (deployable_state_tx, deployable_state_rx) = tokio::sync::mpsc::unbounded_channel();
-
-        let mut claim_state_worker = bitcraft::ClaimStateWorker::new(
+        let item_desc_worker = TableSyncWorker::<entity::item_desc::Entity>::new(
             global_app_state.clone(),
             3000,
             Duration::from_millis(200),
         );
-
-        let mut claim_local_state_worker = bitcraft::ClaimLocalStateWorker::new(
+        let item_list_desc_worker = TableSyncWorker::<entity::item_list_desc::Entity>::new(
             global_app_state.clone(),
             3000,
             Duration::from_millis(200),
         );
 
-        let mut claim_member_state_worker = bitcraft::ClaimMemberStateWorker::new(
+        let cargo_desc_worker = TableSyncWorker::<entity::cargo_desc::Entity>::new(
             global_app_state.clone(),
             3000,
             Duration::from_millis(200),
         );
 
-        let (skill_desc_tx, skill_desc_rx) = tokio::sync::mpsc::unbounded_channel();
+        let (vault_state_collectibles_tx, vault_state_collectibles_rx) =
+            ingest_queue::channel("vault_state");
 
-        let mut claim_tech_state_worker = bitcraft::ClaimTechStateWorker::new(
+        let deployable_state_worker = TableSyncWorker::<entity::deployable_state::Entity>::new(
             global_app_state.clone(),
             3000,
             Duration::from_millis(200),
         );
-        let mut claim_tech_desc_worker = bitcraft::ClaimTechDescWorker::new(
+
+        let mut claim_state_worker = bitcraft::ClaimStateWorker::new(
             global_app_state.clone(),
             3000,
             Duration::from_millis(200),
         );
-        let (building_state_tx, building_state_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (building_desc_tx, building_desc_rx) = tokio::sync::mpsc::unbounded_channel();
-
-        let (building_nickname_state_tx, building_nickname_state_rx) =
-            tokio::sync::mpsc::unbounded_channel();
-
-        let (crafting_recipe_desc_tx, crafting_recipe_desc_rx) =
-            tokio::sync::mpsc::unbounded_channel();
 
-        let (traveler_task_desc_tx, traveler_task_desc_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (traveler_task_state_tx, traveler_task_state_rx) =
-            tokio::sync::mpsc::unbounded_channel();
-        let (trade_order_state_tx, trade_order_state_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (buy_order_state_tx, buy_order_state_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (sell_order_state_tx, sell_order_state_rx) = tokio::sync::mpsc::unbounded_channel();
-
-        let (npc_desc_tx, npc_desc_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (collectible_desc_tx, collectible_desc_rx) = tokio::sync::mpsc::unbounded_channel();
-
-        let (interior_network_desc_tx, interior_network_desc_rx) =
-            tokio::sync::mpsc::unbounded_channel();
-        let (dimension_description_state_tx, dimension_description_state_rx) =
-            tokio::sync::mpsc::unbounded_channel();
-        let (player_housing_state_tx, player_housing_state_rx) =
-            tokio::sync::mpsc::unbounded_channel();
-        let (permission_state_tx, permission_state_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (portal_state_tx, portal_state_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (location_state_tx, location_state_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (resource_desc_tx, resource_desc_rx) = tokio::sync::mpsc::unbounded_channel();
-        let (extraction_recipe_desc_tx, extraction_recipe_desc_rx) =
-            tokio::sync::mpsc::unbounded_channel();
-        let (progressive_action_state_tx, progressive_action_state_rx) =
-            tokio::sync::mpsc::unbounded_channel();
-
-        let mut remove_desc = false;
-
-        let tmp_conf = config.clone();
-        let tmp_global_app_state = global_app_state.clone();
-        let tmp_database = config.spacetimedb.database.clone();
-
-        let result = connect_to_db_global(tmp_global_app_state, &tmp_conf, &tmp_database);
-
-        if let Err(error) = result {
-            tracing::error!(
-                region = tmp_database,
-                error = error.to_string(),
-                "Error creating connection to {tmp_database} on {}",
-                tmp_conf.spacetimedb_url()
-            )
-        };
-
-        config
-            .spacetimedb
-            .databases
-            .iter()
-            .filter(|value| !value.trim().is_empty())
-            .for_each(|database| {
-                let tmp_mobile_entity_state_tx = mobile_entity_state_tx.clone();
-                let tmp_player_state_tx = player_state_worker.tx();
-                let tmp_player_username_state_tx = player_username_state_worker.tx();
-                let tmp_experience_state_tx = experience_state_worker.tx();
-                let tmp_inventory_state_tx = inventory_state_worker.tx();
-                let tmp_item_desc_tx = item_desc_tx.clone();
-                let tmp_cargo_desc_tx = cargo_desc_tx.clone();
-                let tmp_vault_state_collectibles_tx = vault_state_collectibles_tx.clone();
-                let tmp_deployable_state_tx = deployable_state_tx.clone();
-                let tmp_claim_state_tx = claim_state_worker.tx();
-                let tmp_claim_local_state_tx = claim_local_state_worker.tx();
-                let tmp_claim_member_state_tx = claim_member_state_worker.tx();
-                let tmp_skill_desc_tx = skill_desc_tx.clone();
-                let tmp_claim_tech_state_tx = claim_tech_state_worker.tx();
-                let tmp_claim_tech_desc_tx = claim_tech_desc_worker.tx();
-                let tmp_building_state_tx = building_state_tx.clone();
-                let tmp_building_desc_tx = building_desc_tx.clone();
-                let tmp_building_nickname_state_tx = building_nickname_state_tx.clone();
-                let tmp_crafting_recipe_desc_tx = crafting_recipe_desc_tx.clone();
-                let tmp_item_list_desc_tx = item_list_desc_tx.clone();
-                let tmp_traveler_task_desc_tx = traveler_task_desc_tx.clone();
-                let tmp_traveler_task_state_tx = traveler_task_state_tx.clone();
-                let tmp_trade_order_state_tx = trade_order_state_tx.clone();
-                let tmp_user_state_tx = user_state_tx.clone();
-                let tmp_npc_desc_tx = npc_desc_tx.clone();
-                let tmp_buy_order_state_tx = buy_order_state_tx.clone();
-                let tmp_sell_order_state_tx = sell_order_state_tx.clone();
-                let tmp_collectible_desc_tx = collectible_desc_tx.clone();
-                let tmp_interior_network_desc_tx = interior_network_desc_tx.clone();
-                let tmp_dimension_description_state_tx = dimension_description_state_tx.clone();
-                let tmp_player_housing_state_tx = player_housing_state_tx.clone();
-                let tmp_permission_state_tx = permission_state_tx.clone();
-                let tmp_portal_state_tx = portal_state_tx.clone();
-                let tmp_location_state_tx = location_state_tx.clone();
-                let tmp_resource_desc_tx = resource_desc_tx.clone();
-                let tmp_extraction_recipe_desc_tx = extraction_recipe_desc_tx.clone();
-                let tmp_progressive_action_state_tx = progressive_action_state_tx.clone();
-                let tmp_conf = config.clone();
-                let tmp_global_app_state = global_app_state.clone();
-                let tmp_remove_desc = remove_desc;
-                let tmp_database = database.clone();
-
-                tokio::spawn(async move {
-                    let mut tries = 0;
-
-                    loop {
-                        metrics::gauge!(
-                            "bitcraft_database_connected",
-                            &[("region", tmp_database.clone())]
-                        )
-                            .set(0);
-
-                        let last_connected = Instant::now();
-
-                        let result = connect_to_db_logic(
-                            tmp_global_app_state.clone(),
-                            &tmp_conf,
-                            &tmp_database,
-                            &tmp_remove_desc,
-                            &tmp_mobile_entity_state_tx,
-                            &tmp_player_state_tx,
-                            &tmp_player_username_state_tx,
-                            &tmp_experience_state_tx,
-                            &tmp_inventory_state_tx,
-                            &tmp_item_desc_tx,
-                            &tmp_cargo_desc_tx,
-                            &tmp_vault_state_collectibles_tx,
-                            &tmp_deployable_state_tx,
-                            &tmp_claim_state_tx,
-                            &tmp_claim_local_state_tx,
-                            &tmp_claim_member_state_tx,
-                            &tmp_skill_desc_tx,
-                            &tmp_claim_tech_state_tx,
-                            &tmp_claim_tech_desc_tx,
-                            &tmp_building_state_tx,
-                            &tmp_building_desc_tx,
-                            &tmp_building_nickname_state_tx,
-                            &tmp_crafting_recipe_desc_tx,
-                            &tmp_item_list_desc_tx,
-                            &tmp_traveler_task_desc_tx,
-                            &tmp_traveler_task_state_tx,
-                            &tmp_trade_order_state_tx,
-                            &tmp_user_state_tx,
-                            &tmp_npc_desc_tx,
-                            &tmp_buy_order_state_tx,
-                            &tmp_sell_order_state_tx,
-                            &tmp_collectible_desc_tx,
-                            &tmp_interior_network_desc_tx,
-                            &tmp_dimension_description_state_tx,
-                            &tmp_player_housing_state_tx,
-                            &tmp_permission_state_tx,
-                            &tmp_portal_state_tx,
-                            &tmp_location_state_tx,
-                            &tmp_resource_desc_tx,
-                            &tmp_extraction_recipe_desc_tx,
-                            &tmp_progressive_action_state_tx,
-                        )
-                            .await;
-
-
-
-                        if let Err(error) = result {
-                            tracing::error!(
-                                region = tmp_database,
-                                error = error.to_string(),
-                                "Error creating connection to {tmp_database} on {}",
-                                tmp_conf.spacetimedb_url()
-                            );
-
-                            if tries > 10 {
-                              tracing::error!(region = tmp_database, "We tried {} and then we disconnected from region {} and server {}", tries, tmp_database.clone(), tmp_conf.spacetimedb_url());
-                              break;
-                            }
-                        } else if last_connected.elapsed().as_secs() > 120 {
-                            tries = 0;
-                        } else {
-                            tries += 1;
-                        };
-
-                        if tries > 15 {
-                            tracing::error!(region = tmp_database, "We tried {} and then we disconnected from region {} and server {}", tries, tmp_database.clone(), tmp_conf.spacetimedb_url());
-                            break;
-                        }
-
-                        if tries > 0 {
-                            tracing::info!(region = tmp_database, "We retry {} and then we disconnected from region {} and server {}", tries, tmp_database.clone(), tmp_conf.spacetimedb_url());
-                            tokio::time::sleep(Duration::from_secs(15)).await;
-                        } else {
-                            tracing::info!(region = tmp_database, "We retry {} and then we disconnected from region {} and server {}", tries, tmp_database.clone(), tmp_conf.spacetimedb_url());
-                            tokio::time::sleep(Duration::from_secs(tries * 20)).await;
-                        }
-                    }
-                });
-
-                remove_desc = true;
-            });
-
-        start_worker_mobile_entity_state(global_app_state.clone(), mobile_entity_state_rx);
-        player_state_worker.start();
-        player_username_state_worker.start();
-        experience_state_worker.start();
-        inventory_state_worker.start();
-        start_worker_vault_state_collectibles(
+        let mut claim_local_state_worker = bitcraft::ClaimLocalStateWorker::new(
             global_app_state.clone(),
-            vault_state_collectibles_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_item_desc(
+
+        let mut claim_member_state_worker = bitcraft::ClaimMemberStateWorker::new(
             global_app_state.clone(),
-            item_desc_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_cargo_desc(
+
+        let skill_desc_worker = TableSyncWorker::<entity::skill_desc::Entity>::new(
             global_app_state.clone(),
-            cargo_desc_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_deployable_state(
+
+        let mut claim_tech_state_worker = bitcraft::ClaimTechStateWorker::new(
             global_app_state.clone(),
-            deployable_state_rx,
             3000,
             Duration::from_millis(200),
         );
-        claim_state_worker.start();
-        claim_local_state_worker.start();
-        claim_member_state_worker.start();
-        start_worker_skill_desc(
+        let mut claim_tech_desc_worker = bitcraft::ClaimTechDescWorker::new(
             global_app_state.clone(),
-            skill_desc_rx,
             3000,
             Duration::from_millis(200),
         );
-        claim_tech_state_worker.start();
-        claim_tech_desc_worker.start();
-        start_worker_building_state(
+        let (building_state_tx, building_state_rx) = ingest_queue::channel("building_state");
+        let (building_desc_tx, building_desc_rx) = ingest_queue::channel("building_desc");
+
+        let (building_nickname_state_tx, building_nickname_state_rx) =
+            ingest_queue::channel("building_nickname_state");
+
+        let crafting_recipe_desc_worker = TableSyncWorker::<entity::crafting_recipe::Entity>::new(
             global_app_state.clone(),
-            building_state_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_building_desc(
+
+        let traveler_task_desc_worker = TableSyncWorker::<entity::traveler_task_desc::Entity>::new(
             global_app_state.clone(),
-            building_desc_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_location_state(
+        let traveler_task_state_worker =
+            TableSyncWorker::<entity::traveler_task_state::Entity>::new(
+                global_app_state.clone(),
+                6000,
+                Duration::from_millis(200),
+            );
+        let (trade_order_state_tx, trade_order_state_rx) =
+            ingest_queue::channel("trade_order_state");
+        let (buy_order_state_tx, buy_order_state_rx) = ingest_queue::channel("buy_order_state");
+        let (sell_order_state_tx, sell_order_state_rx) = ingest_queue::channel("sell_order_state");
+
+        let npc_desc_worker = TableSyncWorker::<entity::npc_desc::Entity>::new(
             global_app_state.clone(),
-            location_state_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_building_nickname_state(
+        let collectible_desc_worker = TableSyncWorker::<entity::collectible_desc::Entity>::new(
             global_app_state.clone(),
-            building_nickname_state_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_crafting_recipe_desc(
+
+        let interior_network_desc_worker =
+            TableSyncWorker::<entity::interior_network_desc::Entity>::new(
+                global_app_state.clone(),
+                3000,
+                Duration::from_millis(200),
+            );
+        let dimension_description_state_worker =
+            TableSyncWorker::<entity::dimension_description_state::Entity>::new(
+                global_app_state.clone(),
+                3000,
+                Duration::from_millis(200),
+            );
+        let (player_housing_state_tx, player_housing_state_rx) =
+            ingest_queue::channel("player_housing_state");
+        let permission_state_worker = TableSyncWorker::<entity::permission_state::Entity>::new(
             global_app_state.clone(),
-            crafting_recipe_desc_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_item_list_desc(
+        let portal_state_worker = TableSyncWorker::<entity::portal_state::Entity>::new(
             global_app_state.clone(),
-            item_list_desc_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_traveler_task_desc(
+        let location_state_worker = TableSyncWorker::<entity::location_state::Entity>::new(
             global_app_state.clone(),
-            traveler_task_desc_rx,
             3000,
             Duration::from_millis(200),
         );
-        start_worker_traveler_task_state(
+        let resource_desc_worker = TableSyncWorker::<entity::resource_desc::Entity>::new(
             global_app_state.clone(),
-            traveler_task_state_rx,
-            6000,
+            3000,
             Duration::from_millis(200),
         );
-        start_worker_trade_order_state(
-            global_app_state.clone(),
-            trade_order_state_rx,
-            6000,
-            Duration::from_millis(200),
+        let extraction_recipe_desc_worker =
+            TableSyncWorker::<entity::extraction_recipe_desc::Entity>::new(
+                global_app_state.clone(),
+                3000,
+                Duration::from_millis(200),
+            );
+        let (progressive_action_state_tx, progressive_action_state_rx) =
+            ingest_queue::channel("progressive_action_state");
+        let (claim_tile_state_tx, claim_tile_state_rx) =
+            ingest_queue::volatile_channel("claim_tile_state");
+
+        let recorder = Recorder::from_config(&config.spacetimedb.recording).await;
+        let mobile_entity_state_tx = recorder.tap("mobile_entity_state", mobile_entity_state_tx);
+        let player_state_tx = recorder.tap("player_state", player_state_worker.tx());
+        let player_username_state_tx =
+            recorder.tap("player_username_state", player_username_state_worker.tx());
+        let experience_state_tx = recorder.tap("experience_state", experience_state_worker.tx());
+        let inventory_state_tx = recorder.tap("inventory_state", inventory_state_worker.tx());
+        let item_desc_tx = recorder.tap("item_desc", item_desc_worker.tx());
+        let cargo_desc_tx = recorder.tap("cargo_desc", cargo_desc_worker.tx());
+        let vault_state_collectibles_tx = recorder.tap("vault_state", vault_state_collectibles_tx);
+        let deployable_state_tx = recorder.tap("deployable_state", deployable_state_worker.tx());
+        let claim_state_tx = recorder.tap("claim_state", claim_state_worker.tx());
+        let claim_local_state_tx = recorder.tap("claim_local_state", claim_local_state_worker.tx());
+        let claim_member_state_tx =
+            recorder.tap("claim_member_state", claim_member_state_worker.tx());
+        let skill_desc_tx = recorder.tap("skill_desc", skill_desc_worker.tx());
+        let claim_tech_state_tx = recorder.tap("claim_tech_state", claim_tech_state_worker.tx());
+        let claim_tech_desc_tx = recorder.tap("claim_tech_desc", claim_tech_desc_worker.tx());
+        let building_state_tx = recorder.tap("building_state", building_state_tx);
+        let building_desc_tx = recorder.tap("building_desc", building_desc_tx);
+        let building_nickname_state_tx =
+            recorder.tap("building_nickname_state", building_nickname_state_tx);
+        let crafting_recipe_desc_tx =
+            recorder.tap("crafting_recipe_desc", crafting_recipe_desc_worker.tx());
+        let item_list_desc_tx = recorder.tap("item_list_desc", item_list_desc_worker.tx());
+        let traveler_task_desc_tx =
+            recorder.tap("traveler_task_desc", traveler_task_desc_worker.tx());
+        let traveler_task_state_tx =
+            recorder.tap("traveler_task_state", traveler_task_state_worker.tx());
+        let trade_order_state_tx = recorder.tap("trade_order_state", trade_order_state_tx);
+        let user_state_tx = recorder.tap("user_state", user_state_tx);
+        let npc_desc_tx = recorder.tap("npc_desc", npc_desc_worker.tx());
+        let buy_order_state_tx = recorder.tap("buy_order_state", buy_order_state_tx);
+        let sell_order_state_tx = recorder.tap("sell_order_state", sell_order_state_tx);
+        let collectible_desc_tx = recorder.tap("collectible_desc", collectible_desc_worker.tx());
+        let interior_network_desc_tx =
+            recorder.tap("interior_network_desc", interior_network_desc_worker.tx());
+        let dimension_description_state_tx = recorder.tap(
+            "dimension_description_state",
+            dimension_description_state_worker.tx(),
         );
+        let player_housing_state_tx = recorder.tap("player_housing_state", player_housing_state_tx);
+        let permission_state_tx = recorder.tap("permission_state", permission_state_worker.tx());
+        let portal_state_tx = recorder.tap("portal_state", portal_state_worker.tx());
+        let location_state_tx = recorder.tap("location_state", location_state_worker.tx());
+        let resource_desc_tx = recorder.tap("resource_desc", resource_desc_worker.tx());
+        let extraction_recipe_desc_tx =
+            recorder.tap("extraction_recipe_desc", extraction_recipe_desc_worker.tx());
+        let progressive_action_state_tx =
+            recorder.tap("progressive_action_state", progressive_action_state_tx);
+        let claim_tile_state_tx = recorder.tap("claim_tile_state", claim_tile_state_tx);
+
+        if let Some(replay_path) = config.spacetimedb.recording.replay_path.clone() {
+            ReplaySource::new(
+                replay_path,
+                config.spacetimedb.recording.replay_speed,
+                enabled_tables.clone(),
+            )
+            .target("mobile_entity_state", mobile_entity_state_tx.clone())
+            .target("player_state", player_state_tx.clone())
+            .target("player_username_state", player_username_state_tx.clone())
+            .target("experience_state", experience_state_tx.clone())
+            .target("inventory_state", inventory_state_tx.clone())
+            .target("item_desc", item_desc_tx.clone())
+            .target("cargo_desc", cargo_desc_tx.clone())
+            .target("vault_state", vault_state_collectibles_tx.clone())
+            .target("deployable_state", deployable_state_tx.clone())
+            .target("claim_state", claim_state_tx.clone())
+            .target("claim_local_state", claim_local_state_tx.clone())
+            .target("claim_member_state", claim_member_state_tx.clone())
+            .target("skill_desc", skill_desc_tx.clone())
+            .target("claim_tech_state", claim_tech_state_tx.clone())
+            .target("claim_tech_desc", claim_tech_desc_tx.clone())
+            .target("building_state", building_state_tx.clone())
+            .target("building_desc", building_desc_tx.clone())
+            .target(
+                "building_nickname_state",
+                building_nickname_state_tx.clone(),
+            )
+            .target("crafting_recipe_desc", crafting_recipe_desc_tx.clone())
+            .target("item_list_desc", item_list_desc_tx.clone())
+            .target("traveler_task_desc", traveler_task_desc_tx.clone())
+            .target("traveler_task_state", traveler_task_state_tx.clone())
+            .target("trade_order_state", trade_order_state_tx.clone())
+            .target("user_state", user_state_tx.clone())
+            .target("npc_desc", npc_desc_tx.clone())
+            .target("buy_order_state", buy_order_state_tx.clone())
+            .target("sell_order_state", sell_order_state_tx.clone())
+            .target("collectible_desc", collectible_desc_tx.clone())
+            .target("interior_network_desc", interior_network_desc_tx.clone())
+            .target(
+                "dimension_description_state",
+                dimension_description_state_tx.clone(),
+            )
+            .target("player_housing_state", player_housing_state_tx.clone())
+            .target("permission_state", permission_state_tx.clone())
+            .target("portal_state", portal_state_tx.clone())
+            .target("location_state", location_state_tx.clone())
+            .target("resource_desc", resource_desc_tx.clone())
+            .target("extraction_recipe_desc", extraction_recipe_desc_tx.clone())
+            .target(
+                "progressive_action_state",
+                progressive_action_state_tx.clone(),
+            )
+            .target("claim_tile_state", claim_tile_state_tx.clone())
+            .start();
+        } else {
+            let mut remove_desc = false;
 
-        start_worker_npc_desc(
-            global_app_state.clone(),
-            npc_desc_rx,
-            3000,
-            Duration::from_millis(200),
-        );
+            let tmp_conf = config.clone();
+            let tmp_global_app_state = global_app_state.clone();
+            let tmp_database = config.spacetimedb.database.clone();
 
-        start_worker_buy_order_state(
-            global_app_state.clone(),
-            buy_order_state_rx,
-            3000,
-            Duration::from_millis(200),
-        );
+            let result = connect_to_db_global(tmp_global_app_state, &tmp_conf, &tmp_database);
 
-        start_worker_sell_order_state(
-            global_app_state.clone(),
-            sell_order_state_rx,
-            3000,
-            Duration::from_millis(200),
-        );
+            if let Err(error) = result {
+                tracing::error!(
+                    region = tmp_database,
+                    error = error.to_string(),
+                    "Error creating connection to {tmp_database} on {}",
+                    tmp_conf.spacetimedb_url()
+                )
+            };
 
-        start_worker_collectible_desc(
-            global_app_state.clone(),
-            collectible_desc_rx,
-            3000,
-            Duration::from_millis(200),
-        );
+            config
+                .spacetimedb
+                .databases
+                .iter()
+                .filter(|value| !value.trim().is_empty())
+                .for_each(|database| {
+                    let tmp_mobile_entity_state_tx = mobile_entity_state_tx.clone();
+                    let tmp_player_state_tx = player_state_tx.clone();
+                    let tmp_player_username_state_tx = player_username_state_tx.clone();
+                    let tmp_experience_state_tx = experience_state_tx.clone();
+                    let tmp_inventory_state_tx = inventory_state_tx.clone();
+                    let tmp_item_desc_tx = item_desc_tx.clone();
+                    let tmp_cargo_desc_tx = cargo_desc_tx.clone();
+                    let tmp_vault_state_collectibles_tx = vault_state_collectibles_tx.clone();
+                    let tmp_deployable_state_tx = deployable_state_tx.clone();
+                    let tmp_claim_state_tx = claim_state_tx.clone();
+                    let tmp_claim_local_state_tx = claim_local_state_tx.clone();
+                    let tmp_claim_member_state_tx = claim_member_state_tx.clone();
+                    let tmp_skill_desc_tx = skill_desc_tx.clone();
+                    let tmp_claim_tech_state_tx = claim_tech_state_tx.clone();
+                    let tmp_claim_tech_desc_tx = claim_tech_desc_tx.clone();
+                    let tmp_building_state_tx = building_state_tx.clone();
+                    let tmp_building_desc_tx = building_desc_tx.clone();
+                    let tmp_building_nickname_state_tx = building_nickname_state_tx.clone();
+                    let tmp_crafting_recipe_desc_tx = crafting_recipe_desc_tx.clone();
+                    let tmp_item_list_desc_tx = item_list_desc_tx.clone();
+                    let tmp_traveler_task_desc_tx = traveler_task_desc_tx.clone();
+                    let tmp_traveler_task_state_tx = traveler_task_state_tx.clone();
+                    let tmp_trade_order_state_tx = trade_order_state_tx.clone();
+                    let tmp_user_state_tx = user_state_tx.clone();
+                    let tmp_npc_desc_tx = npc_desc_tx.clone();
+                    let tmp_buy_order_state_tx = buy_order_state_tx.clone();
+                    let tmp_sell_order_state_tx = sell_order_state_tx.clone();
+                    let tmp_collectible_desc_tx = collectible_desc_tx.clone();
+                    let tmp_interior_network_desc_tx = interior_network_desc_tx.clone();
+                    let tmp_dimension_description_state_tx = dimension_description_state_tx.clone();
+                    let tmp_player_housing_state_tx = player_housing_state_tx.clone();
+                    let tmp_permission_state_tx = permission_state_tx.clone();
+                    let tmp_portal_state_tx = portal_state_tx.clone();
+                    let tmp_location_state_tx = location_state_tx.clone();
+                    let tmp_resource_desc_tx = resource_desc_tx.clone();
+                    let tmp_extraction_recipe_desc_tx = extraction_recipe_desc_tx.clone();
+                    let tmp_progressive_action_state_tx = progressive_action_state_tx.clone();
+                    let tmp_claim_tile_state_tx = claim_tile_state_tx.clone();
+                    let tmp_conf = config.clone();
+                    let tmp_global_app_state = global_app_state.clone();
+                    let tmp_remove_desc = remove_desc;
+                    let tmp_enabled_tables = enabled_tables.clone();
+                    let tmp_database = database.clone();
+
+                    tokio::spawn(async move {
+                        let mut tries = 0;
+
+                        loop {
+                            metrics::gauge!(
+                                "bitcraft_database_connected",
+                                &[("region", tmp_database.clone())]
+                            )
+                                .set(0);
+
+                            let last_connected = Instant::now();
+
+                            let result = connect_to_db_logic(
+                                tmp_global_app_state.clone(),
+                                &tmp_conf,
+                                &tmp_database,
+                                &tmp_remove_desc,
+                                &tmp_enabled_tables,
+                                &tmp_mobile_entity_state_tx,
+                                &tmp_player_state_tx,
+                                &tmp_player_username_state_tx,
+                                &tmp_experience_state_tx,
+                                &tmp_inventory_state_tx,
+                                &tmp_item_desc_tx,
+                                &tmp_cargo_desc_tx,
+                                &tmp_vault_state_collectibles_tx,
+                                &tmp_deployable_state_tx,
+                                &tmp_claim_state_tx,
+                                &tmp_claim_local_state_tx,
+                                &tmp_claim_member_state_tx,
+                                &tmp_skill_desc_tx,
+                                &tmp_claim_tech_state_tx,
+                                &tmp_claim_tech_desc_tx,
+                                &tmp_building_state_tx,
+                                &tmp_building_desc_tx,
+                                &tmp_building_nickname_state_tx,
+                                &tmp_crafting_recipe_desc_tx,
+                                &tmp_item_list_desc_tx,
+                                &tmp_traveler_task_desc_tx,
+                                &tmp_traveler_task_state_tx,
+                                &tmp_trade_order_state_tx,
+                                &tmp_user_state_tx,
+                                &tmp_npc_desc_tx,
+                                &tmp_buy_order_state_tx,
+                                &tmp_sell_order_state_tx,
+                                &tmp_collectible_desc_tx,
+                                &tmp_interior_network_desc_tx,
+                                &tmp_dimension_description_state_tx,
+                                &tmp_player_housing_state_tx,
+                                &tmp_permission_state_tx,
+                                &tmp_portal_state_tx,
+                                &tmp_location_state_tx,
+                                &tmp_resource_desc_tx,
+                                &tmp_extraction_recipe_desc_tx,
+                                &tmp_progressive_action_state_tx,
+                                &tmp_claim_tile_state_tx,
+                            )
+                                .await;
+
+
+
+                            if let Err(error) = result {
+                                tracing::error!(
+                                    region = tmp_database,
+                                    error = error.to_string(),
+                                    "Error creating connection to {tmp_database} on {}",
+                                    tmp_conf.spacetimedb_url()
+                                );
+                                tmp_global_app_state.status.failed(&tmp_database, error.to_string());
+
+                                if tries > 10 {
+                                  tracing::error!(region = tmp_database, "We tried {} and then we disconnected from region {} and server {}", tries, tmp_database.clone(), tmp_conf.spacetimedb_url());
+                                  break;
+                                }
+                            } else if last_connected.elapsed().as_secs() > 120 {
+                                tries = 0;
+                            } else {
+                                tries += 1;
+                            };
+                            tmp_global_app_state.status.reconnect_attempts(&tmp_database, tries);
+
+                            if tries > 15 {
+                                tracing::error!(region = tmp_database, "We tried {} and then we disconnected from region {} and server {}", tries, tmp_database.clone(), tmp_conf.spacetimedb_url());
+                                break;
+                            }
 
-        start_worker_user_state(global_app_state.clone(), user_state_rx);
+                            if tries > 0 {
+                                tracing::info!(region = tmp_database, "We retry {} and then we disconnected from region {} and server {}", tries, tmp_database.clone(), tmp_conf.spacetimedb_url());
+                                tokio::time::sleep(Duration::from_secs(15)).await;
+                            } else {
+                                tracing::info!(region = tmp_database, "We retry {} and then we disconnected from region {} and server {}", tries, tmp_database.clone(), tmp_conf.spacetimedb_url());
+                                tokio::time::sleep(Duration::from_secs(tries * 20)).await;
+                            }
+                        }
+                    });
 
-        start_worker_interior_network_desc(
-            global_app_state.clone(),
-            interior_network_desc_rx,
-            3000,
-            Duration::from_millis(200),
-        );
-        start_worker_dimension_description_state(
-            global_app_state.clone(),
-            dimension_description_state_rx,
-            3000,
-            Duration::from_millis(200),
-        );
-        start_worker_player_housing_state(
-            global_app_state.clone(),
-            player_housing_state_rx,
-            3000,
-            Duration::from_millis(200),
-        );
-        start_worker_permission_state(
-            global_app_state.clone(),
-            permission_state_rx,
-            3000,
-            Duration::from_millis(200),
-        );
-        start_worker_portal_state(
-            global_app_state.clone(),
-            portal_state_rx,
-            3000,
-            Duration::from_millis(200),
-        );
-        start_worker_resource_desc(
-            global_app_state.clone(),
-            resource_desc_rx,
-            3000,
-            Duration::from_millis(200),
-        );
-        start_worker_extraction_recipe_desc(
-            global_app_state.clone(),
-            extraction_recipe_desc_rx,
-            3000,
-            Duration::from_millis(200),
-        );
-        start_worker_progressive_action_state(
-            global_app_state.clone(),
-            progressive_action_state_rx,
-            3000,
-            Duration::from_millis(200),
-        );
+                    remove_desc = true;
+                });
+        }
+
+        if enabled_tables.is_enabled("mobile_entity_state") {
+            start_worker_mobile_entity_state(global_app_state.clone(), mobile_entity_state_rx);
+        }
+        if enabled_tables.is_enabled("claim_tile_state") {
+            start_worker_claim_tile_state(global_app_state.clone(), claim_tile_state_rx);
+        }
+        if enabled_tables.is_enabled("player_state") {
+            player_state_worker.start();
+        }
+        if enabled_tables.is_enabled("player_username_state") {
+            player_username_state_worker.start();
+        }
+        if enabled_tables.is_enabled("experience_state") {
+            experience_state_worker.start();
+        }
+        if enabled_tables.is_enabled("inventory_state") {
+            inventory_state_worker.start();
+        }
+        if enabled_tables.is_enabled("vault_state") {
+            start_worker_vault_state_collectibles(
+                global_app_state.clone(),
+                vault_state_collectibles_rx,
+                3000,
+                Duration::from_millis(200),
+            );
+        }
+        if enabled_tables.is_enabled("item_desc") {
+            item_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("cargo_desc") {
+            cargo_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("deployable_state") {
+            deployable_state_worker.start();
+        }
+        if enabled_tables.is_enabled("claim_state") {
+            claim_state_worker.start();
+        }
+        if enabled_tables.is_enabled("claim_local_state") {
+            claim_local_state_worker.start();
+        }
+        if enabled_tables.is_enabled("claim_member_state") {
+            claim_member_state_worker.start();
+        }
+        if enabled_tables.is_enabled("skill_desc") {
+            skill_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("claim_tech_state") {
+            claim_tech_state_worker.start();
+        }
+        if enabled_tables.is_enabled("claim_tech_desc") {
+            claim_tech_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("building_state") {
+            start_worker_building_state(
+                global_app_state.clone(),
+                building_state_rx,
+                3000,
+                Duration::from_millis(200),
+            );
+        }
+        if enabled_tables.is_enabled("building_desc") {
+            start_worker_building_desc(
+                global_app_state.clone(),
+                building_desc_rx,
+                3000,
+                Duration::from_millis(200),
+            );
+        }
+        if enabled_tables.is_enabled("location_state") {
+            location_state_worker.start();
+        }
+        if enabled_tables.is_enabled("building_nickname_state") {
+            start_worker_building_nickname_state(
+                global_app_state.clone(),
+                building_nickname_state_rx,
+                3000,
+                Duration::from_millis(200),
+            );
+        }
+        if enabled_tables.is_enabled("crafting_recipe_desc") {
+            crafting_recipe_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("item_list_desc") {
+            item_list_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("traveler_task_desc") {
+            traveler_task_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("traveler_task_state") {
+            traveler_task_state_worker.start();
+        }
+        if enabled_tables.is_enabled("trade_order_state") {
+            start_worker_trade_order_state(
+                global_app_state.clone(),
+                trade_order_state_rx,
+                6000,
+                Duration::from_millis(200),
+            );
+        }
+
+        if enabled_tables.is_enabled("npc_desc") {
+            npc_desc_worker.start();
+        }
+
+        if enabled_tables.is_enabled("buy_order_state") {
+            start_worker_buy_order_state(
+                global_app_state.clone(),
+                buy_order_state_rx,
+                3000,
+                Duration::from_millis(200),
+            );
+        }
+
+        if enabled_tables.is_enabled("sell_order_state") {
+            start_worker_sell_order_state(
+                global_app_state.clone(),
+                sell_order_state_rx,
+                3000,
+                Duration::from_millis(200),
+            );
+        }
+
+        if enabled_tables.is_enabled("collectible_desc") {
+            collectible_desc_worker.start();
+        }
+
+        if enabled_tables.is_enabled("user_state") {
+            start_worker_user_state(global_app_state.clone(), user_state_rx);
+        }
+
+        if enabled_tables.is_enabled("interior_network_desc") {
+            interior_network_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("dimension_description_state") {
+            dimension_description_state_worker.start();
+        }
+        if enabled_tables.is_enabled("player_housing_state") {
+            start_worker_player_housing_state(
+                global_app_state.clone(),
+                player_housing_state_rx,
+                3000,
+                Duration::from_millis(200),
+            );
+        }
+        if enabled_tables.is_enabled("permission_state") {
+            permission_state_worker.start();
+        }
+        if enabled_tables.is_enabled("portal_state") {
+            portal_state_worker.start();
+        }
+        if enabled_tables.is_enabled("resource_desc") {
+            resource_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("extraction_recipe_desc") {
+            extraction_recipe_desc_worker.start();
+        }
+        if enabled_tables.is_enabled("progressive_action_state") {
+            start_worker_progressive_action_state(
+                global_app_state.clone(),
+                progressive_action_state_rx,
+                3000,
+                Duration::from_millis(200),
+            );
+        }
     });
 }
 
@@ -2276,6 +2316,26 @@ async fn websocket_retry_helper(
     false
 }
 
+/// The part of the reducer event behind a message that workers use. Unlike the SDK event it
+/// survives recordings and the ingest log.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ReducerCall {
+    pub(crate) caller_identity: __sdk::Identity,
+    pub(crate) timestamp: __sdk::Timestamp,
+}
+
+impl ReducerCall {
+    fn from_event(event: &Event<game_module::module_bindings::Reducer>) -> Option<Self> {
+        match event {
+            Event::Reducer(reducer) => Some(Self {
+                caller_identity: reducer.caller_identity,
+                timestamp: reducer.timestamp,
+            }),
+            _ => None,
+        }
+    }
+}
+
 #[allow(dead_code)]
 pub(crate) enum SpacetimeUpdateMessages<T> {
     Initial {
@@ -2283,20 +2343,20 @@ pub(crate) enum SpacetimeUpdateMessages<T> {
         database_name: entity::shared::Region,
     },
     Insert {
-        event: Option<Box<__sdk::Event<game_module::module_bindings::Reducer>>>,
+        event: Option<ReducerCall>,
         new: T,
         database_name: entity::shared::Region,
         reducer_name: Option<&'static str>,
     },
     Update {
-        event: Option<Box<__sdk::Event<game_module::module_bindings::Reducer>>>,
+        event: Option<ReducerCall>,
         old: T,
         new: T,
         database_name: entity::shared::Region,
         reducer_name: Option<&'static str>,
     },
     Remove {
-        event: Option<Box<__sdk::Event<game_module::module_bindings::Reducer>>>,
+        event: Option<ReducerCall>,
         delete: T,
         database_name: entity::shared::Region,
         reducer_name: Option<&'static str>,
@@ -2309,12 +2369,34 @@ pub(crate) enum SpacetimeUpdateMessages<T> {
 pub(crate) enum WebSocketMessages {
     Subscribe {
         topics: Vec<String>,
+        /// Send a `Snapshot` of the current state for every topic before its deltas and wrap
+        /// every following delta in `Sequenced`.
+        #[serde(default)]
+        #[ts(optional)]
+        snapshot: Option<bool>,
     },
     ListSubscribedTopics,
     SubscribedTopics(Vec<String>),
     Unsubscribe {
         topic: String,
     },
+    /// Subscribes to the messages matching `filter`, `id` is picked by the client to replace or
+    /// unsubscribe the filter later on.
+    SubscribeFilter {
+        id: String,
+        filter: SubscriptionFilter,
+    },
+    SubscribedFilter {
+        id: String,
+    },
+    UnsubscribeFilter {
+        id: String,
+    },
+    /// A subscription request for `topic` (or the filter id) was rejected.
+    SubscriptionError {
+        topic: String,
+        error: String,
+    },
     MobileEntityState(entity::mobile_entity_state::Model),
     Experience {
         experience: u64,
@@ -2367,10 +2449,15 @@ pub(crate) enum WebSocketMessages {
         skill_name: String,
     },
     PlayerState(entity::player_state::Model),
+    PlayerStateDelete(entity::player_state::Model),
     TravelerTaskState(entity::traveler_task_state::Model),
     TravelerTaskStateDelete(entity::traveler_task_state::Model),
     // ClaimDescriptionState(entity::claim_description_state::Model),
     ClaimLocalState(entity::claim_local_state::Model),
+    ClaimEvent {
+        claim_id: i64,
+        event: ClaimEvent,
+    },
     Message(String),
     ActionState(entity::action_state::Model),
     InsertSellOrder(entity::auction_listing_state::AuctionListingState),
@@ -2381,17 +2468,87 @@ pub(crate) enum WebSocketMessages {
     RemoveBuyOrder(entity::auction_listing_state::AuctionListingState),
     InventoryUpdate {
         resolved_inventory: entity::inventory::ResolvedInventory,
+        #[serde(skip)]
+        #[ts(skip)]
+        region: entity::shared::Region,
     },
     InventoryRemove {
         resolved_inventory: entity::inventory::ResolvedInventory,
+        #[serde(skip)]
+        #[ts(skip)]
+        region: entity::shared::Region,
     },
     InventoryInsert {
         resolved_inventory: entity::inventory::ResolvedInventory,
         player_owner_id: i64,
+        #[serde(skip)]
+        #[ts(skip)]
+        region: entity::shared::Region,
+    },
+    /// The state of `topic` at `sequence`, deltas with a higher sequence apply on top of it.
+    Snapshot {
+        topic: String,
+        sequence: u64,
+        messages: Vec<WebSocketMessages>,
+    },
+    Sequenced {
+        sequence: u64,
+        message: Box<WebSocketMessages>,
     },
 }
 
 impl WebSocketMessages {
+    /// How the message is treated when a client's outbox is full. Messages that carry the whole
+    /// current state of something only keep the latest one per entity, and deltas that would
+    /// leave the client out of sync are never dropped.
+    pub(crate) fn delivery_policy(&self) -> DeliveryPolicy {
+        match self {
+            // A dropped delta would leave a sequenced client behind without it noticing
+            WebSocketMessages::Sequenced { message, .. } => match message.delivery_policy() {
+                DeliveryPolicy::Droppable => DeliveryPolicy::Reliable,
+                policy => policy,
+            },
+            WebSocketMessages::MobileEntityState(mobile_entity_state) => DeliveryPolicy::Coalesce(
+                "mobile_entity_state",
+                mobile_entity_state.entity_id as i64,
+                None,
+            ),
+            WebSocketMessages::PlayerState(player_state)
+            | WebSocketMessages::PlayerStateDelete(player_state) => {
+                DeliveryPolicy::Coalesce("player_state", player_state.entity_id, None)
+            }
+            WebSocketMessages::ClaimLocalState(claim_local_state) => {
+                DeliveryPolicy::Coalesce("claim_local_state", claim_local_state.entity_id, None)
+            }
+            WebSocketMessages::PlayerActionState(player_action_state) => DeliveryPolicy::Coalesce(
+                "player_action_state",
+                player_action_state.entity_id as i64,
+                None,
+            ),
+            WebSocketMessages::Experience {
+                user_id,
+                skill_name,
+                ..
+            } => DeliveryPolicy::Coalesce("experience", *user_id, Some(skill_name.clone())),
+            WebSocketMessages::Level {
+                user_id,
+                skill_name,
+                ..
+            } => DeliveryPolicy::Coalesce("level", *user_id, Some(skill_name.clone())),
+            WebSocketMessages::TotalExperience { user_id, .. } => {
+                DeliveryPolicy::Coalesce("total_experience", *user_id, None)
+            }
+            WebSocketMessages::TimePlayed { user_id, .. } => {
+                DeliveryPolicy::Coalesce("time_played", *user_id, None)
+            }
+            WebSocketMessages::TimeSignedIn { user_id, .. } => {
+                DeliveryPolicy::Coalesce("time_signed_in", *user_id, None)
+            }
+            WebSocketMessages::Message(_) => DeliveryPolicy::Droppable,
+            _ => DeliveryPolicy::Reliable,
+        }
+    }
+
     pub fn topics(&self) -> Option<Vec<(String, Option<i64>)>> {
         match self {
             WebSocketMessages::Experience {
@@ -2406,6 +2563,9 @@ impl WebSocketMessages {
                 "claim_local_state".to_string(),
                 Some(claim_local_state.entity_id),
             )]),
+            WebSocketMessages::ClaimEvent { claim_id, .. } => {
+                Some(vec![("claim".to_string(), Some(*claim_id))])
+            }
             WebSocketMessages::Level {
                 user_id,
                 skill_name,
@@ -2430,7 +2590,8 @@ impl WebSocketMessages {
                 "moved_into_claim".to_string(),
                 Some(*claim_id as i64),
             )]),
-            WebSocketMessages::PlayerState(player) => {
+            WebSocketMessages::PlayerState(player)
+            | WebSocketMessages::PlayerStateDelete(player) => {
                 Some(vec![("player_state".to_string(), Some(player.entity_id))])
             }
             WebSocketMessages::MobileEntityState(mobile_entity_state) => Some(vec![(
@@ -2485,7 +2646,13 @@ impl WebSocketMessages {
             WebSocketMessages::Subscribe { .. } => None,
             WebSocketMessages::SubscribedTopics(_) => None,
             WebSocketMessages::Unsubscribe { .. } => None,
+            WebSocketMessages::SubscribeFilter { .. } => None,
+            WebSocketMessages::SubscribedFilter { .. } => None,
+            WebSocketMessages::UnsubscribeFilter { .. } => None,
+            WebSocketMessages::SubscriptionError { .. } => None,
             WebSocketMessages::Message(_) => None,
+            WebSocketMessages::Snapshot { .. } => None,
+            WebSocketMessages::Sequenced { .. } => None,
             WebSocketMessages::InsertSellOrder(auction_listing_state) => Some(vec![
                 (
                     "insert_sell_order".to_string(),
@@ -2591,3 +2758,135 @@ impl WebSocketMessages {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GraphqlConfig, RateLimitConfig, TableConfig, TableSubscriptionConfig};
+    use sea_orm::{ConnectionTrait, Database, EntityTrait, Schema};
+    use std::collections::HashMap;
+    use test_support::{FakeSpacetimeDb, Transaction};
+    use tokio::sync::mpsc::{UnboundedReceiver, unbounded_channel};
+    use tokio::time::timeout;
+
+    fn claim_local_state(entity_id: u64, supplies: i32) -> ClaimLocalState {
+        ClaimLocalState {
+            entity_id,
+            supplies,
+            building_maintenance: 0.0,
+            num_tiles: 9,
+            num_tile_neighbors: 0,
+            location: None,
+            treasury: 0,
+            xp_gained_since_last_coin_minting: 0,
+            supplies_purchase_threshold: 0,
+            supplies_purchase_price: 0.0,
+            building_description_id: 0,
+        }
+    }
+
+    async fn next_claim_local_state(
+        rx: &mut UnboundedReceiver<WebSocketMessages>,
+    ) -> entity::claim_local_state::Model {
+        loop {
+            let message = timeout(Duration::from_secs(10), rx.recv())
+                .await
+                .expect("No claim_local_state message was broadcast")
+                .unwrap();
+            if let WebSocketMessages::ClaimLocalState(model) = message {
+                return model;
+            }
+        }
+    }
+
+    async fn wait_for_row(
+        conn: &sea_orm::DatabaseConnection,
+        entity_id: i64,
+    ) -> entity::claim_local_state::Model {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            let row = entity::claim_local_state::Entity::find_by_id(entity_id)
+                .one(conn)
+                .await
+                .unwrap();
+            if let Some(row) = row {
+                return row;
+            }
+            assert!(
+                Instant::now() < deadline,
+                "claim_local_state {entity_id} never reached Postgres"
+            );
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Needs a Postgres in `TEST_DATABASE_URL` the test may create and empty `claim_local_state`
+    /// in, it is skipped without one.
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ingests_from_spacetimedb_into_postgres_app_state_and_clients() {
+        let Ok(database_url) = std::env::var("TEST_DATABASE_URL") else {
+            eprintln!("TEST_DATABASE_URL is not set, skipping the ingest test");
+            return;
+        };
+        let conn = Database::connect(database_url).await.unwrap();
+        let backend = conn.get_database_backend();
+        conn.execute(
+            backend.build(
+                Schema::new(backend)
+                    .create_table_from_entity(entity::claim_local_state::Entity)
+                    .if_not_exists(),
+            ),
+        )
+        .await
+        .unwrap();
+        entity::claim_local_state::Entity::delete_many()
+            .exec(&conn)
+            .await
+            .unwrap();
+
+        let fixture = FakeSpacetimeDb::start().await.unwrap();
+        fixture.insert("claim_local_state", [claim_local_state(1, 10)]);
+
+        let mut config = Config::default();
+        config.spacetimedb.protocol = "http://".to_string();
+        config.spacetimedb.domain = fixture.uri().replace("http://", "");
+        config.spacetimedb.database = "bitcraft-global".to_string();
+        config.spacetimedb.databases = vec!["bitcraft-1".to_string()];
+        config.spacetimedb.ingest.log_path = None;
+        config.spacetimedb.tables = TableSubscriptionConfig {
+            default_enabled: false,
+            overrides: HashMap::from([(
+                "claim_local_state".to_string(),
+                TableConfig {
+                    enabled: Some(true),
+                    ..TableConfig::default()
+                },
+            )]),
+        };
+
+        let (tx, mut rx) = unbounded_channel();
+        let state = AppState::new(
+            conn.clone(),
+            tx,
+            HashMap::new(),
+            RateLimitConfig::default(),
+            &GraphqlConfig::default(),
+        );
+        start_websocket_bitcraft_logic(config, state.clone());
+
+        let initial = next_claim_local_state(&mut rx).await;
+        assert_eq!(initial.entity_id, 1);
+        assert_eq!(initial.supplies, 10);
+        assert_eq!(initial.region, 1);
+        assert!(state.claim_local_state.contains_key(&1));
+        assert_eq!(wait_for_row(&conn, 1).await.supplies, 10);
+
+        fixture.commit(Transaction::new().insert("claim_local_state", &claim_local_state(2, 20)));
+
+        let inserted = next_claim_local_state(&mut rx).await;
+        assert_eq!(inserted.entity_id, 2);
+        assert_eq!(inserted.supplies, 20);
+        assert!(state.claim_local_state.contains_key(&2));
+        assert_eq!(wait_for_row(&conn, 2).await.supplies, 20);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::websocket::WebSocketMessages;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use tokio::sync::Notify;
+
+/// How many messages may wait for a client before its queue counts as full.
+pub(crate) const CLIENT_QUEUE_CAPACITY: usize = 256;
+
+/// How a queued message may be treated when the client falls behind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum DeliveryPolicy {
+    /// Only the latest message per key matters, a newer one replaces the queued one. It is never
+    /// dropped, as it may be the only pending state of its entity.
+    Coalesce(&'static str, i64, Option<String>),
+    /// May be dropped to make room for other messages, the only policy that ever is.
+    Droppable,
+    /// Has to be delivered, a client that cannot keep up with these gets disconnected.
+    Reliable,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum PushOutcome {
+    Queued,
+    Coalesced,
+    /// The message was queued after dropping an older one.
+    Dropped,
+    Disconnected,
+}
+
+struct QueuedMessage {
+    policy: DeliveryPolicy,
+    topic: String,
+    message: WebSocketMessages,
+}
+
+/// The per-client queue between the broadcaster and the task writing to the socket. Pushing
+/// never waits, so a slow browser only ever delays itself.
+pub(crate) struct ClientOutbox {
+    queue: Mutex<VecDeque<QueuedMessage>>,
+    notify: Notify,
+    closed: AtomicBool,
+    sequenced: AtomicBool,
+    capacity: usize,
+    // Pushes that found the queue full since the client last took a message
+    overflows: AtomicUsize,
+}
+
+impl ClientOutbox {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::with_capacity(capacity)),
+            notify: Notify::new(),
+            closed: AtomicBool::new(false),
+            sequenced: AtomicBool::new(false),
+            capacity,
+            overflows: AtomicUsize::new(0),
+        }
+    }
+
+    pub(crate) fn push(&self, message: WebSocketMessages, topic: &str) -> PushOutcome {
+        if self.is_closed() {
+            return PushOutcome::Disconnected;
+        }
+
+        let policy = message.delivery_policy();
+        let mut queue = self.queue.lock();
+
+        let coalesced = match policy {
+            DeliveryPolicy::Coalesce(..) => queue.iter().position(|queued| queued.policy == policy),
+            _ => None,
+        };
+        if let Some(index) = coalesced {
+            // Queued behind everything sent before it, so sequence numbers keep increasing
+            queue.remove(index);
+            queue.push_back(QueuedMessage {
+                policy,
+                topic: topic.to_string(),
+                message,
+            });
+            drop(queue);
+            metrics::counter!("websocket_messages_coalesced_total", "topic" => topic.to_string())
+                .increment(1);
+            return PushOutcome::Coalesced;
+        }
+
+        let mut outcome = PushOutcome::Queued;
+        if queue.len() >= self.capacity {
+            let overflows = self.overflows.fetch_add(1, Ordering::Relaxed) + 1;
+            let evictable = queue
+                .iter()
+                .position(|queued| queued.policy == DeliveryPolicy::Droppable);
+
+            match evictable {
+                Some(index) if overflows <= self.capacity => {
+                    if let Some(dropped) = queue.remove(index) {
+                        metrics::counter!(
+                            "websocket_messages_dropped_total",
+                            "topic" => dropped.topic
+                        )
+                        .increment(1);
+                    }
+                    outcome = PushOutcome::Dropped;
+                }
+                _ => {
+                    queue.clear();
+                    drop(queue);
+                    self.close();
+                    metrics::counter!("websocket_slow_consumer_disconnects_total").increment(1);
+                    return PushOutcome::Disconnected;
+                }
+            }
+        }
+
+        queue.push_back(QueuedMessage {
+            policy,
+            topic: topic.to_string(),
+            message,
+        });
+        drop(queue);
+        self.notify.notify_one();
+
+        outcome
+    }
+
+    /// Waits for the next message, `None` once the outbox got closed.
+    pub(crate) async fn recv(&self) -> Option<WebSocketMessages> {
+        loop {
+            if self.is_closed() {
+                return None;
+            }
+
+            if let Some(queued) = self.queue.lock().pop_front() {
+                self.overflows.store(0, Ordering::Relaxed);
+                return Some(queued.message);
+            }
+
+            self.notify.notified().await;
+        }
+    }
+
+    pub(crate) fn close(&self) {
+        self.closed.store(true, Ordering::Relaxed);
+        self.notify.notify_one();
+    }
+
+    pub(crate) fn is_closed(&self) -> bool {
+        self.closed.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn set_sequenced(&self) {
+        self.sequenced.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_sequenced(&self) -> bool {
+        self.sequenced.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time_played(user_id: i64, time: u64) -> WebSocketMessages {
+        WebSocketMessages::TimePlayed {
+            user_id,
+            time,
+            rank: 1,
+        }
+    }
+
+    #[test]
+    fn coalesces_state_per_entity() {
+        let outbox = ClientOutbox::new(4);
+        assert_eq!(
+            outbox.push(time_played(1, 10), "time_played"),
+            PushOutcome::Queued
+        );
+        assert_eq!(
+            outbox.push(time_played(2, 10), "time_played"),
+            PushOutcome::Queued
+        );
+        assert_eq!(
+            outbox.push(time_played(1, 20), "time_played"),
+            PushOutcome::Coalesced
+        );
+
+        let queue = outbox.queue.lock();
+        assert_eq!(queue.len(), 2);
+        assert!(matches!(
+            queue[0].message,
+            WebSocketMessages::TimePlayed { user_id: 2, .. }
+        ));
+        assert!(matches!(
+            queue[1].message,
+            WebSocketMessages::TimePlayed {
+                user_id: 1,
+                time: 20,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn never_drops_the_latest_state_of_an_entity() {
+        let outbox = ClientOutbox::new(2);
+        outbox.push(time_played(1, 10), "time_played");
+        outbox.push(WebSocketMessages::Message("joined".to_string()), "message");
+
+        assert_eq!(
+            outbox.push(time_played(2, 10), "time_played"),
+            PushOutcome::Dropped
+        );
+        assert_eq!(
+            outbox.push(time_played(3, 10), "time_played"),
+            PushOutcome::Disconnected
+        );
+    }
+
+    #[test]
+    fn sequenced_messages_are_never_dropped() {
+        let sequenced = |sequence| WebSocketMessages::Sequenced {
+            sequence,
+            message: Box::new(WebSocketMessages::Message("joined".to_string())),
+        };
+        let outbox = ClientOutbox::new(1);
+        assert_eq!(outbox.push(sequenced(1), "message"), PushOutcome::Queued);
+        assert_eq!(
+            outbox.push(sequenced(2), "message"),
+            PushOutcome::Disconnected
+        );
+    }
+
+    #[test]
+    fn drops_droppable_messages_before_disconnecting() {
+        let outbox = ClientOutbox::new(1);
+        let message = WebSocketMessages::Message("joined".to_string());
+        assert_eq!(outbox.push(message.clone(), "message"), PushOutcome::Queued);
+        assert_eq!(outbox.push(message, "message"), PushOutcome::Dropped);
+        assert!(!outbox.is_closed());
+    }
+
+    #[test]
+    fn disconnects_when_only_reliable_messages_are_queued() {
+        let outbox = ClientOutbox::new(1);
+        let reliable = WebSocketMessages::SubscribedTopics(vec![]);
+        assert_eq!(
+            outbox.push(reliable.clone(), "subscribed_topics"),
+            PushOutcome::Queued
+        );
+        assert_eq!(
+            outbox.push(reliable.clone(), "subscribed_topics"),
+            PushOutcome::Disconnected
+        );
+        assert!(outbox.is_closed());
+        assert_eq!(
+            outbox.push(reliable, "subscribed_topics"),
+            PushOutcome::Disconnected
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use dashmap::DashMap;
+use entity::shared::Region;
+use migration::OnConflict;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, DbErr, EntityTrait, IntoActiveModel,
+    QueryFilter, TransactionTrait, Value,
+};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::time::Instant;
+
+/// Rows per statement, far below the bind parameter limit of Postgres for every table.
+pub(crate) const CHUNK_SIZE: usize = 1000;
+
+/// How a table differs from the `Initial` snapshot of a region, which is the truth after a
+/// (re)connect. Rows that were removed while we were disconnected end up in `deleted`.
+#[derive(Debug, PartialEq)]
+pub(crate) struct Reconciliation<M> {
+    pub(crate) inserted: Vec<M>,
+    pub(crate) updated: Vec<M>,
+    pub(crate) deleted: Vec<M>,
+}
+
+impl<M: PartialEq> Reconciliation<M> {
+    fn diff<K: Eq + Hash>(
+        mut known: HashMap<K, M>,
+        snapshot: Vec<M>,
+        key: impl Fn(&M) -> K,
+        merge: impl Fn(&mut M, &M),
+    ) -> Self {
+        let mut inserted = Vec::new();
+        let mut updated = Vec::new();
+
+        for mut model in snapshot {
+            match known.remove(&key(&model)) {
+                Some(existing) => {
+                    merge(&mut model, &existing);
+                    if model != existing {
+                        updated.push(model);
+                    }
+                }
+                None => inserted.push(model),
+            }
+        }
+
+        Self {
+            inserted,
+            updated,
+            deleted: known.into_values().collect(),
+        }
+    }
+
+    /// Like [`Self::diff`] for a table. Rows of a shared table are never deleted, a region
+    /// knows nothing of the rows other regions added, so its snapshot can not remove them.
+    fn diff_table<E>(known: HashMap<E::Key, M>, snapshot: Vec<M>) -> Self
+    where
+        E: ReconciledTable<Model = M>,
+    {
+        let mut reconciliation = Self::diff(known, snapshot, E::key, E::merge);
+        if E::region_column().is_none() {
+            reconciliation.deleted.clear();
+        }
+
+        reconciliation
+    }
+
+    /// The rows of the snapshot that are new or changed.
+    pub(crate) fn upserted(&self) -> impl Iterator<Item = &M> {
+        self.inserted.iter().chain(&self.updated)
+    }
+
+    fn record(&self, table: &'static str, region: Region, started: Instant) {
+        for (change, rows) in [
+            ("inserted", self.inserted.len()),
+            ("updated", self.updated.len()),
+            ("deleted", self.deleted.len()),
+        ] {
+            metrics::counter!(
+                "reconciled_rows_total",
+                &[
+                    ("table", table.to_string()),
+                    ("region", region.to_string()),
+                    ("change", change.to_string())
+                ]
+            )
+            .increment(rows as u64);
+        }
+        metrics::histogram!(
+            "reconciliation_duration_seconds",
+            &[("table", table.to_string()), ("region", region.to_string())]
+        )
+        .record(started.elapsed().as_secs_f64());
+
+        tracing::info!(
+            table,
+            region,
+            inserted = self.inserted.len(),
+            updated = self.updated.len(),
+            deleted = self.deleted.len(),
+            "Reconciled snapshot"
+        );
+    }
+}
+
+/// A persisted table that can be reconciled against the `Initial` snapshot of a region.
+pub(crate) trait ReconciledTable: EntityTrait {
+    /// The SpacetimeDB table, used as the metric label.
+    const NAME: &'static str;
+
+    type Key: Eq + Hash + Into<Value>;
+
+    fn key(model: &Self::Model) -> Self::Key;
+
+    fn key_column() -> Self::Column;
+
+    /// `None` for the description tables, which every region shares.
+    fn region_column() -> Option<Self::Column>;
+
+    /// The region of a row, `None` for the description tables.
+    fn region(model: &Self::Model) -> Option<Region>;
+
+    /// Carries over what the snapshot can not know from the persisted row.
+    fn merge(_snapshot: &mut Self::Model, _persisted: &Self::Model) {}
+}
+
+/// Implements [`ReconciledTable`] for a table of `entity` keyed by a single column. Tables are
+/// scoped to a region unless marked `shared`, `as` names the SpacetimeDB table where it differs
+/// from the entity.
+macro_rules! reconciled_table {
+    ($entity:ident, $key:ident: $key_type:ty, $key_column:ident) => {
+        $crate::websocket::reconcile::reconciled_table!(
+            @impl $entity, $entity, $key: $key_type, $key_column,
+            Some(::entity::$entity::Column::Region), |model| Some(model.region)
+        );
+    };
+    ($entity:ident, $key:ident: $key_type:ty, $key_column:ident, shared) => {
+        $crate::websocket::reconcile::reconciled_table!(
+            @impl $entity, $entity, $key: $key_type, $key_column, None, |_model| None
+        );
+    };
+    ($entity:ident as $name:ident, $key:ident: $key_type:ty, $key_column:ident, shared) => {
+        $crate::websocket::reconcile::reconciled_table!(
+            @impl $entity, $name, $key: $key_type, $key_column, None, |_model| None
+        );
+    };
+    (
+        @impl $entity:ident, $name:ident, $key:ident: $key_type:ty, $key_column:ident,
+        $region_column:expr, |$model:ident| $region:expr
+    ) => {
+        impl $crate::websocket::reconcile::ReconciledTable for ::entity::$entity::Entity {
+            const NAME: &'static str = stringify!($name);
+
+            type Key = $key_type;
+
+            fn key(model: &Self::Model) -> Self::Key {
+                model.$key
+            }
+
+            fn key_column() -> Self::Column {
+                ::entity::$entity::Column::$key_column
+            }
+
+            fn region_column() -> Option<Self::Column> {
+                $region_column
+            }
+
+            fn region($model: &Self::Model) -> Option<::entity::shared::Region> {
+                $region
+            }
+        }
+    };
+}
+
+pub(crate) use reconciled_table;
+
+/// Makes the rows of `region` match `snapshot`. Either the whole difference is persisted or,
+/// on error, none of it.
+pub(crate) async fn reconcile_table<E>(
+    conn: &DatabaseConnection,
+    region: Region,
+    snapshot: Vec<E::Model>,
+    on_conflict: &OnConflict,
+) -> Result<Reconciliation<E::Model>, DbErr>
+where
+    E: ReconciledTable,
+    E::Model: IntoActiveModel<E::ActiveModel> + PartialEq + Clone,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+{
+    let started = Instant::now();
+
+    let mut persisted = E::find();
+    if let Some(region_column) = E::region_column() {
+        persisted = persisted.filter(region_column.eq(region));
+    }
+    let persisted = persisted
+        .all(conn)
+        .await?
+        .into_iter()
+        .map(|model| (E::key(&model), model))
+        .collect();
+
+    let reconciliation = Reconciliation::diff_table::<E>(persisted, snapshot);
+
+    let txn = conn.begin().await?;
+    for chunk in reconciliation
+        .upserted()
+        .collect::<Vec<_>>()
+        .chunks(CHUNK_SIZE)
+    {
+        E::insert_many(
+            chunk
+                .iter()
+                .map(|model| (*model).clone().into_active_model()),
+        )
+        .on_conflict(on_conflict.clone())
+        .exec(&txn)
+        .await?;
+    }
+    for chunk in reconciliation.deleted.chunks(CHUNK_SIZE) {
+        let mut delete = E::delete_many().filter(E::key_column().is_in(chunk.iter().map(E::key)));
+        if let Some(region_column) = E::region_column() {
+            delete = delete.filter(region_column.eq(region));
+        }
+        delete.exec(&txn).await?;
+    }
+    txn.commit().await?;
+
+    reconciliation.record(E::NAME, region, started);
+
+    Ok(reconciliation)
+}
+
+/// How `snapshot` differs from the `known` rows of a region in memory, for when the table could
+/// not be reconciled. Memory still follows the snapshot, Postgres catches up with the next one.
+pub(crate) fn diff_known<E>(
+    known: impl IntoIterator<Item = E::Model>,
+    snapshot: Vec<E::Model>,
+) -> Reconciliation<E::Model>
+where
+    E: ReconciledTable,
+    E::Model: PartialEq,
+{
+    let known = known
+        .into_iter()
+        .map(|model| (E::key(&model), model))
+        .collect();
+
+    Reconciliation::diff_table::<E>(known, snapshot)
+}
+
+/// Makes the entries of `region` in an in-memory `map` match `snapshot`, for tables that are
+/// not persisted.
+pub(crate) fn reconcile_map<K, M>(
+    table: &'static str,
+    map: &DashMap<K, M>,
+    region: Region,
+    snapshot: Vec<M>,
+    key: impl Fn(&M) -> K,
+    region_of: impl Fn(&M) -> Region,
+) -> Reconciliation<M>
+where
+    K: Eq + Hash + Clone,
+    M: PartialEq + Clone,
+{
+    let started = Instant::now();
+
+    let known = map
+        .iter()
+        .filter(|entry| region_of(entry.value()) == region)
+        .map(|entry| (entry.key().clone(), entry.value().clone()))
+        .collect();
+
+    let reconciliation = Reconciliation::diff(known, snapshot, &key, |_, _| {});
+
+    for model in reconciliation.upserted() {
+        map.insert(key(model), model.clone());
+    }
+    for model in &reconciliation.deleted {
+        map.remove(&key(model));
+    }
+
+    reconciliation.record(table, region, started);
+
+    reconciliation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_finds_rows_removed_while_disconnected() {
+        let known = HashMap::from([(1, (1, "a")), (2, (2, "b")), (3, (3, "c"))]);
+        let snapshot = vec![(1, "a"), (2, "changed"), (4, "d")];
+
+        let reconciliation = Reconciliation::diff(known, snapshot, |row| row.0, |_, _| {});
+
+        assert_eq!(
+            reconciliation,
+            Reconciliation {
+                inserted: vec![(4, "d")],
+                updated: vec![(2, "changed")],
+                deleted: vec![(3, "c")],
+            }
+        );
+    }
+
+    #[test]
+    fn shared_tables_keep_rows_missing_from_a_snapshot() {
+        let npc = |npc_type| entity::npc_desc::Model {
+            npc_type,
+            name: format!("npc {npc_type}"),
+            population: 1.0,
+            speed: 1,
+            min_time_at_ruin: 0,
+            max_time_at_ruin: 0,
+            prefab_address: String::new(),
+            icon_address: String::new(),
+            force_market_mode: false,
+            task_skill_check: Vec::new(),
+        };
+
+        let reconciliation = diff_known::<entity::npc_desc::Entity>([npc(1), npc(2)], vec![npc(2)]);
+
+        assert!(reconciliation.deleted.is_empty());
+    }
+
+    #[test]
+    fn reconciling_a_map_leaves_other_regions_alone() {
+        let map = DashMap::from_iter([(1, (1, 1)), (2, (2, 1)), (3, (3, 2))]);
+
+        let reconciliation = reconcile_map("test", &map, 1, vec![(2, 1)], |row| row.0, |row| row.1);
+
+        assert_eq!(reconciliation.deleted, vec![(1, 1)]);
+        assert!(map.contains_key(&2));
+        assert!(map.contains_key(&3));
+        assert!(!map.contains_key(&1));
+    }
+}
@@ -0,0 +1,462 @@
+use crate::config::RecordingConfig;
+use crate::websocket::ingest_queue::IngestSender;
+use crate::websocket::tables::EnabledTables;
+use crate::websocket::{ReducerCall, SpacetimeUpdateMessages};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use spacetimedb_sdk::__codegen::__lib;
+use std::collections::{HashMap, HashSet};
+use std::sync::LazyLock;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tokio::sync::mpsc::{UnboundedSender, unbounded_channel};
+use tokio::time::{Duration, Instant};
+
+/// Reducer names have to be `&'static str` inside [`SpacetimeUpdateMessages`], replayed ones get
+/// leaked once per distinct name.
+static REDUCER_NAMES: LazyLock<Mutex<HashSet<&'static str>>> =
+    LazyLock::new(|| Mutex::new(HashSet::new()));
+
+fn intern_reducer_name(name: String) -> &'static str {
+    let mut names = REDUCER_NAMES.lock();
+    if let Some(name) = names.get(name.as_str()) {
+        return name;
+    }
+
+    let name: &'static str = Box::leak(name.into_boxed_str());
+    names.insert(name);
+    name
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+enum RecordedKind {
+    Initial,
+    Insert,
+    Update,
+    Remove,
+}
+
+/// One line of a recording. Rows are stored as hex encoded BSATN, the same encoding
+/// SpacetimeDB uses on the wire, because the generated bindings only implement SATS.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub(crate) struct RecordedMessage {
+    /// Milliseconds since the recording started, in an ingest log the unix time in milliseconds
+    /// the message was queued at.
+    pub(crate) at: u64,
+    /// Unix time in milliseconds SpacetimeDB ran the reducer behind the message at, only ingest
+    /// logs keep it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) event_at: Option<u64>,
+    table: String,
+    region: entity::shared::Region,
+    kind: RecordedKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reducer_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event: Option<RecordedEvent>,
+    /// `Update` stores the old row followed by the new one.
+    rows: Vec<String>,
+}
+
+/// The [`ReducerCall`] behind a message.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+struct RecordedEvent {
+    caller_identity: String,
+    /// Microseconds since the unix epoch.
+    timestamp: i64,
+}
+
+impl From<ReducerCall> for RecordedEvent {
+    fn from(event: ReducerCall) -> Self {
+        Self {
+            caller_identity: event.caller_identity.to_hex().to_string(),
+            timestamp: event.timestamp.to_micros_since_unix_epoch(),
+        }
+    }
+}
+
+impl RecordedEvent {
+    fn decode(self) -> anyhow::Result<ReducerCall> {
+        Ok(ReducerCall {
+            caller_identity: __lib::Identity::from_hex(&self.caller_identity)
+                .map_err(|error| anyhow::anyhow!("Invalid caller identity: {error}"))?,
+            timestamp: __lib::Timestamp::from_micros_since_unix_epoch(self.timestamp),
+        })
+    }
+}
+
+impl RecordedMessage {
+    pub(crate) fn encode<T: __lib::ser::Serialize>(
+        table: &str,
+        at: Duration,
+        message: &SpacetimeUpdateMessages<T>,
+    ) -> anyhow::Result<Self> {
+        let (kind, region, reducer_name, event, rows) = match message {
+            SpacetimeUpdateMessages::Initial {
+                data,
+                database_name,
+            } => (
+                RecordedKind::Initial,
+                *database_name,
+                None,
+                None,
+                data.iter().collect::<Vec<_>>(),
+            ),
+            SpacetimeUpdateMessages::Insert {
+                event,
+                new,
+                database_name,
+                reducer_name,
+            } => (
+                RecordedKind::Insert,
+                *database_name,
+                *reducer_name,
+                *event,
+                vec![new],
+            ),
+            SpacetimeUpdateMessages::Update {
+                event,
+                old,
+                new,
+                database_name,
+                reducer_name,
+            } => (
+                RecordedKind::Update,
+                *database_name,
+                *reducer_name,
+                *event,
+                vec![old, new],
+            ),
+            SpacetimeUpdateMessages::Remove {
+                event,
+                delete,
+                database_name,
+                reducer_name,
+            } => (
+                RecordedKind::Remove,
+                *database_name,
+                *reducer_name,
+                *event,
+                vec![delete],
+            ),
+        };
+
+        let rows = rows
+            .into_iter()
+            .map(|row| {
+                __lib::bsatn::to_vec(row)
+                    .map(hex::encode)
+                    .map_err(|error| anyhow::anyhow!("Could not encode {table} row: {error}"))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            at: at.as_millis() as u64,
+            event_at: None,
+            table: table.to_string(),
+            region,
+            kind,
+            reducer_name: reducer_name.map(str::to_string),
+            event: event.map(RecordedEvent::from),
+            rows,
+        })
+    }
+
+    pub(crate) fn decode<T>(self) -> anyhow::Result<SpacetimeUpdateMessages<T>>
+    where
+        T: for<'de> __lib::de::Deserialize<'de>,
+    {
+        let table = self.table;
+        let kind = self.kind;
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                let bytes = hex::decode(row)
+                    .map_err(|error| anyhow::anyhow!("Invalid {table} row: {error}"))?;
+                __lib::bsatn::from_slice::<T>(&bytes)
+                    .map_err(|error| anyhow::anyhow!("Could not decode {table} row: {error}"))
+            })
+            .collect::<anyhow::Result<Vec<T>>>()?;
+        let reducer_name = self.reducer_name.map(intern_reducer_name);
+        let event = self.event.map(RecordedEvent::decode).transpose()?;
+        let database_name = self.region;
+
+        let mut rows = rows.into_iter();
+        let missing_row = || anyhow::anyhow!("Missing row for {table} {kind:?}");
+
+        Ok(match kind {
+            RecordedKind::Initial => SpacetimeUpdateMessages::Initial {
+                data: rows.collect(),
+                database_name,
+            },
+            RecordedKind::Insert => SpacetimeUpdateMessages::Insert {
+                event,
+                new: rows.next().ok_or_else(missing_row)?,
+                database_name,
+                reducer_name,
+            },
+            RecordedKind::Update => SpacetimeUpdateMessages::Update {
+                event,
+                old: rows.next().ok_or_else(missing_row)?,
+                new: rows.next().ok_or_else(missing_row)?,
+                database_name,
+                reducer_name,
+            },
+            RecordedKind::Remove => SpacetimeUpdateMessages::Remove {
+                event,
+                delete: rows.next().ok_or_else(missing_row)?,
+                database_name,
+                reducer_name,
+            },
+        })
+    }
+}
+
+/// Writes every message that passes through a tapped worker channel to `record_path`.
+#[derive(Clone)]
+pub(crate) struct Recorder {
+    sink: Option<UnboundedSender<RecordedMessage>>,
+    started: Instant,
+}
+
+impl Recorder {
+    pub(crate) async fn from_config(config: &RecordingConfig) -> Self {
+        let started = Instant::now();
+        let Some(path) = &config.record_path else {
+            return Self {
+                sink: None,
+                started,
+            };
+        };
+
+        let file = match tokio::fs::File::create(path).await {
+            Ok(file) => file,
+            Err(error) => {
+                tracing::error!(path, "Could not create recording: {error}");
+                return Self {
+                    sink: None,
+                    started,
+                };
+            }
+        };
+        let (sink, mut rx) = unbounded_channel::<RecordedMessage>();
+        let path = path.clone();
+        tracing::info!(path, "Recording SpacetimeDB updates");
+
+        tokio::spawn(async move {
+            let mut writer = BufWriter::new(file);
+            let mut written = 0u64;
+
+            while let Some(message) = rx.recv().await {
+                let mut messages = vec![message];
+                while let Ok(message) = rx.try_recv() {
+                    messages.push(message);
+                }
+
+                for message in messages {
+                    let mut line = match serde_json::to_vec(&message) {
+                        Ok(line) => line,
+                        Err(error) => {
+                            tracing::warn!(
+                                table = message.table,
+                                "Could not record message: {error}"
+                            );
+                            continue;
+                        }
+                    };
+                    line.push(b'\n');
+
+                    if let Err(error) = writer.write_all(&line).await {
+                        tracing::error!(path, "Could not write recording: {error}");
+                        return;
+                    }
+                    written += 1;
+                }
+
+                if let Err(error) = writer.flush().await {
+                    tracing::error!(path, "Could not flush recording: {error}");
+                    return;
+                }
+                metrics::gauge!("spacetimedb_recorded_messages").set(written as f64);
+            }
+        });
+
+        Self {
+            sink: Some(sink),
+            started,
+        }
+    }
+
+    /// Returns a sender that records each message before queueing it. Without a `record_path`
+    /// this is `tx` itself.
+    pub(crate) fn tap<T>(&self, table: &'static str, tx: IngestSender<T>) -> IngestSender<T>
+    where
+        T: __lib::ser::Serialize + Send + 'static,
+    {
+        let Some(sink) = self.sink.clone() else {
+            return tx;
+        };
+
+        let started = self.started;
+        tx.with_tap(move |message| {
+            match RecordedMessage::encode(table, started.elapsed(), message) {
+                Ok(recorded) => {
+                    let _ = sink.send(recorded);
+                }
+                Err(error) => tracing::warn!(table, "Could not record message: {error}"),
+            }
+        })
+    }
+}
+
+trait ReplayTarget: Send + Sync {
+    fn send(&self, message: RecordedMessage) -> anyhow::Result<()>;
+}
+
+impl<T> ReplayTarget for IngestSender<T>
+where
+    T: __lib::ser::Serialize + for<'de> __lib::de::Deserialize<'de> + Send + 'static,
+{
+    fn send(&self, message: RecordedMessage) -> anyhow::Result<()> {
+        let message = message.decode::<T>()?;
+        IngestSender::send(self, message)
+            .map_err(|error| anyhow::anyhow!("Worker did not take the message: {error:?}"))
+    }
+}
+
+/// Feeds a recording into the worker channels in place of the SpacetimeDB listeners.
+pub(crate) struct ReplaySource {
+    path: String,
+    speed: f64,
+    enabled_tables: EnabledTables,
+    targets: HashMap<&'static str, Box<dyn ReplayTarget>>,
+}
+
+impl ReplaySource {
+    pub(crate) fn new(path: String, speed: f64, enabled_tables: EnabledTables) -> Self {
+        Self {
+            path,
+            speed,
+            enabled_tables,
+            targets: HashMap::new(),
+        }
+    }
+
+    pub(crate) fn target<T>(mut self, table: &'static str, tx: IngestSender<T>) -> Self
+    where
+        T: __lib::ser::Serialize + for<'de> __lib::de::Deserialize<'de> + Send + 'static,
+    {
+        if self.enabled_tables.is_enabled(table) {
+            self.targets.insert(table, Box::new(tx));
+        }
+        self
+    }
+
+    pub(crate) fn start(self) {
+        tokio::spawn(async move {
+            if let Err(error) = self.run().await {
+                tracing::error!(path = self.path, "Replay failed: {error}");
+            }
+        });
+    }
+
+    async fn run(&self) -> anyhow::Result<()> {
+        let file = tokio::fs::File::open(&self.path).await?;
+        let mut lines = BufReader::new(file).lines();
+        let started = Instant::now();
+        let mut replayed = 0u64;
+        let mut skipped = 0u64;
+
+        tracing::info!(
+            path = self.path,
+            speed = self.speed,
+            "Replaying SpacetimeDB updates"
+        );
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let message: RecordedMessage = serde_json::from_str(&line)?;
+            let Some(target) = self.targets.get(message.table.as_str()) else {
+                skipped += 1;
+                continue;
+            };
+
+            if self.speed > 0.0 {
+                let due = Duration::from_secs_f64(message.at as f64 / 1000.0 / self.speed);
+                tokio::time::sleep_until(started + due).await;
+            }
+
+            let table = message.table.clone();
+            if let Err(error) = target.send(message) {
+                tracing::warn!(table, "Could not replay message: {error}");
+                skipped += 1;
+                continue;
+            }
+            replayed += 1;
+        }
+
+        tracing::info!(
+            path = self.path,
+            replayed,
+            skipped,
+            "Finished replaying SpacetimeDB updates"
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use game_module::module_bindings::ClaimTileState;
+
+    #[test]
+    fn recorded_update_round_trips() {
+        let event = ReducerCall {
+            caller_identity: __lib::Identity::from_byte_array([7; 32]),
+            timestamp: __lib::Timestamp::from_micros_since_unix_epoch(1_704_067_200_000_000),
+        };
+        let message = SpacetimeUpdateMessages::Update {
+            event: Some(event),
+            old: ClaimTileState {
+                entity_id: 1,
+                claim_id: 2,
+            },
+            new: ClaimTileState {
+                entity_id: 1,
+                claim_id: 3,
+            },
+            database_name: 4,
+            reducer_name: Some("claim_add_tile"),
+        };
+
+        let recorded =
+            RecordedMessage::encode("claim_tile_state", Duration::from_millis(1500), &message)
+                .unwrap();
+        let line = serde_json::to_string(&recorded).unwrap();
+        let recorded: RecordedMessage = serde_json::from_str(&line).unwrap();
+        assert_eq!(recorded.at, 1500);
+
+        match recorded.decode::<ClaimTileState>().unwrap() {
+            SpacetimeUpdateMessages::Update {
+                event: replayed_event,
+                old,
+                new,
+                database_name,
+                reducer_name,
+            } => {
+                assert_eq!(replayed_event, Some(event));
+                assert_eq!(old.claim_id, 2);
+                assert_eq!(new.claim_id, 3);
+                assert_eq!(database_name, 4);
+                assert_eq!(reducer_name, Some("claim_add_tile"));
+            }
+            _ => panic!("Expected an update"),
+        }
+    }
+}
@@ -0,0 +1,222 @@
+use crate::AppState;
+use crate::inventory::resolve_pocket;
+use crate::leaderboard::experience_to_level;
+use crate::websocket::WebSocketMessages;
+use service::Query as QueryCore;
+
+/// The current state behind `topic.topic_id`, built from the same caches the workers keep up
+/// to date. Topics that have no state of their own (e.g. claim enter/exit events) return nothing.
+pub(crate) async fn topic_snapshot(
+    state: &AppState,
+    topic: &str,
+    topic_id: i64,
+) -> Vec<WebSocketMessages> {
+    match topic.split_once(':') {
+        Some(("experience", skill_name)) => {
+            return skill_experience(state, topic_id, Some(skill_name), false);
+        }
+        Some(("level", skill_name)) => {
+            return skill_experience(state, topic_id, Some(skill_name), true);
+        }
+        _ => {}
+    }
+
+    match topic {
+        "player_state" => state
+            .player_state
+            .get(&topic_id)
+            .map(|player_state| vec![WebSocketMessages::PlayerState(player_state.clone())])
+            .unwrap_or_default(),
+        "mobile_entity_state" => state
+            .mobile_entity_state
+            .get(&(topic_id as u64))
+            .map(|mobile_entity_state| {
+                vec![WebSocketMessages::MobileEntityState(
+                    mobile_entity_state.clone(),
+                )]
+            })
+            .unwrap_or_default(),
+        "claim_local_state" => state
+            .claim_local_state
+            .get(&(topic_id as u64))
+            .map(|claim_local_state| {
+                vec![WebSocketMessages::ClaimLocalState(
+                    claim_local_state.clone(),
+                )]
+            })
+            .unwrap_or_default(),
+        "inventory_update" => inventory(state, topic_id).await,
+        "insert_sell_order:item_id" | "update_sell_order:item_id" | "remove_sell_order:item_id" => {
+            state
+                .sell_order_state
+                .iter()
+                .filter(|order| order.item_id as i64 == topic_id)
+                .map(|order| WebSocketMessages::UpdateSellOrder(order.clone()))
+                .collect()
+        }
+        "update_buy_order:item_id" | "remove_buy_order:item_id" => state
+            .buy_order_state
+            .iter()
+            .filter(|order| order.item_id as i64 == topic_id)
+            .map(|order| WebSocketMessages::UpdateBuyOrder(order.clone()))
+            .collect(),
+        "experience" => skill_experience(state, topic_id, None, false),
+        "level" => skill_experience(state, topic_id, None, true),
+        "total_experience" => {
+            let leaderboards = &state.ranking_system;
+            let Some(experience) = leaderboards.global_leaderboard.get_value(&topic_id) else {
+                return vec![];
+            };
+
+            vec![WebSocketMessages::TotalExperience {
+                user_id: topic_id,
+                experience: experience as u64,
+                experience_per_hour: leaderboards.xp_per_hour.get_value(&topic_id).unwrap_or(0)
+                    as u64,
+                rank: leaderboards
+                    .global_leaderboard
+                    .get_rank(topic_id)
+                    .unwrap_or(0) as u64,
+            }]
+        }
+        _ => vec![],
+    }
+}
+
+async fn inventory(state: &AppState, entity_id: i64) -> Vec<WebSocketMessages> {
+    // Postgres lags behind the broadcast by a batch, changes that were already sent win
+    let pending = state
+        .pending_inventory
+        .get(&entity_id)
+        .map(|pending| pending.clone());
+    let inventory = match pending {
+        Some(Some(inventory)) => inventory,
+        Some(None) => return vec![],
+        None => match QueryCore::find_inventory_by_id(&state.conn, entity_id).await {
+            Ok(Some(inventory)) => inventory,
+            Ok(None) => return vec![],
+            Err(error) => {
+                tracing::error!(entity_id, "Error loading inventory for snapshot: {error}");
+                return vec![];
+            }
+        },
+    };
+
+    let pockets = inventory
+        .pockets
+        .iter()
+        .map(|pocket| resolve_pocket(pocket, &state.item_desc, &state.cargo_desc))
+        .collect();
+
+    vec![WebSocketMessages::InventoryUpdate {
+        resolved_inventory: entity::inventory::ResolvedInventory {
+            entity_id: inventory.entity_id,
+            pockets,
+            inventory_index: inventory.inventory_index,
+            cargo_index: inventory.cargo_index,
+            owner_entity_id: inventory.owner_entity_id,
+            player_owner_entity_id: inventory.player_owner_entity_id,
+            nickname: None,
+            claim: None,
+        },
+        region: inventory.region,
+    }]
+}
+
+/// `Experience` (or `Level` when `as_level` is set) messages for every ranked skill of the
+/// player, or only for `skill_name`.
+fn skill_experience(
+    state: &AppState,
+    user_id: i64,
+    skill_name: Option<&str>,
+    as_level: bool,
+) -> Vec<WebSocketMessages> {
+    state
+        .skill_desc
+        .iter()
+        .filter(|skill| skill.skill_category != 0)
+        .filter(|skill| skill_name.is_none_or(|skill_name| skill.name == skill_name))
+        .filter_map(|skill| {
+            let leaderboard = state.ranking_system.skill_leaderboards.get(&skill.id)?;
+            let experience = leaderboard.get_value(&user_id)?;
+            let level = experience_to_level(experience) as u64;
+
+            Some(if as_level {
+                WebSocketMessages::Level {
+                    level,
+                    user_id,
+                    skill_name: skill.name.clone(),
+                }
+            } else {
+                WebSocketMessages::Experience {
+                    experience: experience as u64,
+                    level,
+                    rank: leaderboard.get_rank(user_id).unwrap_or(0) as u64,
+                    skill_name: skill.name.clone(),
+                    user_id,
+                }
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GraphqlConfig, RateLimitConfig};
+    use sea_orm::DatabaseConnection;
+    use std::collections::HashMap;
+
+    fn state() -> AppState {
+        AppState::new(
+            DatabaseConnection::Disconnected,
+            tokio::sync::mpsc::unbounded_channel().0,
+            HashMap::new(),
+            RateLimitConfig::default(),
+            &GraphqlConfig::default(),
+        )
+    }
+
+    #[tokio::test]
+    async fn inventory_snapshots_prefer_changes_postgres_does_not_have_yet() {
+        let state = state();
+        state.pending_inventory.insert(
+            5,
+            Some(entity::inventory::Model {
+                entity_id: 5,
+                pockets: vec![],
+                inventory_index: 0,
+                cargo_index: 0,
+                owner_entity_id: 6,
+                player_owner_entity_id: 7,
+                region: 2,
+            }),
+        );
+
+        let messages = topic_snapshot(&state, "inventory_update", 5).await;
+
+        assert_eq!(messages.len(), 1);
+        let WebSocketMessages::InventoryUpdate {
+            resolved_inventory,
+            region,
+        } = &messages[0]
+        else {
+            panic!("Expected an inventory update");
+        };
+        assert_eq!(resolved_inventory.entity_id, 5);
+        assert_eq!(resolved_inventory.owner_entity_id, 6);
+        assert_eq!(*region, 2);
+    }
+
+    #[tokio::test]
+    async fn pending_inventory_deletes_have_no_snapshot() {
+        let state = state();
+        state.pending_inventory.insert(5, None);
+
+        assert!(
+            topic_snapshot(&state, "inventory_update", 5)
+                .await
+                .is_empty()
+        );
+    }
+}
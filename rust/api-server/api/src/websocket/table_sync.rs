@@ -0,0 +1,397 @@
+use super::batched_worker::BatchedWorker;
+use super::ingest_queue::{self, IngestReceiver, IngestSender};
+use super::reconcile::{CHUNK_SIZE, ReconciledTable, diff_known, reconcile_table};
+use super::{SpacetimeUpdateMessages, WebSocketMessages};
+use crate::AppState;
+use crate::desc_cache::DescTable;
+use dashmap::DashMap;
+use entity::shared::Region;
+use migration::OnConflict;
+use sea_orm::{ActiveModelTrait, ColumnTrait, DbErr, IntoActiveModel, QueryFilter};
+use spacetimedb_sdk::__codegen::__lib;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::time::Instant;
+use tokio::time::Duration;
+
+/// A persisted table that [`TableSyncWorker`] keeps in sync with SpacetimeDB. Besides the
+/// conversion and the columns to upsert everything is opt-in.
+pub(crate) trait SyncedTable: ReconciledTable {
+    /// The row as SpacetimeDB sends it.
+    type Row: __lib::ser::Serialize + for<'de> __lib::de::Deserialize<'de> + Send + 'static;
+
+    fn model(row: Self::Row, region: Region) -> Self::Model;
+
+    /// The columns an upsert overwrites when the key already exists.
+    fn update_columns() -> Vec<Self::Column>;
+
+    /// The map in [`AppState`] that mirrors the table.
+    fn cache(_state: &AppState) -> Option<&DashMap<Self::Key, Self::Model>> {
+        None
+    }
+
+    /// The description endpoints whose cached bodies are stale once the table changed.
+    fn desc_table() -> Option<DescTable> {
+        None
+    }
+
+    /// Broadcast to websocket subscribers when a row is inserted or updated.
+    fn upserted_message(_model: &Self::Model) -> Option<WebSocketMessages> {
+        None
+    }
+
+    /// Broadcast to websocket subscribers when a row is removed.
+    fn removed_message(_model: &Self::Model) -> Option<WebSocketMessages> {
+        None
+    }
+}
+
+enum Change<M> {
+    Upsert(M),
+    Delete(Region, M),
+}
+
+/// The changes of the current batch, only the last one per key gets written.
+struct Batch<K, M> {
+    changes: HashMap<K, Change<M>>,
+}
+
+impl<K: Eq + Hash, M> Batch<K, M> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            changes: HashMap::with_capacity(capacity),
+        }
+    }
+
+    fn upsert(&mut self, key: K, model: M) {
+        self.changes.insert(key, Change::Upsert(model));
+    }
+
+    fn delete(&mut self, key: K, region: Region, model: M) {
+        self.changes.insert(key, Change::Delete(region, model));
+    }
+
+    fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Empties the batch into the rows to upsert and the rows to delete per region.
+    fn take(&mut self) -> (Vec<M>, HashMap<Region, Vec<M>>) {
+        let mut upserts = Vec::new();
+        let mut deletes = HashMap::<Region, Vec<M>>::new();
+        for change in std::mem::take(&mut self.changes).into_values() {
+            match change {
+                Change::Upsert(model) => upserts.push(model),
+                Change::Delete(region, model) => deletes.entry(region).or_default().push(model),
+            }
+        }
+
+        (upserts, deletes)
+    }
+}
+
+/// Batches the updates of a [`SyncedTable`] into Postgres, keeps its cache current and
+/// reconciles the table against every `Initial` snapshot.
+pub(crate) struct TableSyncWorker<E: SyncedTable> {
+    rx: IngestReceiver<E::Row>,
+    tx: IngestSender<E::Row>,
+    global_app_state: AppState,
+    batch_size: usize,
+    time_limit: Duration,
+    on_conflict: OnConflict,
+    batch: Batch<E::Key, E::Model>,
+    /// Whether every batch since the last commit got into Postgres.
+    written: bool,
+    table: PhantomData<E>,
+}
+
+impl<E> TableSyncWorker<E>
+where
+    E: SyncedTable,
+    E::Key: Send + Sync,
+    E::Model: IntoActiveModel<E::ActiveModel> + PartialEq + Clone + Sync,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+{
+    pub(crate) fn new(global_app_state: AppState, batch_size: usize, time_limit: Duration) -> Self {
+        let (tx, rx) = ingest_queue::channel(E::NAME);
+        let on_conflict = OnConflict::column(E::key_column())
+            .update_columns(E::update_columns())
+            .to_owned();
+
+        Self {
+            rx,
+            tx,
+            global_app_state,
+            batch_size,
+            time_limit,
+            on_conflict,
+            batch: Batch::new(batch_size + 10),
+            written: true,
+            table: PhantomData,
+        }
+    }
+
+    fn upsert(&mut self, model: E::Model) {
+        if let Some(cache) = E::cache(&self.global_app_state) {
+            cache.insert(E::key(&model), model.clone());
+        }
+        if let Some(message) = E::upserted_message(&model) {
+            let _ = self.global_app_state.tx.send(message);
+        }
+
+        self.batch.upsert(E::key(&model), model);
+    }
+
+    fn remove(&mut self, region: Region, model: E::Model) {
+        if let Some(cache) = E::cache(&self.global_app_state) {
+            cache.remove(&E::key(&model));
+        }
+        if let Some(message) = E::removed_message(&model) {
+            let _ = self.global_app_state.tx.send(message);
+        }
+
+        self.batch.delete(E::key(&model), region, model);
+    }
+
+    async fn reconcile(&mut self, region: Region, data: Vec<E::Row>) {
+        // The pending changes are older than the snapshot and must not overwrite it.
+        self.flush().await;
+
+        let snapshot = data
+            .into_iter()
+            .map(|row| E::model(row, region))
+            .collect::<Vec<_>>();
+
+        let deleted = match reconcile_table::<E>(
+            &self.global_app_state.conn,
+            region,
+            snapshot.clone(),
+            &self.on_conflict,
+        )
+        .await
+        {
+            Ok(reconciliation) => reconciliation.deleted,
+            Err(error) => {
+                record_error(E::NAME, "reconcile");
+                tracing::error!(
+                    table = E::NAME,
+                    region,
+                    error = error.to_string(),
+                    "Could not reconcile table"
+                );
+                // The cache still follows the snapshot, Postgres catches up with the next one
+                match E::cache(&self.global_app_state) {
+                    Some(cache) => {
+                        let known = cache
+                            .iter()
+                            .filter(|entry| E::region(entry.value()).is_none_or(|of| of == region))
+                            .map(|entry| entry.value().clone())
+                            .collect::<Vec<_>>();
+                        diff_known::<E>(known, snapshot.clone()).deleted
+                    }
+                    None => Vec::new(),
+                }
+            }
+        };
+
+        if let Some(cache) = E::cache(&self.global_app_state) {
+            for model in snapshot {
+                cache.insert(E::key(&model), model);
+            }
+            for model in &deleted {
+                cache.remove(&E::key(model));
+            }
+        }
+
+        self.changed();
+    }
+
+    /// Stops at the first error, what is left of the batch stays unacknowledged in the log.
+    async fn flush_batch(&mut self) -> Result<(), DbErr> {
+        let started = Instant::now();
+        let (upserts, deletes) = self.batch.take();
+        let conn = &self.global_app_state.conn;
+
+        for chunk in upserts.chunks(CHUNK_SIZE) {
+            E::insert_many(chunk.iter().map(|model| model.clone().into_active_model()))
+                .on_conflict(self.on_conflict.clone())
+                .exec(conn)
+                .await
+                .inspect_err(|error| {
+                    record_error(E::NAME, "upsert");
+                    tracing::error!(
+                        table = E::NAME,
+                        rows = chunk.len(),
+                        error = error.to_string(),
+                        "Could not upsert rows"
+                    );
+                })?;
+        }
+        record_rows(E::NAME, "upsert", upserts.len());
+
+        for (region, models) in deletes {
+            for chunk in models.chunks(CHUNK_SIZE) {
+                let mut delete =
+                    E::delete_many().filter(E::key_column().is_in(chunk.iter().map(E::key)));
+                if let Some(region_column) = E::region_column() {
+                    delete = delete.filter(region_column.eq(region));
+                }
+
+                delete.exec(conn).await.inspect_err(|error| {
+                    record_error(E::NAME, "delete");
+                    tracing::error!(
+                        table = E::NAME,
+                        region,
+                        rows = chunk.len(),
+                        error = error.to_string(),
+                        "Could not delete rows"
+                    );
+                })?;
+            }
+            record_rows(E::NAME, "delete", models.len());
+        }
+
+        metrics::histogram!(
+            "table_sync_flush_duration_seconds",
+            &[("table", E::NAME.to_string())]
+        )
+        .record(started.elapsed().as_secs_f64());
+
+        Ok(())
+    }
+
+    fn changed(&self) {
+        if let Some(desc_table) = E::desc_table() {
+            self.global_app_state.desc_cache.bump(desc_table);
+        }
+    }
+}
+
+fn record_rows(table: &'static str, operation: &'static str, rows: usize) {
+    metrics::counter!(
+        "table_sync_rows_total",
+        &[
+            ("table", table.to_string()),
+            ("operation", operation.to_string())
+        ]
+    )
+    .increment(rows as u64);
+}
+
+fn record_error(table: &'static str, operation: &'static str) {
+    metrics::counter!(
+        "table_sync_errors_total",
+        &[
+            ("table", table.to_string()),
+            ("operation", operation.to_string())
+        ]
+    )
+    .increment(1);
+}
+
+impl<E> BatchedWorker for TableSyncWorker<E>
+where
+    E: SyncedTable,
+    E::Key: Send + Sync,
+    E::Model: IntoActiveModel<E::ActiveModel> + PartialEq + Clone + Sync,
+    E::ActiveModel: ActiveModelTrait<Entity = E> + Send,
+{
+    type Entity = E::Row;
+
+    fn rx(&mut self) -> &mut IngestReceiver<Self::Entity> {
+        &mut self.rx
+    }
+
+    fn tx(&self) -> IngestSender<Self::Entity> {
+        self.tx.clone()
+    }
+
+    fn worker_name(&self) -> &'static str {
+        E::NAME
+    }
+
+    fn batch_delay(&self) -> Duration {
+        self.time_limit
+    }
+
+    fn should_flush(&self) -> bool {
+        self.batch.len() >= self.batch_size
+    }
+
+    fn is_idle(&self) -> bool {
+        self.batch.is_empty()
+    }
+
+    fn reset_batch(&mut self) {}
+
+    async fn handle_message(&mut self, msg: SpacetimeUpdateMessages<Self::Entity>) {
+        match msg {
+            SpacetimeUpdateMessages::Initial {
+                data,
+                database_name,
+            } => {
+                self.reconcile(database_name, data).await;
+            }
+            SpacetimeUpdateMessages::Insert {
+                new, database_name, ..
+            }
+            | SpacetimeUpdateMessages::Update {
+                new, database_name, ..
+            } => {
+                self.upsert(E::model(new, database_name));
+            }
+            SpacetimeUpdateMessages::Remove {
+                delete,
+                database_name,
+                ..
+            } => {
+                self.remove(database_name, E::model(delete, database_name));
+            }
+        }
+    }
+
+    async fn flush(&mut self) {
+        if self.batch.is_empty() {
+            return;
+        }
+
+        if self.flush_batch().await.is_err() {
+            self.written = false;
+        }
+        self.changed();
+    }
+
+    async fn commit(&mut self) {
+        let written = std::mem::replace(&mut self.written, true);
+        self.rx.checkpoint().commit_if(written);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_keeps_the_last_change_per_key() {
+        let mut batch = Batch::new(4);
+        batch.upsert(1, "a");
+        batch.delete(1, 2, "a");
+        batch.delete(2, 3, "b");
+        batch.upsert(2, "b");
+        batch.delete(3, 2, "c");
+        assert_eq!(batch.len(), 3);
+
+        let (upserts, mut deletes) = batch.take();
+        assert_eq!(upserts, vec!["b"]);
+        let mut deleted = deletes.remove(&2).unwrap_or_default();
+        deleted.sort();
+        assert_eq!(deleted, vec!["a", "c"]);
+        assert!(deletes.is_empty());
+        assert!(batch.is_empty());
+    }
+}
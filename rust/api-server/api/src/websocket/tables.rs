@@ -0,0 +1,355 @@
+use crate::config::{FilterValue, TableSubscriptionConfig};
+use std::collections::BTreeMap;
+
+/// A SpacetimeDB table the hub knows how to subscribe to.
+struct IngestTable {
+    name: &'static str,
+    /// Subscription query when a plain `SELECT * FROM <name>` is not enough.
+    query: Option<&'static str>,
+    default_enabled: bool,
+    /// Description tables are identical in every region, so they only get subscribed once.
+    shared: bool,
+}
+
+const fn table(name: &'static str) -> IngestTable {
+    IngestTable {
+        name,
+        query: None,
+        default_enabled: true,
+        shared: false,
+    }
+}
+
+const fn shared_table(name: &'static str) -> IngestTable {
+    IngestTable {
+        name,
+        query: None,
+        default_enabled: true,
+        shared: true,
+    }
+}
+
+const INGEST_TABLES: &[IngestTable] = &[
+    table("user_state"),
+    shared_table("item_desc"),
+    shared_table("item_list_desc"),
+    shared_table("cargo_desc"),
+    shared_table("crafting_recipe_desc"),
+    table("player_state"),
+    shared_table("skill_desc"),
+    table("player_username_state"),
+    shared_table("building_desc"),
+    table("building_state"),
+    table("building_nickname_state"),
+    table("vault_state"),
+    table("experience_state"),
+    table("claim_tech_state"),
+    table("claim_state"),
+    table("claim_member_state"),
+    table("claim_local_state"),
+    table("deployable_state"),
+    table("inventory_state"),
+    shared_table("collectible_desc"),
+    shared_table("claim_tech_desc"),
+    shared_table("resource_desc"),
+    shared_table("extraction_recipe_desc"),
+    table("progressive_action_state"),
+    table("character_stats_state"),
+    table("identity_role"),
+    shared_table("traveler_task_desc"),
+    table("traveler_task_state"),
+    IngestTable {
+        default_enabled: false,
+        ..table("trade_order_state")
+    },
+    table("buy_order_state"),
+    table("sell_order_state"),
+    shared_table("npc_desc"),
+    shared_table("interior_network_desc"),
+    shared_table("dimension_description_state"),
+    shared_table("player_housing_state"),
+    table("permission_state"),
+    table("portal_state"),
+    table("equipment_desc"),
+    table("equipment_state"),
+    IngestTable {
+        query: Some(
+            "SELECT mobile_entity_state.* FROM mobile_entity_state JOIN player_state ON mobile_entity_state.entity_id = player_state.entity_id",
+        ),
+        ..table("mobile_entity_state")
+    },
+    // Neutral claims are not owned by players, leaving their tiles out keeps the subscription small.
+    IngestTable {
+        query: Some(
+            "SELECT claim_tile_state.* FROM claim_tile_state JOIN claim_state ON claim_tile_state.claim_id = claim_state.entity_id WHERE claim_state.neutral = false",
+        ),
+        ..table("claim_tile_state")
+    },
+    // Subscribing to the whole location_state currently takes too much cpu, only the claim tiles are needed.
+    IngestTable {
+        query: Some(
+            "SELECT location_state.* FROM location_state JOIN claim_tile_state ON location_state.entity_id = claim_tile_state.entity_id",
+        ),
+        ..table("location_state")
+    },
+];
+
+/// The tables that are enabled for this instance together with their subscription query.
+#[derive(Clone, Debug)]
+pub(crate) struct EnabledTables {
+    tables: Vec<EnabledTable>,
+}
+
+#[derive(Clone, Debug)]
+struct EnabledTable {
+    name: &'static str,
+    shared: bool,
+    query: String,
+}
+
+impl EnabledTables {
+    pub(crate) fn from_config(config: &TableSubscriptionConfig) -> Self {
+        for name in config.overrides.keys() {
+            if !INGEST_TABLES.iter().any(|table| table.name == name) {
+                tracing::warn!(table = name, "Unknown table in spacetimedb.tables config");
+            }
+        }
+
+        let tables = INGEST_TABLES
+            .iter()
+            .filter_map(|table| {
+                let table_config = config.overrides.get(table.name);
+                let enabled = table_config
+                    .and_then(|table_config| table_config.enabled)
+                    .unwrap_or(config.default_enabled && table.default_enabled);
+
+                if !enabled {
+                    return None;
+                }
+
+                let query = table
+                    .query
+                    .map(str::to_string)
+                    .unwrap_or_else(|| format!("SELECT * FROM {}", table.name));
+                let query = match table_config {
+                    Some(table_config) => {
+                        match filtered_query(table.name, query, &table_config.filter) {
+                            Ok(query) => query,
+                            Err(column) => {
+                                tracing::error!(
+                                    table = table.name,
+                                    column,
+                                    "Invalid filter column in spacetimedb.tables config, the table does not get ingested"
+                                );
+                                return None;
+                            }
+                        }
+                    }
+                    None => query,
+                };
+
+                Some(EnabledTable {
+                    name: table.name,
+                    shared: table.shared,
+                    query,
+                })
+            })
+            .collect();
+
+        Self { tables }
+    }
+
+    pub(crate) fn is_enabled(&self, name: &str) -> bool {
+        self.tables.iter().any(|table| table.name == name)
+    }
+
+    pub(crate) fn names(&self) -> Vec<&'static str> {
+        self.tables.iter().map(|table| table.name).collect()
+    }
+
+    /// Subscription queries for one region, `skip_shared` leaves out the tables that an earlier
+    /// region connection already subscribed to.
+    pub(crate) fn queries(&self, skip_shared: bool) -> Vec<String> {
+        self.tables
+            .iter()
+            .filter(|table| !(skip_shared && table.shared))
+            .map(|table| table.query.clone())
+            .collect()
+    }
+}
+
+/// Adds a `<table>.<column> = <value>` condition per filter entry to `query`, or returns the first
+/// column that is not a plain identifier.
+fn filtered_query(
+    table: &str,
+    query: String,
+    filter: &BTreeMap<String, FilterValue>,
+) -> Result<String, String> {
+    if filter.is_empty() {
+        return Ok(query);
+    }
+
+    let mut conditions = Vec::with_capacity(filter.len());
+    for (column, value) in filter {
+        let is_identifier = column
+            .chars()
+            .next()
+            .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+            && column
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == '_');
+        if !is_identifier {
+            return Err(column.clone());
+        }
+
+        let value = match value {
+            FilterValue::Bool(value) => value.to_string(),
+            FilterValue::Integer(value) => value.to_string(),
+            FilterValue::Text(value) => format!("'{}'", value.replace('\'', "''")),
+        };
+        conditions.push(format!("{table}.{column} = {value}"));
+    }
+
+    // The built-in queries are written by hand, so a plain search is enough to find their WHERE
+    let keyword = if query.contains(" WHERE ") {
+        "AND"
+    } else {
+        "WHERE"
+    };
+
+    Ok(format!("{query} {keyword} {}", conditions.join(" AND ")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TableConfig;
+    use std::collections::HashMap;
+
+    fn config(
+        default_enabled: bool,
+        overrides: impl IntoIterator<Item = (&'static str, TableConfig)>,
+    ) -> TableSubscriptionConfig {
+        TableSubscriptionConfig {
+            default_enabled,
+            overrides: overrides
+                .into_iter()
+                .map(|(name, table_config)| (name.to_string(), table_config))
+                .collect::<HashMap<_, _>>(),
+        }
+    }
+
+    fn query_of(tables: &EnabledTables, name: &str) -> String {
+        tables
+            .tables
+            .iter()
+            .find(|table| table.name == name)
+            .map(|table| table.query.clone())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn overrides_enable_and_disable_tables() {
+        let tables = EnabledTables::from_config(&config(true, []));
+        assert!(tables.is_enabled("player_state"));
+        assert!(!tables.is_enabled("trade_order_state"));
+
+        let tables = EnabledTables::from_config(&config(
+            true,
+            [
+                (
+                    "trade_order_state",
+                    TableConfig {
+                        enabled: Some(true),
+                        ..TableConfig::default()
+                    },
+                ),
+                (
+                    "player_state",
+                    TableConfig {
+                        enabled: Some(false),
+                        ..TableConfig::default()
+                    },
+                ),
+            ],
+        ));
+        assert!(tables.is_enabled("trade_order_state"));
+        assert!(!tables.is_enabled("player_state"));
+    }
+
+    #[test]
+    fn only_enabled_overrides_are_ingested_without_defaults() {
+        let tables = EnabledTables::from_config(&config(
+            false,
+            [(
+                "item_desc",
+                TableConfig {
+                    enabled: Some(true),
+                    ..TableConfig::default()
+                },
+            )],
+        ));
+
+        assert_eq!(tables.names(), vec!["item_desc"]);
+        assert_eq!(tables.queries(false), vec!["SELECT * FROM item_desc"]);
+        assert!(tables.queries(true).is_empty());
+    }
+
+    #[test]
+    fn filters_are_added_to_the_query() {
+        let filter = |column: &str, value: FilterValue| TableConfig {
+            enabled: None,
+            filter: BTreeMap::from([(column.to_string(), value)]),
+        };
+        let tables = EnabledTables::from_config(&config(
+            true,
+            [
+                (
+                    "inventory_state",
+                    filter("owner_entity_id", FilterValue::Integer(10)),
+                ),
+                (
+                    "claim_tile_state",
+                    filter("claim_id", FilterValue::Integer(10)),
+                ),
+                (
+                    "player_username_state",
+                    filter("username", FilterValue::Text("O'Neil".to_string())),
+                ),
+            ],
+        ));
+
+        assert_eq!(
+            query_of(&tables, "inventory_state"),
+            "SELECT * FROM inventory_state WHERE inventory_state.owner_entity_id = 10"
+        );
+        assert_eq!(
+            query_of(&tables, "claim_tile_state"),
+            "SELECT claim_tile_state.* FROM claim_tile_state JOIN claim_state ON claim_tile_state.claim_id = claim_state.entity_id WHERE claim_state.neutral = false AND claim_tile_state.claim_id = 10"
+        );
+        assert_eq!(
+            query_of(&tables, "player_username_state"),
+            "SELECT * FROM player_username_state WHERE player_username_state.username = 'O''Neil'"
+        );
+    }
+
+    #[test]
+    fn tables_with_an_invalid_filter_column_are_left_out() {
+        let tables = EnabledTables::from_config(&config(
+            true,
+            [(
+                "inventory_state",
+                TableConfig {
+                    enabled: None,
+                    filter: BTreeMap::from([(
+                        "1 = 1 OR owner_entity_id".to_string(),
+                        FilterValue::Integer(10),
+                    )]),
+                },
+            )],
+        ));
+
+        assert!(!tables.is_enabled("inventory_state"));
+        assert!(tables.is_enabled("player_state"));
+    }
+}
@@ -5,8 +5,10 @@ use sea_orm::FromJsonQueryResult;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = BuildingDesc)]
 #[sea_orm(table_name = "building_desc")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -44,7 +46,9 @@ pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
 
-#[derive(Clone, Debug, PartialEq, Eq, FromJsonQueryResult, Serialize, Deserialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, FromJsonQueryResult, Serialize, Deserialize, TS, ToSchema,
+)]
 pub struct Function {
     pub function_type: i32,
     pub level: i32,
@@ -85,7 +89,9 @@ impl From<BuildingFunction> for Function {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, FromJsonQueryResult, Serialize, Deserialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, FromJsonQueryResult, Serialize, Deserialize, TS, ToSchema,
+)]
 pub struct FootprintTile {
     pub x: i32,
     pub z: i32,
@@ -102,7 +108,9 @@ impl From<game_module::module_bindings::FootprintTile> for FootprintTile {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Eq, Hash, Deserialize, Serialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, FromJsonQueryResult, Eq, Hash, Deserialize, Serialize, TS, ToSchema,
+)]
 pub enum FootprintType {
     Hitbox,
     Walkable,
@@ -123,7 +131,7 @@ impl From<game_module::module_bindings::FootprintType> for FootprintType {
     }
 }
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, ToSchema)]
 pub struct ApiResponse {
     pub id: i64,
     pub functions: Vec<Function>,
@@ -175,7 +183,9 @@ impl From<BuildingDesc> for Model {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Eq, Hash, Deserialize, Serialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, FromJsonQueryResult, Eq, Hash, Deserialize, Serialize, TS, ToSchema,
+)]
 pub enum BuildingInteractionLevel {
     None,
     Claim,
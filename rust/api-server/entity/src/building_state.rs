@@ -4,8 +4,10 @@ use game_module::module_bindings::BuildingState;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = BuildingState)]
 #[ts(rename = "BuildingState")]
 #[sea_orm(table_name = "building_state")]
 pub struct Model {
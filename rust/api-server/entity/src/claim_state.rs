@@ -2,8 +2,10 @@ use game_module::module_bindings::ClaimState;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = ClaimState)]
 #[ts(rename = "ClaimState")]
 #[sea_orm(table_name = "claim_state")]
 pub struct Model {
@@ -4,8 +4,10 @@ use game_module::module_bindings::ClaimTechDesc;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = ClaimTechDesc)]
 #[ts(rename = "ClaimTechDesc")]
 #[sea_orm(table_name = "claim_tech_desc")]
 pub struct Model {
@@ -33,7 +35,9 @@ pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
 
-#[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS, ToSchema,
+)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum ClaimTechType {
     Undefined = 0,
@@ -3,8 +3,10 @@ use game_module::module_bindings::{CollectibleDesc, Rarity};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = CollectibleDesc)]
 #[ts(rename = "CollectibleDesc")]
 #[sea_orm(table_name = "collectible_desc")]
 pub struct Model {
@@ -65,7 +67,9 @@ impl From<game_module::module_bindings::CollectibleDesc> for Model {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS, ToSchema,
+)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum CollectibleType {
     Default = 0,
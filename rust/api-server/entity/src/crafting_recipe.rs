@@ -10,8 +10,9 @@ use sea_orm::FromJsonQueryResult;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS, ToSchema)]
 pub struct ConsumedItemStack {
     pub item_id: i32,
     pub quantity: i32,
@@ -20,25 +21,26 @@ pub struct ConsumedItemStack {
     pub consumption_chance: f32,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS, ToSchema)]
 pub struct BuildingRequirement {
     pub building_type: i32,
     pub tier: i32,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS, ToSchema)]
 pub struct LevelRequirement {
     pub skill_id: i32,
     pub level: i32,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS, ToSchema)]
 pub struct ToolRequirement {
     pub tool_type: i32,
     pub level: i32,
     pub power: i32,
 }
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = CraftingRecipe)]
 #[ts(rename = "CraftingRecipe")]
 #[sea_orm(table_name = "crafting_recipe")]
 pub struct Model {
@@ -4,8 +4,10 @@ use game_module::module_bindings::ExtractionRecipeDesc;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = ExtractionRecipeDesc)]
 #[ts(rename = "ExtractionRecipeDesc")]
 #[sea_orm(table_name = "extraction_recipe_desc")]
 pub struct Model {
@@ -6,8 +6,10 @@ use sea_orm::entity::prelude::*;
 use sea_orm::{FromJsonQueryResult, FromQueryResult, JsonValue};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = Inventory)]
 #[sea_orm(table_name = "inventory")]
 pub struct Model {
     #[sea_orm(primary_key, auto_increment = false)]
@@ -21,14 +23,17 @@ pub struct Model {
     pub region: crate::shared::Region,
 }
 
-#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS)]
+#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS, ToSchema)]
 pub struct Pocket {
     pub volume: i32,
     pub contents: Option<ItemStack>,
     pub locked: bool,
 }
 
-#[derive(Eq, Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS)]
+#[derive(
+    Eq, Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS, ToSchema,
+)]
+#[schema(as = InventoryItemStack)]
 pub struct ItemStack {
     pub item_id: i32,
     pub quantity: i32,
@@ -41,7 +46,7 @@ pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, TS)]
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, TS, ToSchema)]
 pub struct ResolvedInventory {
     pub entity_id: i64,
     pub pockets: Vec<ItemSlotResolved>,
@@ -53,7 +58,7 @@ pub struct ResolvedInventory {
     pub claim: Option<super::claim_state::Model>,
 }
 
-#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS)]
+#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS, ToSchema)]
 pub struct ItemSlotResolved {
     pub volume: i64,
     pub contents: Option<ExpendedRefrence>,
@@ -112,20 +117,22 @@ impl Content {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Eq, Hash, Deserialize, Serialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, FromJsonQueryResult, Eq, Hash, Deserialize, Serialize, TS, ToSchema,
+)]
 pub enum ItemType {
     Item,
     Cargo,
 }
 
-#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS)]
+#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS, ToSchema)]
 #[serde(untagged)]
 pub enum ItemExpended {
     Item(item_desc::Model),
     Cargo(cargo_desc::Model),
 }
 
-#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS)]
+#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS, ToSchema)]
 pub struct ExpendedRefrence {
     pub item_id: i32,
     pub item: ItemExpended,
@@ -3,8 +3,12 @@ use sea_orm::FromJsonQueryResult;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS, ToSchema,
+)]
+#[schema(as = InventoryChangelogItemType)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 #[ts(export)]
 pub enum ItemType {
@@ -20,7 +24,8 @@ impl From<game_module::module_bindings::ItemType> for ItemType {
         }
     }
 }
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = InventoryChangelog)]
 #[ts(export)]
 #[ts(rename = "InventoryChangelog")]
 #[sea_orm(table_name = "inventory_changelog")]
@@ -37,6 +42,7 @@ pub struct Model {
     pub new_item_id: Option<i32>,
     pub new_item_quantity: Option<i32>,
     pub type_of_change: TypeOfChange,
+    pub cause: ChangeCause,
     #[sea_orm(primary_key)]
     pub timestamp: DateTime<Utc>,
 }
@@ -46,7 +52,9 @@ pub enum Relation {}
 
 impl ActiveModelBehavior for ActiveModel {}
 
-#[derive(Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS, ToSchema,
+)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum TypeOfChange {
     Add = 0,
@@ -55,6 +63,43 @@ pub enum TypeOfChange {
     AddAndRemove = 3,
 }
 
+/// What kind of reducer call caused an inventory change.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    EnumIter,
+    DeriveActiveEnum,
+    Serialize,
+    Deserialize,
+    TS,
+    ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[ts(export)]
+pub enum ChangeCause {
+    Unknown = 0,
+    Craft = 1,
+    Trade = 2,
+    PickUp = 3,
+    Drop = 4,
+    Deposit = 5,
+    Withdraw = 6,
+    MarketFill = 7,
+    MarketOrder = 8,
+    Deconstruct = 9,
+    Extract = 10,
+    Move = 11,
+    Equipment = 12,
+    Consume = 13,
+    Quest = 14,
+    Loot = 15,
+    Build = 16,
+    Admin = 17,
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, FromJsonQueryResult, Serialize, Deserialize)]
 pub enum InventoryChangelogEventData {
     Update(UpdateEvent),
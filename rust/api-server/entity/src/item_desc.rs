@@ -5,8 +5,10 @@ use game_module::module_bindings::item_desc_type;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = ItemDesc)]
 #[ts(rename = "ItemDesc")]
 #[sea_orm(table_name = "item_desc")]
 pub struct Model {
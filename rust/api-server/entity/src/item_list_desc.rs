@@ -5,8 +5,10 @@ use game_module::module_bindings::item_list_desc_type;
 use sea_orm::{FromJsonQueryResult, entity::prelude::*};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = ItemListDesc)]
 #[ts(rename = "ItemListDesc")]
 #[sea_orm(table_name = "item_list_desc")]
 pub struct Model {
@@ -17,7 +19,7 @@ pub struct Model {
     pub possibilities: Vec<ItemListPossibility>,
 }
 
-#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS)]
+#[derive(Clone, Debug, PartialEq, FromJsonQueryResult, Deserialize, Serialize, TS, ToSchema)]
 pub struct ItemListPossibility {
     pub probability: f32,
     pub items: Vec<ItemStack>,
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use sea_orm::FromQueryResult;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = LeaderboardSnapshot)]
+#[ts(export)]
+#[ts(rename = "LeaderboardSnapshot")]
+#[sea_orm(table_name = "leaderboard_snapshot")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    /// Name of the board, e.g. `global_experience`, `level` or `skill_{skill_id}`.
+    pub board: String,
+    /// `None` for the global boards.
+    pub region: Option<crate::shared::Region>,
+    pub player_entity_id: i64,
+    pub rank: i32,
+    pub value: i64,
+    #[sea_orm(primary_key)]
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+#[derive(Debug, Clone, FromQueryResult, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct LeaderboardMover {
+    pub player_entity_id: i64,
+    pub from_rank: i32,
+    pub to_rank: i32,
+    pub from_value: i64,
+    pub to_value: i64,
+    pub rank_change: i32,
+}
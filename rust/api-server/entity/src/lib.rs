@@ -2,7 +2,10 @@
 
 pub mod prelude;
 
+pub mod account;
+pub mod account_session;
 pub mod action_state;
+pub mod api_key;
 pub mod auction_listing_state;
 pub mod building_desc;
 pub mod building_nickname_state;
@@ -28,8 +31,10 @@ pub mod inventory;
 pub mod inventory_changelog;
 pub mod item_desc;
 pub mod item_list_desc;
+pub mod leaderboard_snapshot;
 pub mod location;
 pub mod location_state;
+pub mod market_order_changelog;
 pub mod mobile_entity_state;
 pub mod npc_desc;
 pub mod permission_state;
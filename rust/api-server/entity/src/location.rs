@@ -4,9 +4,11 @@
 use game_module::module_bindings::LocationState;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 // #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = LocationState)]
 #[ts(rename = "LocationState")]
 // #[sea_orm(table_name = "location")]
 pub struct Model {
@@ -0,0 +1,298 @@
+use crate::auction_listing_state::AuctionListingState;
+use crate::inventory_changelog::TypeOfChange;
+use chrono::{DateTime, Utc};
+use sea_orm::FromQueryResult;
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use ts_rs::TS;
+use utoipa::ToSchema;
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, EnumIter, DeriveActiveEnum, Serialize, Deserialize, TS, ToSchema,
+)]
+#[sea_orm(rs_type = "i32", db_type = "Integer")]
+#[ts(export)]
+pub enum MarketOrderType {
+    Buy = 0,
+    Sell = 1,
+}
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = MarketOrderChangelog)]
+#[ts(export)]
+#[ts(rename = "MarketOrderChangelog")]
+#[sea_orm(table_name = "market_order_changelog")]
+pub struct Model {
+    #[sea_orm(primary_key, auto_increment = true)]
+    pub id: i64,
+    pub entity_id: i64,
+    pub owner_entity_id: i64,
+    pub claim_entity_id: i64,
+    pub order_type: MarketOrderType,
+    pub item_id: i32,
+    pub item_type: i32,
+    pub price_threshold: i32,
+    pub quantity: i32,
+    pub old_quantity: Option<i32>,
+    pub type_of_change: TypeOfChange,
+    pub reducer_name: Option<String>,
+    pub region: crate::shared::Region,
+    #[sea_orm(primary_key)]
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+pub struct ModelBuilder {
+    order: AuctionListingState,
+    order_type: MarketOrderType,
+    type_of_change: TypeOfChange,
+    old_quantity: Option<i32>,
+    reducer_name: Option<String>,
+    timestamp: DateTime<Utc>,
+}
+
+impl ModelBuilder {
+    pub fn new(
+        order: AuctionListingState,
+        order_type: MarketOrderType,
+        type_of_change: TypeOfChange,
+    ) -> Self {
+        Self {
+            order,
+            order_type,
+            type_of_change,
+            old_quantity: None,
+            reducer_name: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn with_old_quantity(mut self, old_quantity: i32) -> Self {
+        self.old_quantity = Some(old_quantity);
+        self
+    }
+
+    pub fn with_reducer_name(mut self, reducer_name: Option<&str>) -> Self {
+        self.reducer_name = reducer_name.map(ToOwned::to_owned);
+        self
+    }
+
+    pub fn build(self) -> ActiveModel {
+        ActiveModel {
+            id: sea_orm::NotSet,
+            entity_id: sea_orm::Set(self.order.entity_id as i64),
+            owner_entity_id: sea_orm::Set(self.order.owner_entity_id as i64),
+            claim_entity_id: sea_orm::Set(self.order.claim_entity_id as i64),
+            order_type: sea_orm::Set(self.order_type),
+            item_id: sea_orm::Set(self.order.item_id),
+            item_type: sea_orm::Set(self.order.item_type),
+            price_threshold: sea_orm::Set(self.order.price_threshold),
+            quantity: sea_orm::Set(self.order.quantity),
+            old_quantity: sea_orm::Set(self.old_quantity),
+            type_of_change: sea_orm::Set(self.type_of_change),
+            reducer_name: sea_orm::Set(self.reducer_name),
+            region: sea_orm::Set(self.order.region),
+            timestamp: sea_orm::Set(self.timestamp),
+        }
+    }
+}
+
+#[derive(Debug, Clone, FromQueryResult, Serialize, Deserialize, TS, ToSchema)]
+#[ts(export)]
+pub struct MarketPriceCandle {
+    #[ts(as = "String")]
+    pub bucket: DateTime<Utc>,
+    pub order_type: MarketOrderType,
+    pub open: i32,
+    pub high: i32,
+    pub low: i32,
+    pub close: i32,
+    pub volume: i64,
+    pub listed: i64,
+    pub removed: i64,
+    pub events: i64,
+}
+
+/// The columns of a change that the price history is built from.
+#[derive(Debug, Clone, FromQueryResult)]
+pub struct MarketOrderChange {
+    pub timestamp: DateTime<Utc>,
+    pub order_type: MarketOrderType,
+    pub price_threshold: i32,
+    pub quantity: i32,
+    pub old_quantity: Option<i32>,
+    pub type_of_change: TypeOfChange,
+    pub reducer_name: Option<String>,
+}
+
+impl MarketOrderChange {
+    /// Fills reduce the quantity of an order or remove it, cancellations and edits are not
+    /// counted towards the traded volume.
+    pub fn traded_quantity(&self) -> i64 {
+        let reducer_name = self.reducer_name.as_deref().unwrap_or_default();
+
+        match self.type_of_change {
+            TypeOfChange::Update if !reducer_name.starts_with("order_edit_") => {
+                self.old_quantity.map_or(0, |old_quantity| {
+                    (old_quantity - self.quantity).max(0) as i64
+                })
+            }
+            TypeOfChange::Remove if reducer_name != "order_cancel" => self.quantity as i64,
+            _ => 0,
+        }
+    }
+}
+
+/// Buckets start at the same origin as TimescaleDB's `time_bucket`, so weeks start on a Monday.
+fn bucket_start(timestamp: DateTime<Utc>, width: chrono::Duration) -> DateTime<Utc> {
+    let origin = DateTime::<Utc>::from_timestamp(946_857_600, 0).expect("2000-01-03 is valid");
+    let width = width.num_seconds().max(1);
+    let offset = (timestamp - origin).num_seconds().div_euclid(width) * width;
+
+    origin + chrono::Duration::seconds(offset)
+}
+
+/// One candle per bucket of `width` and order type, ordered by bucket and then order type.
+pub fn price_candles(
+    changes: impl IntoIterator<Item = MarketOrderChange>,
+    width: chrono::Duration,
+) -> Vec<MarketPriceCandle> {
+    let mut candles = std::collections::BTreeMap::<
+        (DateTime<Utc>, i32),
+        (MarketPriceCandle, DateTime<Utc>, DateTime<Utc>),
+    >::new();
+
+    for change in changes {
+        let bucket = bucket_start(change.timestamp, width);
+        let volume = change.traded_quantity();
+        let (candle, opened_at, closed_at) = candles
+            .entry((bucket, change.order_type.clone().into_value()))
+            .or_insert_with(|| {
+                (
+                    MarketPriceCandle {
+                        bucket,
+                        order_type: change.order_type.clone(),
+                        open: change.price_threshold,
+                        high: change.price_threshold,
+                        low: change.price_threshold,
+                        close: change.price_threshold,
+                        volume: 0,
+                        listed: 0,
+                        removed: 0,
+                        events: 0,
+                    },
+                    change.timestamp,
+                    change.timestamp,
+                )
+            });
+
+        if change.timestamp < *opened_at {
+            candle.open = change.price_threshold;
+            *opened_at = change.timestamp;
+        }
+        if change.timestamp >= *closed_at {
+            candle.close = change.price_threshold;
+            *closed_at = change.timestamp;
+        }
+        candle.high = candle.high.max(change.price_threshold);
+        candle.low = candle.low.min(change.price_threshold);
+        candle.volume += volume;
+        match change.type_of_change {
+            TypeOfChange::Add => candle.listed += 1,
+            TypeOfChange::Remove => candle.removed += 1,
+            _ => {}
+        }
+        candle.events += 1;
+    }
+
+    candles.into_values().map(|(candle, _, _)| candle).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(
+        minute: i64,
+        price_threshold: i32,
+        type_of_change: TypeOfChange,
+        quantity: i32,
+        old_quantity: Option<i32>,
+        reducer_name: &str,
+    ) -> MarketOrderChange {
+        MarketOrderChange {
+            // 2024-01-01 00:00 UTC, a Monday
+            timestamp: DateTime::<Utc>::from_timestamp(1_704_067_200 + minute * 60, 0).unwrap(),
+            order_type: MarketOrderType::Sell,
+            price_threshold,
+            quantity,
+            old_quantity,
+            type_of_change,
+            reducer_name: Some(reducer_name.to_string()),
+        }
+    }
+
+    #[test]
+    fn candles_count_fills_but_not_edits_or_cancellations() {
+        let changes = vec![
+            change(5, 10, TypeOfChange::Add, 20, None, "order_post_sell_order"),
+            change(
+                10,
+                12,
+                TypeOfChange::Update,
+                15,
+                Some(20),
+                "order_post_buy_order",
+            ),
+            change(
+                20,
+                8,
+                TypeOfChange::Update,
+                30,
+                Some(15),
+                "order_edit_sell_order",
+            ),
+            change(30, 11, TypeOfChange::Remove, 5, None, "order_cancel"),
+            change(40, 9, TypeOfChange::Remove, 4, None, "order_post_buy_order"),
+            change(70, 14, TypeOfChange::Add, 1, None, "order_post_sell_order"),
+        ];
+
+        let candles = price_candles(changes, chrono::Duration::hours(1));
+
+        assert_eq!(candles.len(), 2);
+        let first = &candles[0];
+        assert_eq!(first.bucket.timestamp(), 1_704_067_200);
+        assert_eq!(
+            (first.open, first.high, first.low, first.close),
+            (10, 12, 8, 9)
+        );
+        assert_eq!(first.volume, 5 + 4);
+        assert_eq!((first.listed, first.removed, first.events), (1, 2, 5));
+
+        let second = &candles[1];
+        assert_eq!(second.bucket.timestamp(), 1_704_067_200 + 3600);
+        assert_eq!((second.open, second.close, second.volume), (14, 14, 0));
+    }
+
+    #[test]
+    fn weekly_candles_start_on_monday() {
+        // Sunday 2024-01-07 23:00 UTC still belongs to the week of Monday 2024-01-01
+        let candles = price_candles(
+            vec![change(
+                6 * 1440 + 23 * 60,
+                10,
+                TypeOfChange::Add,
+                1,
+                None,
+                "",
+            )],
+            chrono::Duration::weeks(1),
+        );
+
+        assert_eq!(candles[0].bucket.timestamp(), 1_704_067_200);
+    }
+}
@@ -4,8 +4,10 @@ use game_module::module_bindings::MobileEntityState;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = MobileEntityState)]
 #[ts(rename = "MobileEntityState")]
 pub struct Model {
     pub entity_id: u64,
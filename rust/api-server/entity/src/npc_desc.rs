@@ -3,8 +3,10 @@ use game_module::module_bindings::NpcDesc;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = NpcDesc)]
 #[sea_orm(table_name = "npc_desc")]
 #[ts(rename = "NpcDesc", export)]
 pub struct Model {
@@ -5,8 +5,10 @@ use serde::de::{MapAccess, SeqAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, de};
 use std::fmt;
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = PlayerActionState)]
 #[ts(rename = "PlayerActionState")]
 pub struct Model {
     pub auto_id: u64,
@@ -56,7 +58,7 @@ where
     Err(serde::de::Error::custom("Invalid value"))
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, TS, ToSchema)]
 pub enum ActionType {
     None,
     Attack,
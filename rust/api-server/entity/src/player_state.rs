@@ -5,8 +5,10 @@ use sea_orm::FromJsonQueryResult;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = PlayerState)]
 #[ts(rename = "PlayerState")]
 #[sea_orm(table_name = "player_state")]
 pub struct Model {
@@ -41,20 +43,20 @@ impl Related<super::player_username_state::Entity> for Entity {
 
 impl ActiveModelBehavior for ActiveModel {}
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS, ToSchema)]
 pub struct TeleportLocation {
     pub location: OffsetCoordinatesSmallMessage,
     pub location_type: TeleportLocationType,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS, ToSchema)]
 pub struct OffsetCoordinatesSmallMessage {
     pub x: i32,
     pub z: i32,
     pub dimension: u32,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS, ToSchema)]
 pub enum TeleportLocationType {
     BirthLocation,
     TradingPost,
@@ -63,7 +65,7 @@ pub enum TeleportLocationType {
     Waystone,
 }
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TS, ToSchema)]
 pub struct PlayerStateMerged {
     pub teleport_location: TeleportLocation,
     pub entity_id: u64,
@@ -4,8 +4,10 @@ use game_module::module_bindings::PlayerUsernameState;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = PlayerUsernameState)]
 #[sea_orm(table_name = "player_username_state")]
 #[ts(rename = "PlayerUsernameState")]
 pub struct Model {
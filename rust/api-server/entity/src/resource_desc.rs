@@ -4,8 +4,10 @@ use game_module::module_bindings::ResourceDesc;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = ResourceDesc)]
 #[ts(rename = "ResourceDesc")]
 #[sea_orm(table_name = "resource_desc")]
 pub struct Model {
@@ -2,8 +2,9 @@ use game_module::module_bindings;
 use sea_orm::FromJsonQueryResult;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, FromJsonQueryResult, TS, ToSchema)]
 pub struct ExperienceStackF32 {
     pub skill_id: i32,
     pub quantity: f32,
@@ -1,8 +1,11 @@
 use crate::inventory::ItemType;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Eq, Clone, Debug, PartialEq, sea_orm::FromJsonQueryResult, TS)]
+#[derive(
+    Serialize, Deserialize, Eq, Clone, Debug, PartialEq, sea_orm::FromJsonQueryResult, TS, ToSchema,
+)]
 pub struct ItemStack {
     pub item_id: i32,
     pub quantity: i32,
@@ -1,10 +1,20 @@
 use game_module::module_bindings::OffsetCoordinatesSmallMessage;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 #[derive(
-    Serialize, Deserialize, Clone, Debug, PartialEq, sea_orm::FromJsonQueryResult, Default, TS,
+    Serialize,
+    Deserialize,
+    Clone,
+    Debug,
+    PartialEq,
+    sea_orm::FromJsonQueryResult,
+    Default,
+    TS,
+    ToSchema,
 )]
+#[schema(as = SharedState)]
 #[ts(rename = "SharedState")]
 pub struct Location {
     pub x: i32,
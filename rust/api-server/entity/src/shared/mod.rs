@@ -3,6 +3,7 @@ use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 pub mod experience_stack_f32;
 pub mod item_stack;
@@ -10,7 +11,9 @@ pub mod location;
 pub mod probabilistic_item_stack;
 pub mod timestamp;
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Eq, TS, FromJsonQueryResult)]
+#[derive(
+    Clone, Debug, PartialEq, Deserialize, Serialize, Eq, TS, ToSchema, FromJsonQueryResult,
+)]
 pub enum JsonRarity {
     Default = 0,
     Common = 1,
@@ -35,7 +38,9 @@ impl Display for JsonRarity {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Eq, TS, DeriveActiveEnum, EnumIter)]
+#[derive(
+    Clone, Debug, PartialEq, Deserialize, Serialize, Eq, TS, ToSchema, DeriveActiveEnum, EnumIter,
+)]
 #[sea_orm(rs_type = "i32", db_type = "Integer")]
 pub enum Rarity {
     Default = 0,
@@ -1,8 +1,11 @@
 use crate::shared::item_stack::ItemStack;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, sea_orm::FromJsonQueryResult, TS)]
+#[derive(
+    Serialize, Deserialize, Clone, Debug, PartialEq, sea_orm::FromJsonQueryResult, TS, ToSchema,
+)]
 pub struct ProbabilisticItemStack {
     pub item_stack: Option<ItemStack>,
     pub probability: f32,
@@ -1,8 +1,11 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, sea_orm::FromJsonQueryResult, TS)]
+#[derive(
+    Serialize, Deserialize, Clone, Debug, PartialEq, sea_orm::FromJsonQueryResult, TS, ToSchema,
+)]
 pub struct Timestamp {
     #[ts(as = "String")]
     pub __timestamp_micros_since_unix_epoch__: DateTime<Utc>,
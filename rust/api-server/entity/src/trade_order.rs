@@ -6,8 +6,10 @@ use sea_orm::entity::prelude::*;
 use sea_orm::{FromJsonQueryResult, JsonValue};
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = TradeOrderState)]
 #[ts(rename = "TradeOrderState")]
 #[sea_orm(table_name = "trade_order")]
 pub struct Model {
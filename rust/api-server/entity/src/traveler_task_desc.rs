@@ -5,8 +5,10 @@ use game_module::module_bindings::TravelerTaskDesc;
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize, TS, ToSchema)]
+#[schema(as = TravelerTaskDesc)]
 #[sea_orm(table_name = "traveler_task_desc")]
 #[ts(rename = "TravelerTaskDesc", export)]
 pub struct Model {
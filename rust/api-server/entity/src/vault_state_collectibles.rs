@@ -3,6 +3,7 @@ use game_module::module_bindings::{VaultCollectible, VaultState, vault_state_typ
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
 use ts_rs::TS;
+use utoipa::ToSchema;
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
 #[sea_orm(table_name = "vault_state_collectibles")]
@@ -39,7 +40,7 @@ impl RawVaultStateCollectibles {
     }
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, TS)]
+#[derive(Clone, Debug, Serialize, Deserialize, TS, ToSchema)]
 pub struct VaultStateCollectibleWithDesc {
     pub entity_id: i64,
     pub id: i32,
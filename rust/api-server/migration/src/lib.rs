@@ -45,6 +45,10 @@ mod m20260321_113758_inventory_pocket_jsonb;
 mod m20260330_184152_progressive_action_state;
 mod m20260503_080615_migrate_to_number_for_region_storage;
 mod m20260503_091149_remove_region_from_player_housing_state;
+mod m20261018_000001_market_order_changelog;
+mod m20261018_000002_leaderboard_snapshot;
+mod m20261018_000003_inventory_changelog_cause;
+mod m20261018_000004_accounts;
 
 pub struct Migrator;
 
@@ -97,6 +101,10 @@ impl MigratorTrait for Migrator {
             Box::new(m20260330_184152_progressive_action_state::Migration),
             Box::new(m20260503_080615_migrate_to_number_for_region_storage::Migration),
             Box::new(m20260503_091149_remove_region_from_player_housing_state::Migration),
+            Box::new(m20261018_000001_market_order_changelog::Migration),
+            Box::new(m20261018_000002_leaderboard_snapshot::Migration),
+            Box::new(m20261018_000003_inventory_changelog_cause::Migration),
+            Box::new(m20261018_000004_accounts::Migration),
         ]
     }
 }
@@ -0,0 +1,157 @@
+use crate::sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(MarketOrderChangelog::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::EntityId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::OwnerEntityId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::ClaimEntityId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::OrderType)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::ItemId)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::ItemType)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::PriceThreshold)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::Quantity)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MarketOrderChangelog::OldQuantity).integer())
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::TypeOfChange)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(MarketOrderChangelog::ReducerName).string())
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::Region)
+                            .small_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(MarketOrderChangelog::Timestamp)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .name("pk-market_order_changelog")
+                            .col(MarketOrderChangelog::Id)
+                            .col(MarketOrderChangelog::Timestamp),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("MarketOrderChangelogItem")
+                    .table(MarketOrderChangelog::Table)
+                    .col(MarketOrderChangelog::ItemType)
+                    .col(MarketOrderChangelog::ItemId)
+                    .col(MarketOrderChangelog::Timestamp)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("MarketOrderChangelogEntityId")
+                    .table(MarketOrderChangelog::Table)
+                    .col(MarketOrderChangelog::EntityId)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            "SELECT create_hypertable('market_order_changelog', by_range('timestamp', INTERVAL '1 day'), migrate_data => true);".to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            "ALTER TABLE market_order_changelog SET(timescaledb.enable_columnstore, timescaledb.orderby = 'timestamp DESC', timescaledb.segmentby = 'item_type, item_id');".to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            "ALTER TABLE market_order_changelog SET (timescaledb.compress_chunk_time_interval = '24 hours');".to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(MarketOrderChangelog::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum MarketOrderChangelog {
+    Table,
+    Id,
+    EntityId,
+    OwnerEntityId,
+    ClaimEntityId,
+    OrderType,
+    ItemId,
+    ItemType,
+    PriceThreshold,
+    Quantity,
+    OldQuantity,
+    TypeOfChange,
+    ReducerName,
+    Region,
+    Timestamp,
+}
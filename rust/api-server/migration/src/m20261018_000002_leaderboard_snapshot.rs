@@ -0,0 +1,115 @@
+use crate::sea_orm::Statement;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(LeaderboardSnapshot::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(LeaderboardSnapshot::Id)
+                            .big_integer()
+                            .not_null()
+                            .auto_increment(),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardSnapshot::Board)
+                            .string()
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(LeaderboardSnapshot::Region).small_integer())
+                    .col(
+                        ColumnDef::new(LeaderboardSnapshot::PlayerEntityId)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardSnapshot::Rank)
+                            .integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardSnapshot::Value)
+                            .big_integer()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(LeaderboardSnapshot::Timestamp)
+                            .timestamp_with_time_zone()
+                            .not_null(),
+                    )
+                    .primary_key(
+                        Index::create()
+                            .name("pk-leaderboard_snapshot")
+                            .col(LeaderboardSnapshot::Id)
+                            .col(LeaderboardSnapshot::Timestamp),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("LeaderboardSnapshotPlayer")
+                    .table(LeaderboardSnapshot::Table)
+                    .col(LeaderboardSnapshot::PlayerEntityId)
+                    .col(LeaderboardSnapshot::Board)
+                    .col(LeaderboardSnapshot::Timestamp)
+                    .to_owned(),
+            )
+            .await?;
+
+        manager
+            .create_index(
+                Index::create()
+                    .name("LeaderboardSnapshotBoard")
+                    .table(LeaderboardSnapshot::Table)
+                    .col(LeaderboardSnapshot::Board)
+                    .col(LeaderboardSnapshot::Region)
+                    .col(LeaderboardSnapshot::Timestamp)
+                    .to_owned(),
+            )
+            .await?;
+
+        let db = manager.get_connection();
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            "SELECT create_hypertable('leaderboard_snapshot', by_range('timestamp', INTERVAL '7 days'), migrate_data => true);".to_string(),
+        ))
+        .await?;
+
+        db.execute(Statement::from_string(
+            manager.get_database_backend(),
+            "ALTER TABLE leaderboard_snapshot SET(timescaledb.enable_columnstore, timescaledb.orderby = 'timestamp DESC', timescaledb.segmentby = 'board, region');".to_string(),
+        ))
+        .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(LeaderboardSnapshot::Table).to_owned())
+            .await
+    }
+}
+
+#[derive(DeriveIden)]
+enum LeaderboardSnapshot {
+    Table,
+    Id,
+    Board,
+    Region,
+    PlayerEntityId,
+    Rank,
+    Value,
+    Timestamp,
+}
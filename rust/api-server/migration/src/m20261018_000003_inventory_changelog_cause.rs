@@ -0,0 +1,50 @@
+use sea_orm_migration::{prelude::*, schema::*};
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        if !manager
+            .has_column(
+                InventoryChangelog::Table.to_string(),
+                InventoryChangelog::Cause.to_string(),
+            )
+            .await?
+        {
+            let inventory_changelog_alter = Table::alter()
+                .table(InventoryChangelog::Table)
+                .add_column(integer(InventoryChangelog::Cause).default(0))
+                .to_owned();
+            manager.alter_table(inventory_changelog_alter).await?;
+        }
+
+        manager
+            .create_index(
+                Index::create()
+                    .if_not_exists()
+                    .name("InventoryChangelogCauseIndex")
+                    .table(InventoryChangelog::Table)
+                    .col(InventoryChangelog::Cause)
+                    .to_owned(),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        let inventory_changelog_alter = Table::alter()
+            .table(InventoryChangelog::Table)
+            .drop_column(InventoryChangelog::Cause)
+            .to_owned();
+        manager.alter_table(inventory_changelog_alter).await
+    }
+}
+
+#[derive(DeriveIden)]
+enum InventoryChangelog {
+    Table,
+    Cause,
+}
@@ -1,3 +1,6 @@
+use ::entity::account;
+use ::entity::account_session;
+use ::entity::api_key;
 use ::entity::building_state;
 use ::entity::cargo_desc;
 use ::entity::claim_member_state;
@@ -10,7 +13,11 @@ use ::entity::deployable_state;
 use ::entity::extraction_recipe_desc;
 use ::entity::inventory;
 use ::entity::inventory_changelog;
-use ::entity::inventory_changelog::ItemType;
+use ::entity::inventory_changelog::{ChangeCause, ItemType};
+use ::entity::leaderboard_snapshot;
+use ::entity::leaderboard_snapshot::LeaderboardMover;
+use ::entity::market_order_changelog;
+use ::entity::market_order_changelog::{MarketOrderChange, MarketPriceCandle};
 use ::entity::resource_desc;
 use ::entity::trade_order;
 use ::entity::traveler_task_state;
@@ -22,7 +29,7 @@ use ::entity::{
     player_username_state::Entity as PlayerUsernameState, skill_desc,
 };
 use sea_orm::sea_query::extension::postgres::PgExpr;
-use sea_orm::sea_query::{Alias, Expr, ExprTrait, PgFunc, PostgresQueryBuilder};
+use sea_orm::sea_query::{Alias, Expr, ExprTrait, PgFunc, PostgresQueryBuilder, SimpleExpr};
 use sea_orm::sqlx::types::chrono::{DateTime, Utc};
 use sea_orm::*;
 
@@ -63,6 +70,7 @@ impl Query {
         per_page: u64,
         search: Option<String>,
         online: Option<bool>,
+        sort: Option<(SimpleExpr, Order)>,
     ) -> Result<
         (
             Vec<player_state::Model>,
@@ -80,6 +88,7 @@ impl Query {
                     .to(player_state::Column::EntityId)
                     .into(),
             )
+            .apply_if(sort, |query, (expr, order)| query.order_by(expr, order))
             .order_by_asc(player_username_state::Column::Username)
             .apply_if(search, |query, value| match db.get_database_backend() {
                 DbBackend::Postgres => query.filter(
@@ -145,9 +154,11 @@ impl Query {
         page: u64,
         per_page: u64,
         search: Option<String>,
+        sort: Option<(SimpleExpr, Order)>,
     ) -> Result<(Vec<item_desc::Model>, ItemsAndPagesNumber), DbErr> {
         // Setup paginator
         let paginator = Item::find()
+            .apply_if(sort, |query, (expr, order)| query.order_by(expr, order))
             .order_by_asc(item_desc::Column::Id)
             .apply_if(search, |query, value| match db.get_database_backend() {
                 DbBackend::Postgres => {
@@ -356,9 +367,11 @@ impl Query {
         search: Option<String>,
         has_research: Option<i32>,
         is_running_upgrade: Option<bool>,
+        sort: Option<(SimpleExpr, Order)>,
     ) -> Result<(Vec<claim_state::Model>, ItemsAndPagesNumber), DbErr> {
         // Setup paginator
         let paginator = claim_state::Entity::find()
+            .apply_if(sort, |query, (expr, order)| query.order_by(expr, order))
             .order_by_desc(Expr::cust("boost"))
             .order_by_asc(claim_state::Column::EntityId)
             .expr_as(
@@ -2030,9 +2043,11 @@ impl Query {
         per_page: u64,
         id: Option<i64>,
         buildings_with_inventory_ids: Option<Vec<i64>>,
+        sort: Option<(SimpleExpr, Order)>,
     ) -> Result<(Vec<building_state::Model>, ItemsAndPagesNumber), DbErr> {
         // Setup paginator
         let paginator = building_state::Entity::find()
+            .apply_if(sort, |query, (expr, order)| query.order_by(expr, order))
             .order_by_asc(building_state::Column::EntityId)
             .apply_if(id, |query, value| {
                 query.filter(building_state::Column::ClaimEntityId.eq(value))
@@ -2150,6 +2165,7 @@ impl Query {
         item_id: Option<i32>,
         item_type: Option<ItemType>,
         user_id: Option<i64>,
+        cause: Option<ChangeCause>,
     ) -> Result<(Vec<inventory_changelog::Model>, ItemsAndPagesNumber), DbErr> {
         let start_time: DateTime<Utc> = Utc::now() - chrono::Duration::hours(24);
         let end_time: DateTime<Utc> = Utc::now();
@@ -2173,6 +2189,9 @@ impl Query {
             .apply_if(user_id, |query, value| {
                 query.filter(inventory_changelog::Column::UserId.eq(value))
             })
+            .apply_if(cause, |query, value| {
+                query.filter(inventory_changelog::Column::Cause.eq(value))
+            })
             .filter(inventory_changelog::Column::Timestamp.between(start_time, end_time))
             .order_by_desc(inventory_changelog::Column::Timestamp)
             .paginate(db, page_size);
@@ -2310,6 +2329,189 @@ impl Query {
     ) -> Result<Vec<extraction_recipe_desc::Model>, DbErr> {
         extraction_recipe_desc::Entity::find().all(db).await
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn get_market_price_candles(
+        db: &DbConn,
+        item_type: i32,
+        item_id: i32,
+        region: Option<i16>,
+        bucket_width: chrono::Duration,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<MarketPriceCandle>, DbErr> {
+        let mut query = market_order_changelog::Entity::find()
+            .select_only()
+            .columns([
+                market_order_changelog::Column::Timestamp,
+                market_order_changelog::Column::OrderType,
+                market_order_changelog::Column::PriceThreshold,
+                market_order_changelog::Column::Quantity,
+                market_order_changelog::Column::OldQuantity,
+                market_order_changelog::Column::TypeOfChange,
+                market_order_changelog::Column::ReducerName,
+            ])
+            .filter(market_order_changelog::Column::ItemType.eq(item_type))
+            .filter(market_order_changelog::Column::ItemId.eq(item_id))
+            .filter(market_order_changelog::Column::Timestamp.between(start_time, end_time));
+
+        if let Some(region) = region {
+            query = query.filter(market_order_changelog::Column::Region.eq(region));
+        }
+
+        let changes = query
+            .order_by_asc(market_order_changelog::Column::Timestamp)
+            .into_model::<MarketOrderChange>()
+            .all(db)
+            .await?;
+
+        Ok(market_order_changelog::price_candles(changes, bucket_width))
+    }
+
+    pub async fn get_leaderboard_rank_history(
+        db: &DbConn,
+        player_entity_id: i64,
+        board: Option<String>,
+        region: Option<i16>,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<leaderboard_snapshot::Model>, DbErr> {
+        let mut query = leaderboard_snapshot::Entity::find()
+            .filter(leaderboard_snapshot::Column::PlayerEntityId.eq(player_entity_id))
+            .filter(leaderboard_snapshot::Column::Timestamp.between(start_time, end_time));
+
+        if let Some(board) = board {
+            query = query.filter(leaderboard_snapshot::Column::Board.eq(board));
+        }
+
+        query = match region {
+            Some(region) => query.filter(leaderboard_snapshot::Column::Region.eq(region)),
+            None => query.filter(leaderboard_snapshot::Column::Region.is_null()),
+        };
+
+        query
+            .order_by_asc(leaderboard_snapshot::Column::Timestamp)
+            .all(db)
+            .await
+    }
+
+    /// Compares the latest snapshots taken at or before `from_time` and `to_time`.
+    /// Only players that are present in both snapshots are returned.
+    pub async fn get_leaderboard_movers(
+        db: &DbConn,
+        board: String,
+        region: Option<i16>,
+        from_time: DateTime<Utc>,
+        to_time: DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<LeaderboardMover>, DbErr> {
+        let sql = r#"
+            WITH from_snapshot AS (
+                SELECT max(timestamp) AS timestamp
+                FROM leaderboard_snapshot
+                WHERE board = $1 AND region IS NOT DISTINCT FROM $2::smallint AND timestamp <= $3
+            ), to_snapshot AS (
+                SELECT max(timestamp) AS timestamp
+                FROM leaderboard_snapshot
+                WHERE board = $1 AND region IS NOT DISTINCT FROM $2::smallint AND timestamp <= $4
+            )
+            SELECT
+                to_rows.player_entity_id,
+                from_rows.rank AS from_rank,
+                to_rows.rank AS to_rank,
+                from_rows.value AS from_value,
+                to_rows.value AS to_value,
+                from_rows.rank - to_rows.rank AS rank_change
+            FROM leaderboard_snapshot to_rows
+            JOIN leaderboard_snapshot from_rows
+                ON from_rows.player_entity_id = to_rows.player_entity_id
+                AND from_rows.board = to_rows.board
+                AND from_rows.region IS NOT DISTINCT FROM to_rows.region
+                AND from_rows.timestamp = (SELECT timestamp FROM from_snapshot)
+            WHERE to_rows.board = $1
+                AND to_rows.region IS NOT DISTINCT FROM $2::smallint
+                AND to_rows.timestamp = (SELECT timestamp FROM to_snapshot)
+            ORDER BY rank_change DESC, to_rows.rank ASC
+            LIMIT $5;
+        "#;
+
+        LeaderboardMover::find_by_statement(Statement::from_sql_and_values(
+            DbBackend::Postgres,
+            sql,
+            [
+                board.into(),
+                region.into(),
+                from_time.into(),
+                to_time.into(),
+                (limit as i64).into(),
+            ],
+        ))
+        .all(db)
+        .await
+    }
+
+    pub async fn find_account_by_id(db: &DbConn, id: i64) -> Result<Option<account::Model>, DbErr> {
+        account::Entity::find_by_id(id).one(db).await
+    }
+
+    pub async fn find_account_by_player_entity_id(
+        db: &DbConn,
+        player_entity_id: i64,
+    ) -> Result<Option<account::Model>, DbErr> {
+        account::Entity::find()
+            .filter(account::Column::PlayerEntityId.eq(player_entity_id))
+            .one(db)
+            .await
+    }
+
+    /// The key matching `key_hash` together with its account, revoked keys are ignored.
+    pub async fn find_account_by_api_key_hash(
+        db: &DbConn,
+        key_hash: &str,
+    ) -> Result<Option<(api_key::Model, account::Model)>, DbErr> {
+        let Some(api_key) = api_key::Entity::find()
+            .filter(api_key::Column::KeyHash.eq(key_hash))
+            .filter(api_key::Column::RevokedAt.is_null())
+            .one(db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        Ok(account::Entity::find_by_id(api_key.account_id)
+            .one(db)
+            .await?
+            .map(|account| (api_key, account)))
+    }
+
+    pub async fn find_account_by_session_hash(
+        db: &DbConn,
+        token_hash: &str,
+        now: DateTime<Utc>,
+    ) -> Result<Option<account::Model>, DbErr> {
+        let Some(session) = account_session::Entity::find_by_id(token_hash)
+            .filter(account_session::Column::ExpiresAt.gt(now))
+            .one(db)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        account::Entity::find_by_id(session.account_id)
+            .one(db)
+            .await
+    }
+
+    pub async fn get_api_keys_for_account(
+        db: &DbConn,
+        account_id: i64,
+    ) -> Result<Vec<api_key::Model>, DbErr> {
+        api_key::Entity::find()
+            .filter(api_key::Column::AccountId.eq(account_id))
+            .order_by_asc(api_key::Column::Id)
+            .all(db)
+            .await
+    }
 }
 
 #[derive(FromQueryResult)]
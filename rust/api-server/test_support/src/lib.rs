@@ -0,0 +1,397 @@
+//! A stand-in for a SpacetimeDB host that speaks enough of the v1 BSATN websocket protocol for
+//! `DbConnection::builder()` to connect, subscribe to tables and receive transactions, so the
+//! ingest path can be tested without network access or game credentials.
+//!
+//! Subscription queries are only used to find the table name, `WHERE` clauses and joins are
+//! ignored and every row of the table gets delivered.
+
+use axum::Router;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, State};
+use axum::response::Response;
+use axum::routing::{get, post};
+use spacetimedb_client_api_messages::energy::EnergyQuanta;
+use spacetimedb_client_api_messages::websocket as ws;
+use spacetimedb_lib::ser::Serialize;
+use spacetimedb_lib::{ConnectionId, Identity, TimeDuration, Timestamp, bsatn};
+use spacetimedb_primitives::TableId;
+use std::collections::{BTreeMap, HashSet};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+use tokio::sync::{broadcast, oneshot};
+
+const BSATN_PROTOCOL: &str = "v1.bsatn.spacetimedb";
+
+#[derive(Default)]
+struct Table {
+    id: u32,
+    rows: Vec<Vec<u8>>,
+}
+
+#[derive(Default)]
+struct FixtureState {
+    tables: BTreeMap<String, Table>,
+}
+
+impl FixtureState {
+    fn table(&mut self, name: &str) -> &mut Table {
+        let next_id = self.tables.len() as u32;
+        self.tables
+            .entry(name.to_string())
+            .or_insert_with(|| Table {
+                id: next_id,
+                rows: vec![],
+            })
+    }
+}
+
+#[derive(Clone)]
+struct Fixture {
+    state: Arc<Mutex<FixtureState>>,
+    transactions: broadcast::Sender<Arc<CommittedTransaction>>,
+}
+
+#[derive(Clone, Default)]
+struct TableChanges {
+    inserts: Vec<Vec<u8>>,
+    deletes: Vec<Vec<u8>>,
+}
+
+/// A set of row changes that reaches every subscribed client as a single `TransactionUpdate`.
+#[derive(Clone, Default)]
+pub struct Transaction {
+    reducer_name: String,
+    reducer_args: Vec<u8>,
+    changes: BTreeMap<String, TableChanges>,
+}
+
+impl Transaction {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The reducer the client sees as the cause of the transaction. `args` are the BSATN encoded
+    /// reducer arguments, without a known reducer the SDK reports an unknown transaction.
+    pub fn reducer(mut self, name: &str, args: Vec<u8>) -> Self {
+        self.reducer_name = name.to_string();
+        self.reducer_args = args;
+        self
+    }
+
+    pub fn insert<T: Serialize>(mut self, table: &str, row: &T) -> Self {
+        self.changes
+            .entry(table.to_string())
+            .or_default()
+            .inserts
+            .push(encode_row(row));
+        self
+    }
+
+    pub fn delete<T: Serialize>(mut self, table: &str, row: &T) -> Self {
+        self.changes
+            .entry(table.to_string())
+            .or_default()
+            .deletes
+            .push(encode_row(row));
+        self
+    }
+
+    /// The SDK turns a delete and an insert with the same primary key into an update.
+    pub fn update<T: Serialize>(self, table: &str, old: &T, new: &T) -> Self {
+        self.delete(table, old).insert(table, new)
+    }
+}
+
+struct CommittedTransaction {
+    reducer_name: String,
+    reducer_args: Vec<u8>,
+    // Table name -> (table id, changes)
+    changes: BTreeMap<String, (u32, TableChanges)>,
+    timestamp: Timestamp,
+}
+
+/// The fake SpacetimeDB host, it stops serving when dropped.
+pub struct FakeSpacetimeDb {
+    address: SocketAddr,
+    fixture: Fixture,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl FakeSpacetimeDb {
+    /// Starts serving on a random local port.
+    pub async fn start() -> anyhow::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let (transactions, _) = broadcast::channel(1024);
+        let fixture = Fixture {
+            state: Arc::new(Mutex::new(FixtureState::default())),
+            transactions,
+        };
+
+        let app = Router::new()
+            .route("/v1/identity/websocket-token", post(websocket_token))
+            .route("/v1/database/{database}/subscribe", get(subscribe))
+            .with_state(fixture.clone());
+
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        tokio::spawn(async move {
+            let server = axum::serve(listener, app).with_graceful_shutdown(async move {
+                let _ = shutdown_rx.await;
+            });
+            if let Err(error) = server.await {
+                tracing::error!("Fake SpacetimeDB stopped: {error}");
+            }
+        });
+
+        Ok(Self {
+            address,
+            fixture,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// The value to pass to `DbConnectionBuilder::with_uri`.
+    pub fn uri(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Adds rows that are part of the initial subscription of every client connecting afterwards.
+    pub fn insert<T: Serialize>(&self, table: &str, rows: impl IntoIterator<Item = T>) {
+        let mut state = self.fixture.state.lock().unwrap();
+        let table = state.table(table);
+        table
+            .rows
+            .extend(rows.into_iter().map(|row| encode_row(&row)));
+    }
+
+    /// Applies the transaction to the stored rows and pushes it to every connected client that
+    /// is subscribed to one of the touched tables.
+    pub fn commit(&self, transaction: Transaction) {
+        let mut state = self.fixture.state.lock().unwrap();
+        let mut changes = BTreeMap::new();
+
+        for (name, table_changes) in transaction.changes {
+            let table = state.table(&name);
+            for delete in &table_changes.deletes {
+                if let Some(index) = table.rows.iter().position(|row| row == delete) {
+                    table.rows.remove(index);
+                }
+            }
+            table.rows.extend(table_changes.inserts.iter().cloned());
+            changes.insert(name, (table.id, table_changes));
+        }
+
+        // Nobody listening is fine, late clients get the rows through their initial subscription.
+        let _ = self
+            .fixture
+            .transactions
+            .send(Arc::new(CommittedTransaction {
+                reducer_name: transaction.reducer_name,
+                reducer_args: transaction.reducer_args,
+                changes,
+                timestamp: Timestamp::now(),
+            }));
+    }
+}
+
+impl Drop for FakeSpacetimeDb {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
+
+fn encode_row<T: Serialize>(row: &T) -> Vec<u8> {
+    bsatn::to_vec(row).expect("Fixture rows have to be BSATN encodable")
+}
+
+async fn websocket_token() -> axum::Json<serde_json::Value> {
+    axum::Json(serde_json::json!({ "token": "fake-spacetimedb-token" }))
+}
+
+async fn subscribe(
+    ws: WebSocketUpgrade,
+    Path(database): Path<String>,
+    State(fixture): State<Fixture>,
+) -> Response {
+    ws.protocols([BSATN_PROTOCOL])
+        .on_upgrade(move |socket| handle_socket(socket, database, fixture))
+}
+
+async fn handle_socket(mut socket: WebSocket, database: String, fixture: Fixture) {
+    let mut transactions = fixture.transactions.subscribe();
+    let mut subscribed_tables: HashSet<String> = HashSet::new();
+
+    let identity_token = ws::ServerMessage::IdentityToken(ws::IdentityToken {
+        identity: Identity::ZERO,
+        token: "fake-spacetimedb-token".into(),
+        connection_id: ConnectionId::ZERO,
+    });
+    if send(&mut socket, identity_token).await.is_err() {
+        return;
+    }
+
+    loop {
+        tokio::select! {
+            message = socket.recv() => {
+                let Some(Ok(message)) = message else {
+                    break;
+                };
+
+                let bytes = match message {
+                    Message::Binary(bytes) => bytes,
+                    Message::Close(_) => break,
+                    _ => continue,
+                };
+
+                let client_message = match bsatn::from_slice::<ws::ClientMessage<bytes::Bytes>>(&bytes) {
+                    Ok(client_message) => client_message,
+                    Err(error) => {
+                        tracing::warn!(database, "Fake SpacetimeDB got an invalid message: {error}");
+                        continue;
+                    }
+                };
+
+                let reply = match client_message {
+                    ws::ClientMessage::SubscribeMulti(subscribe) => {
+                        let tables = table_names(&subscribe.query_strings);
+                        let update = initial_update(&fixture, &tables);
+                        subscribed_tables.extend(tables);
+
+                        ws::ServerMessage::SubscribeMultiApplied(ws::SubscribeMultiApplied {
+                            request_id: subscribe.request_id,
+                            total_host_execution_duration_micros: 0,
+                            query_id: subscribe.query_id,
+                            update,
+                        })
+                    }
+                    ws::ClientMessage::Subscribe(subscribe) => {
+                        let tables = table_names(&subscribe.query_strings);
+                        let database_update = initial_update(&fixture, &tables);
+                        subscribed_tables = tables.into_iter().collect();
+
+                        ws::ServerMessage::InitialSubscription(ws::InitialSubscription {
+                            database_update,
+                            request_id: subscribe.request_id,
+                            total_host_execution_duration: TimeDuration::from_micros(0),
+                        })
+                    }
+                    _ => continue,
+                };
+
+                if send(&mut socket, reply).await.is_err() {
+                    break;
+                }
+            }
+            transaction = transactions.recv() => {
+                let transaction = match transaction {
+                    Ok(transaction) => transaction,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(database, skipped, "Fake SpacetimeDB client lagged behind");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                };
+
+                let tables = transaction
+                    .changes
+                    .iter()
+                    .filter(|(name, _)| subscribed_tables.contains(*name))
+                    .map(|(name, (id, changes))| table_update(*id, name, &changes.inserts, &changes.deletes))
+                    .collect::<Vec<_>>();
+                if tables.is_empty() {
+                    continue;
+                }
+
+                let update = ws::ServerMessage::TransactionUpdate(ws::TransactionUpdate {
+                    status: ws::UpdateStatus::Committed(ws::DatabaseUpdate { tables }),
+                    timestamp: transaction.timestamp,
+                    caller_identity: Identity::ZERO,
+                    caller_connection_id: ConnectionId::ZERO,
+                    reducer_call: ws::ReducerCallInfo {
+                        reducer_name: transaction.reducer_name.as_str().into(),
+                        reducer_id: 0,
+                        args: transaction.reducer_args.clone().into(),
+                        request_id: 0,
+                    },
+                    energy_quanta_used: EnergyQuanta { quanta: 0 },
+                    total_host_execution_duration: TimeDuration::from_micros(0),
+                });
+
+                if send(&mut socket, update).await.is_err() {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+/// `SELECT * FROM x`, `SELECT x.* FROM x JOIN y ...` and `... WHERE ...` all subscribe to `x`.
+fn table_names(queries: &[Box<str>]) -> Vec<String> {
+    queries
+        .iter()
+        .filter_map(|query| {
+            let mut words = query.split_whitespace();
+            words.find(|word| word.eq_ignore_ascii_case("from"))?;
+            words
+                .next()
+                .map(|table| table.trim_end_matches(';').to_string())
+        })
+        .collect()
+}
+
+fn initial_update(fixture: &Fixture, tables: &[String]) -> ws::DatabaseUpdate<ws::BsatnFormat> {
+    let mut state = fixture.state.lock().unwrap();
+
+    ws::DatabaseUpdate {
+        tables: tables
+            .iter()
+            .map(|name| {
+                let table = state.table(name);
+                table_update(table.id, name, &table.rows, &[])
+            })
+            .collect(),
+    }
+}
+
+fn table_update(
+    table_id: u32,
+    table_name: &str,
+    inserts: &[Vec<u8>],
+    deletes: &[Vec<u8>],
+) -> ws::TableUpdate<ws::BsatnFormat> {
+    let query_update = ws::QueryUpdate {
+        deletes: row_list(deletes),
+        inserts: row_list(inserts),
+    };
+
+    ws::TableUpdate {
+        table_id: TableId(table_id),
+        table_name: table_name.into(),
+        num_rows: (inserts.len() + deletes.len()) as u64,
+        updates: [ws::CompressableQueryUpdate::Uncompressed(query_update)].into(),
+    }
+}
+
+fn row_list(rows: &[Vec<u8>]) -> ws::BsatnRowList {
+    let mut offsets = Vec::with_capacity(rows.len());
+    let mut data = Vec::new();
+    for row in rows {
+        offsets.push(data.len() as u64);
+        data.extend_from_slice(row);
+    }
+
+    ws::BsatnRowList::new(ws::RowSizeHint::RowOffsets(offsets.into()), data.into())
+}
+
+async fn send(
+    socket: &mut WebSocket,
+    message: ws::ServerMessage<ws::BsatnFormat>,
+) -> Result<(), axum::Error> {
+    let mut bytes = vec![ws::SERVER_MSG_COMPRESSION_TAG_NONE];
+    bytes.extend(bsatn::to_vec(&message).expect("Server messages are BSATN encodable"));
+
+    socket.send(Message::Binary(bytes.into())).await
+}
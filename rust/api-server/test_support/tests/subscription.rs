@@ -0,0 +1,60 @@
+use game_module::module_bindings::*;
+use spacetimedb_sdk::{DbContext, Table};
+use std::time::Duration;
+use test_support::{FakeSpacetimeDb, Transaction};
+use tokio::sync::mpsc::unbounded_channel;
+use tokio::time::timeout;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn delivers_initial_rows_and_transactions() {
+    let fixture = FakeSpacetimeDb::start().await.unwrap();
+    fixture.insert(
+        "claim_tile_state",
+        [ClaimTileState {
+            entity_id: 1,
+            claim_id: 10,
+        }],
+    );
+
+    let connection = DbConnection::builder()
+        .with_uri(fixture.uri())
+        .with_module_name("bitcraft-test")
+        .with_token(Some("token"))
+        .build()
+        .unwrap();
+
+    let (tx, mut rx) = unbounded_channel();
+    connection
+        .db
+        .claim_tile_state()
+        .on_insert(move |_ctx, row| {
+            let _ = tx.send(row.clone());
+        });
+    connection
+        .subscription_builder()
+        .subscribe(["SELECT * FROM claim_tile_state"]);
+    connection.run_threaded();
+
+    let initial = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(initial.entity_id, 1);
+
+    fixture.commit(Transaction::new().insert(
+        "claim_tile_state",
+        &ClaimTileState {
+            entity_id: 2,
+            claim_id: 10,
+        },
+    ));
+
+    let inserted = timeout(Duration::from_secs(5), rx.recv())
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(inserted.entity_id, 2);
+    assert_eq!(connection.db.claim_tile_state().count(), 2);
+
+    let _ = connection.disconnect();
+}